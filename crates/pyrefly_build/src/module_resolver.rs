@@ -7,6 +7,7 @@
 
 use std::ffi::OsString;
 use std::fmt::Debug;
+use std::fs;
 use std::io::Read;
 use std::iter;
 use std::path::Path;
@@ -65,7 +66,7 @@ static PKGUTIL_EXTEND_PATH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
 
 fn is_pkgutil_namespace(init_path: &Path, observer: Option<&dyn ModuleResolutionObserver>) -> bool {
     let start = observer.map(|_| Instant::now());
-    let Ok(mut file) = std::fs::File::open(init_path) else {
+    let Ok(mut file) = fs::File::open(init_path) else {
         return false;
     };
     let mut buf = [0u8; PKGUTIL_DETECTION_MAX_BYTES];
@@ -97,6 +98,12 @@ pub struct DirEntryCache {
     entry_cache: LockedMap<PathBuf, Option<Arc<SmallMap<OsString, bool>>>>,
     /// Cached `pkgutil.extend_path` namespace-package check, keyed by `__init__` path.
     pkgutil_cache: LockedMap<PathBuf, bool>,
+    /// Cached CPython extension modules in a directory: maps a module name to its file name.
+    extension_cache: LockedMap<PathBuf, Arc<SmallMap<String, OsString>>>,
+    /// Cached partial stub package check, keyed by package root.
+    partial_stub_cache: LockedMap<PathBuf, bool>,
+    /// Cached distributions installed in a site-packages directory.
+    distribution_cache: LockedMap<PathBuf, Arc<Vec<InstalledDistribution>>>,
 }
 
 impl Debug for DirEntryCache {
@@ -110,9 +117,51 @@ impl DirEntryCache {
         Self {
             entry_cache: LockedMap::new(),
             pkgutil_cache: LockedMap::new(),
+            extension_cache: LockedMap::new(),
+            partial_stub_cache: LockedMap::new(),
+            distribution_cache: LockedMap::new(),
         }
     }
 
+    /// The distributions installed in `site_packages`, read from their `.dist-info`
+    /// metadata once per directory.
+    pub fn installed_distributions(&self, site_packages: &Path) -> Arc<Vec<InstalledDistribution>> {
+        let key = site_packages.to_path_buf();
+        if let Some(cached) = self.distribution_cache.get(&key) {
+            return cached.clone();
+        }
+        let mut distributions = Vec::new();
+        for (file_name, is_dir) in self.get_entries(site_packages).unwrap_or_default().iter() {
+            if *is_dir
+                && file_name
+                    .to_str()
+                    .is_some_and(|name| name.ends_with(".dist-info"))
+                && let Some(distribution) =
+                    InstalledDistribution::read(&site_packages.join(file_name))
+            {
+                distributions.push(distribution);
+            }
+        }
+        let distributions = Arc::new(distributions);
+        self.distribution_cache.insert(key, distributions.clone());
+        distributions
+    }
+
+    /// Whether the `py.typed` of the package at `package_root` marks it as partial, read
+    /// once per package.
+    fn is_partial_package(&self, package_root: &Path) -> bool {
+        let key = package_root.to_path_buf();
+        if let Some(cached) = self.partial_stub_cache.get(&key) {
+            return *cached;
+        }
+        let py_typed = package_root.join("py.typed");
+        let result = self.file_exists(&py_typed)
+            && fs::read_to_string(&py_typed)
+                .is_ok_and(|contents| contents.lines().any(|line| line.trim() == "partial"));
+        self.partial_stub_cache.insert(key, result);
+        result
+    }
+
     /// Cached form of [`is_pkgutil_namespace`], keyed by `__init__` path.
     fn is_pkgutil_namespace(
         &self,
@@ -149,6 +198,35 @@ impl DirEntryCache {
         }
     }
 
+    /// Find a CPython extension module named `name` directly inside `dir`. If there
+    /// are several (e.g. built for different interpreters), the first by file name wins.
+    pub fn find_extension_module(&self, dir: &Path, name: &str) -> Option<PathBuf> {
+        let key = dir.to_path_buf();
+        let modules = match self.extension_cache.get(&key) {
+            Some(cached) => cached.clone(),
+            None => {
+                let mut modules: SmallMap<String, OsString> = SmallMap::new();
+                let entries = self.get_entries(dir).unwrap_or_default();
+                for (file_name, is_dir) in entries.iter() {
+                    if !*is_dir
+                        && let Some(module) = file_name.to_str().and_then(extension_module_name)
+                    {
+                        let entry = modules
+                            .entry(module.to_owned())
+                            .or_insert_with(|| file_name.clone());
+                        if *file_name < *entry {
+                            *entry = file_name.clone();
+                        }
+                    }
+                }
+                let modules = Arc::new(modules);
+                self.extension_cache.insert(key, modules.clone());
+                modules
+            }
+        };
+        modules.get(name).map(|file_name| dir.join(file_name))
+    }
+
     fn get_entries(&self, dir: &Path) -> Option<Arc<SmallMap<OsString, bool>>> {
         let key = dir.to_path_buf();
         if let Some(cached) = self.entry_cache.get(&key) {
@@ -160,14 +238,14 @@ impl DirEntryCache {
     }
 
     fn read_dir_entries(dir: &Path) -> Option<Arc<SmallMap<OsString, bool>>> {
-        std::fs::read_dir(dir).ok().map(|entries| {
+        fs::read_dir(dir).ok().map(|entries| {
             Arc::new(
                 entries
                     .filter_map(|e| e.ok())
                     .map(|e| {
                         let is_dir = e.file_type().is_ok_and(|ft| {
                             if ft.is_symlink() {
-                                std::fs::metadata(e.path()).is_ok_and(|m| m.file_type().is_dir())
+                                fs::metadata(e.path()).is_ok_and(|m| m.file_type().is_dir())
                             } else {
                                 ft.is_dir()
                             }
@@ -180,6 +258,123 @@ impl DirEntryCache {
     }
 }
 
+/// The module name of a CPython extension module file, e.g. `foo` for `foo.so`, `foo.pyd`,
+/// `foo.cpython-311-x86_64-linux-gnu.so`, `foo.abi3.so` or `foo.cp311-win_amd64.pyd`.
+/// Any other tag between the name and the suffix (e.g. `foo.bar.so`) isn't an extension
+/// module CPython would import as `foo`.
+fn extension_module_name(file_name: &str) -> Option<&str> {
+    // A CPython version tag, e.g. `311` or `313t`, followed by a non-empty platform.
+    fn is_version_and_platform(tag: &str) -> bool {
+        let Some((version, platform)) = tag.split_once('-') else {
+            return false;
+        };
+        let flags = version.trim_start_matches(|c: char| c.is_ascii_digit());
+        flags.len() < version.len()
+            && flags.chars().all(|c| c.is_ascii_lowercase())
+            && !platform.is_empty()
+    }
+    // Linux and macOS tag with `cpython-` and allow `abi3`; Windows tags with `cp`.
+    let (stem, is_so) = match file_name.strip_suffix(".so") {
+        Some(stem) => (stem, true),
+        None => (file_name.strip_suffix(".pyd")?, false),
+    };
+    let is_tag = |tag: &str| {
+        if is_so {
+            tag == "abi3"
+                || tag
+                    .strip_prefix("cpython-")
+                    .is_some_and(is_version_and_platform)
+        } else {
+            tag.strip_prefix("cp").is_some_and(is_version_and_platform)
+        }
+    };
+    let name = match stem.split_once('.') {
+        None => stem,
+        Some((name, tag)) if is_tag(tag) => name,
+        Some(_) => return None,
+    };
+    (!name.is_empty()).then_some(name)
+}
+
+/// A distribution installed in a site-packages directory, as described by its `.dist-info`
+/// metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledDistribution {
+    /// The distribution name, from the `Name` field of its `METADATA`.
+    pub name: String,
+    /// The top-level modules it installs, from its `top_level.txt`, or else its `RECORD`.
+    pub top_level: Vec<String>,
+}
+
+impl InstalledDistribution {
+    fn read(dist_info: &Path) -> Option<Self> {
+        let metadata = fs::read_to_string(dist_info.join("METADATA")).ok()?;
+        // The fields are a header block, which ends at the first blank line.
+        let name = metadata
+            .lines()
+            .take_while(|line| !line.trim().is_empty())
+            .find_map(|line| line.strip_prefix("Name:"))?
+            .trim()
+            .to_owned();
+        let mut top_level = match fs::read_to_string(dist_info.join("top_level.txt")) {
+            Ok(contents) => contents
+                .lines()
+                .map(|line| line.trim().to_owned())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>(),
+            Err(_) => fs::read_to_string(dist_info.join("RECORD"))
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| {
+                    // Each line is `path,hash,size`, with paths relative to site-packages.
+                    let path = line.split(',').next()?;
+                    let (first, rest) = match path.split_once('/') {
+                        Some((first, rest)) => (first, Some(rest)),
+                        None => (path, None),
+                    };
+                    if first.starts_with('.')
+                        || first.ends_with(".dist-info")
+                        || first.ends_with(".data")
+                        || first == "__pycache__"
+                    {
+                        return None;
+                    }
+                    match rest {
+                        Some(_) => Some(first.to_owned()),
+                        None => first
+                            .strip_suffix(".py")
+                            .or_else(|| first.strip_suffix(".pyi"))
+                            .or_else(|| extension_module_name(first))
+                            .map(|name| name.to_owned()),
+                    }
+                })
+                .collect(),
+        };
+        top_level.sort();
+        top_level.dedup();
+        Some(Self { name, top_level })
+    }
+
+    /// Whether this is the distribution named `name`, comparing names as pip does, so that
+    /// e.g. `PyYAML` matches `pyyaml` and `typing_extensions` matches `typing-extensions`.
+    pub fn is_named(&self, name: &str) -> bool {
+        fn normalize(name: &str) -> String {
+            name.split(['-', '_', '.'])
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_lowercase()
+        }
+        normalize(&self.name) == normalize(name)
+    }
+
+    /// Whether this distribution installs the top-level module `module`.
+    pub fn provides(&self, module: ModuleName) -> bool {
+        let top_level = module.first_component();
+        self.top_level.iter().any(|m| m == top_level.as_str())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FindResult {
     /// Found a single-file .pyi module. The path must not point to an __init__ file.
@@ -247,39 +442,71 @@ impl FindResult {
     }
 }
 
-pub fn package_has_py_typed(
-    module: ModuleName,
-    result: &FindResult,
-    dir_cache: &DirEntryCache,
-) -> bool {
+/// The top-level package directory that `result`, found for `module`, lives in.
+fn package_root<'a>(module: ModuleName, result: &'a FindResult) -> Option<&'a Path> {
     let depth = module.components().len().saturating_sub(1);
     let mut package_root = match result {
         FindResult::RegularPackage(_, dir) => dir.as_path(),
-        FindResult::LegacyNamespacePackage(init_path, _) => {
-            let Some(dir) = init_path.parent() else {
-                return false;
-            };
-            dir
-        }
+        FindResult::LegacyNamespacePackage(init_path, _) => init_path.parent()?,
+        FindResult::ImplicitNamespacePackage(roots) => roots.first().as_path(),
         FindResult::SingleFilePyModule(path)
         | FindResult::SingleFilePyiModule(path)
         | FindResult::CompiledModule(path) => {
             if depth == 0 {
-                return false;
+                return None;
             }
             path.as_path()
         }
-        FindResult::ImplicitNamespacePackage(_) => return false,
     };
-
     for _ in 0..depth {
-        let Some(parent) = package_root.parent() else {
-            return false;
-        };
-        package_root = parent;
+        package_root = package_root.parent()?;
+    }
+    Some(package_root)
+}
+
+pub fn package_has_py_typed(
+    module: ModuleName,
+    result: &FindResult,
+    dir_cache: &DirEntryCache,
+) -> bool {
+    if matches!(result, FindResult::ImplicitNamespacePackage(_)) {
+        return false;
     }
+    package_root(module, result)
+        .is_some_and(|package_root| dir_cache.file_exists(&package_root.join("py.typed")))
+}
 
-    dir_cache.file_exists(&package_root.join("py.typed"))
+/// Whether `result` is a `.pyi` stub shipped next to the compiled extension module it
+/// describes, e.g. `foo.pyi` beside `foo.cpython-311-x86_64-linux-gnu.so`, or
+/// `__init__.pyi` beside `__init__.abi3.so`. Such a module is typed even without a
+/// `py.typed` marker, since the stub is the only source of types for it.
+pub fn is_extension_module_stub(result: &FindResult, dir_cache: &DirEntryCache) -> bool {
+    let (FindResult::SingleFilePyiModule(path) | FindResult::RegularPackage(path, _)) = result
+    else {
+        return false;
+    };
+    if path.extension().is_none_or(|ext| ext != "pyi") {
+        return false;
+    }
+    match (
+        path.parent(),
+        path.file_stem().and_then(|stem| stem.to_str()),
+    ) {
+        (Some(dir), Some(stem)) => dir_cache.find_extension_module(dir, stem).is_some(),
+        _ => false,
+    }
+}
+
+/// Whether the `-stubs` package that `stub_result` was found in is a partial stub package,
+/// i.e. its `py.typed` contains the line `partial`. A partial stub package only overlays the
+/// runtime package, so modules it doesn't provide stubs for come from the runtime package.
+pub fn is_partial_stub_package(
+    module: ModuleName,
+    stub_result: &FindResult,
+    dir_cache: &DirEntryCache,
+) -> bool {
+    package_root(module, stub_result)
+        .is_some_and(|package_root| dir_cache.is_partial_package(package_root))
 }
 
 fn find_one_part_in_root(
@@ -353,6 +580,12 @@ fn find_one_part_in_root(
         }
     }
 
+    if style_filter != Some(ModuleStyle::Interface)
+        && let Some(extension_path) = dir_cache.find_extension_module(root, name)
+    {
+        return Some(FindResult::CompiledModule(extension_path));
+    }
+
     if dir_exists {
         Some(FindResult::ImplicitNamespacePackage(Vec1::new(
             candidate_dir,
@@ -647,7 +880,7 @@ fn find_one_part_prefix<'a>(
     let mut namespace_roots: SmallMap<ModuleName, Vec<PathBuf>> = SmallMap::new();
 
     for root in roots {
-        if let Ok(entries) = std::fs::read_dir(root) {
+        if let Ok(entries) = fs::read_dir(root) {
             for entry in entries.filter_map(Result::ok) {
                 let path = entry.path();
                 let file_name = path.file_name().and_then(|n| n.to_str());
@@ -696,6 +929,13 @@ fn find_one_part_prefix<'a>(
                                 FindResult::CompiledModule(path.clone()),
                                 ModuleName::from_str(stem),
                             ));
+                        } else if let Some(module_name) = extension_module_name(name)
+                            && !["__init__", "__main__"].contains(&module_name)
+                        {
+                            results.push((
+                                FindResult::CompiledModule(path.clone()),
+                                ModuleName::from_str(module_name),
+                            ));
                         }
                     }
                 }
@@ -794,7 +1034,7 @@ mod tests {
         let root = tempdir.path();
 
         let init1 = root.join("init1.py");
-        std::fs::write(
+        fs::write(
             &init1,
             "from pkgutil import extend_path\n__path__ = extend_path(__path__, __name__)\n",
         )
@@ -802,7 +1042,7 @@ mod tests {
         assert!(is_pkgutil_namespace(&init1, None));
 
         let init2 = root.join("init2.py");
-        std::fs::write(
+        fs::write(
             &init2,
             "__path__ = __import__('pkgutil').extend_path(__path__, __name__)\n",
         )
@@ -810,7 +1050,7 @@ mod tests {
         assert!(is_pkgutil_namespace(&init2, None));
 
         let init3 = root.join("init3.py");
-        std::fs::write(
+        fs::write(
             &init3,
             "import pkgutil\n__path__ = pkgutil.extend_path(__path__, __name__)\n",
         )
@@ -818,15 +1058,15 @@ mod tests {
         assert!(is_pkgutil_namespace(&init3, None));
 
         let init4 = root.join("init4.py");
-        std::fs::write(&init4, "from . import foo\n__all__ = ['foo']\n").unwrap();
+        fs::write(&init4, "from . import foo\n__all__ = ['foo']\n").unwrap();
         assert!(!is_pkgutil_namespace(&init4, None));
 
         let init5 = root.join("init5.py");
-        std::fs::write(&init5, "").unwrap();
+        fs::write(&init5, "").unwrap();
         assert!(!is_pkgutil_namespace(&init5, None));
 
         let init_comment = root.join("init_comment.py");
-        std::fs::write(
+        fs::write(
             &init_comment,
             "# __path__ = pkgutil.extend_path(__path__, __name__)\n",
         )
@@ -834,7 +1074,7 @@ mod tests {
         assert!(!is_pkgutil_namespace(&init_comment, None));
 
         let init_inline_comment = root.join("init_inline_comment.py");
-        std::fs::write(
+        fs::write(
             &init_inline_comment,
             "__path__ = pkgutil. # commented out\n    extend_path(__path__, __name__)\n",
         )
@@ -842,7 +1082,7 @@ mod tests {
         assert!(!is_pkgutil_namespace(&init_inline_comment, None));
 
         let init_suffix = root.join("init_suffix.py");
-        std::fs::write(
+        fs::write(
             &init_suffix,
             "__path__ = mymod._extend_path(__path__, __name__)\n",
         )
@@ -850,7 +1090,7 @@ mod tests {
         assert!(!is_pkgutil_namespace(&init_suffix, None));
 
         let init_multiline = root.join("init_multiline.py");
-        std::fs::write(
+        fs::write(
             &init_multiline,
             "__path__ = (\n    pkgutil.extend_path(__path__, __name__)\n)\n",
         )
@@ -858,7 +1098,7 @@ mod tests {
         assert!(!is_pkgutil_namespace(&init_multiline, None));
 
         let init_trailing_comment = root.join("init_trailing_comment.py");
-        std::fs::write(
+        fs::write(
             &init_trailing_comment,
             "__path__ = pkgutil.extend_path(__path__, __name__)  # legacy ns\n",
         )
@@ -866,7 +1106,7 @@ mod tests {
         assert!(is_pkgutil_namespace(&init_trailing_comment, None));
 
         let init_indented = root.join("init_indented.py");
-        std::fs::write(
+        fs::write(
             &init_indented,
             "if True:\n    __path__ = pkgutil.extend_path(__path__, __name__)\n",
         )
@@ -879,7 +1119,7 @@ mod tests {
             padding.push_str("# x\n");
         }
         padding.push_str("__path__ = pkgutil.extend_path(__path__, __name__)\n");
-        std::fs::write(&init_truncated, &padding).unwrap();
+        fs::write(&init_truncated, &padding).unwrap();
         assert!(!is_pkgutil_namespace(&init_truncated, None));
     }
 
@@ -1201,6 +1441,148 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extension_module_name() {
+        assert_eq!(extension_module_name("foo.so"), Some("foo"));
+        assert_eq!(
+            extension_module_name("foo.cpython-311-x86_64-linux-gnu.so"),
+            Some("foo")
+        );
+        assert_eq!(extension_module_name("foo.abi3.so"), Some("foo"));
+        assert_eq!(
+            extension_module_name("foo.cp311-win_amd64.pyd"),
+            Some("foo")
+        );
+        assert_eq!(extension_module_name("foo.pyd"), Some("foo"));
+        assert_eq!(
+            extension_module_name("foo.cpython-313t-darwin.so"),
+            Some("foo")
+        );
+        assert_eq!(extension_module_name("foo.py"), None);
+        assert_eq!(extension_module_name(".so"), None);
+        assert_eq!(extension_module_name("foo.bar.so"), None);
+        assert_eq!(extension_module_name("foo.cpython.so"), None);
+        assert_eq!(extension_module_name("foo.cpython-311.so"), None);
+        assert_eq!(extension_module_name("foo.cpython-311-linux.pyd"), None);
+        assert_eq!(extension_module_name("foo.bar.cpython-311-linux.so"), None);
+    }
+
+    #[test]
+    fn test_find_one_part_with_extension_module() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::file("compiled.cpython-311-x86_64-linux-gnu.so"),
+                TestPath::file("typed.cpython-311-x86_64-linux-gnu.so"),
+                TestPath::file("typed.pyi"),
+                TestPath::dir(
+                    "pkg",
+                    vec![
+                        TestPath::file("__init__.abi3.so"),
+                        TestPath::file("__init__.pyi"),
+                    ],
+                ),
+            ],
+        );
+        let find = |name: &str, style_filter| {
+            find_one_part(
+                name,
+                [root.to_path_buf()].iter(),
+                style_filter,
+                &mut None,
+                &DirEntryCache::new(),
+                None,
+            )
+            .map(|x| x.0)
+        };
+        let dir_cache = DirEntryCache::new();
+
+        let compiled = find("compiled", None).unwrap();
+        assert_eq!(
+            compiled,
+            FindResult::CompiledModule(root.join("compiled.cpython-311-x86_64-linux-gnu.so"))
+        );
+        assert!(!is_extension_module_stub(&compiled, &dir_cache));
+        assert_eq!(find("compiled", Some(ModuleStyle::Interface)), None);
+
+        let typed = find("typed", None).unwrap();
+        assert_eq!(
+            typed,
+            FindResult::SingleFilePyiModule(root.join("typed.pyi"))
+        );
+        assert!(is_extension_module_stub(&typed, &dir_cache));
+
+        let pkg = find("pkg", None).unwrap();
+        assert_eq!(
+            pkg,
+            FindResult::RegularPackage(root.join("pkg/__init__.pyi"), root.join("pkg"))
+        );
+        assert!(is_extension_module_stub(&pkg, &dir_cache));
+
+        assert_eq!(
+            find_module_prefixes(ModuleName::from_str("comp"), [root.to_path_buf()].iter()),
+            vec![ModuleName::from_str("compiled")]
+        );
+    }
+
+    #[test]
+    fn test_is_partial_stub_package() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir(
+                    "partial-stubs",
+                    vec![
+                        TestPath::file_with_contents("py.typed", "partial\n"),
+                        TestPath::dir("sub", vec![TestPath::file("mod.pyi")]),
+                    ],
+                ),
+                TestPath::dir(
+                    "full-stubs",
+                    vec![
+                        TestPath::file("py.typed"),
+                        TestPath::dir("sub", vec![TestPath::file("mod.pyi")]),
+                    ],
+                ),
+            ],
+        );
+        let dir_cache = DirEntryCache::new();
+        let partial = find_module_components(
+            "partial-stubs",
+            "sub",
+            [root.to_path_buf()].iter(),
+            None,
+            &mut None,
+            &dir_cache,
+            None,
+        )
+        .unwrap();
+        assert!(is_partial_stub_package(
+            ModuleName::from_str("partial.sub"),
+            &partial,
+            &dir_cache
+        ));
+        let full = find_module_components(
+            "full-stubs",
+            "sub",
+            [root.to_path_buf()].iter(),
+            None,
+            &mut None,
+            &dir_cache,
+            None,
+        )
+        .unwrap();
+        assert!(!is_partial_stub_package(
+            ModuleName::from_str("full.sub"),
+            &full,
+            &dir_cache
+        ));
+    }
+
     #[test]
     fn test_continue_find_module_with_pyc() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -17,6 +17,8 @@ use pyrefly_util::absolutize::Absolutize as _;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::display;
 
+use crate::base::ConstructorOverrides;
+use crate::base::FollowImports;
 use crate::base::InferReturnTypes;
 use crate::base::Preset;
 use crate::base::RecursionOverflowHandler;
@@ -24,6 +26,7 @@ use crate::base::UntypedDefBehavior;
 use crate::config::ConfigFile;
 use crate::config::SynthesizedPresetReason;
 use crate::config::validate_path;
+use crate::environment::venv;
 use crate::error::ErrorDisplayConfig;
 use crate::error_kind::ErrorKind;
 use crate::error_kind::Severity;
@@ -63,6 +66,10 @@ pub struct EnvironmentArgs {
     #[arg(long, value_parser = absolute_path_parser)]
     pub(crate) search_path: Option<Vec<PathBuf>>,
 
+    /// Directories of local `.pyi` stubs, searched before typeshed and site packages.
+    #[arg(long, value_parser = absolute_path_parser)]
+    pub(crate) stub_path: Option<Vec<PathBuf>>,
+
     /// Disable Pyrefly default heuristics, specifically those around
     /// constructing a modified search path. Setting this flag will instruct
     /// Pyrefly to use the exact `search_path` you give it through your config
@@ -118,6 +125,11 @@ pub struct EnvironmentArgs {
     #[arg(long, value_name = "COMMAND", group = "env_source")]
     pub(crate) fallback_python_interpreter_name: Option<String>,
 
+    /// The root of a virtual environment (the directory containing `pyvenv.cfg`) whose
+    /// interpreter will be queried, even if it isn't activated.
+    #[arg(long, value_name = "DIR", group = "env_source")]
+    pub(crate) venv: Option<PathBuf>,
+
     /// Skip doing any automatic querying for `python-interpreter-path`,
     /// `fallback-python-interpreter-name`, or `conda-environment`
     #[arg(long, group = "env_source")]
@@ -140,6 +152,15 @@ impl EnvironmentArgs {
         }
         validate_arg("--site-package-path", self.site_package_path.as_deref())?;
         validate_arg("--search-path", self.search_path.as_deref())?;
+        validate_arg("--stub-path", self.stub_path.as_deref())?;
+        if let Some(venv) = &self.venv
+            && venv::find_in_venv(venv).is_none()
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid --venv: no Python interpreter found in `{}`",
+                venv.display()
+            ));
+        }
         Ok(())
     }
 
@@ -169,6 +190,10 @@ impl EnvironmentArgs {
         if let Some(x) = &self.search_path {
             config.search_path_from_args = x.clone();
         }
+        if let Some(x) = &self.stub_path {
+            config.stub_path = x.clone();
+            config.stub_path_from_args = true;
+        }
         if let Some(x) = &self.disable_search_path_heuristics {
             config.disable_search_path_heuristics = *x;
         }
@@ -198,6 +223,11 @@ impl EnvironmentArgs {
             config.interpreters.python_interpreter_path = None;
             config.interpreters.conda_environment = None;
         }
+        if let Some(x) = self.venv.as_deref().and_then(venv::find_in_venv) {
+            config.interpreters.python_interpreter_path = Some(ConfigOrigin::cli(x));
+            config.interpreters.fallback_python_interpreter_name = None;
+            config.interpreters.conda_environment = None;
+        }
         if let Some(conda_environment) = &self.conda_environment {
             config.interpreters.conda_environment =
                 Some(ConfigOrigin::cli(conda_environment.clone()));
@@ -238,6 +268,10 @@ pub struct ConfigOverrideArgs {
     /// related import errors.
     #[arg(long)]
     ignore_missing_imports: Option<Vec<String>>,
+    /// How to treat Python source files outside the project that are found by following imports.
+    /// Values: normal, silent (default), skip, error.
+    #[arg(long)]
+    follow_imports: Option<FollowImports>,
     /// Whether to ignore type errors in generated code.
     #[arg(
         long,
@@ -246,6 +280,14 @@ pub struct ConfigOverrideArgs {
         num_args = 0..=1
     )]
     ignore_errors_in_generated_code: Option<bool>,
+    /// Whether to report errors in third-party modules from the site package path.
+    #[arg(
+        long,
+        default_missing_value = "true",
+        require_equals = true,
+        num_args = 0..=1
+    )]
+    check_site_packages: Option<bool>,
     /// If this is true, infer type variables not determined by a call or constructor based on their first usage.
     /// For example, the type of an empty container would be determined by the first thing you put into it.
     /// If this is false, any unsolved type variables at the end of a call or constructor will be replaced with `Any`.
@@ -296,6 +338,15 @@ pub struct ConfigOverrideArgs {
     /// Defaults to type,pyrefly. Passing the names of all tools is equivalent to `--permissive-ignores`.
     #[arg(long, value_delimiter = ',')]
     enabled_ignores: Option<Vec<Tool>>,
+    /// Only honor `# pyrefly: ignore[...]` suppressions that name error codes, so that bare
+    /// `# type: ignore` comments and those from other tools suppress nothing.
+    #[arg(
+        long,
+        default_missing_value = "true",
+        require_equals = true,
+        num_args = 0..=1
+    )]
+    require_ignore_codes: Option<bool>,
     /// Force this rule to emit an error. Can be passed multiple times or as a comma-separated list.
     #[arg(long, hide_possible_values = true, value_delimiter = ',')]
     error: Vec<ErrorKind>,
@@ -315,6 +366,9 @@ pub struct ConfigOverrideArgs {
     /// How to handle when recursion depth limit is exceeded.
     #[arg(long)]
     recursion_overflow_handler: Option<RecursionOverflowHandler>,
+    /// Enable lints for annotation style issues, like implicit `Optional` and `typing.List`.
+    #[arg(long)]
+    annotation_style_lints: Option<bool>,
     /// Enable PyTorch efficiency lints that detect common GPU performance anti-patterns.
     #[arg(long)]
     pytorch_efficiency_lints: Option<bool>,
@@ -353,6 +407,50 @@ pub struct ConfigOverrideArgs {
         num_args = 0..=1
     )]
     spec_compliant_overloads: Option<bool>,
+    /// Whether `hasattr(x, "name")` narrows `x` so that `x.name` can be accessed afterwards.
+    #[arg(
+        long,
+        default_missing_value = "true",
+        require_equals = true,
+        num_args = 0..=1
+    )]
+    hasattr_narrowing: Option<bool>,
+    /// Show a union that came from a type alias by the alias name in error messages and hovers
+    /// when it has more than this many members. Set to 0 to always expand unions.
+    #[arg(long)]
+    union_display_limit: Option<usize>,
+    /// Always show unions fully expanded in error messages, instead of by their type alias name.
+    #[arg(long, conflicts_with = "union_display_limit")]
+    verbose_types: bool,
+    /// Widen an inferred union to the literals' class once it holds more than this many
+    /// literals of one kind.
+    #[arg(long)]
+    literal_union_limit: Option<usize>,
+    /// Widen an inferred union to the enum class once it holds more than this many members
+    /// of one enum.
+    #[arg(long)]
+    enum_union_limit: Option<usize>,
+    /// Widen an inferred union to the closest base class its members share once it still
+    /// holds more than this many members after literals are widened.
+    #[arg(long)]
+    union_limit: Option<usize>,
+    /// Give up on an assignability check, treating it as passing, once it recurses more than
+    /// this many levels into its types.
+    #[arg(long)]
+    subset_recursion_limit: Option<usize>,
+    /// Whether overrides of `__init__` and `__new__` are checked against the parent class:
+    /// never (`ignore`), only with `@override` (`explicit`, the default), or always (`check`).
+    #[arg(long)]
+    constructor_overrides: Option<ConstructorOverrides>,
+    /// Whether a missing attribute on an optional value (`X | None`) is explained with a
+    /// hint to check for `None` first.
+    #[arg(
+        long,
+        default_missing_value = "true",
+        require_equals = true,
+        num_args = 0..=1
+    )]
+    optional_attribute_hints: Option<bool>,
 }
 
 impl ConfigOverrideArgs {
@@ -480,9 +578,18 @@ impl ConfigOverrideArgs {
                     .collect(),
             );
         }
+        if let Some(x) = &self.follow_imports {
+            config.root.follow_imports = Some(*x);
+        }
         if let Some(x) = &self.ignore_errors_in_generated_code {
             config.root.ignore_errors_in_generated_code = Some(*x);
         }
+        if let Some(x) = &self.require_ignore_codes {
+            config.root.require_ignore_codes = Some(*x);
+        }
+        if let Some(x) = &self.check_site_packages {
+            config.check_site_packages = *x;
+        }
         if let Some(x) = &self.infer_with_first_use {
             config.root.infer_with_first_use = Some(*x);
         }
@@ -492,6 +599,9 @@ impl ConfigOverrideArgs {
         if let Some(x) = &self.recursion_overflow_handler {
             config.root.recursion_overflow_handler = Some(*x);
         }
+        if let Some(x) = &self.annotation_style_lints {
+            config.root.annotation_style_lints = Some(*x);
+        }
         if let Some(x) = &self.pytorch_efficiency_lints {
             config.root.pytorch_efficiency_lints = Some(*x);
         }
@@ -504,6 +614,36 @@ impl ConfigOverrideArgs {
         if let Some(x) = &self.spec_compliant_overloads {
             config.root.spec_compliant_overloads = Some(*x);
         }
+        if let Some(x) = &self.hasattr_narrowing {
+            config.root.hasattr_narrowing = Some(*x);
+        }
+        if let Some(x) = &self.union_display_limit {
+            config.root.union_display_limit = Some(*x);
+        }
+        if let Some(x) = &self.literal_union_limit {
+            config.root.literal_union_limit = Some(*x);
+        }
+        if let Some(x) = &self.enum_union_limit {
+            config.root.enum_union_limit = Some(*x);
+        }
+        if let Some(x) = &self.union_limit {
+            config.root.union_limit = Some(*x);
+        }
+        if let Some(x) = &self.subset_recursion_limit {
+            config.root.subset_recursion_limit = Some(*x);
+        }
+        if let Some(x) = &self.constructor_overrides {
+            config.root.constructor_overrides = Some(*x);
+        }
+        if let Some(x) = &self.optional_attribute_hints {
+            config.root.optional_attribute_hints = Some(*x);
+        }
+        if self.verbose_types {
+            config.root.union_display_limit = Some(0);
+            for sub_config in config.sub_configs.iter_mut() {
+                sub_config.settings.union_display_limit = Some(0);
+            }
+        }
         let apply_error_settings = |error_config: &mut ErrorDisplayConfig| {
             for error_kind in &self.error {
                 error_config.set_error_severity(*error_kind, Severity::Error);
@@ -562,6 +702,8 @@ impl ConfigOverrideArgs {
 
 #[cfg(test)]
 mod tests {
+    use std::env;
+
     use super::*;
 
     #[test]
@@ -602,4 +744,25 @@ mod tests {
             Some(PythonPlatform::All)
         );
     }
+
+    #[test]
+    fn stub_path_flag_is_described_as_from_command_line() {
+        let stubs = env::temp_dir();
+        let args =
+            ConfigOverrideArgs::parse_from(["pyrefly", "--stub-path", stubs.to_str().unwrap()]);
+        args.validate().unwrap();
+        let (config, errors) = args.override_config(ConfigFile::default());
+        assert!(errors.is_empty());
+        let parts = config
+            .structured_import_lookup_path(None)
+            .iter()
+            .map(|part| part.to_string())
+            .collect::<Vec<_>>();
+        assert!(
+            parts
+                .iter()
+                .any(|part| part.starts_with("Stub path (from command line)")),
+            "{parts:?}"
+        );
+    }
 }
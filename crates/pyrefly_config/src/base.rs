@@ -19,6 +19,7 @@ use toml::Table;
 use crate::error::ErrorDisplayConfig;
 use crate::error_kind::ErrorKind;
 use crate::error_kind::Severity;
+use crate::literal_string_sink::LiteralStringSinks;
 use crate::module_wildcard::ModuleWildcard;
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
@@ -45,6 +46,38 @@ pub enum InferReturnTypes {
     Checked,
 }
 
+/// Controls whether overrides of `__init__` and `__new__` are checked for compatibility
+/// with the parent class.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
+#[derive(ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConstructorOverrides {
+    /// Never check constructor overrides, even ones marked with `@override`.
+    Ignore,
+    /// Only check constructor overrides marked with `@override`.
+    #[default]
+    Explicit,
+    /// Check all constructor overrides.
+    Check,
+}
+
+/// Controls how Pyrefly treats Python source files outside the project that are found by
+/// following imports.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
+#[derive(ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum FollowImports {
+    /// Analyze imported modules and report their errors.
+    Normal,
+    /// Analyze imported modules, but don't report their errors.
+    #[default]
+    Silent,
+    /// Don't analyze imported modules; names imported from them are `Any`.
+    Skip,
+    /// Like `skip`, but also report an error at the import.
+    Error,
+}
+
 /// How to handle when recursion depth limit is exceeded.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Default)]
 #[derive(ValueEnum)]
@@ -223,6 +256,10 @@ pub struct ConfigBase {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled_ignores: Option<SmallSet<Tool>>,
 
+    /// Only honor `# pyrefly: ignore[...]` suppressions that name error codes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_ignore_codes: Option<bool>,
+
     /// Modules from which import errors should be ignored
     /// and the module should always be replaced with `typing.Any`
     #[serde(
@@ -239,6 +276,11 @@ pub struct ConfigBase {
     #[serde(default, skip_serializing_if = "crate::util::none_or_empty")]
     pub(crate) ignore_missing_imports: Option<Vec<ModuleWildcard>>,
 
+    /// How to treat Python source files outside the project that are found by following imports.
+    /// Stub files are always followed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_imports: Option<FollowImports>,
+
     /// Deprecated: use `check-unannotated-defs` and `infer-return-types` instead.
     /// How should we handle analyzing and inferring the function signature if it's untyped?
     #[serde(
@@ -284,6 +326,13 @@ pub struct ConfigBase {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub infer_with_first_use: Option<bool>,
 
+    /// Enable lints for annotation style issues that affect checking quality.
+    /// When true, `implicit-optional`, `deprecated-typing-alias`, `unnecessary-quoted-annotation`
+    /// and `implicit-any-type-argument` are set to `Warn` severity unless individually
+    /// overridden in `[errors]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation_style_lints: Option<bool>,
+
     /// Enable PyTorch efficiency lints that detect common GPU performance anti-patterns.
     /// When true, all `pytorch-efficiency-lint-*` error kinds are set to `Warn` severity
     /// unless individually overridden in `[errors]`.
@@ -320,6 +369,57 @@ pub struct ConfigBase {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub spec_compliant_overloads: Option<bool>,
 
+    /// Whether `hasattr(x, "name")` narrows `x` so that `x.name` can be accessed afterwards.
+    /// When true (the default), the attribute is treated as present in the positive branch.
+    /// When false, `hasattr` checks have no effect on the type of `x`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hasattr_narrowing: Option<bool>,
+
+    /// A union that came from a type alias is shown by the alias name in error messages and hovers
+    /// when it has more than this many members, rather than being expanded.
+    /// Set to 0 to always expand unions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub union_display_limit: Option<usize>,
+
+    /// An inferred union with more than this many literals of one kind (e.g. `str` literals)
+    /// widens them to their class, to keep very large unions from slowing down checking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal_union_limit: Option<usize>,
+
+    /// An inferred union with more than this many members of one enum widens them to the enum
+    /// class, even when not every member is present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enum_union_limit: Option<usize>,
+
+    /// An inferred union that still has more than this many members after literals are widened
+    /// becomes the closest base class its members share.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub union_limit: Option<usize>,
+
+    /// How deeply one assignability check may recurse into the structure of its types (e.g.
+    /// protocol members of recursive generics) before giving up and treating the check as
+    /// passing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subset_recursion_limit: Option<usize>,
+
+    /// Functions, by qualified name, whose first parameter must be passed a `LiteralString`
+    /// when it is passed a string, e.g. `sqlite3.Cursor.execute`. An entry such as
+    /// `subprocess.run(shell=True)` only applies to calls passing that keyword argument as `True`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal_string_sinks: Option<LiteralStringSinks>,
+
+    /// Whether overrides of `__init__` and `__new__` are checked against the parent class.
+    /// - `ignore`: never, even when marked with `@override`.
+    /// - `explicit`: only when marked with `@override` (default).
+    /// - `check`: always.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constructor_overrides: Option<ConstructorOverrides>,
+
+    /// Whether a missing attribute on an optional value (`X | None`) is explained with a
+    /// hint to check for `None` first. Defaults to true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional_attribute_hints: Option<bool>,
+
     /// Any unknown config items
     #[serde(default, flatten)]
     pub(crate) extras: ExtraConfigs,
@@ -380,6 +480,10 @@ impl ConfigBase {
         base.ignore_missing_imports.as_deref()
     }
 
+    pub fn get_follow_imports(base: &Self) -> Option<FollowImports> {
+        base.follow_imports
+    }
+
     pub fn get_check_unannotated_defs(base: &Self) -> Option<bool> {
         base.check_unannotated_defs
     }
@@ -404,6 +508,10 @@ impl ConfigBase {
         base.enabled_ignores.as_ref()
     }
 
+    pub fn get_require_ignore_codes(base: &Self) -> Option<bool> {
+        base.require_ignore_codes
+    }
+
     /// Get the recursion limit configuration, if enabled.
     /// Returns None if recursion_depth_limit is not set or is 0.
     pub fn get_recursion_limit_config(base: &Self) -> Option<RecursionLimitConfig> {
@@ -432,6 +540,42 @@ impl ConfigBase {
     pub fn get_spec_compliant_overloads(base: &Self) -> Option<bool> {
         base.spec_compliant_overloads
     }
+
+    pub fn get_hasattr_narrowing(base: &Self) -> Option<bool> {
+        base.hasattr_narrowing
+    }
+
+    pub fn get_union_display_limit(base: &Self) -> Option<usize> {
+        base.union_display_limit
+    }
+
+    pub fn get_literal_union_limit(base: &Self) -> Option<usize> {
+        base.literal_union_limit
+    }
+
+    pub fn get_enum_union_limit(base: &Self) -> Option<usize> {
+        base.enum_union_limit
+    }
+
+    pub fn get_union_limit(base: &Self) -> Option<usize> {
+        base.union_limit
+    }
+
+    pub fn get_subset_recursion_limit(base: &Self) -> Option<usize> {
+        base.subset_recursion_limit
+    }
+
+    pub fn get_literal_string_sinks(base: &Self) -> Option<&LiteralStringSinks> {
+        base.literal_string_sinks.as_ref()
+    }
+
+    pub fn get_constructor_overrides(base: &Self) -> Option<ConstructorOverrides> {
+        base.constructor_overrides
+    }
+
+    pub fn get_optional_attribute_hints(base: &Self) -> Option<bool> {
+        base.optional_attribute_hints
+    }
 }
 
 #[cfg(test)]
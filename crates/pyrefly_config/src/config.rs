@@ -33,6 +33,7 @@ use pyrefly_python::ignore::Tool;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_name::ModuleNameWithKind;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
 use pyrefly_python::sys_info::PythonPlatform;
 use pyrefly_python::sys_info::PythonVersion;
 use pyrefly_python::sys_info::SysInfo;
@@ -60,10 +61,13 @@ use tracing::debug;
 use tracing::error;
 
 use crate::base::ConfigBase;
+use crate::base::ConstructorOverrides;
 use crate::base::ExtraConfigs;
+use crate::base::FollowImports;
 use crate::base::InferReturnTypes;
 use crate::base::Preset;
 use crate::base::RecursionLimitConfig;
+use crate::dynamic_modules::DynamicModules;
 use crate::environment::environment::PythonEnvironment;
 use crate::environment::interpreters::Interpreters;
 use crate::error::ErrorConfig;
@@ -71,6 +75,7 @@ use crate::error::ErrorDisplayConfig;
 use crate::error_kind::ErrorKind;
 use crate::error_kind::Severity;
 use crate::finder::ConfigError;
+use crate::literal_string_sink::LiteralStringSinks;
 use crate::migration::run::MigratedFromKind;
 use crate::module_wildcard::Match;
 use crate::pyproject::PyProject;
@@ -206,6 +211,8 @@ pub enum OutputFormat {
     JunitXml,
     /// Emit CodeClimate issues in a JSON array (e.g. for GitLab Code Quality reports)
     CodeClimate,
+    /// Emit a SARIF 2.1.0 log (e.g. for GitHub code scanning)
+    Sarif,
     /// Only show error count, omitting individual errors
     OmitErrors,
 }
@@ -441,8 +448,11 @@ impl FallbackSearchPath {
 pub enum ImportLookupPathPart<'a> {
     SearchPathFromArgs(&'a [PathBuf]),
     SearchPathFromFile(&'a [PathBuf]),
+    ExtraSearchRoots(&'a [PathBuf]),
     ImportRoot(Option<&'a PathBuf>),
     FallbackSearchPath(&'a FallbackSearchPath, Option<&'a Path>),
+    StubPathFromArgs(&'a [PathBuf]),
+    StubPathFromFile(&'a [PathBuf]),
     SitePackagePath(&'a [PathBuf]),
     InterpreterSitePackagePath(&'a [PathBuf]),
     BuildSystem(Option<Target>),
@@ -457,6 +467,9 @@ impl Display for ImportLookupPathPart<'_> {
             Self::SearchPathFromFile(paths) => {
                 write!(f, "Search path (from config file): {paths:?}")
             }
+            Self::ExtraSearchRoots(paths) => {
+                write!(f, "Extra search roots (from config file): {paths:?}")
+            }
             Self::ImportRoot(Some(root)) => {
                 write!(f, "Import root (inferred from project layout): {root:?}")
             }
@@ -478,6 +491,12 @@ impl Display for ImportLookupPathPart<'_> {
                     fallback.repr_for_directory(*start),
                 )
             }
+            Self::StubPathFromArgs(paths) => {
+                write!(f, "Stub path (from command line): {paths:?}")
+            }
+            Self::StubPathFromFile(paths) => {
+                write!(f, "Stub path (from config file): {paths:?}")
+            }
             Self::SitePackagePath(paths) => {
                 write!(f, "Site package path from user: {paths:?}")
             }
@@ -500,6 +519,9 @@ impl ImportLookupPathPart<'_> {
         match self {
             Self::SearchPathFromArgs(paths)
             | Self::SearchPathFromFile(paths)
+            | Self::ExtraSearchRoots(paths)
+            | Self::StubPathFromArgs(paths)
+            | Self::StubPathFromFile(paths)
             | Self::SitePackagePath(paths)
             | Self::InterpreterSitePackagePath(paths) => paths.is_empty(),
             Self::ImportRoot(root) => root.is_none(),
@@ -562,6 +584,17 @@ pub struct ConfigFile {
          )]
     pub search_path_from_file: Vec<PathBuf>,
 
+    /// Globs of additional import roots, for projects with more than one source root,
+    /// such as a monorepo whose packages each have their own `src/` directory.
+    /// Every directory a glob matches is searched after the search path.
+    /// Use ConfigFile::search_path() to get the full search path.
+    #[serde(default, skip_serializing_if = "Globs::is_empty")]
+    pub extra_search_roots: Globs,
+
+    /// The directories matched by `extra_search_roots`, found in `configure()`.
+    #[serde(skip)]
+    pub extra_search_root_dirs: Vec<PathBuf>,
+
     /// The automatically inferred subdirectory that importable Python packages live in.
     #[serde(skip)]
     pub import_root: Option<PathBuf>,
@@ -586,6 +619,15 @@ pub struct ConfigFile {
     #[serde(default, skip_serializing_if = "crate::util::skip_default_false")]
     pub enable_fallback_search_path: bool,
 
+    /// Directories of local `.pyi` stubs, typically overrides for third-party packages.
+    /// These are searched after the search path, but before typeshed and site packages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stub_path: Vec<PathBuf>,
+
+    /// Whether `stub_path` was set with `--stub-path`, which replaces the config file's value.
+    #[serde(skip)]
+    pub stub_path_from_args: bool,
+
     /// Override the bundled typeshed with a custom path.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub typeshed_path: Option<PathBuf>,
@@ -663,6 +705,12 @@ pub struct ConfigFile {
     #[serde(default, skip_serializing_if = "crate::util::skip_default_false")]
     pub skip_lsp_config_indexing: bool,
 
+    /// Should errors be reported for third-party modules, i.e. those from the site package
+    /// path? They are always analyzed for their interfaces, but their errors are hidden
+    /// unless this is set.
+    #[serde(default, skip_serializing_if = "crate::util::skip_default_false")]
+    pub check_site_packages: bool,
+
     /// Additional file extensions to treat as Python source files.
     /// Used for Python dialects that use non-standard extensions.
     /// Unlike standard Python extensions, these extensions become part
@@ -671,6 +719,12 @@ pub struct ConfigFile {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extra_file_extensions: Vec<String>,
 
+    /// Modules that only exist at runtime, such as settings objects or plugin registries,
+    /// mapped to the names they define and the types of those names. Pyrefly synthesizes
+    /// a stub for each of them.
+    #[serde(default, skip_serializing_if = "DynamicModules::is_empty")]
+    pub dynamic_modules: DynamicModules,
+
     /// Runtime-only metadata. Populated by `resolve_unconfigured_config`
     /// when this `ConfigFile` was synthesized rather than loaded from a
     /// `pyrefly.toml` / `[tool.pyrefly]` section, and by the `--preset`
@@ -697,6 +751,8 @@ impl Default for ConfigFile {
             },
             search_path_from_args: Vec::new(),
             search_path_from_file: Vec::new(),
+            extra_search_roots: Globs::empty(),
+            extra_search_root_dirs: Vec::new(),
             disable_search_path_heuristics: false,
             enable_fallback_search_path: false,
             disable_project_excludes_heuristics: false,
@@ -710,12 +766,16 @@ impl Default for ConfigFile {
             build_system: Default::default(),
             source_db: Default::default(),
             use_ignore_files: true,
+            stub_path: Vec::new(),
+            stub_path_from_args: false,
             typeshed_path: None,
             baseline: None,
             min_severity: None,
             output_format: None,
             skip_lsp_config_indexing: false,
+            check_site_packages: false,
             extra_file_extensions: Vec::new(),
+            dynamic_modules: Default::default(),
             synthesized_preset_reason: None,
         }
     }
@@ -821,6 +881,19 @@ impl ConfigFile {
     /// Files that don't contain pyrefly-specific config information but indicate that we're at the
     /// root of a Python project, which should be added to the search path.
     pub const ADDITIONAL_ROOT_FILE_NAMES: &[&str] = &["mypy.ini", "pyrightconfig.json"];
+    /// Aliased unions with more members than this are shown by name in error messages.
+    pub const DEFAULT_UNION_DISPLAY_LIMIT: usize = 5;
+    /// Inferred unions with more literals of one kind than this are widened to the class.
+    pub const DEFAULT_LITERAL_UNION_LIMIT: usize = 256;
+    /// Inferred unions with more members of one enum than this are widened to the enum.
+    pub const DEFAULT_ENUM_UNION_LIMIT: usize = 4096;
+    /// Inferred unions with more members than this, after widening literals, are widened to
+    /// the closest base class their members share.
+    pub const DEFAULT_UNION_LIMIT: usize = 4096;
+    /// Assignability checks that recurse deeper than this give up. All practical types are
+    /// shallower than this, and it is low enough to avoid a stack overflow: each level of
+    /// `is_subset_eq` can use several KB of stack.
+    pub const DEFAULT_SUBSET_RECURSION_LIMIT: usize = 200;
 
     /// Writes the configuration to a file in the specified directory.
     pub fn write_to_toml_in_directory(&self, directory: &Path) -> Result<()> {
@@ -877,10 +950,37 @@ impl ConfigFile {
         !self.extra_file_extensions.is_empty()
     }
 
+    /// The path of the stub synthesized for `module`, if it is declared in `dynamic-modules`
+    /// or is a package containing a declared module.
+    pub fn dynamic_module_path(&self, module: ModuleName) -> Option<ModulePath> {
+        let path = self.dynamic_modules.relative_path(module)?;
+        Some(ModulePath::memory(match self.source.root() {
+            Some(root) => root.join(path),
+            None => path,
+        }))
+    }
+
+    /// The source of the stub synthesized at `path`, as returned by `dynamic_module_path`.
+    pub fn dynamic_module_source(&self, path: &ModulePath) -> Option<String> {
+        if self.dynamic_modules.is_empty() {
+            return None;
+        }
+        let ModulePathDetails::Memory(path) = path.details() else {
+            return None;
+        };
+        let relative = match self.source.root() {
+            Some(root) => path.strip_prefix(root).ok()?,
+            None => path.as_path(),
+        };
+        self.dynamic_modules
+            .stub(self.dynamic_modules.module_for_relative_path(relative)?)
+    }
+
     pub fn search_path(&self) -> impl Iterator<Item = &PathBuf> + Clone {
         self.search_path_from_args
             .iter()
             .chain(self.search_path_from_file.iter())
+            .chain(self.extra_search_root_dirs.iter())
             .chain(if self.disable_search_path_heuristics {
                 None.iter()
             } else {
@@ -888,12 +988,13 @@ impl ConfigFile {
             })
     }
 
-    /// Explicit search paths from CLI args and config file, excluding the
-    /// heuristic import_root.
+    /// Explicit search paths from CLI args and config file, including the directories
+    /// matched by `extra_search_roots`, excluding the heuristic import_root.
     pub fn explicit_search_path(&self) -> impl Iterator<Item = &PathBuf> + Clone {
         self.search_path_from_args
             .iter()
             .chain(self.search_path_from_file.iter())
+            .chain(self.extra_search_root_dirs.iter())
     }
 
     /// The heuristic import_root, if search path heuristics are enabled.
@@ -934,8 +1035,18 @@ impl ConfigFile {
         result.push(ImportLookupPathPart::SearchPathFromFile(
             &self.search_path_from_file,
         ));
+        result.push(ImportLookupPathPart::ExtraSearchRoots(
+            &self.extra_search_root_dirs,
+        ));
         if !self.disable_search_path_heuristics {
             result.push(ImportLookupPathPart::ImportRoot(self.import_root.as_ref()));
+        }
+        result.push(if self.stub_path_from_args {
+            ImportLookupPathPart::StubPathFromArgs(&self.stub_path)
+        } else {
+            ImportLookupPathPart::StubPathFromFile(&self.stub_path)
+        });
+        if !self.disable_search_path_heuristics {
             result.push(ImportLookupPathPart::FallbackSearchPath(
                 &self.fallback_search_path,
                 origin.and_then(|p| p.parent()),
@@ -1005,6 +1116,12 @@ impl ConfigFile {
         found_match == Some(true)
     }
 
+    pub fn follow_imports(&self, path: Option<&Path>) -> FollowImports {
+        path.and_then(|path| self.get_from_sub_configs(ConfigBase::get_follow_imports, path))
+            .or(self.root.follow_imports)
+            .unwrap_or_default()
+    }
+
     pub fn check_unannotated_defs(&self, path: &Path) -> bool {
         self.get_from_sub_configs(ConfigBase::get_check_unannotated_defs, path)
             .unwrap_or_else(|| self.root.check_unannotated_defs.unwrap())
@@ -1060,6 +1177,73 @@ impl ConfigFile {
                  self.root.spec_compliant_overloads.unwrap())
     }
 
+    pub fn hasattr_narrowing(&self, path: &Path) -> bool {
+        self.get_from_sub_configs(ConfigBase::get_hasattr_narrowing, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.hasattr_narrowing.unwrap())
+    }
+
+    pub fn union_display_limit(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_union_display_limit, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.union_display_limit.unwrap())
+    }
+
+    pub fn literal_union_limit(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_literal_union_limit, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.literal_union_limit.unwrap())
+    }
+
+    pub fn enum_union_limit(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_enum_union_limit, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.enum_union_limit.unwrap())
+    }
+
+    pub fn union_limit(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_union_limit, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.union_limit.unwrap())
+    }
+
+    pub fn subset_recursion_limit(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_subset_recursion_limit, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.subset_recursion_limit.unwrap())
+    }
+
+    pub fn literal_string_sinks(&self, path: &Path) -> LiteralStringSinks {
+        self.get_from_sub_configs(ConfigBase::get_literal_string_sinks, path)
+            .or(self.root.literal_string_sinks.as_ref())
+            .map(|sinks| sinks.dupe())
+            .unwrap_or_default()
+    }
+
+    pub fn constructor_overrides(&self, path: &Path) -> ConstructorOverrides {
+        self.get_from_sub_configs(ConfigBase::get_constructor_overrides, path)
+            .or(self.root.constructor_overrides)
+            .unwrap_or_default()
+    }
+
+    pub fn optional_attribute_hints(&self, path: &Path) -> bool {
+        self.get_from_sub_configs(ConfigBase::get_optional_attribute_hints, path)
+            .or(self.root.optional_attribute_hints)
+            .unwrap_or(true)
+    }
+
     pub fn enabled_ignores(&self, path: &Path) -> &SmallSet<Tool> {
         self.get_from_sub_configs(ConfigBase::get_enabled_ignores, path)
             .unwrap_or_else(||
@@ -1068,18 +1252,60 @@ impl ConfigFile {
                  self.root.enabled_ignores.as_ref().unwrap())
     }
 
+    pub fn require_ignore_codes(&self, path: &Path) -> bool {
+        self.get_from_sub_configs(ConfigBase::get_require_ignore_codes, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.require_ignore_codes.unwrap())
+    }
+
     /// Get the recursion limit configuration.
     /// Returns None if not set (disabled).
     pub fn recursion_limit_config(&self) -> Option<RecursionLimitConfig> {
         ConfigBase::get_recursion_limit_config(&self.root)
     }
 
+    /// Should errors in the module at `path` be reported? Third-party modules, from the site
+    /// package path or bundled third-party stubs, only report errors with `check-site-packages`,
+    /// or, for Python source files reached by following imports from `importer`, when the
+    /// importer has `follow-imports = "normal"`.
+    pub fn reports_errors_for(&self, path: &ModulePath, importer: Option<&Path>) -> bool {
+        if self.check_site_packages {
+            return true;
+        }
+        if !path.is_interface()
+            && let Some(importer) = importer
+        {
+            match self.follow_imports(Some(importer)) {
+                FollowImports::Normal => return true,
+                FollowImports::Silent | FollowImports::Skip | FollowImports::Error => {}
+            }
+        }
+        match path.details() {
+            ModulePathDetails::BundledTypeshedThirdParty(_)
+            | ModulePathDetails::BundledThirdParty(_) => false,
+            ModulePathDetails::FileSystem(path) => !self.site_package_path().any(|site_root| {
+                // A search path inside the site package path (e.g. an editable install)
+                // still belongs to the project.
+                path.starts_with(site_root)
+                    && !self.search_path().any(|search_root| {
+                        search_root.starts_with(site_root) && path.starts_with(search_root)
+                    })
+            }),
+            ModulePathDetails::Namespace(_)
+            | ModulePathDetails::Memory(_)
+            | ModulePathDetails::BundledTypeshed(_) => true,
+        }
+    }
+
     pub fn get_error_config(&self, path: &Path) -> ErrorConfig<'_> {
         ErrorConfig::new(
             self.errors(path),
             self.ignore_errors_in_generated_code(path),
             self.enabled_ignores(path).clone(),
         )
+        .with_require_ignore_codes(self.require_ignore_codes(path))
     }
 
     /// Filter to sub configs whose matches succeed for the given `path`,
@@ -1125,13 +1351,29 @@ impl ConfigFile {
                         .unwrap_or(ModuleNameWithKind::guaranteed(ModuleName::unknown()))
                 } else {
                     let fallback_paths = self.fallback_search_path.for_directory(Some(path));
-                    ModuleName::from_path_with_fallback(
+                    match ModuleName::from_path_with_fallback(
                         path,
                         search_paths,
                         fallback_paths.iter(),
                         &self.extra_file_extensions,
-                    )
-                    .unwrap_or(ModuleNameWithKind::guaranteed(ModuleName::unknown()))
+                    ) {
+                        Some(kind) if !kind.is_fallback() => kind,
+                        kind => {
+                            // The file isn't under any search root, so name it after the
+                            // package it lives in. That keeps its relative imports resolvable
+                            // from the package's parent, which is one of the fallback paths.
+                            // This only runs for files outside every search root, and only
+                            // checks the directories above one file, so a cache wouldn't help.
+                            ModuleName::from_package_path(path, |dir| {
+                                dir.join("__init__.py").exists()
+                                    || dir.join("__init__.pyi").exists()
+                            })
+                            .filter(|(_, root)| fallback_paths.iter().any(|p| p == root))
+                            .map(|(name, _)| ModuleNameWithKind::fallback(name))
+                            .or(kind)
+                            .unwrap_or(ModuleNameWithKind::guaranteed(ModuleName::unknown()))
+                        }
+                    }
                 };
                 Handle::from_with_module_name_kind(module_kind, module_path, self.get_sys_info())
             }
@@ -1356,6 +1598,12 @@ impl ConfigFile {
                 errors.set_default_severity(kind, Severity::Warn);
             }
         }
+        if self.root.annotation_style_lints == Some(true) {
+            let errors = self.root.errors.get_or_insert_default();
+            for &kind in ErrorKind::annotation_style_lints() {
+                errors.set_default_severity(kind, Severity::Warn);
+            }
+        }
 
         // Apply preset as defaults: preset values fill in any fields the user
         // didn't explicitly set. For errors, preset errors are the base and user
@@ -1391,6 +1639,7 @@ impl ConfigFile {
             apply_preset_default!(strict_callable_subtyping);
             apply_preset_default!(strict_partial_subtyping);
             apply_preset_default!(spec_compliant_overloads);
+            apply_preset_default!(hasattr_narrowing);
             apply_preset_default!(ignore_errors_in_generated_code);
             apply_preset_default!(permissive_ignores);
         }
@@ -1413,6 +1662,12 @@ impl ConfigFile {
                         sub_errors.set_default_severity(kind, Severity::Warn);
                     }
                 }
+                if sub.settings.annotation_style_lints == Some(true) {
+                    let sub_errors = sub.settings.errors.get_or_insert_default();
+                    for &kind in ErrorKind::annotation_style_lints() {
+                        sub_errors.set_default_severity(kind, Severity::Warn);
+                    }
+                }
                 if let Some(sub_errors) = &mut sub.settings.errors {
                     let mut merged = root_errors.clone();
                     merged.merge_user_overrides(sub_errors);
@@ -1437,6 +1692,10 @@ impl ConfigFile {
             self.root.infer_return_types = Some(InferReturnTypes::Checked);
         }
 
+        if self.root.follow_imports.is_none() {
+            self.root.follow_imports = Some(FollowImports::Silent);
+        }
+
         if self.root.ignore_errors_in_generated_code.is_none() {
             self.root.ignore_errors_in_generated_code = Some(Default::default());
         }
@@ -1457,6 +1716,34 @@ impl ConfigFile {
             self.root.spec_compliant_overloads = Some(false);
         }
 
+        if self.root.hasattr_narrowing.is_none() {
+            self.root.hasattr_narrowing = Some(true);
+        }
+
+        if self.root.require_ignore_codes.is_none() {
+            self.root.require_ignore_codes = Some(false);
+        }
+
+        if self.root.union_display_limit.is_none() {
+            self.root.union_display_limit = Some(Self::DEFAULT_UNION_DISPLAY_LIMIT);
+        }
+
+        if self.root.literal_union_limit.is_none() {
+            self.root.literal_union_limit = Some(Self::DEFAULT_LITERAL_UNION_LIMIT);
+        }
+
+        if self.root.enum_union_limit.is_none() {
+            self.root.enum_union_limit = Some(Self::DEFAULT_ENUM_UNION_LIMIT);
+        }
+
+        if self.root.union_limit.is_none() {
+            self.root.union_limit = Some(Self::DEFAULT_UNION_LIMIT);
+        }
+
+        if self.root.subset_recursion_limit.is_none() {
+            self.root.subset_recursion_limit = Some(Self::DEFAULT_SUBSET_RECURSION_LIMIT);
+        }
+
         let tools_from_permissive_ignores = match self.root.permissive_ignores {
             Some(true) => Some(Tool::all()),
             Some(false) => Some(Tool::default_enabled()),
@@ -1536,6 +1823,22 @@ impl ConfigFile {
             configure_errors.extend(validate(site_package_path.as_ref(), "site-package-path"));
         }
         configure_errors.extend(validate(&self.search_path_from_file, "search-path"));
+        self.extra_search_root_dirs = Vec::new();
+        for glob in self.extra_search_roots.globs() {
+            let dirs = glob.matching_dirs();
+            if dirs.is_empty() {
+                configure_errors.push(anyhow!(
+                    "Invalid extra-search-roots: `{glob}` does not match any directory"
+                ));
+            }
+            for dir in dirs {
+                if !self.extra_search_root_dirs.contains(&dir) {
+                    self.extra_search_root_dirs.push(dir);
+                }
+            }
+        }
+        configure_errors.extend(validate(&self.stub_path, "stub-path"));
+        configure_errors.extend(self.dynamic_modules.validate());
 
         if self.interpreters.python_interpreter_path.is_some()
             && self.interpreters.conda_environment.is_some()
@@ -1567,6 +1870,10 @@ impl ConfigFile {
             .for_each(|search_root| {
                 *search_root = search_root.absolutize_from(config_root);
             });
+        self.extra_search_roots = self.extra_search_roots.clone().from_root(config_root);
+        self.stub_path.iter_mut().for_each(|stub_root| {
+            *stub_root = stub_root.absolutize_from(config_root);
+        });
         if let Some(import_root) = &self.import_root {
             self.import_root = Some(import_root.absolutize_from(config_root));
         }
@@ -1806,6 +2113,8 @@ mod tests {
                 project_excludes: Globs::new(vec!["tests/untyped/**".to_owned()]).unwrap(),
                 search_path_from_args: Vec::new(),
                 search_path_from_file: vec![PathBuf::from("../..")],
+                extra_search_roots: Globs::empty(),
+                extra_search_root_dirs: Vec::new(),
                 disable_search_path_heuristics: false,
                 enable_fallback_search_path: false,
                 disable_project_excludes_heuristics: false,
@@ -1844,19 +2153,31 @@ mod tests {
                     disable_type_errors_in_ide: None,
                     ignore_errors_in_generated_code: Some(true),
                     infer_with_first_use: None,
+                    annotation_style_lints: None,
                     pytorch_efficiency_lints: None,
                     strict_callable_subtyping: None,
                     strict_partial_subtyping: None,
                     replace_imports_with_any: Some(vec![ModuleWildcard::new("fibonacci").unwrap()]),
                     ignore_missing_imports: Some(vec![ModuleWildcard::new("sprout").unwrap()]),
+                    follow_imports: None,
                     untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
                     check_unannotated_defs: None,
                     infer_return_types: None,
                     permissive_ignores: None,
                     enabled_ignores: None,
+                    require_ignore_codes: None,
                     recursion_depth_limit: None,
                     recursion_overflow_handler: None,
                     spec_compliant_overloads: None,
+                    hasattr_narrowing: None,
+                    union_display_limit: None,
+                    literal_union_limit: None,
+                    enum_union_limit: None,
+                    union_limit: None,
+                    subset_recursion_limit: None,
+                    literal_string_sinks: None,
+                    constructor_overrides: None,
+                    optional_attribute_hints: None,
                 },
                 source_db: Default::default(),
                 sub_configs: vec![SubConfig {
@@ -1870,19 +2191,31 @@ mod tests {
                         disable_type_errors_in_ide: None,
                         ignore_errors_in_generated_code: Some(false),
                         infer_with_first_use: Some(false),
+                        annotation_style_lints: None,
                         pytorch_efficiency_lints: None,
                         strict_callable_subtyping: Some(false),
                         strict_partial_subtyping: None,
                         replace_imports_with_any: Some(Vec::new()),
                         ignore_missing_imports: Some(Vec::new()),
+                        follow_imports: None,
                         untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnAny),
                         check_unannotated_defs: None,
                         infer_return_types: None,
                         permissive_ignores: None,
                         enabled_ignores: None,
+                        require_ignore_codes: None,
                         recursion_depth_limit: None,
                         recursion_overflow_handler: None,
                         spec_compliant_overloads: None,
+                        hasattr_narrowing: None,
+                        union_display_limit: None,
+                        literal_union_limit: None,
+                        enum_union_limit: None,
+                        union_limit: None,
+                        subset_recursion_limit: None,
+                        literal_string_sinks: None,
+                        constructor_overrides: None,
+                        optional_attribute_hints: None,
                     }
                 }],
                 coverage: CoverageConfig {
@@ -1892,11 +2225,15 @@ mod tests {
                     ),
                     extras: Default::default(),
                 },
+                stub_path: Vec::new(),
+                stub_path_from_args: false,
                 typeshed_path: None,
                 baseline: None,
                 min_severity: None,
                 skip_lsp_config_indexing: false,
+                check_site_packages: false,
                 extra_file_extensions: Vec::new(),
+                dynamic_modules: Default::default(),
                 synthesized_preset_reason: None,
             }
         );
@@ -2187,6 +2524,8 @@ mod tests {
             project_excludes: Globs::new(vec!["tests/untyped/**".to_owned()]).unwrap(),
             search_path_from_args: Vec::new(),
             search_path_from_file: vec![PathBuf::from("../..")],
+            extra_search_roots: Globs::empty(),
+            extra_search_root_dirs: Vec::new(),
             disable_search_path_heuristics: false,
             enable_fallback_search_path: false,
             disable_project_excludes_heuristics: false,
@@ -2216,11 +2555,15 @@ mod tests {
                 excludes: Some(Globs::new(vec!["covered/vendored/**".to_owned()]).unwrap()),
                 extras: Default::default(),
             },
+            stub_path: Vec::new(),
+            stub_path_from_args: false,
             typeshed_path: Some(PathBuf::from(typeshed)),
             baseline: Some(PathBuf::from("baseline.json")),
             min_severity: None,
             skip_lsp_config_indexing: false,
+            check_site_packages: false,
             extra_file_extensions: Vec::new(),
+            dynamic_modules: Default::default(),
             synthesized_preset_reason: None,
         };
 
@@ -2266,6 +2609,8 @@ mod tests {
             },
             search_path_from_args: Vec::new(),
             search_path_from_file: search_path,
+            extra_search_roots: Globs::empty(),
+            extra_search_root_dirs: Vec::new(),
             disable_search_path_heuristics: false,
             enable_fallback_search_path: false,
             disable_project_excludes_heuristics: false,
@@ -2287,11 +2632,15 @@ mod tests {
                 excludes: Some(globs_at("covered/vendored/**").unwrap()),
                 extras: Default::default(),
             },
+            stub_path: Vec::new(),
+            stub_path_from_args: false,
             typeshed_path: Some(expected_typeshed),
             baseline: Some(test_path.join("baseline.json")),
             min_severity: None,
             skip_lsp_config_indexing: false,
+            check_site_packages: false,
             extra_file_extensions: Vec::new(),
+            dynamic_modules: Default::default(),
             synthesized_preset_reason: None,
         };
         assert_eq!(config, expected_config);
@@ -2344,6 +2693,13 @@ output-format = "omit-errors"
         assert_eq!(config.output_format, Some(OutputFormat::JunitXml));
     }
 
+    #[test]
+    fn test_output_format_sarif_config_parsing() {
+        let config_str = r#"output-format = "sarif""#;
+        let config = ConfigFile::parse_config(config_str).unwrap();
+        assert_eq!(config.output_format, Some(OutputFormat::Sarif));
+    }
+
     #[test]
     fn test_output_format_full_text_with_github_config_parsing() {
         let config_str = r#"output-format = "full-text-with-github""#;
@@ -2394,21 +2750,33 @@ output-format = "omit-errors"
                 errors: Some(Default::default()),
                 replace_imports_with_any: Some(vec![ModuleWildcard::new("root").unwrap()]),
                 ignore_missing_imports: None,
+                follow_imports: None,
                 untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
                 check_unannotated_defs: None,
                 infer_return_types: None,
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                annotation_style_lints: None,
                 pytorch_efficiency_lints: None,
                 strict_callable_subtyping: Some(false),
                 strict_partial_subtyping: Some(false),
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
+                require_ignore_codes: None,
                 recursion_depth_limit: None,
                 recursion_overflow_handler: None,
                 spec_compliant_overloads: None,
+                hasattr_narrowing: None,
+                union_display_limit: None,
+                literal_union_limit: None,
+                enum_union_limit: None,
+                union_limit: None,
+                subset_recursion_limit: None,
+                literal_string_sinks: None,
+                constructor_overrides: None,
+                optional_attribute_hints: None,
             },
             sub_configs: vec![
                 SubConfig {
@@ -2659,6 +3027,7 @@ output-format = "omit-errors"
             ErrorKind::ImplicitAnyAttribute,
             ErrorKind::ImplicitAnyTypeArgument,
             ErrorKind::ImplicitAnyEmptyContainer,
+            ErrorKind::ImplicitAnyImport,
             ErrorKind::ImplicitAnyLambda,
         ] {
             assert_eq!(
@@ -3258,21 +3627,33 @@ output-format = "omit-errors"
                     ModuleWildcard::new("example.path.*").unwrap(),
                 ]),
                 ignore_missing_imports: None,
+                follow_imports: None,
                 untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
                 check_unannotated_defs: None,
                 infer_return_types: None,
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                annotation_style_lints: None,
                 pytorch_efficiency_lints: None,
                 strict_callable_subtyping: Some(false),
                 strict_partial_subtyping: Some(false),
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
+                require_ignore_codes: None,
                 recursion_depth_limit: None,
                 recursion_overflow_handler: None,
                 spec_compliant_overloads: None,
+                hasattr_narrowing: None,
+                union_display_limit: None,
+                literal_union_limit: None,
+                enum_union_limit: None,
+                union_limit: None,
+                subset_recursion_limit: None,
+                literal_string_sinks: None,
+                constructor_overrides: None,
+                optional_attribute_hints: None,
             },
             sub_configs: vec![],
             ..Default::default()
@@ -3298,21 +3679,33 @@ output-format = "omit-errors"
                     ModuleWildcard::new("!example.path.specific.*").unwrap(),
                 ]),
                 ignore_missing_imports: None,
+                follow_imports: None,
                 untyped_def_behavior: Some(UntypedDefBehavior::CheckAndInferReturnType),
                 check_unannotated_defs: None,
                 infer_return_types: None,
                 disable_type_errors_in_ide: Some(true),
                 ignore_errors_in_generated_code: Some(false),
                 infer_with_first_use: Some(true),
+                annotation_style_lints: None,
                 pytorch_efficiency_lints: None,
                 strict_callable_subtyping: Some(false),
                 strict_partial_subtyping: Some(false),
                 extras: Default::default(),
                 permissive_ignores: Some(false),
                 enabled_ignores: None,
+                require_ignore_codes: None,
                 recursion_depth_limit: None,
                 recursion_overflow_handler: None,
                 spec_compliant_overloads: None,
+                hasattr_narrowing: None,
+                union_display_limit: None,
+                literal_union_limit: None,
+                enum_union_limit: None,
+                union_limit: None,
+                subset_recursion_limit: None,
+                literal_string_sinks: None,
+                constructor_overrides: None,
+                optional_attribute_hints: None,
             },
             sub_configs: vec![],
             ..Default::default()
@@ -3655,6 +4048,77 @@ output-format = "omit-errors"
         assert_eq!(config.source.root(), Some(root.path()));
     }
 
+    #[test]
+    fn test_extra_search_roots() {
+        // A monorepo whose packages each have their own `src/` root.
+        let root = TempDir::new().unwrap();
+        let core_file = root.path().join("packages/core/src/core/util.py");
+        fs::create_dir_all(core_file.parent().unwrap()).unwrap();
+        fs::write(&core_file, "").unwrap();
+        fs::create_dir_all(root.path().join("packages/web/src/web")).unwrap();
+        let pyrefly_path = root.path().join(ConfigFile::PYREFLY_FILE_NAME);
+        fs::write(&pyrefly_path, "extra-search-roots = [\"packages/*/src\"]\n").unwrap();
+
+        let (mut config, _errors) = ConfigFile::from_file(&pyrefly_path);
+        config.interpreters.skip_interpreter_query = true;
+        config.configure();
+
+        assert_eq!(
+            config.extra_search_root_dirs,
+            vec![
+                root.path().join("packages/core/src"),
+                root.path().join("packages/web/src"),
+            ]
+        );
+        assert!(
+            config
+                .search_path()
+                .any(|p| p == &root.path().join("packages/web/src"))
+        );
+        let handle = config.handle_from_module_path(ModulePath::filesystem(core_file));
+        assert_eq!(handle.module(), ModuleName::from_str("core.util"));
+    }
+
+    #[test]
+    fn test_dynamic_modules() {
+        let root = TempDir::new().unwrap();
+        let pyrefly_path = root.path().join(ConfigFile::PYREFLY_FILE_NAME);
+        fs::write(
+            &pyrefly_path,
+            "[dynamic-modules.\"myapp.settings\"]\nDEBUG = \"bool\"\n",
+        )
+        .unwrap();
+
+        let (mut config, errors) = ConfigFile::from_file(&pyrefly_path);
+        assert!(errors.is_empty());
+        config.interpreters.skip_interpreter_query = true;
+        config.configure();
+
+        let settings = config
+            .dynamic_module_path(ModuleName::from_str("myapp.settings"))
+            .unwrap();
+        assert_eq!(
+            settings,
+            ModulePath::memory(root.path().join("myapp/settings.pyi"))
+        );
+        assert_eq!(
+            config.dynamic_module_source(&settings).unwrap(),
+            "DEBUG: bool\n"
+        );
+        let package = config
+            .dynamic_module_path(ModuleName::from_str("myapp"))
+            .unwrap();
+        assert_eq!(config.dynamic_module_source(&package).unwrap(), "");
+        assert_eq!(
+            config.dynamic_module_path(ModuleName::from_str("other")),
+            None
+        );
+        assert_eq!(
+            config.dynamic_module_source(&ModulePath::memory(root.path().join("other.pyi"))),
+            None
+        );
+    }
+
     #[test]
     fn test_explicit_search_path_wins_over_site_packages() {
         // An explicit search path should take priority over a site-package
@@ -3686,6 +4150,88 @@ output-format = "omit-errors"
         assert_eq!(handle.module(), ModuleName::from_str("submod"));
     }
 
+    #[test]
+    fn test_reports_errors_for_site_packages() {
+        let root = Path::new("/project");
+        let site_packages = root.join("venv/lib/python3.13/site-packages");
+        let mut config = ConfigFile {
+            search_path_from_args: vec![root.to_path_buf(), site_packages.join("editable")],
+            interpreters: Interpreters {
+                skip_interpreter_query: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config.python_environment.site_package_path = Some(vec![site_packages.clone()]);
+        config.python_environment.set_empty_to_default();
+
+        let reports = |config: &ConfigFile, path: PathBuf| {
+            config.reports_errors_for(&ModulePath::filesystem(path), None)
+        };
+        let reports_followed = |config: &ConfigFile, path: PathBuf, importer: PathBuf| {
+            config.reports_errors_for(&ModulePath::filesystem(path), Some(&importer))
+        };
+        assert!(reports(&config, root.join("main.py")));
+        assert!(!reports(&config, site_packages.join("dep/__init__.py")));
+        assert!(reports(&config, site_packages.join("editable/mod.py")));
+
+        // Followed imports report their errors, but stubs don't.
+        config.root.follow_imports = Some(FollowImports::Normal);
+        assert!(!reports(&config, site_packages.join("dep/__init__.py")));
+        assert!(reports_followed(
+            &config,
+            site_packages.join("dep/__init__.py"),
+            root.join("main.py")
+        ));
+        assert!(!reports_followed(
+            &config,
+            site_packages.join("dep-stubs/__init__.pyi"),
+            root.join("main.py")
+        ));
+
+        config.root.follow_imports = None;
+        config.check_site_packages = true;
+        assert!(reports(&config, site_packages.join("dep/__init__.py")));
+    }
+
+    #[test]
+    fn test_reports_errors_for_follows_importer_sub_config() {
+        let root = Path::new("/project");
+        let site_packages = root.join("venv/lib/python3.13/site-packages");
+        let mut config = ConfigFile {
+            search_path_from_args: vec![root.to_path_buf()],
+            interpreters: Interpreters {
+                skip_interpreter_query: true,
+                ..Default::default()
+            },
+            sub_configs: vec![
+                SubConfig {
+                    matches: Glob::new("**/app/**".to_owned()).unwrap(),
+                    settings: ConfigBase {
+                        follow_imports: Some(FollowImports::Normal),
+                        ..Default::default()
+                    },
+                },
+                SubConfig {
+                    matches: Glob::new("**/venv/**".to_owned()).unwrap(),
+                    settings: ConfigBase {
+                        follow_imports: Some(FollowImports::Skip),
+                        ..Default::default()
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+        config.python_environment.site_package_path = Some(vec![site_packages.clone()]);
+        config.python_environment.set_empty_to_default();
+
+        let dep = ModulePath::filesystem(site_packages.join("dep/__init__.py"));
+        // The importer's setting decides, not the dependency's.
+        assert!(config.reports_errors_for(&dep, Some(&root.join("app/main.py"))));
+        assert!(!config.reports_errors_for(&dep, Some(&root.join("scripts/run.py"))));
+        assert!(!config.reports_errors_for(&dep, None));
+    }
+
     #[test]
     fn test_site_packages_wins_over_heuristic_import_root() {
         // A site-package path should take priority over the heuristic
@@ -3712,6 +4258,59 @@ output-format = "omit-errors"
         assert_eq!(handle.module(), ModuleName::from_str("fastapi"));
     }
 
+    #[test]
+    fn test_fallback_module_name_from_package() {
+        // root/
+        // ├── pkg/
+        // │   ├── __init__.py
+        // │   └── sub/
+        // │       ├── __init__.py
+        // │       └── mod.py
+        // └── my-scripts/run.py
+        let root = TempDir::new().unwrap();
+        let sub = root.path().join("pkg/sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(root.path().join("pkg/__init__.py"), "").unwrap();
+        fs::write(sub.join("__init__.py"), "").unwrap();
+        fs::write(sub.join("mod.py"), "").unwrap();
+        let scripts = root.path().join("my-scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("run.py"), "").unwrap();
+
+        let module_name = |path: PathBuf| {
+            let mut config = ConfigFile {
+                fallback_search_path: FallbackSearchPath::Explicit(Arc::new(
+                    path.parent()
+                        .unwrap()
+                        .ancestors()
+                        .map(Path::to_path_buf)
+                        .collect(),
+                )),
+                interpreters: Interpreters {
+                    skip_interpreter_query: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            config.python_environment.set_empty_to_default();
+            let handle = config.handle_from_module_path(ModulePath::filesystem(path));
+            (handle.module(), handle.module_kind().is_fallback())
+        };
+        assert_eq!(
+            module_name(sub.join("mod.py")),
+            (ModuleName::from_str("pkg.sub.mod"), true)
+        );
+        assert_eq!(
+            module_name(sub.join("__init__.py")),
+            (ModuleName::from_str("pkg.sub"), true)
+        );
+        // `my-scripts` isn't a package, so `run.py` is importable from its own directory.
+        assert_eq!(
+            module_name(scripts.join("run.py")),
+            (ModuleName::from_str("run"), true)
+        );
+    }
+
     #[test]
     fn test_typings_autodiscovered_relative_to_config_root() {
         // With no explicit `site_package_path`, a `typings/` directory under the
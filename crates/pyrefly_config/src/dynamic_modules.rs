@@ -0,0 +1,339 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Modules that only exist at runtime, declared in the `dynamic-modules` config table.
+//!
+//! Each declared module maps the names it defines to their types, written as annotations:
+//!
+//! ```toml
+//! [dynamic-modules."myapp.settings"]
+//! DEBUG = "bool"
+//! DATABASES = "dict[str, dict[str, Any]]"
+//! ```
+//!
+//! Pyrefly synthesizes an in-memory stub for each declared module, and an empty package
+//! for each parent of a declared module that can't be found otherwise.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use pyrefly_python::module_name::ModuleName;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The `typing` names that can be used unqualified in a declared type. The stub imports the
+/// ones it uses under a private alias, so they don't become attributes of the module.
+const TYPING_NAMES: &[&str] = &[
+    "AbstractSet",
+    "Annotated",
+    "Any",
+    "AnyStr",
+    "AsyncGenerator",
+    "AsyncIterable",
+    "AsyncIterator",
+    "Awaitable",
+    "BinaryIO",
+    "Callable",
+    "ChainMap",
+    "ClassVar",
+    "Collection",
+    "Concatenate",
+    "Container",
+    "Coroutine",
+    "Counter",
+    "DefaultDict",
+    "Deque",
+    "Dict",
+    "Final",
+    "FrozenSet",
+    "Generator",
+    "Hashable",
+    "IO",
+    "ItemsView",
+    "Iterable",
+    "Iterator",
+    "KeysView",
+    "List",
+    "Literal",
+    "LiteralString",
+    "Mapping",
+    "MappingView",
+    "Match",
+    "MutableMapping",
+    "MutableSequence",
+    "MutableSet",
+    "Never",
+    "NoReturn",
+    "NotRequired",
+    "Optional",
+    "OrderedDict",
+    "Pattern",
+    "ReadOnly",
+    "Required",
+    "Reversible",
+    "Self",
+    "Sequence",
+    "Set",
+    "Sized",
+    "SupportsAbs",
+    "SupportsBytes",
+    "SupportsComplex",
+    "SupportsFloat",
+    "SupportsIndex",
+    "SupportsInt",
+    "SupportsRound",
+    "TextIO",
+    "Tuple",
+    "Type",
+    "TypeGuard",
+    "TypeIs",
+    "Union",
+    "ValuesView",
+];
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct DynamicModules(BTreeMap<String, BTreeMap<String, String>>);
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
+
+impl DynamicModules {
+    pub fn new(modules: BTreeMap<String, BTreeMap<String, String>>) -> Self {
+        Self(modules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `module` is declared.
+    pub fn declares(&self, module: ModuleName) -> bool {
+        self.0.contains_key(module.as_str())
+    }
+
+    /// Whether `module` contains a declared module.
+    fn is_package(&self, module: ModuleName) -> bool {
+        self.0.keys().any(|declared| {
+            declared
+                .strip_prefix(module.as_str())
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    /// The path of the stub for `module`, relative to the config root, if `module` is
+    /// declared or contains a declared module.
+    pub fn relative_path(&self, module: ModuleName) -> Option<PathBuf> {
+        let is_package = self.is_package(module);
+        if !is_package && !self.declares(module) {
+            return None;
+        }
+        let mut path: PathBuf = module.components().iter().map(|c| c.as_str()).collect();
+        if is_package {
+            path.push("__init__.pyi");
+        } else {
+            path.set_extension("pyi");
+        }
+        Some(path)
+    }
+
+    /// The module whose stub lives at `path`, relative to the config root.
+    pub fn module_for_relative_path(&self, path: &Path) -> Option<ModuleName> {
+        let mut components: Vec<&str> = path.iter().map(|c| c.to_str()).collect::<Option<_>>()?;
+        let stem = components.pop()?.strip_suffix(".pyi")?;
+        if stem != "__init__" {
+            components.push(stem);
+        }
+        let module = ModuleName::from_parts(components);
+        (self.relative_path(module)? == path).then_some(module)
+    }
+
+    /// The source of the stub for `module`. Parents of declared modules that aren't
+    /// declared themselves are empty.
+    pub fn stub(&self, module: ModuleName) -> Option<String> {
+        let Some(names) = self.0.get(module.as_str()) else {
+            return self.is_package(module).then(String::new);
+        };
+        let mut typing_imports = BTreeSet::new();
+        let mut imports = BTreeSet::new();
+        let mut annotations = Vec::with_capacity(names.len());
+        for (name, ty) in names {
+            imports.extend(qualified_modules(ty));
+            // Refer to `typing` names through their private aliases.
+            let mut annotation = String::with_capacity(ty.len());
+            let mut end = 0;
+            for (start, dotted) in dotted_names(ty) {
+                if let Some(typing_name) = TYPING_NAMES.iter().find(|n| **n == dotted) {
+                    annotation.push_str(&ty[end..start]);
+                    write!(annotation, "_{typing_name}").unwrap();
+                    end = start + dotted.len();
+                    typing_imports.insert(*typing_name);
+                }
+            }
+            annotation.push_str(&ty[end..]);
+            annotations.push((name, annotation));
+        }
+        let mut out = String::new();
+        for typing_name in typing_imports {
+            writeln!(out, "from typing import {typing_name} as _{typing_name}").unwrap();
+        }
+        for import in imports {
+            writeln!(out, "import {import}").unwrap();
+        }
+        for (name, annotation) in annotations {
+            writeln!(out, "{name}: {annotation}").unwrap();
+        }
+        Some(out)
+    }
+
+    /// Check that every module name, defined name, and type is well formed.
+    pub fn validate(&self) -> Vec<anyhow::Error> {
+        let mut errors = Vec::new();
+        for (module, names) in &self.0 {
+            if !module.split('.').all(is_identifier) {
+                errors.push(anyhow!(
+                    "Invalid dynamic-modules: `{module}` is not a valid module name"
+                ));
+            }
+            for (name, ty) in names {
+                if !is_identifier(name) {
+                    errors.push(anyhow!(
+                        "Invalid dynamic-modules: `{name}` in `{module}` is not a valid name"
+                    ));
+                }
+                if ty.trim().is_empty() || ty.contains(['\n', '\r']) {
+                    errors.push(anyhow!(
+                        "Invalid dynamic-modules: the type of `{name}` in `{module}` must be a single-line annotation"
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+/// The possibly dotted names in an annotation and their offsets, e.g. `collections.abc.Mapping`,
+/// `str` and `int` for `collections.abc.Mapping[str, int]`. Names inside string literals and
+/// numbers are skipped.
+fn dotted_names(ty: &str) -> Vec<(usize, &str)> {
+    let mut names = Vec::new();
+    let mut quote = None;
+    let mut start = None;
+    for (i, c) in ty.char_indices().chain([(ty.len(), ' ')]) {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c == '_' || c == '.' || c.is_alphanumeric() {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(s) = start.take()
+            && ty[s..i].split('.').all(is_identifier)
+        {
+            names.push((s, &ty[s..i]));
+        }
+        if c == '"' || c == '\'' {
+            quote = Some(c);
+        }
+    }
+    names
+}
+
+/// The modules referenced by the dotted names in an annotation, e.g. `collections.abc`
+/// for `collections.abc.Mapping[str, int]`.
+fn qualified_modules(ty: &str) -> Vec<&str> {
+    dotted_names(ty)
+        .into_iter()
+        .filter_map(|(_, name)| Some(name.rsplit_once('.')?.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modules(toml: &str) -> DynamicModules {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_stub() {
+        let modules = modules(
+            r#"
+            ["myapp.settings"]
+            DEBUG = "bool"
+            TIMEOUT = "datetime.timedelta | None"
+            HOOKS = "collections.abc.Mapping[str, Callable[[], None]]"
+            MODE = "Literal['a.b']"
+            "#,
+        );
+        assert_eq!(
+            modules
+                .stub(ModuleName::from_str("myapp.settings"))
+                .unwrap(),
+            "from typing import Callable as _Callable\nfrom typing import Literal as _Literal\nimport collections.abc\nimport datetime\nDEBUG: bool\nHOOKS: collections.abc.Mapping[str, _Callable[[], None]]\nMODE: _Literal['a.b']\nTIMEOUT: datetime.timedelta | None\n"
+        );
+        assert_eq!(
+            modules.stub(ModuleName::from_str("myapp")),
+            Some(String::new())
+        );
+        assert_eq!(modules.stub(ModuleName::from_str("my")), None);
+        assert_eq!(modules.stub(ModuleName::from_str("other")), None);
+    }
+
+    #[test]
+    fn test_paths() {
+        let modules = modules(
+            r#"
+            [plugins]
+            REGISTRY = "dict[str, type]"
+            ["plugins.builtin"]
+            NAMES = "list[str]"
+            ["a.b.c"]
+            "#,
+        );
+        for (module, path) in [
+            ("plugins", "plugins/__init__.pyi"),
+            ("plugins.builtin", "plugins/builtin.pyi"),
+            ("a", "a/__init__.pyi"),
+            ("a.b", "a/b/__init__.pyi"),
+            ("a.b.c", "a/b/c.pyi"),
+        ] {
+            let module = ModuleName::from_str(module);
+            let relative = modules.relative_path(module).unwrap();
+            assert_eq!(relative, PathBuf::from(path));
+            assert_eq!(modules.module_for_relative_path(&relative), Some(module));
+        }
+        assert_eq!(modules.relative_path(ModuleName::from_str("plug")), None);
+        assert_eq!(
+            modules.module_for_relative_path(Path::new("plugins.pyi")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let modules = modules(
+            r#"
+            ["my-app.settings"]
+            "not a name" = "int"
+            EMPTY = ""
+            "#,
+        );
+        assert_eq!(modules.validate().len(), 3);
+    }
+}
@@ -5,12 +5,20 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::env;
 use std::fmt;
 use std::fmt::Display;
+use std::io::Read;
+use std::panic;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
 use std::sync::LazyLock;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::anyhow;
@@ -22,9 +30,15 @@ use pyrefly_util::stdlib::register_stdlib_paths;
 use serde::Deserialize;
 use serde::Serialize;
 use starlark_map::small_map::SmallMap;
+use tracing::debug;
 use tracing::warn;
 
 use crate::environment::interpreters::Interpreters;
+use crate::environment::query_cache;
+
+/// How long we wait for an interpreter to answer before giving up and using default settings,
+/// so a misbehaving interpreter can't hang a check or the language server.
+const INTERPRETER_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
 
 static INTERPRETER_ENV_REGISTRY: LazyLock<
     Mutex<SmallMap<PathBuf, Result<PythonEnvironment, String>>>,
@@ -122,7 +136,7 @@ impl PythonEnvironment {
     /// version, platform, and site package path. Return an error in the case of failure during
     /// execution, parsing, or deserializing.
     pub fn get_env_from_interpreter(interpreter: &Path) -> anyhow::Result<PythonEnvironment> {
-        if let Ok(pythonpath) = std::env::var("PYTHONPATH") {
+        if let Ok(pythonpath) = env::var("PYTHONPATH") {
             warn!(
                 "PYTHONPATH environment variable is set to `{}`. Checks in other environments may not include these paths.",
                 pythonpath
@@ -143,7 +157,8 @@ print(json.dumps({'python_platform': platform, 'python_version': version, 'site_
         command.arg("-c");
         command.arg(script);
 
-        let python_info = command.output()?;
+        let python_info = Self::output_with_timeout(command, INTERPRETER_QUERY_TIMEOUT)
+            .with_context(|| format!("while querying interpreter `{}`", interpreter.display()))?;
 
         let stdout = String::from_utf8(python_info.stdout).with_context(|| {
             format!(
@@ -185,18 +200,66 @@ print(json.dumps({'python_platform': platform, 'python_version': version, 'site_
         Ok(deserialized)
     }
 
+    /// Run `command` to completion and collect its output, killing it if it takes longer
+    /// than `timeout`.
+    fn output_with_timeout(mut command: Command, timeout: Duration) -> anyhow::Result<Output> {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // Drain the pipes on separate threads, so the child never blocks on a full pipe.
+        fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+            thread::spawn(move || {
+                let mut buf = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut buf);
+                }
+                buf
+            })
+        }
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(anyhow!("timed out after {}s", timeout.as_secs()));
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_else(|e| panic::resume_unwind(e)),
+            stderr: stderr.join().unwrap_or_else(|e| panic::resume_unwind(e)),
+        })
+    }
+
     /// Given a path to an interpreter, query the interpreter with
-    /// [`Self::get_env_from_interpreter()`] and cache the result. If a cached
-    /// result already exists, return that.
+    /// [`Self::get_env_from_interpreter()`] and cache the result, both in memory and
+    /// on disk. If a cached result already exists, return that. A result from the
+    /// on-disk cache is returned without waiting for the interpreter, and refreshed
+    /// in the background.
     ///
     /// In the case of failure, log an error message and return Pyrefly's
     /// [`PythonEnvironment::default()`].
     pub fn get_interpreter_env(interpreter: &Path) -> (PythonEnvironment, Option<anyhow::Error>) {
         let env = INTERPRETER_ENV_REGISTRY.lock()
         .entry(interpreter.to_path_buf()).or_insert_with(move || {
-            Self::get_env_from_interpreter(interpreter).map_err(|e| {
+            if let Some(env) = query_cache::load(interpreter) {
+                Self::cache_interpreter_stdlib_path(env.interpreter_stdlib_path.clone());
+                Self::refresh_in_background(interpreter.to_path_buf());
+                return Ok(env);
+            }
+            let env = Self::get_env_from_interpreter(interpreter).map_err(|e| {
                 format!("Failed to query interpreter at {}, falling back to default Python environment settings\n{}", interpreter.display(), e)
-            })
+            })?;
+            query_cache::store(interpreter, &env);
+            Ok(env)
         }).clone();
         match env {
             Ok(env) => (env, None),
@@ -204,6 +267,22 @@ print(json.dumps({'python_platform': platform, 'python_version': version, 'site_
         }
     }
 
+    /// Query `interpreter` again on another thread and update both caches, so a result
+    /// loaded from disk is only used until the interpreter answers. Later lookups, e.g.
+    /// when the language server reloads its configs, see the fresh result.
+    fn refresh_in_background(interpreter: PathBuf) {
+        thread::spawn(move || match Self::get_env_from_interpreter(&interpreter) {
+            Ok(env) => {
+                query_cache::store(&interpreter, &env);
+                INTERPRETER_ENV_REGISTRY.lock().insert(interpreter, Ok(env));
+            }
+            Err(e) => debug!(
+                "Failed to refresh query of interpreter `{}`: {e:#}",
+                interpreter.display()
+            ),
+        });
+    }
+
     fn cache_interpreter_stdlib_path(path: Vec<PathBuf>) {
         register_stdlib_paths(path);
     }
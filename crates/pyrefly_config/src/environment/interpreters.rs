@@ -98,6 +98,15 @@ impl Interpreters {
         self.python_interpreter_path = Some(ConfigOrigin::lsp(interpreter));
     }
 
+    /// The interpreter found while configuring, or the first one on the `$PATH`
+    /// if none was.
+    pub fn python_interpreter(&self) -> Option<&Path> {
+        match &self.python_interpreter_path {
+            Some(path) => Some(path.as_path()),
+            None => Self::get_default_interpreter(),
+        }
+    }
+
     /// Finds interpreters by searching in prioritized locations for the given project
     /// and interpreter settings.
     ///
@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! An on-disk cache of interpreter query results, so that a fresh process doesn't have to
+//! wait for the same interpreter again. Entries live in the per-user cache directory, are
+//! keyed by the interpreter path, and are only used while the interpreter's modification
+//! time, `PYTHONPATH` and the `.pth` files in its site-packages (all of which feed into the
+//! queried `sys.path`) are unchanged.
+
+use std::env;
+use std::fs;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::time::UNIX_EPOCH;
+
+use pyrefly_python::sys_info::PythonPlatform;
+use pyrefly_python::sys_info::PythonVersion;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::environment::environment::PythonEnvironment;
+
+/// Bump this when the query script or the format below changes, to ignore old entries.
+const CACHE_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    version: u32,
+    interpreter: PathBuf,
+    modified: u128,
+    pythonpath: Option<String>,
+    python_platform: PythonPlatform,
+    python_version: PythonVersion,
+    site_package_path: Vec<PathBuf>,
+    stdlib_path: Vec<PathBuf>,
+    pth_files: Vec<(PathBuf, u128)>,
+}
+
+/// The directory for the cache, under the platform's per-user cache directory.
+fn cache_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(env::var_os("LOCALAPPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        env::home_dir()?.join("Library").join("Caches")
+    } else {
+        match env::var_os("XDG_CACHE_HOME") {
+            Some(dir) if Path::new(&dir).is_absolute() => PathBuf::from(dir),
+            _ => env::home_dir()?.join(".cache"),
+        }
+    };
+    Some(base.join("pyrefly").join("interpreter-cache"))
+}
+
+fn cache_file(dir: &Path, interpreter: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    interpreter.hash(&mut hasher);
+    dir.join(format!("{:016x}.json", hasher.finish()))
+}
+
+/// The modification time of `path` (following symlinks), in nanoseconds.
+fn modified(path: &Path) -> Option<u128> {
+    let time = fs::metadata(path).ok()?.modified().ok()?;
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+/// The `.pth` files in `site_packages`, with their modification times. The interpreter
+/// processes them at startup, and they can add to `sys.path` (e.g. editable installs).
+fn pth_files(site_packages: &[PathBuf]) -> Vec<(PathBuf, u128)> {
+    let mut files: Vec<_> = site_packages
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "pth" {
+                return None;
+            }
+            let modified = modified(&path)?;
+            Some((path, modified))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn pythonpath() -> Option<String> {
+    env::var("PYTHONPATH").ok()
+}
+
+fn load_from(dir: &Path, interpreter: &Path) -> Option<PythonEnvironment> {
+    let contents = fs::read_to_string(cache_file(dir, interpreter)).ok()?;
+    let entry: Entry = serde_json::from_str(&contents).ok()?;
+    if entry.version != CACHE_VERSION
+        || entry.interpreter != interpreter
+        || Some(entry.modified) != modified(interpreter)
+        || entry.pythonpath != pythonpath()
+        || entry.pth_files != pth_files(&entry.site_package_path)
+    {
+        return None;
+    }
+    Some(PythonEnvironment {
+        python_platform: Some(entry.python_platform),
+        python_version: Some(entry.python_version),
+        site_package_path: Some(Vec::new()),
+        interpreter_site_package_path: entry.site_package_path,
+        interpreter_stdlib_path: entry.stdlib_path,
+    })
+}
+
+fn store_to(dir: &Path, interpreter: &Path, env: &PythonEnvironment) -> anyhow::Result<()> {
+    let (Some(python_platform), Some(python_version), Some(modified)) = (
+        env.python_platform.clone(),
+        env.python_version,
+        modified(interpreter),
+    ) else {
+        return Ok(());
+    };
+    let entry = Entry {
+        version: CACHE_VERSION,
+        interpreter: interpreter.to_path_buf(),
+        modified,
+        pythonpath: pythonpath(),
+        python_platform,
+        python_version,
+        site_package_path: env.interpreter_site_package_path.clone(),
+        stdlib_path: env.interpreter_stdlib_path.clone(),
+        pth_files: pth_files(&env.interpreter_site_package_path),
+    };
+    fs::create_dir_all(dir)?;
+    // Write to a temporary file and rename, so concurrent processes never see a partial entry.
+    let path = cache_file(dir, interpreter);
+    let tmp = path.with_extension(format!("{}.tmp", process::id()));
+    fs::write(&tmp, serde_json::to_string(&entry)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Look up a previous query of `interpreter`, if it hasn't changed since.
+pub(crate) fn load(interpreter: &Path) -> Option<PythonEnvironment> {
+    load_from(&cache_dir()?, interpreter)
+}
+
+/// Record the result of querying `interpreter`. Failures are logged and otherwise ignored,
+/// since the cache is only an optimization.
+pub(crate) fn store(interpreter: &Path, env: &PythonEnvironment) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    if let Err(e) = store_to(&dir, interpreter, env) {
+        debug!(
+            "Failed to cache query of interpreter `{}`: {e:#}",
+            interpreter.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_invalidate_on_change() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().join("cache");
+        let interpreter = tempdir.path().join("python3");
+        fs::write(&interpreter, "").unwrap();
+        let site_packages = tempdir.path().join("site-packages");
+        fs::create_dir(&site_packages).unwrap();
+        let env = PythonEnvironment {
+            python_platform: Some(PythonPlatform::linux()),
+            python_version: Some(PythonVersion::new(3, 12, 1)),
+            site_package_path: Some(Vec::new()),
+            interpreter_site_package_path: vec![site_packages.clone()],
+            interpreter_stdlib_path: vec![PathBuf::from("/usr/lib/python3.12")],
+        };
+
+        assert!(load_from(&dir, &interpreter).is_none());
+        store_to(&dir, &interpreter, &env).unwrap();
+        assert_eq!(load_from(&dir, &interpreter), Some(env.clone()));
+
+        // A new `.pth` file may add to `sys.path`.
+        fs::write(site_packages.join("editable.pth"), "/src\n").unwrap();
+        assert!(load_from(&dir, &interpreter).is_none());
+        store_to(&dir, &interpreter, &env).unwrap();
+        assert_eq!(load_from(&dir, &interpreter), Some(env));
+
+        // A different modification time means the interpreter may have been replaced.
+        let file = fs::File::options().write(true).open(&interpreter).unwrap();
+        file.set_modified(UNIX_EPOCH).unwrap();
+        assert!(load_from(&dir, &interpreter).is_none());
+    }
+}
@@ -106,6 +106,16 @@ pub fn find(project_path: &Path) -> Option<PathBuf> {
         .or_else(|| search_roots(project_path).skip(1).find_map(find_in_root))
 }
 
+/// Find the interpreter of the virtual environment rooted at `venv`, which must contain
+/// `pyvenv.cfg`. Unlike `find`, no other directories are searched.
+pub fn find_in_venv(venv: &Path) -> Option<PathBuf> {
+    if venv.join(CONFIG_FILE).exists() {
+        find_in_dir(venv)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pyrefly_util::test_path::TestPath;
@@ -398,4 +408,39 @@ mod tests {
             vec![PathBuf::from("project/src"), PathBuf::from("project")],
         );
     }
+
+    #[test]
+    fn test_find_in_venv_does_not_search_elsewhere() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        let interp_name = interp_name("3");
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir(
+                    "custom-venv",
+                    vec![
+                        TestPath::file(CONFIG_FILE),
+                        TestPath::dir("bin", vec![TestPath::file(&interp_name)]),
+                    ],
+                ),
+                TestPath::dir(
+                    "project",
+                    vec![TestPath::dir(
+                        ".venv",
+                        vec![
+                            TestPath::file(CONFIG_FILE),
+                            TestPath::dir("bin", vec![TestPath::file(&interp_name)]),
+                        ],
+                    )],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            find_in_venv(&root.join("custom-venv")),
+            Some(root.join("custom-venv/bin").join(&interp_name)),
+        );
+        assert_eq!(find_in_venv(&root.join("project")), None);
+    }
 }
@@ -160,6 +160,8 @@ pub struct ErrorConfig<'a> {
     pub display_config: &'a ErrorDisplayConfig,
     pub ignore_errors_in_generated_code: bool,
     pub enabled_ignores: SmallSet<Tool>,
+    /// Only honor `# pyrefly: ignore[...]` suppressions that name error codes.
+    pub require_ignore_codes: bool,
 }
 
 impl<'a> ErrorConfig<'a> {
@@ -172,8 +174,14 @@ impl<'a> ErrorConfig<'a> {
             display_config,
             ignore_errors_in_generated_code,
             enabled_ignores,
+            require_ignore_codes: false,
         }
     }
+
+    pub fn with_require_ignore_codes(mut self, require_ignore_codes: bool) -> Self {
+        self.require_ignore_codes = require_ignore_codes;
+        self
+    }
 }
 
 #[cfg(test)]
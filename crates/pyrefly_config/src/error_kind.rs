@@ -90,6 +90,10 @@ pub enum ErrorKind {
     BadArgumentCount,
     /// Attempting to call a function with an argument that does not match the parameter's type.
     BadArgumentType,
+    /// Passing a `str` that is not known to be built from literals to a `LiteralString` parameter.
+    /// This is a sub-kind of [BadArgumentType]: suppressing `bad-argument-type` also suppresses
+    /// this error.
+    BadArgumentTypeLiteralString,
     /// Assigning a value of the wrong type to a variable.
     BadAssignment,
     /// A class definition has some typing-related error.
@@ -157,6 +161,9 @@ pub enum ErrorKind {
     CoveragePartial,
     /// Calling a function marked with `@deprecated`
     Deprecated,
+    /// A `typing` alias such as `typing.List` is used where the builtin generic
+    /// (`list`) is available for the target Python version.
+    DeprecatedTypingAlias,
     /// Division, floor division, or modulo by a literal zero value.
     DivisionByZero,
     /// Explicit usage of `typing.Any` in an annotation.
@@ -177,6 +184,10 @@ pub enum ErrorKind {
     /// be inferred from context and is pinned to a container of `Any`.
     /// This is a sub-kind of [ImplicitAny]: suppressing `implicit-any` also suppresses this error.
     ImplicitAnyEmptyContainer,
+    /// An implicit `Any` introduced by importing from a module that is not type checked,
+    /// e.g. because it matches `replace-imports-with-any` or `ignore-missing-imports`.
+    /// This is a sub-kind of [ImplicitAny]: suppressing `implicit-any` also suppresses this error.
+    ImplicitAnyImport,
     /// An implicit `Any` introduced when a lambda parameter or return type cannot
     /// be inferred from context.
     /// This is a sub-kind of [ImplicitAny]: suppressing `implicit-any` also suppresses this error.
@@ -193,14 +204,22 @@ pub enum ErrorKind {
     ImplicitAnyTypeArgument,
     /// Usage of a module that was not actually imported, but does exist.
     ImplicitImport,
+    /// A parameter annotated with a type that does not accept `None` has a `None` default.
+    ImplicitOptional,
     /// An attribute was implicitly defined by assignment to `self` in a method that we
     /// do not recognize as always executing (we recognize constructors and some test setup
     /// methods).
     ImplicitlyDefinedAttribute,
+    /// An import of a Python source file outside the project, which isn't analyzed because
+    /// `follow-imports` is set to `error`.
+    ImportNotFollowed,
     /// Equality or inequality comparison between incompatible types.
     IncompatibleComparison,
     /// Overload residual branch pruning left no valid branch for a solved type variable.
     IncompatibleOverloadResidual,
+    /// A class's `__new__` and `__init__` accept incompatible arguments, so any call to the
+    /// class is rejected by one of them at runtime.
+    InconsistentConstructor,
     /// An inconsistency between inherited fields or methods from multiple base classes.
     InconsistentInheritance,
     /// An inconsistency between the signature of a function overload and the implementation.
@@ -236,6 +255,9 @@ pub enum ErrorKind {
     InvalidParamSpec,
     /// An error caused by an invalid match pattern.
     InvalidPattern,
+    /// A relative import that can't refer to any module, e.g. one that goes beyond the
+    /// top-level package.
+    InvalidRelativeImport,
     /// A use of `typing.Self` in a context where Pyrefly does not recognize it as
     /// mapping to a valid class type.
     InvalidSelfType,
@@ -266,6 +288,9 @@ pub enum ErrorKind {
     /// appears after the first line of code, where it is silently inert. File-level
     /// suppressions are only honored in the preamble, at the top of the file.
     MisplacedIgnore,
+    /// A suppression comment does not name the error codes it suppresses,
+    /// e.g. a bare `# type: ignore` rather than `# pyrefly: ignore[bad-return]`.
+    MissingIgnoreCode,
     /// An error caused by calling a function without all the required arguments.
     /// Should be used when we can name the specific arguments that are missing.
     MissingArgument,
@@ -302,6 +327,9 @@ pub enum ErrorKind {
     NonConvergentRecursion,
     /// Matching on an enum without covering all possible cases.
     NonExhaustiveMatch,
+    /// A string that is not a `LiteralString` is passed to a function listed in
+    /// `literal-string-sinks`, such as a SQL query built from untrusted input.
+    NonLiteralStringSink,
     /// Attempting to use something that isn't a type where a type is expected.
     /// This is a very general error and should be used sparingly.
     NotAType,
@@ -315,6 +343,9 @@ pub enum ErrorKind {
     NotRequiredKeyAccess,
     /// Unpacking an open TypedDict that may contain a bad key via inheritance.
     OpenUnpacking,
+    /// Subclassing a `@final` class, or overriding a `@final` method or `Final` attribute
+    /// of a parent class.
+    OverridesFinal,
     /// An error related to parsing or syntax.
     ParseError,
     /// A potential conflict between an explicit keyword argument and a NotRequired
@@ -339,6 +370,8 @@ pub enum ErrorKind {
     PytorchEfficiencyLintRedundantToCall,
     /// The attribute exists but cannot be modified.
     ReadOnly,
+    /// An assignability check recursed too deeply to finish, and was treated as passing.
+    RecursionLimit,
     /// Attempting to annotate or redefine a name with a type that conflicts with an existing annotation in scope.
     Redefinition,
     /// Warning when casting a value to a type it is already compatible with.
@@ -347,8 +380,15 @@ pub enum ErrorKind {
     RedundantCondition,
     /// Raised by a call to reveal_type().
     RevealType,
+    /// A `from m import *` overwrites a name that was already defined in the importing module.
+    StarImportShadowing,
     /// Passing a string to something that expects an iterable of strings.
     StringAsIterable,
+    /// An override that is compatible with the parent class, but changes a parameter's default
+    /// value or stops accepting a parameter as a named keyword argument (strict Liskov mode).
+    StrictOverride,
+    /// A name imported only under `if TYPE_CHECKING:` is used at runtime.
+    TypeCheckingOnlyImport,
     /// DEPRECATED: use [ImplicitAnyAttribute] (`implicit-any-attribute`) instead.
     /// Kept so that existing `# pyrefly: ignore[unannotated-attribute]` comments
     /// and config entries continue to work. This variant is never emitted by
@@ -382,6 +422,9 @@ pub enum ErrorKind {
     /// Identity comparison (`is` or `is not`) between types that are provably disjoint
     /// or between literals whose comparison result is statically known.
     UnnecessaryComparison,
+    /// A quoted annotation that would behave the same unquoted, because annotations in the
+    /// file are never evaluated at runtime.
+    UnnecessaryQuotedAnnotation,
     /// Warning when calling a builtin type constructor (str, int, float, bool, bytes) on a value that is already of that type.
     UnnecessaryTypeConversion,
     /// A return or yield that can never be reached.
@@ -414,6 +457,8 @@ pub enum ErrorKind {
     UnusedCoroutine,
     /// A suppression comment is unused (no error to suppress, or specific codes are unused)
     UnusedIgnore,
+    /// A module-level import is never used, re-exported, or listed in `__all__`.
+    UnusedImport,
     /// A `# type: ignore` comment is unused (no error to suppress on that line)
     UnusedTypeIgnore,
     /// `@overload` bodies are never executed, so executable body logic is usually dead code.
@@ -441,6 +486,14 @@ static PYTORCH_EFFICIENCY_LINTS: LazyLock<Vec<ErrorKind>> = LazyLock::new(|| {
         .collect()
 });
 
+/// Error kinds enabled by `annotation-style-lints`.
+const ANNOTATION_STYLE_LINTS: &[ErrorKind] = &[
+    ErrorKind::DeprecatedTypingAlias,
+    ErrorKind::ImplicitAnyTypeArgument,
+    ErrorKind::ImplicitOptional,
+    ErrorKind::UnnecessaryQuotedAnnotation,
+];
+
 impl ErrorKind {
     fn cache() -> SmallMap<String, ErrorKind> {
         let mut map = SmallMap::new();
@@ -458,6 +511,11 @@ impl ErrorKind {
         &PYTORCH_EFFICIENCY_LINTS
     }
 
+    /// The annotation style error kinds enabled by `annotation-style-lints`.
+    pub fn annotation_style_lints() -> &'static [ErrorKind] {
+        ANNOTATION_STYLE_LINTS
+    }
+
     pub fn to_name(self) -> &'static str {
         ERROR_KIND_CACHE
             .get_index(self as usize)
@@ -470,11 +528,14 @@ impl ErrorKind {
     /// Suppressing the parent kind also suppresses this kind.
     pub fn parent_kind(self) -> Option<ErrorKind> {
         match self {
-            ErrorKind::BadOverrideMutableAttribute | ErrorKind::BadOverrideParamName => {
-                Some(ErrorKind::BadOverride)
-            }
+            ErrorKind::BadArgumentTypeLiteralString => Some(ErrorKind::BadArgumentType),
+            ErrorKind::BadOverrideMutableAttribute
+            | ErrorKind::BadOverrideParamName
+            | ErrorKind::OverridesFinal => Some(ErrorKind::BadOverride),
+            ErrorKind::InvalidRelativeImport => Some(ErrorKind::MissingImport),
             ErrorKind::ImplicitAnyAttribute
             | ErrorKind::ImplicitAnyEmptyContainer
+            | ErrorKind::ImplicitAnyImport
             | ErrorKind::ImplicitAnyLambda
             | ErrorKind::ImplicitAnyParameter
             | ErrorKind::ImplicitAnyTypeArgument => Some(ErrorKind::ImplicitAny),
@@ -510,20 +571,25 @@ impl ErrorKind {
             ErrorKind::CoverageMissing => Severity::Warn,
             ErrorKind::CoveragePartial => Severity::Warn,
             ErrorKind::Deprecated => Severity::Warn,
+            ErrorKind::DeprecatedTypingAlias => Severity::Ignore,
             ErrorKind::DivisionByZero => Severity::Warn,
             ErrorKind::ExplicitAny => Severity::Ignore,
             ErrorKind::ImplicitAbstractClass => Severity::Ignore,
             ErrorKind::ImplicitAny => Severity::Ignore,
             ErrorKind::ImplicitAnyAttribute => Severity::Ignore,
             ErrorKind::ImplicitAnyEmptyContainer => Severity::Ignore,
+            ErrorKind::ImplicitAnyImport => Severity::Ignore,
             ErrorKind::ImplicitAnyParameter => Severity::Ignore,
             ErrorKind::ImplicitAnyTypeArgument => Severity::Ignore,
             ErrorKind::ImplicitImport => Severity::Warn,
+            ErrorKind::ImplicitOptional => Severity::Ignore,
             ErrorKind::ImplicitlyDefinedAttribute => Severity::Ignore,
             ErrorKind::IncompatibleComparison => Severity::Ignore,
+            ErrorKind::InconsistentConstructor => Severity::Ignore,
             ErrorKind::InvalidAbstractMethod => Severity::Ignore,
             ErrorKind::InvalidDecorator => Severity::Warn,
             ErrorKind::MisplacedIgnore => Severity::Warn,
+            ErrorKind::MissingIgnoreCode => Severity::Ignore,
             ErrorKind::MissingOverrideDecorator => Severity::Ignore,
             ErrorKind::MissingSuperCall => Severity::Ignore,
             ErrorKind::MissingSource => Severity::Ignore,
@@ -532,6 +598,7 @@ impl ErrorKind {
             ErrorKind::NoAnyReturnExplicit => Severity::Ignore,
             ErrorKind::NoAnyReturnImplicit => Severity::Ignore,
             ErrorKind::NonExhaustiveMatch => Severity::Warn,
+            ErrorKind::NonLiteralStringSink => Severity::Error,
             ErrorKind::NonConvergentRecursion => Severity::Warn,
             ErrorKind::NotRequiredKeyAccess => Severity::Ignore,
             ErrorKind::OpenUnpacking => Severity::Ignore,
@@ -539,10 +606,13 @@ impl ErrorKind {
             ErrorKind::PytorchEfficiencyLintItemCall => Severity::Ignore,
             ErrorKind::PytorchEfficiencyLintPrintTensor => Severity::Ignore,
             ErrorKind::PytorchEfficiencyLintRedundantToCall => Severity::Ignore,
+            ErrorKind::RecursionLimit => Severity::Warn,
             ErrorKind::RedundantCast => Severity::Warn,
             ErrorKind::RedundantCondition => Severity::Warn,
             ErrorKind::RevealType => Severity::Info,
+            ErrorKind::StarImportShadowing => Severity::Warn,
             ErrorKind::StringAsIterable => Severity::Ignore,
+            ErrorKind::StrictOverride => Severity::Ignore,
             ErrorKind::UnannotatedAttribute => Severity::Ignore,
             ErrorKind::UnannotatedParameter => Severity::Ignore,
             ErrorKind::UnannotatedReturn => Severity::Ignore,
@@ -550,6 +620,7 @@ impl ErrorKind {
             ErrorKind::UnknownAttributeType => Severity::Ignore,
             ErrorKind::UnknownVariableType => Severity::Ignore,
             ErrorKind::UnnecessaryComparison => Severity::Warn,
+            ErrorKind::UnnecessaryQuotedAnnotation => Severity::Ignore,
             ErrorKind::UnnecessaryTypeConversion => Severity::Warn,
             ErrorKind::Unreachable => Severity::Warn,
             ErrorKind::UnreachableMatchCase => Severity::Warn,
@@ -559,6 +630,7 @@ impl ErrorKind {
             ErrorKind::UntypedImport => Severity::Warn,
             ErrorKind::UnusedCallResult => Severity::Ignore,
             ErrorKind::UnusedIgnore => Severity::Ignore,
+            ErrorKind::UnusedImport => Severity::Ignore,
             ErrorKind::UnusedTypeIgnore => Severity::Ignore,
             ErrorKind::VarianceMismatch => Severity::Warn,
             // Overload bodies are runtime-dead, so this should warn rather than fail CI by default.
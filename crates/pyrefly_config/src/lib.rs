@@ -8,13 +8,15 @@
 pub mod args;
 pub mod base;
 pub mod config;
+pub mod dynamic_modules;
 pub mod environment;
 pub mod error;
 pub mod error_kind;
 pub mod file_kind;
 pub mod finder;
+pub mod literal_string_sink;
 pub mod migration;
-pub(crate) mod module_wildcard;
+pub mod module_wildcard;
 pub mod pyproject;
 pub mod resolve_unconfigured;
 pub(crate) mod util;
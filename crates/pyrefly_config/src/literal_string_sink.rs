@@ -0,0 +1,218 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Functions listed in the `literal-string-sinks` config option, whose first parameter must be
+//! passed a `LiteralString`.
+//!
+//! An entry is a qualified function name, optionally followed by a keyword condition that
+//! restricts it to calls passing that keyword as a literal value:
+//!
+//! ```toml
+//! literal-string-sinks = ["sqlite3.Cursor.execute", "subprocess.run(shell=True)"]
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use dupe::Dupe;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::Visitor;
+
+/// The literal value a keyword condition requires, e.g. `True` in `subprocess.run(shell=True)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SinkValue {
+    Bool(bool),
+    None,
+    Int(i64),
+    Str(String),
+}
+
+impl SinkValue {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "True" => return Some(Self::Bool(true)),
+            "False" => return Some(Self::Bool(false)),
+            "None" => return Some(Self::None),
+            _ => {}
+        }
+        if let Ok(i) = value.parse() {
+            return Some(Self::Int(i));
+        }
+        ['"', '\'']
+            .into_iter()
+            .find_map(|q| value.strip_prefix(q)?.strip_suffix(q))
+            .filter(|s| !s.contains(['"', '\'']))
+            .map(|s| Self::Str(s.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LiteralStringSink {
+    /// The qualified name of the function, e.g. `sqlite3.Cursor.execute`.
+    function: String,
+    /// The keyword argument and value a call must pass for the sink to apply.
+    condition: Option<(String, SinkValue)>,
+    /// The entry as written in the config, for serialization.
+    origin: String,
+}
+
+impl LiteralStringSink {
+    pub fn new(entry: &str) -> anyhow::Result<Self> {
+        let invalid = || anyhow!("Invalid literal-string-sinks entry `{entry}`");
+        let (function, condition) = match entry.split_once('(') {
+            None => (entry.trim(), None),
+            Some((function, condition)) => {
+                let (keyword, value) = condition
+                    .strip_suffix(')')
+                    .and_then(|c| c.split_once('='))
+                    .ok_or_else(invalid)?;
+                let keyword = keyword.trim();
+                if keyword.is_empty() {
+                    return Err(invalid());
+                }
+                let value = SinkValue::parse(value.trim()).ok_or_else(|| {
+                    anyhow!(
+                        "Invalid literal-string-sinks entry `{entry}`: the value must be `True`, `False`, `None`, an integer, or a quoted string"
+                    )
+                })?;
+                (function.trim(), Some((keyword.to_owned(), value)))
+            }
+        };
+        if function.is_empty() || function.split('.').any(str::is_empty) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            function: function.to_owned(),
+            condition,
+            origin: entry.to_owned(),
+        })
+    }
+
+    pub fn function(&self) -> &str {
+        &self.function
+    }
+
+    pub fn condition(&self) -> Option<(&str, &SinkValue)> {
+        self.condition.as_ref().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl<'de> Deserialize<'de> for LiteralStringSink {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct StrVisitor;
+        impl<'de> Visitor<'de> for StrVisitor {
+            type Value = LiteralStringSink;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("A qualified function name, e.g. `sqlite3.Cursor.execute`")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                LiteralStringSink::new(value).map_err(serde::de::Error::custom)
+            }
+        }
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+impl Serialize for LiteralStringSink {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.origin)
+    }
+}
+
+/// The parsed `literal-string-sinks` of a config. Cheap to clone, so each module's solver
+/// can hold its own.
+#[derive(
+    Debug,
+    Clone,
+    Dupe,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Deserialize,
+    Serialize
+)]
+#[serde(transparent)]
+pub struct LiteralStringSinks(Arc<[LiteralStringSink]>);
+
+impl LiteralStringSinks {
+    pub fn new(sinks: Vec<LiteralStringSink>) -> Self {
+        Self(sinks.into())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The sinks for the function with the given qualified name.
+    pub fn for_function<'a>(
+        &'a self,
+        function: &'a str,
+    ) -> impl Iterator<Item = &'a LiteralStringSink> + 'a {
+        self.0.iter().filter(move |sink| sink.function == function)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let sink = LiteralStringSink::new("sqlite3.Cursor.execute").unwrap();
+        assert_eq!(sink.function(), "sqlite3.Cursor.execute");
+        assert_eq!(sink.condition(), None);
+        for (entry, keyword, value) in [
+            ("subprocess.run(shell=True)", "shell", SinkValue::Bool(true)),
+            ("m.f( mode = None )", "mode", SinkValue::None),
+            ("m.f(level=-1)", "level", SinkValue::Int(-1)),
+            (
+                "m.f(engine='raw')",
+                "engine",
+                SinkValue::Str("raw".to_owned()),
+            ),
+        ] {
+            let sink = LiteralStringSink::new(entry).unwrap();
+            assert_eq!(sink.condition(), Some((keyword, &value)), "{entry}");
+        }
+        for entry in [
+            "",
+            "m..f",
+            "m.f(shell)",
+            "m.f(shell=yes)",
+            "m.f(=True)",
+            "m.f(x=1",
+        ] {
+            assert!(LiteralStringSink::new(entry).is_err(), "{entry}");
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let sinks: LiteralStringSinks =
+            serde_json::from_str(r#"["a.f", "b.g(shell=True)"]"#).unwrap();
+        assert_eq!(sinks.for_function("b.g").count(), 1);
+        assert_eq!(
+            serde_json::to_string(&sinks).unwrap(),
+            r#"["a.f","b.g(shell=True)"]"#
+        );
+        assert!(serde_json::from_str::<LiteralStringSinks>(r#"["a.f(x)"]"#).is_err());
+    }
+}
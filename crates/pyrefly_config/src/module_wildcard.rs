@@ -69,6 +69,51 @@ impl ModuleWildcard {
     }
 }
 
+/// Whether the first pattern in `wildcards` that matches `module` is a positive one.
+fn matches_first(wildcards: &[ModuleWildcard], module: ModuleName) -> bool {
+    wildcards
+        .iter()
+        .find_map(|w| match w.matches(module) {
+            Match::NoMatch => None,
+            Match::Positive => Some(true),
+            Match::Negative => Some(false),
+        })
+        .unwrap_or(false)
+}
+
+/// Module name patterns that select which of the discovered files should be checked,
+/// independent of where the files live on disk.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleFilter {
+    include: Vec<ModuleWildcard>,
+    exclude: Vec<ModuleWildcard>,
+}
+
+impl ModuleFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> anyhow::Result<Self> {
+        let parse = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|x| {
+                    ModuleWildcard::new(x)
+                        .map_err(|e| anyhow::anyhow!("Invalid module pattern `{x}`: {e:#}"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(Self {
+            include: parse(include)?,
+            exclude: parse(exclude)?,
+        })
+    }
+
+    /// A module is allowed if it matches an include pattern (or there are none),
+    /// and doesn't match an exclude pattern.
+    pub fn allows(&self, module: ModuleName) -> bool {
+        (self.include.is_empty() || matches_first(&self.include, module))
+            && !matches_first(&self.exclude, module)
+    }
+}
+
 impl PartialEq<ModuleWildcard> for ModuleWildcard {
     fn eq(&self, other: &Self) -> bool {
         self.origin == other.origin
@@ -155,6 +200,31 @@ fn rewrite_pattern_as_regex(original: &str) -> anyhow::Result<Regex> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_module_filter() {
+        let allows = |include: &[&str], exclude: &[&str], module: &str| {
+            let strings = |xs: &[&str]| xs.iter().map(|x| (*x).to_owned()).collect::<Vec<_>>();
+            ModuleFilter::new(&strings(include), &strings(exclude))
+                .unwrap()
+                .allows(ModuleName::from_str(module))
+        };
+        assert!(allows(&[], &[], "foo.bar"));
+        assert!(!allows(&[], &["proto.*"], "proto.gen.messages"));
+        assert!(allows(&[], &["proto.*"], "protocol"));
+        assert!(allows(&["app.*"], &[], "app.models"));
+        assert!(!allows(&["app.*"], &[], "scripts.run"));
+        assert!(!allows(
+            &["app.*"],
+            &["app.generated.*"],
+            "app.generated.api"
+        ));
+        assert!(allows(
+            &["app.*"],
+            &["!app.generated.keep", "app.generated.*"],
+            "app.generated.keep"
+        ));
+    }
+
     #[test]
     fn test_rewrite_pattern_as_regex() {
         let pattern = rewrite_pattern_as_regex("path.to.my.file").unwrap();
@@ -130,6 +130,8 @@ pub enum CellKind {
 #[derive(Default)]
 pub struct InitializeSettings {
     pub workspace_folders: Option<Vec<(String, Url)>>,
+    // The deprecated `rootUri`, sent by clients without workspace folder support
+    pub root_uri: Option<Url>,
     // initial configuration to send after initialization
     // When Some, configuration will be sent after initialization
     // When None, no configuration will be sent
@@ -739,6 +741,9 @@ impl TestClient {
                     .collect::<Vec<_>>()
             );
         }
+        if let Some(root_uri) = &settings.root_uri {
+            params["rootUri"] = json!(root_uri.to_string());
+        }
         if settings.file_watch {
             params["capabilities"]["workspace"]["didChangeWatchedFiles"] =
                 json!({"dynamicRegistration": true});
@@ -1442,11 +1447,10 @@ impl LspInteraction {
     }
 
     pub fn initialize(&self, settings: InitializeSettings) -> Result<(), LspMessageError> {
-        let scope_uris: Vec<Url> = settings
-            .workspace_folders
-            .as_ref()
-            .map(|folders| folders.iter().map(|(_, uri)| uri.clone()).collect())
-            .unwrap_or_default();
+        let scope_uris: Vec<Url> = match &settings.workspace_folders {
+            Some(folders) => folders.iter().map(|(_, uri)| uri.clone()).collect(),
+            None => settings.root_uri.iter().cloned().collect(),
+        };
         let file_watch = settings.file_watch;
 
         self.client
@@ -0,0 +1,231 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Decoding the bytes of a Python source file into text.
+//!
+//! Source files are UTF-8 unless they start with a
+//! [PEP 263](https://peps.python.org/pep-0263/) declaration such as
+//! `# -*- coding: latin-1 -*-` on one of their first two lines. We support the
+//! single-byte encodings that show up in practice, and report anything else as
+//! a `DecodeError`.
+
+use std::sync::LazyLock;
+
+use regex::bytes::Regex;
+use thiserror::Error;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Matches a PEP 263 encoding declaration, capturing the encoding name.
+static RE_CODING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?-u)^[ \t\x0C]*#.*?coding[:=][ \t]*([-\w.]+)").expect("Invalid regex pattern")
+});
+
+/// The characters for bytes `0x80..=0x9F` in `cp1252`, where it differs from `latin-1`.
+/// `None` marks bytes that `cp1252` leaves undefined.
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DecodeError {
+    #[error(
+        "invalid UTF-8 at byte offset {0}; add a `# -*- coding: ... -*-` declaration if the file uses a different encoding"
+    )]
+    InvalidUtf8(usize),
+    #[error("invalid `{encoding}` byte at offset {offset}")]
+    InvalidByte { encoding: String, offset: usize },
+    #[error("unsupported source encoding `{0}`")]
+    UnsupportedEncoding(String),
+}
+
+/// The encodings we know how to decode, after normalizing their names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Ascii,
+    Latin1,
+    Cp1252,
+}
+
+impl Encoding {
+    /// Normalize an encoding name the way CPython's tokenizer does, then look it up.
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        match name.as_str() {
+            "utf-8" | "utf8" | "utf-8-sig" => Some(Self::Utf8),
+            _ if name.starts_with("utf-8-") => Some(Self::Utf8),
+            "ascii" | "us-ascii" | "646" => Some(Self::Ascii),
+            "latin-1" | "latin1" | "l1" | "iso-8859-1" | "iso8859-1" | "iso-latin-1" | "cp819" => {
+                Some(Self::Latin1)
+            }
+            _ if name.starts_with("latin-1-")
+                || name.starts_with("iso-8859-1-")
+                || name.starts_with("iso-latin-1-") =>
+            {
+                Some(Self::Latin1)
+            }
+            "cp1252" | "windows-1252" => Some(Self::Cp1252),
+            _ => None,
+        }
+    }
+
+    fn decode_byte(self, byte: u8) -> Option<char> {
+        match self {
+            _ if byte.is_ascii() => Some(byte as char),
+            Self::Ascii => None,
+            Self::Cp1252 if (0x80..=0x9F).contains(&byte) => CP1252_HIGH[(byte - 0x80) as usize],
+            Self::Latin1 | Self::Cp1252 => Some(byte as char),
+            Self::Utf8 => unreachable!("UTF-8 is not a single-byte encoding"),
+        }
+    }
+}
+
+/// Find the encoding declared on the first or second line of a file, if any.
+/// The second line only counts if the first is blank or a comment, as in CPython.
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let mut lines = bytes.split(|b| *b == b'\n');
+    let first = lines.next()?;
+    if let Some(m) = RE_CODING.captures(first) {
+        return std::str::from_utf8(&m[1]).ok();
+    }
+    let trimmed = first.trim_ascii_start();
+    if !trimmed.is_empty() && !trimmed.starts_with(b"#") {
+        return None;
+    }
+    let m = RE_CODING.captures(lines.next()?)?;
+    std::str::from_utf8(&m[1]).ok()
+}
+
+/// Decode the contents of a Python source file. A leading UTF-8 byte order mark is kept,
+/// since positions in the rest of Pyrefly already account for it.
+pub fn decode_source(bytes: Vec<u8>) -> Result<String, DecodeError> {
+    let declared = if bytes.starts_with(UTF8_BOM) {
+        None
+    } else {
+        declared_encoding(&bytes).map(str::to_owned)
+    };
+    let Some(name) = declared else {
+        return String::from_utf8(bytes)
+            .map_err(|e| DecodeError::InvalidUtf8(e.utf8_error().valid_up_to()));
+    };
+    let encoding = match Encoding::from_name(&name) {
+        Some(encoding) => encoding,
+        // An encoding we don't know still decodes ASCII-only files the same way.
+        None if bytes.is_ascii() => Encoding::Ascii,
+        None => return Err(DecodeError::UnsupportedEncoding(name)),
+    };
+    if encoding == Encoding::Utf8 {
+        return String::from_utf8(bytes).map_err(|e| DecodeError::InvalidByte {
+            encoding: name,
+            offset: e.utf8_error().valid_up_to(),
+        });
+    }
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(offset, byte)| {
+            encoding
+                .decode_byte(*byte)
+                .ok_or_else(|| DecodeError::InvalidByte {
+                    encoding: name.clone(),
+                    offset,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utf8() {
+        assert_eq!(
+            decode_source(b"x = 'caf\xC3\xA9'\n".to_vec()).unwrap(),
+            "x = 'café'\n"
+        );
+        assert_eq!(
+            decode_source(b"\xEF\xBB\xBFx = 1\n".to_vec()).unwrap(),
+            "\u{FEFF}x = 1\n"
+        );
+        assert_eq!(
+            decode_source(b"x = '\xE9'\n".to_vec()),
+            Err(DecodeError::InvalidUtf8(5))
+        );
+    }
+
+    #[test]
+    fn test_decode_declared() {
+        assert_eq!(
+            decode_source(b"# -*- coding: latin-1 -*-\nx = '\xE9'\n".to_vec()).unwrap(),
+            "# -*- coding: latin-1 -*-\nx = 'é'\n"
+        );
+        assert_eq!(
+            decode_source(
+                b"#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\nx = '\x80'\n".to_vec()
+            )
+            .unwrap(),
+            "#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\nx = '€'\n"
+        );
+        // The declaration only counts on the second line if the first is a comment.
+        assert_eq!(
+            decode_source(b"import os\n# coding: latin-1\nx = '\xE9'\n".to_vec()),
+            Err(DecodeError::InvalidUtf8(33))
+        );
+        assert_eq!(
+            decode_source(b"# coding: ascii\nx = '\xE9'\n".to_vec()),
+            Err(DecodeError::InvalidByte {
+                encoding: "ascii".to_owned(),
+                offset: 21
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_unsupported() {
+        assert_eq!(
+            decode_source(b"# coding: euc-jp\nx = 1\n".to_vec()).unwrap(),
+            "# coding: euc-jp\nx = 1\n"
+        );
+        assert_eq!(
+            decode_source(b"# coding: euc-jp\nx = '\xA4\xA2'\n".to_vec()),
+            Err(DecodeError::UnsupportedEncoding("euc-jp".to_owned()))
+        );
+    }
+}
@@ -16,6 +16,10 @@
 //! You can specify a specific error code, e.g. `# type: ignore[invalid-type]`.
 //! Note that Pyright will only honor such codes after `# pyright: ignore[code]`.
 //!
+//! A region of lines can be suppressed with `# pyrefly: ignore-start[invalid-type]`
+//! and a later `# pyrefly: ignore-end`. The error codes are required, and blocks
+//! that are never closed run to the end of the file.
+//!
 //! You can also use `# mypy: ignore-errors`, `# pyrefly: ignore-errors`
 //! or `# type: ignore` at the beginning of a file to suppress all errors.
 //! `# pyrefly: ignore-errors[invalid-type]` suppresses only the listed error
@@ -136,6 +140,28 @@ pub fn find_comment_start_in_line(line: &str) -> Option<usize> {
     find_comment_start(line, None).0
 }
 
+/// Returns `true` if `line` has a `# noqa` comment that applies to the flake8/ruff rule
+/// `code`, either as a bare `# noqa` or as a `# noqa: CODE1, CODE2` list.
+pub fn has_noqa(line: &str, code: &str) -> bool {
+    let Some(comment_start) = find_comment_start_in_line(line) else {
+        return false;
+    };
+    line[comment_start..].split('#').skip(1).any(|comment| {
+        let mut lex = Lexer(comment);
+        lex.trim_start();
+        if !lex.starts_with("noqa") {
+            return false;
+        }
+        if lex.starts_with(":") {
+            lex.rest()
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .any(|x| x == code)
+        } else {
+            lex.word_boundary()
+        }
+    })
+}
+
 /// The name of the tool that is being suppressed.
 /// Note that the variant names and docstrings are displayed in `pyrefly check --help`.
 #[derive(PartialEq, Debug, Clone, Hash, Eq, Dupe, Copy, Sequence)]
@@ -285,6 +311,47 @@ impl Suppression {
     pub fn tool(&self) -> Tool {
         self.tool
     }
+
+    /// Whether this suppression silences an error of `kind`. Only `# pyrefly: ignore`
+    /// checks its error codes; other tools suppress everything. With `require_codes`,
+    /// blanket suppressions and those from other tools suppress nothing.
+    pub fn suppresses(
+        &self,
+        kind: &str,
+        enabled_ignores: &SmallSet<Tool>,
+        require_codes: bool,
+    ) -> bool {
+        enabled_ignores.contains(&self.tool)
+            && match self.tool {
+                Tool::Pyrefly if self.kind.is_empty() => !require_codes,
+                Tool::Pyrefly => self.kind.iter().any(|x| x == kind),
+                _ => !require_codes,
+            }
+    }
+}
+
+/// The lines between `# pyrefly: ignore-start[code]` and `# pyrefly: ignore-end`.
+#[derive(PartialEq, Debug, Clone, Hash, Eq)]
+pub struct IgnoreBlock {
+    /// The line of the `ignore-start` comment.
+    start: LineNumber,
+    /// The line of the matching `ignore-end` comment, or the last line of the file
+    /// if the block is never closed.
+    end: LineNumber,
+    /// The error codes suppressed within the block, never empty.
+    kind: Vec<String>,
+}
+
+impl IgnoreBlock {
+    fn suppresses(&self, line: LineNumber, kind: &str) -> bool {
+        self.start <= line && line <= self.end && self.kind.iter().any(|x| x == kind)
+    }
+}
+
+/// A comment that opens or closes an `IgnoreBlock`.
+enum BlockPragma {
+    Start(Vec<String>),
+    End,
 }
 
 /// Record the position of lines affected by `# type: ignore[valid-type]` suppressions.
@@ -294,12 +361,80 @@ pub struct Ignore {
     /// The line number here represents the line that the suppression applies to,
     /// not the line of the suppression comment.
     ignores: SmallMap<LineNumber, Vec<Suppression>>,
+    /// Regions suppressed by `# pyrefly: ignore-start` / `# pyrefly: ignore-end`.
+    blocks: Vec<IgnoreBlock>,
 }
 
 impl Ignore {
     pub fn new(code: &str) -> Self {
         Self {
             ignores: Self::parse_ignores(code),
+            blocks: Self::parse_ignore_blocks(code),
+        }
+    }
+
+    /// Pair up `ignore-start` and `ignore-end` comments, with each `ignore-end`
+    /// closing the most recently opened block.
+    fn parse_ignore_blocks(code: &str) -> Vec<IgnoreBlock> {
+        let mut blocks = Vec::new();
+        let mut open: Vec<(LineNumber, Vec<String>)> = Vec::new();
+        let mut line = LineNumber::default();
+        let mut in_triple_quote = None;
+        for (idx, line_str) in code.lines().enumerate() {
+            let (comment_start, new_state) = find_comment_start(line_str, in_triple_quote);
+            in_triple_quote = new_state;
+            line = LineNumber::from_zero_indexed(idx as u32);
+            let Some(comment_start) = comment_start else {
+                continue;
+            };
+            for x in line_str[comment_start..].split('#').skip(1) {
+                match Self::parse_block_pragma(x) {
+                    Some(BlockPragma::Start(kind)) => open.push((line, kind)),
+                    Some(BlockPragma::End) => {
+                        if let Some((start, kind)) = open.pop() {
+                            blocks.push(IgnoreBlock {
+                                start,
+                                end: line,
+                                kind,
+                            });
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+        blocks.extend(open.into_iter().map(|(start, kind)| IgnoreBlock {
+            start,
+            end: line,
+            kind,
+        }));
+        blocks
+    }
+
+    /// Given the content of a comment, parse it as `pyrefly: ignore-start[code]` or
+    /// `pyrefly: ignore-end`. An `ignore-start` without error codes is not a pragma.
+    fn parse_block_pragma(l: &str) -> Option<BlockPragma> {
+        let mut lex = Lexer(l);
+        lex.trim_start();
+        if lex.starts_with_tool() != Some(Tool::Pyrefly) {
+            return None;
+        }
+        lex.trim_start();
+        if lex.starts_with("ignore-start") {
+            lex.trim_start();
+            if !lex.starts_with("[") {
+                return None;
+            }
+            let (inside, _) = lex.rest().split_once(']')?;
+            let kind: Vec<String> = parse_error_codes(inside)
+                .into_iter()
+                .filter(|code| !code.is_empty())
+                .collect();
+            (!kind.is_empty()).then_some(BlockPragma::Start(kind))
+        } else if lex.starts_with("ignore-end") && lex.word_boundary() {
+            Some(BlockPragma::End)
+        } else {
+            None
         }
     }
 
@@ -379,27 +514,27 @@ impl Ignore {
         None
     }
 
+    /// Whether an error of `kind` starting on `start_line` is suppressed. With
+    /// `require_codes`, only `# pyrefly: ignore[...]` comments that name error codes count.
     pub fn is_ignored(
         &self,
         start_line: LineNumber,
         kind: &str,
         enabled_ignores: &SmallSet<Tool>,
+        require_codes: bool,
     ) -> bool {
         if let Some(suppressions) = self.ignores.get(&start_line)
-            && suppressions.iter().any(|supp| {
-                enabled_ignores.contains(&supp.tool)
-                    && match supp.tool {
-                        // We only check the subkind if they do `# pyrefly: ignore`
-                        Tool::Pyrefly => {
-                            supp.kind.is_empty() || supp.kind.iter().any(|x| x == kind)
-                        }
-                        _ => true,
-                    }
-            })
+            && suppressions
+                .iter()
+                .any(|supp| supp.suppresses(kind, enabled_ignores, require_codes))
         {
             return true;
         }
-        false
+        enabled_ignores.contains(&Tool::Pyrefly)
+            && self
+                .blocks
+                .iter()
+                .any(|block| block.suppresses(start_line, kind))
     }
 
     /// Similar to `is_ignored`, but it only returns true if the error is ignored
@@ -419,17 +554,9 @@ impl Ignore {
         let Some(suppressions) = self.ignores.get(&suppression_line) else {
             return false;
         };
-        if suppressions.iter().any(|supp| {
-            enabled_ignores.contains(&supp.tool)
-                && match supp.tool {
-                    // We only check the subkind if they do `# pyrefly: ignore`
-                    Tool::Pyrefly => supp.kind.is_empty() || supp.kind.iter().any(|x| x == kind),
-                    _ => true,
-                }
-        }) {
-            return true;
-        }
-        false
+        suppressions
+            .iter()
+            .any(|supp| supp.suppresses(kind, enabled_ignores, false))
     }
 
     // gets either just pyrefly ignores or pyrefly and type: ignore comments
@@ -461,7 +588,7 @@ impl Ignore {
 
     /// Returns true if there are no suppressions.
     pub fn is_empty(&self) -> bool {
-        self.ignores.is_empty()
+        self.ignores.is_empty() && self.blocks.is_empty()
     }
 }
 
@@ -741,6 +868,47 @@ x = """
         f("x = ''''''  # pyrefly: ignore", &[(Tool::Pyrefly, 1)]);
     }
 
+    #[test]
+    fn test_has_noqa() {
+        assert!(has_noqa("import os  # noqa", "F401"));
+        assert!(has_noqa("import os  # noqa: F401", "F401"));
+        assert!(has_noqa("import os  #noqa:E501,F401", "F401"));
+        assert!(has_noqa("import os  # type: ignore # noqa: F401", "F401"));
+        assert!(!has_noqa("import os  # noqa: E501", "F401"));
+        assert!(!has_noqa("import os  # noqaz", "F401"));
+        assert!(!has_noqa("x = '# noqa'", "F401"));
+        assert!(!has_noqa("import os", "F401"));
+    }
+
+    #[test]
+    fn test_parse_ignore_blocks() {
+        fn f(x: &str, expect: &[(u32, u32, &[&str])]) {
+            assert_eq!(
+                Ignore::parse_ignore_blocks(x)
+                    .iter()
+                    .map(|b| (b.start.get(), b.end.get(), b.kind.map(|k| k.as_str())))
+                    .collect::<Vec<_>>(),
+                expect.map(|(start, end, kind)| (*start, *end, kind.to_vec())),
+                "{x:?}"
+            );
+        }
+
+        f(
+            "# pyrefly: ignore-start[a, b]\nx\n# pyrefly: ignore-end",
+            &[(1, 3, &["a", "b"])],
+        );
+        f(
+            "# pyrefly: ignore-start[a]\n# pyrefly: ignore-start[b]\n# pyrefly: ignore-end\nx\n# pyrefly: ignore-end",
+            &[(2, 3, &["b"]), (1, 5, &["a"])],
+        );
+        f("# pyrefly: ignore-start[a]\nx\ny", &[(1, 3, &["a"])]);
+        f("# pyrefly: ignore-start\nx\n# pyrefly: ignore-end", &[]);
+        f("# pyrefly: ignore-start[]\nx", &[]);
+        f("# type: ignore-start[a]\nx", &[]);
+        f("x = '# pyrefly: ignore-start[a]'\ny", &[]);
+        f("# pyrefly: ignore-end\nx", &[]);
+    }
+
     #[test]
     fn test_suppression_comment_offset() {
         fn f(x: &str, expect: &[(u32, usize)]) {
@@ -22,6 +22,7 @@ pub mod deprecated_aliases;
 pub mod display;
 pub mod docstring;
 pub mod dunder;
+pub mod encoding;
 pub mod folding;
 pub mod ignore;
 pub mod keywords;
@@ -14,6 +14,7 @@ use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::lined_buffer::DisplayPos;
 use pyrefly_util::lined_buffer::DisplayRange;
 use pyrefly_util::lined_buffer::LinedBuffer;
+use pyrefly_util::lined_buffer::NotebookCells;
 use ruff_notebook::Notebook;
 use ruff_python_ast::PySourceType;
 use ruff_text_size::TextRange;
@@ -49,7 +50,7 @@ struct ModuleInner {
     ignore: Ignore,
     is_generated: bool,
     contents: LinedBuffer,
-    notebook: Option<Arc<Notebook>>,
+    notebook: Option<NotebookCells>,
 }
 
 impl Debug for Module {
@@ -88,7 +89,7 @@ impl Module {
             ignore,
             is_generated,
             contents,
-            notebook: Some(notebook),
+            notebook: Some(NotebookCells::new(notebook)),
         }))
     }
 
@@ -123,17 +124,26 @@ impl Module {
     }
 
     pub fn to_lsp_range(&self, x: TextRange) -> lsp_types::Range {
-        self.lined_buffer().to_lsp_range(x, self.notebook())
+        self.lined_buffer()
+            .to_lsp_range(x, self.0.notebook.as_ref())
+    }
+
+    /// Translates many text ranges to LSP ranges, as `to_lsp_range` does.
+    pub fn to_lsp_ranges(&self, xs: impl IntoIterator<Item = TextRange>) -> Vec<lsp_types::Range> {
+        self.lined_buffer()
+            .to_lsp_ranges(xs, self.0.notebook.as_ref())
     }
 
     pub fn to_lsp_position(&self, x: TextSize) -> lsp_types::Position {
-        self.lined_buffer().to_lsp_position(x, self.notebook())
+        self.lined_buffer()
+            .to_lsp_position(x, self.0.notebook.as_ref())
     }
 
     /// If the module is a notebook, take an input position relative to the concatenated contents
     /// and return the index of the corresponding notebook cell.
     pub fn to_cell_for_lsp(&self, x: TextSize) -> Option<usize> {
-        self.lined_buffer().to_cell_for_lsp(x, self.notebook())
+        self.lined_buffer()
+            .to_cell_for_lsp(x, self.0.notebook.as_ref())
     }
 
     /// Translates an LSP position to a text size.
@@ -201,11 +211,13 @@ impl Module {
         source_range: &DisplayRange,
         error_kind: &str,
         enabled_ignores: &SmallSet<Tool>,
+        require_codes: bool,
     ) -> bool {
         self.0.ignore.is_ignored(
             source_range.start.line_within_file(),
             error_kind,
             enabled_ignores,
+            require_codes,
         )
     }
 
@@ -214,7 +226,10 @@ impl Module {
     }
 
     pub fn notebook(&self) -> Option<&Notebook> {
-        self.0.notebook.as_deref()
+        self.0
+            .notebook
+            .as_ref()
+            .map(|notebook| &**notebook.notebook())
     }
 
     pub fn source_type(&self) -> PySourceType {
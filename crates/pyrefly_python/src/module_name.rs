@@ -523,6 +523,44 @@ impl ModuleName {
         None
     }
 
+    /// The name of the module at `path` within the package it belongs to, and the directory
+    /// that package is importable from. Walks up from `path` through the directories that
+    /// `is_package` accepts (usually those with an `__init__` file), so `root/pkg/sub/mod.py`
+    /// is `pkg.sub.mod` from `root` if `pkg` and `sub` are packages. Stops at a directory that
+    /// isn't a valid identifier. Returns `None` if `path` isn't a Python file, or its own name
+    /// isn't a valid identifier.
+    pub fn from_package_path(
+        path: &Path,
+        is_package: impl Fn(&Path) -> bool,
+    ) -> Option<(ModuleName, &Path)> {
+        let extension = path.extension()?.to_str()?;
+        if !PYTHON_EXTENSIONS.contains(&extension) {
+            return None;
+        }
+        let stem = path.file_stem()?.to_str()?;
+        let mut dir = path.parent()?;
+        let mut components = Vec::new();
+        if stem == dunder::INIT.as_str() {
+            components.push(dir.file_name()?.to_str()?);
+            dir = dir.parent()?;
+        } else {
+            components.push(stem);
+        }
+        if !is_python_identifier(components[0]) {
+            return None;
+        }
+        while is_package(dir)
+            && let Some(name) = dir.file_name().and_then(|name| name.to_str())
+            && is_python_identifier(name)
+            && let Some(parent) = dir.parent()
+        {
+            components.push(name);
+            dir = parent;
+        }
+        components.reverse();
+        Some((ModuleName::from_parts(components), dir))
+    }
+
     /// Pop off the last name component from this [`ModuleName`]. If the `ModuleName`
     /// would be empty, return `None` instead.
     pub fn parent(&self) -> Option<Self> {
@@ -696,6 +734,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_module_from_package_path() {
+        let packages = [Path::new("/root/pkg"), Path::new("/root/pkg/sub")];
+        let is_package = |dir: &Path| packages.contains(&dir);
+        let f = |path: &str| {
+            ModuleName::from_package_path(Path::new(path), is_package)
+                .map(|(name, root)| (name.as_str().to_owned(), root.to_owned()))
+        };
+        assert_eq!(
+            f("/root/pkg/sub/mod.py"),
+            Some(("pkg.sub.mod".to_owned(), PathBuf::from("/root")))
+        );
+        assert_eq!(
+            f("/root/pkg/sub/__init__.pyi"),
+            Some(("pkg.sub".to_owned(), PathBuf::from("/root")))
+        );
+        assert_eq!(
+            f("/root/pkg/mod.py"),
+            Some(("pkg.mod".to_owned(), PathBuf::from("/root")))
+        );
+        // Not in a package.
+        assert_eq!(
+            f("/root/other/mod.py"),
+            Some(("mod".to_owned(), PathBuf::from("/root/other")))
+        );
+        assert_eq!(f("/root/pkg/my-script.py"), None);
+        assert_eq!(f("/root/pkg/data.json"), None);
+    }
+
     #[test]
     fn test_module_from_path_extra_extensions() {
         let includes = [PathBuf::from("/root")];
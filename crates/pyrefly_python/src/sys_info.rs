@@ -799,6 +799,18 @@ impl SysInfo {
         x == "TYPE_CHECKING" || x == "TYPE_CHECKING_WITH_PYREFLY"
     }
 
+    /// Is this expression exactly a `TYPE_CHECKING` constant, e.g. `TYPE_CHECKING` or
+    /// `typing.TYPE_CHECKING`, as opposed to a larger expression that mentions one?
+    pub fn is_type_checking_test(x: &Expr) -> bool {
+        match x {
+            Expr::Name(name) => Self::is_type_checking_constant_name(name.id()),
+            Expr::Attribute(ExprAttribute { value, attr, .. }) => {
+                value.is_name_expr() && Self::is_type_checking_constant_name(attr.as_str())
+            }
+            _ => false,
+        }
+    }
+
     fn evaluate(self, x: &Expr) -> Option<Value> {
         match x {
             Expr::Compare(x) if x.ops.len() == 1 && x.comparators.len() == 1 => Some(Value::Bool(
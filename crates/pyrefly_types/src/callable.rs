@@ -873,6 +873,9 @@ pub enum FunctionKind {
     /// returns a subtype of `int` (e.g. a shaped array's `Int[N]`), `len(x)`
     /// yields that type instead of typeshed's plain `int`.
     Len,
+    /// The builtin `setattr`. Special-cased so that when the attribute name is a
+    /// string literal, `setattr(x, "a", v)` is checked like the assignment `x.a = v`.
+    SetAttr,
     Dataclass,
     DataclassField,
     DataclassReplace,
@@ -1299,6 +1302,7 @@ impl FunctionKind {
             ("builtins", None, "isinstance") => Self::IsInstance,
             ("builtins", None, "issubclass") => Self::IsSubclass,
             ("builtins", None, "len") => Self::Len,
+            ("builtins", None, "setattr") => Self::SetAttr,
             ("builtins", None, "classmethod") => Self::ClassMethod,
             ("dataclasses", None, "dataclass") => Self::Dataclass,
             ("dataclasses", None, "field") => Self::DataclassField,
@@ -1343,6 +1347,7 @@ impl FunctionKind {
             Self::IsInstance => ModuleName::builtins(),
             Self::IsSubclass => ModuleName::builtins(),
             Self::Len => ModuleName::builtins(),
+            Self::SetAttr => ModuleName::builtins(),
             Self::ClassMethod => ModuleName::builtins(),
             Self::Dataclass => ModuleName::dataclasses(),
             Self::DataclassField => ModuleName::dataclasses(),
@@ -1382,6 +1387,7 @@ impl FunctionKind {
             Self::IsInstance => Cow::Owned(Name::new_static("isinstance")),
             Self::IsSubclass => Cow::Owned(Name::new_static("issubclass")),
             Self::Len => Cow::Owned(Name::new_static("len")),
+            Self::SetAttr => Cow::Owned(Name::new_static("setattr")),
             Self::ClassMethod => Cow::Owned(Name::new_static("classmethod")),
             Self::Dataclass => Cow::Owned(Name::new_static("dataclass")),
             Self::DataclassField => Cow::Owned(Name::new_static("field")),
@@ -1421,6 +1427,7 @@ impl FunctionKind {
             Self::IsInstance => None,
             Self::IsSubclass => None,
             Self::Len => None,
+            Self::SetAttr => None,
             Self::ClassMethod => None,
             Self::Dataclass => None,
             Self::DataclassField => None,
@@ -24,6 +24,7 @@ use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use starlark_map::smallmap;
 
+use crate::callable::Callable;
 use crate::callable::Function;
 use crate::callable::FunctionKind;
 use crate::callable::ParamOverlay;
@@ -138,6 +139,15 @@ pub enum LspDisplayMode {
     ProvideType,
 }
 
+/// Extra formatting knobs for `Type::as_lsp_string_with_options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LspDisplayOptions {
+    /// See `TypeDisplayContext::set_signature_line_width`.
+    pub signature_line_width: Option<usize>,
+    /// See `TypeDisplayContext::collapse_aliased_unions_over`.
+    pub union_display_limit: usize,
+}
+
 #[derive(Debug, Default)]
 pub struct TypeDisplayContext<'a> {
     qnames: SmallMap<&'a Name, QNameInfo>,
@@ -145,6 +155,12 @@ pub struct TypeDisplayContext<'a> {
     lsp_display_mode: LspDisplayMode,
     always_display_module_name: bool,
     always_display_expanded_unions: bool,
+    /// Top-level unions that came from a type alias and have more members than this are
+    /// displayed by the alias name instead of being expanded.
+    max_expanded_union_members: Option<usize>,
+    /// When set, signatures are broken over multiple lines exactly when their single-line
+    /// rendering is wider than this, instead of based on the display mode.
+    signature_line_width: Option<usize>,
     render_self_type_as_self: bool,
     /// Optional stdlib reference for resolving builtin type locations
     stdlib: Option<&'a Stdlib>,
@@ -221,6 +237,43 @@ impl<'a> TypeDisplayContext<'a> {
         self.always_display_expanded_unions = true;
     }
 
+    /// Display top-level unions with more than `limit` members by their type alias name, if
+    /// they have one. Nested aliased unions are always displayed by name. A `limit` of 0
+    /// keeps the default of expanding every top-level union.
+    pub fn collapse_aliased_unions_over(&mut self, limit: usize) {
+        self.max_expanded_union_members = (limit > 0).then_some(limit);
+    }
+
+    /// Render a signature with one parameter per line when its parameters and return type
+    /// don't fit on one line of `width` characters, and on a single line otherwise. Without
+    /// this, only hover breaks signatures, and it breaks every one with several parameters.
+    pub fn set_signature_line_width(&mut self, width: usize) {
+        self.signature_line_width = Some(width);
+    }
+
+    /// Write the parameters and return type of `signature`, over multiple lines if
+    /// configured to (see `set_signature_line_width`).
+    fn fmt_signature<O: TypeOutput>(&self, signature: &Callable, output: &mut O) -> fmt::Result {
+        let multiline = match self.signature_line_width {
+            Some(width) => {
+                let single_line = Fmt(|f| {
+                    signature.fmt_with_type(&mut DisplayOutput::new(self, f), &|t, o| {
+                        self.fmt_helper_generic(t, false, o)
+                    })
+                })
+                .to_string();
+                single_line.chars().count() > width
+            }
+            None => self.lsp_display_mode == LspDisplayMode::Hover,
+        };
+        if multiline {
+            signature
+                .fmt_with_type_with_newlines(output, &|t, o| self.fmt_helper_generic(t, false, o))
+        } else {
+            signature.fmt_with_type(output, &|t, o| self.fmt_helper_generic(t, false, o))
+        }
+    }
+
     pub fn render_self_type_as_self(&mut self) {
         self.render_self_type_as_self = true;
     }
@@ -792,13 +845,7 @@ impl<'a> TypeDisplayContext<'a> {
             Type::Callable(c) => {
                 // Hover output should be readable even when callables appear inside unions
                 // (e.g. constructor display that unions __new__ and __init__).
-                if self.lsp_display_mode == LspDisplayMode::Hover {
-                    c.fmt_with_type_with_newlines(output, &|t, o| {
-                        self.fmt_helper_generic(t, false, o)
-                    })
-                } else {
-                    c.fmt_with_type(output, &|t, o| self.fmt_helper_generic(t, false, o))
-                }
+                self.fmt_signature(c, output)
             }
             Type::CallableResidual(residual) => match &residual.kind {
                 CallableResidualKind::Generic { quantified } => {
@@ -845,34 +892,14 @@ impl<'a> TypeDisplayContext<'a> {
                         let func_name = metadata.kind.function_name();
                         output.write_str("def ")?;
                         self.write_func_fqn(output, &func_name, &metadata.kind)?;
-                        match self.lsp_display_mode {
-                            LspDisplayMode::Hover => {
-                                signature.fmt_with_type_with_newlines(output, &|t, o| {
-                                    self.fmt_helper_generic(t, false, o)
-                                })?;
-                            }
-                            _ => {
-                                signature.fmt_with_type(output, &|t, o| {
-                                    self.fmt_helper_generic(t, false, o)
-                                })?;
-                            }
-                        }
+                        self.fmt_signature(signature, output)?;
                         if self.lsp_display_mode == LspDisplayMode::ProvideType {
                             Ok(())
                         } else {
                             output.write_str(": ...")
                         }
                     }
-                    _ => {
-                        if self.lsp_display_mode == LspDisplayMode::Hover {
-                            signature.fmt_with_type_with_newlines(output, &|t, o| {
-                                self.fmt_helper_generic(t, false, o)
-                            })
-                        } else {
-                            signature
-                                .fmt_with_type(output, &|t, o| self.fmt_helper_generic(t, false, o))
-                        }
-                    }
+                    _ => self.fmt_signature(signature, output),
                 }
             }
             Type::Overload(overload) => {
@@ -954,19 +981,7 @@ impl<'a> TypeDisplayContext<'a> {
                                 } else {
                                     signature.clone()
                                 };
-                                match self.lsp_display_mode {
-                                    LspDisplayMode::Hover => {
-                                        effective_sig
-                                            .fmt_with_type_with_newlines(output, &|t, o| {
-                                                self.fmt_helper_generic(t, false, o)
-                                            })?;
-                                    }
-                                    _ => {
-                                        effective_sig.fmt_with_type(output, &|t, o| {
-                                            self.fmt_helper_generic(t, false, o)
-                                        })?;
-                                    }
-                                }
+                                self.fmt_signature(&effective_sig, output)?;
                                 if self.always_display_module_name {
                                     Ok(())
                                 } else {
@@ -1002,16 +1017,7 @@ impl<'a> TypeDisplayContext<'a> {
                                     signature.clone()
                                 };
                                 let _scope = self.push_forall_scope(tparams.iter());
-                                let result = match self.lsp_display_mode {
-                                    LspDisplayMode::Hover => effective_sig
-                                        .fmt_with_type_with_newlines(output, &|t, o| {
-                                            self.fmt_helper_generic(t, false, o)
-                                        }),
-                                    _ => effective_sig.fmt_with_type(output, &|t, o| {
-                                        self.fmt_helper_generic(t, false, o)
-                                    }),
-                                };
-                                result?;
+                                self.fmt_signature(&effective_sig, output)?;
                                 if self.always_display_module_name {
                                     Ok(())
                                 } else {
@@ -1052,7 +1058,11 @@ impl<'a> TypeDisplayContext<'a> {
                 output.write_builtin("Never", qname)
             }
             Type::Union(u)
-                if !(self.always_display_expanded_unions || is_toplevel)
+                if !self.always_display_expanded_unions
+                    && (!is_toplevel
+                        || self
+                            .max_expanded_union_members
+                            .is_some_and(|limit| u.members.len() > limit))
                     && let Some((module, name)) = &u.display_name =>
             {
                 if self.always_display_module_name && *module != ModuleName::unknown() {
@@ -1192,9 +1202,7 @@ impl<'a> TypeDisplayContext<'a> {
                                 commas_iter(|| tparams.iter().map(|q| q.display_with_bounds()))
                             )?;
                             output.write_str("]")?;
-                            c.fmt_with_type_with_newlines(output, &|t, o| {
-                                self.fmt_helper_generic(t, false, o)
-                            })
+                            self.fmt_signature(c, output)
                         } else {
                             output.write_str("[")?;
                             write!(
@@ -1228,15 +1236,7 @@ impl<'a> TypeDisplayContext<'a> {
                             )?;
                             output.write_str("]")?;
                             let _scope = self.push_forall_scope(tparams.iter());
-                            match self.lsp_display_mode {
-                                LspDisplayMode::Hover => signature
-                                    .fmt_with_type_with_newlines(output, &|t, o| {
-                                        self.fmt_helper_generic(t, false, o)
-                                    }),
-                                _ => signature.fmt_with_type(output, &|t, o| {
-                                    self.fmt_helper_generic(t, false, o)
-                                }),
-                            }?;
+                            self.fmt_signature(signature, output)?;
                             if self.always_display_module_name {
                                 Ok(())
                             } else {
@@ -1519,9 +1519,23 @@ impl Type {
         &self,
         fallback_name: Option<&str>,
         mode: LspDisplayMode,
+    ) -> String {
+        self.as_lsp_string_with_options(fallback_name, mode, LspDisplayOptions::default())
+    }
+
+    /// Like `as_lsp_string_with_fallback_name`, but with the extra formatting in `options`.
+    pub fn as_lsp_string_with_options(
+        &self,
+        fallback_name: Option<&str>,
+        mode: LspDisplayMode,
+        options: LspDisplayOptions,
     ) -> String {
         let mut c = TypeDisplayContext::new(&[self]);
         c.set_lsp_display_mode(mode);
+        if let Some(width) = options.signature_line_width {
+            c.set_signature_line_width(width);
+        }
+        c.collapse_aliased_unions_over(options.union_display_limit);
         let rendered = c.display(self).to_string();
         if let Some(name) = fallback_name
             && self.is_toplevel_callable()
@@ -2088,6 +2102,35 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_display_collapsed_aliased_union() {
+        let t = Type::Union(Box::new(Union {
+            members: vec![
+                Type::None,
+                Type::LiteralString(LitStyle::Implicit),
+                Type::Ellipsis,
+            ],
+            display_name: Some((ModuleName::unknown(), Name::new("MyUnion"))),
+        }));
+        let mut ctx = TypeDisplayContext::new(&[&t]);
+        assert_eq!(
+            ctx.display(&t).to_string(),
+            "None | LiteralString | Ellipsis"
+        );
+        ctx.collapse_aliased_unions_over(3);
+        assert_eq!(
+            ctx.display(&t).to_string(),
+            "None | LiteralString | Ellipsis"
+        );
+        ctx.collapse_aliased_unions_over(2);
+        assert_eq!(ctx.display(&t).to_string(), "MyUnion");
+        ctx.always_display_expanded_unions();
+        assert_eq!(
+            ctx.display(&t).to_string(),
+            "None | LiteralString | Ellipsis"
+        );
+    }
+
     #[test]
     fn test_display_single_param_callable() {
         let param1 = Param::Pos(Name::new_static("hello"), Type::None, Required::Required);
@@ -2127,6 +2170,32 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_display_callable_with_line_width() {
+        let param1 = Param::Pos(Name::new_static("hello"), Type::None, Required::Required);
+        let param2 = Param::KwOnly(Name::new_static("world"), Type::None, Required::Required);
+        let callable = Callable::list(ParamList::new(vec![param1, param2]), Type::None);
+        let callable_type = Type::Callable(Box::new(callable));
+        let mut ctx = TypeDisplayContext::new(&[&callable_type]);
+        ctx.set_lsp_display_mode(LspDisplayMode::Hover);
+        // The single-line rendering is 37 characters wide.
+        ctx.set_signature_line_width(40);
+        assert_eq!(
+            ctx.display(&callable_type).to_string(),
+            "(hello: None, *, world: None) -> None"
+        );
+        ctx.set_lsp_display_mode(LspDisplayMode::Standard);
+        ctx.set_signature_line_width(36);
+        assert_eq!(
+            ctx.display(&callable_type).to_string(),
+            r#"(
+    hello: None,
+    *,
+    world: None
+) -> None"#
+        );
+    }
+
     #[test]
     fn test_display_generic_callable() {
         let param1 = Param::Pos(Name::new_static("hello"), Type::None, Required::Required);
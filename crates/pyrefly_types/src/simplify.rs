@@ -84,20 +84,47 @@ fn simplify_intersections(xs: &mut [Type], heap: &TypeHeap) {
     }
 }
 
-/// After literal/enum squashing, a union with more than this many members is widened to
-/// `Any` to bound type complexity.
-static MAX_UNION_MEMBERS: usize = 4096;
+/// Caps on the size of an inferred union, which keep very large unions from slowing down
+/// checking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnionLimits {
+    /// Literals of one kind (e.g. `str` literals) past this count are widened to their class.
+    pub literals: usize,
+    /// Members of one enum past this count are widened to the enum class.
+    pub enum_members: usize,
+    /// After literal/enum squashing, a union with more than this many members is widened to
+    /// the closest common superclass of its members. That needs the class hierarchy, so the
+    /// solver does it (see `Solver::unions`); `unions` without a stdlib widens to `Any`.
+    pub members: usize,
+}
+
+impl Default for UnionLimits {
+    fn default() -> Self {
+        Self {
+            literals: 256,
+            enum_members: 4096,
+            members: 4096,
+        }
+    }
+}
 
 fn unions_internal(
     xs: Vec<Type>,
     stdlib: Option<&Stdlib>,
     enum_members: Option<&dyn Fn(&Class) -> Option<usize>>,
+    limits: UnionLimits,
     heap: &TypeHeap,
 ) -> Type {
     try_collapse(xs, heap).unwrap_or_else(|xs| {
         let mut res = flatten_and_dedup(xs, heap);
         if let Some(stdlib) = stdlib {
-            collapse_literals(&mut res, stdlib, enum_members.unwrap_or(&|_| None), heap);
+            collapse_literals(
+                &mut res,
+                stdlib,
+                enum_members.unwrap_or(&|_| None),
+                limits,
+                heap,
+            );
             promote_anonymous_typed_dicts(&mut res, stdlib, heap);
         }
         collapse_tuple_unions_with_empty(&mut res, heap);
@@ -105,8 +132,9 @@ fn unions_internal(
         collapse_wide_tuple_unions(&mut res, heap);
         collapse_quantifieds(&mut res, heap);
         // Second pass: squashing can still leave a pathologically large union (e.g. thousands
-        // of distinct class types). Widen anything still over the cap to `Any`.
-        if res.len() > MAX_UNION_MEMBERS {
+        // of distinct class types). Without a class hierarchy to join the members with, widen
+        // anything still over the cap to `Any`.
+        if stdlib.is_none() && res.len() > limits.members {
             return heap.mk_any_implicit();
         }
         // `res` is collapsible again if `flatten_and_dedup` drops `xs` to 0 or 1 elements
@@ -116,18 +144,20 @@ fn unions_internal(
 
 /// Union a set of types together, simplifying as much as you can.
 pub fn unions(xs: Vec<Type>, heap: &TypeHeap) -> Type {
-    unions_internal(xs, None, None, heap)
+    unions_internal(xs, None, None, UnionLimits::default(), heap)
 }
 
 /// Like `unions`, but also simplify away things regarding literals if you can,
-/// e.g. `Literal[True, False] ==> bool`.
+/// e.g. `Literal[True, False] ==> bool`. Literals and enum members past `limits` are widened
+/// to their class.
 pub fn unions_with_literals(
     xs: Vec<Type>,
     stdlib: &Stdlib,
     enum_members: &dyn Fn(&Class) -> Option<usize>,
+    limits: UnionLimits,
     heap: &TypeHeap,
 ) -> Type {
-    unions_internal(xs, Some(stdlib), Some(enum_members), heap)
+    unions_internal(xs, Some(stdlib), Some(enum_members), limits, heap)
 }
 
 pub fn intersect(ts: Vec<Type>, fallback: Type, heap: &TypeHeap) -> Type {
@@ -172,9 +202,6 @@ fn remove_maximum<T: Ord>(xs: &mut Vec<T>) {
     xs.pop();
 }
 
-static MAX_LITERAL_UNION_MEMBERS: usize = 256;
-static MAX_ENUM_UNION_MEMBERS: usize = 4096;
-
 /// Perform all literal transformations we can think of.
 ///
 /// 1. Literal[True, False] ==> bool
@@ -187,6 +214,7 @@ fn collapse_literals(
     types: &mut Vec<Type>,
     stdlib: &Stdlib,
     enum_members: &dyn Fn(&Class) -> Option<usize>,
+    limits: UnionLimits,
     heap: &TypeHeap,
 ) {
     // All literal types we see, plus `true` to indicate the promoted class was found
@@ -252,7 +280,8 @@ fn collapse_literals(
     }
 
     // True when a literal kind appears too many times to be worth tracking precisely.
-    let over_cap = |count: usize| count > MAX_LITERAL_UNION_MEMBERS;
+    let over_cap = |count: usize| count > limits.literals;
+    let over_enum_cap = |count: usize| count > limits.enum_members;
 
     let enums_to_delete: SmallSet<ClassType> = enums
         .into_iter()
@@ -265,7 +294,7 @@ fn collapse_literals(
             };
             // Promote to the enum class once every member is present, or once the union
             // exceeds the cap (some generated enums have thousands of members).
-            *n >= num_members || *n >= MAX_ENUM_UNION_MEMBERS
+            *n >= num_members || over_enum_cap(*n)
         })
         .map(|x| x.0)
         .collect();
@@ -240,6 +240,17 @@ impl Glob {
             .collect())
     }
 
+    /// The existing directories this glob matches, in sorted order.
+    pub fn matching_dirs(&self) -> Vec<PathBuf> {
+        match glob::glob(self.pattern.as_str()) {
+            Ok(paths) => paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_dir())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     /// Returns true if the given file matches this glob. The precompiled
     /// `<pattern>/**` variant lets a glob naming a directory match the files
     /// beneath it.
@@ -1089,6 +1100,28 @@ mod tests {
         f("/absolute/path/**/files", "/absolute/path");
     }
 
+    #[test]
+    fn test_matching_dirs() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::dir(
+                "packages",
+                vec![
+                    TestPath::dir("b", vec![TestPath::dir("src", vec![])]),
+                    TestPath::dir("a", vec![TestPath::dir("src", vec![])]),
+                    TestPath::dir("c", vec![TestPath::file("src")]),
+                ],
+            )],
+        );
+        let glob = Glob::new_with_root(root, "packages/*/src".to_owned()).unwrap();
+        assert_eq!(
+            glob.matching_dirs(),
+            vec![root.join("packages/a/src"), root.join("packages/b/src")]
+        );
+    }
+
     #[test]
     fn test_contains_glob_char() {
         assert!(!Glob::contains_glob_char(&OsString::from("")));
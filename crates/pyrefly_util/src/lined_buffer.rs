@@ -27,6 +27,40 @@ use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 use serde::Serialize;
 
+/// A notebook, with the code cell index of each of its cells computed once, so that mapping a
+/// position to its cell doesn't walk the cells every time.
+#[derive(Debug, Clone)]
+pub struct NotebookCells {
+    notebook: Arc<Notebook>,
+    /// For each cell, numbered as ruff does (markdown included), its index among the code cells.
+    code_cell_index: Box<[usize]>,
+}
+
+impl NotebookCells {
+    pub fn new(notebook: Arc<Notebook>) -> Self {
+        let mut code_cells = 0;
+        let code_cell_index = notebook
+            .cells()
+            .iter()
+            .map(|cell| {
+                let index = code_cells;
+                if cell.is_code_cell() {
+                    code_cells += 1;
+                }
+                index
+            })
+            .collect();
+        Self {
+            notebook,
+            code_cell_index,
+        }
+    }
+
+    pub fn notebook(&self) -> &Arc<Notebook> {
+        &self.notebook
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LinedBuffer {
     buffer: Arc<String>,
@@ -203,11 +237,9 @@ impl LinedBuffer {
     /// Translates a text range to a LSP range.
     /// For notebook, the input range is relative to the concatenated contents of the whole notebook
     /// and the output range is relative to a specific cell.
-    pub fn to_lsp_range(&self, x: TextRange, notebook: Option<&Notebook>) -> lsp_types::Range {
-        let start_cell = self.to_cell_for_lsp(x.start(), notebook);
-        let end_cell = self.to_cell_for_lsp(x.end(), notebook);
-        let start = self.to_lsp_position(x.start(), notebook);
-        let mut end = self.to_lsp_position(x.end(), notebook);
+    pub fn to_lsp_range(&self, x: TextRange, notebook: Option<&NotebookCells>) -> lsp_types::Range {
+        let (start, start_cell) = self.to_lsp_position_and_cell(x.start(), notebook);
+        let (mut end, end_cell) = self.to_lsp_position_and_cell(x.end(), notebook);
         if let Some(start_cell) = start_cell
             && let Some(end_cell) = end_cell
             && end_cell != start_cell
@@ -222,56 +254,69 @@ impl LinedBuffer {
         lsp_types::Range::new(start, end)
     }
 
+    /// Translates many text ranges to LSP ranges, as `to_lsp_range` does.
+    pub fn to_lsp_ranges(
+        &self,
+        xs: impl IntoIterator<Item = TextRange>,
+        notebook: Option<&NotebookCells>,
+    ) -> Vec<lsp_types::Range> {
+        xs.into_iter()
+            .map(|x| self.to_lsp_range(x, notebook))
+            .collect()
+    }
+
     /// Translates a text size to a LSP position.
     /// For notebook, the input position is relative to the concatenated contents of the whole notebook
     /// and the output position is relative to a specific cell.
-    pub fn to_lsp_position(&self, x: TextSize, notebook: Option<&Notebook>) -> lsp_types::Position {
-        let x = self.clamp_position(x);
-        let loc = self
-            .lines
-            .source_location(x, &self.buffer, PositionEncoding::Utf16);
-        if let Some(notebook) = notebook
-            && let Some((_, cell_line)) = self.get_cell_and_line_from_concatenated_line(
-                notebook,
-                LineNumber::from_one_indexed(loc.line),
-            )
-        {
-            lsp_types::Position {
-                line: cell_line.to_zero_indexed(),
-                character: loc.character_offset.to_zero_indexed() as u32,
-            }
-        } else {
-            lsp_types::Position {
-                line: loc.line.to_zero_indexed() as u32,
-                character: loc.character_offset.to_zero_indexed() as u32,
-            }
-        }
+    pub fn to_lsp_position(
+        &self,
+        x: TextSize,
+        notebook: Option<&NotebookCells>,
+    ) -> lsp_types::Position {
+        self.to_lsp_position_and_cell(x, notebook).0
     }
 
     /// If the module is a notebook, take an input position relative to the concatenated contents
     /// and return the index of the corresponding notebook cell.
-    pub fn to_cell_for_lsp(&self, x: TextSize, notebook: Option<&Notebook>) -> Option<usize> {
+    pub fn to_cell_for_lsp(&self, x: TextSize, notebook: Option<&NotebookCells>) -> Option<usize> {
+        self.to_lsp_position_and_cell(x, notebook).1
+    }
+
+    /// The LSP position of `x` together with its code cell index, looking up the line only once.
+    fn to_lsp_position_and_cell(
+        &self,
+        x: TextSize,
+        notebook: Option<&NotebookCells>,
+    ) -> (lsp_types::Position, Option<usize>) {
         let x = self.clamp_position(x);
         let loc = self
             .lines
             .source_location(x, &self.buffer, PositionEncoding::Utf16);
+        let character = loc.character_offset.to_zero_indexed() as u32;
         if let Some(notebook) = notebook
-            && let Some((cell, _)) = self.get_cell_and_line_from_concatenated_line(
-                notebook,
+            && let Some((cell, cell_line)) = self.get_cell_and_line_from_concatenated_line(
+                &notebook.notebook,
                 LineNumber::from_one_indexed(loc.line),
             )
         {
             // ruff's `cell()` is the raw all-cells index (markdown included), but callers
             // compare against the code-cell index from `get_code_cell_index`. Translate.
-            let abs = cell.to_zero_indexed();
-            Some(
-                notebook.cells()[..abs]
-                    .iter()
-                    .filter(|c| c.is_code_cell())
-                    .count(),
+            let code_cell = notebook.code_cell_index[cell.to_zero_indexed()];
+            (
+                lsp_types::Position {
+                    line: cell_line.to_zero_indexed(),
+                    character,
+                },
+                Some(code_cell),
             )
         } else {
-            None
+            (
+                lsp_types::Position {
+                    line: loc.line.to_zero_indexed() as u32,
+                    character,
+                },
+                None,
+            )
         }
     }
 
@@ -807,8 +852,8 @@ mod tests {
     /// all-cells index 2.
     #[test]
     fn test_to_cell_for_lsp_returns_code_cell_index() {
-        let notebook = code_markdown_code_notebook();
-        let source = notebook.source_code().to_owned();
+        let notebook = NotebookCells::new(Arc::new(code_markdown_code_notebook()));
+        let source = notebook.notebook().source_code().to_owned();
         assert_eq!(source, "x = 1\ny = 2\n");
         let lined_buffer = LinedBuffer::new(Arc::new(source));
 
@@ -823,5 +868,20 @@ mod tests {
             lined_buffer.to_cell_for_lsp(TextSize::new(6), Some(&notebook)),
             Some(1)
         );
+        // Each range is relative to its own cell.
+        let first_char = lsp_types::Range::new(
+            lsp_types::Position::new(0, 0),
+            lsp_types::Position::new(0, 1),
+        );
+        assert_eq!(
+            lined_buffer.to_lsp_ranges(
+                [
+                    TextRange::new(TextSize::new(0), TextSize::new(1)),
+                    TextRange::new(TextSize::new(6), TextSize::new(7)),
+                ],
+                Some(&notebook)
+            ),
+            vec![first_char, first_char]
+        );
     }
 }
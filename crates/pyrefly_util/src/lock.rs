@@ -56,6 +56,15 @@ impl<T> RwLock<T> {
         self.0.write().unwrap()
     }
 
+    /// Returns `None` if the lock is currently held for writing.
+    pub fn try_read(&self) -> Option<sync::RwLockReadGuard<'_, T>> {
+        match self.0.try_read() {
+            Ok(guard) => Some(guard),
+            Err(sync::TryLockError::WouldBlock) => None,
+            Err(sync::TryLockError::Poisoned(e)) => panic!("{e}"),
+        }
+    }
+
     pub fn into_inner(self) -> T {
         self.0.into_inner().unwrap()
     }
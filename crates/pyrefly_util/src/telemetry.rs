@@ -211,7 +211,7 @@ pub struct TelemetryTransactionStats {
     pub slow_stat_count: usize,
     /// Cumulative time of slow stat calls.
     pub slow_stat_time: Duration,
-    /// Total filesystem read calls (read_to_string in load).
+    /// Total filesystem read calls (file reads in load).
     pub total_read_count: usize,
     /// Read calls exceeding 1ms (likely EdenFS remote fetch).
     pub slow_read_count: usize,
@@ -11,11 +11,11 @@
 //! Each case builds a synthetic Python snippet that stresses one part of the
 //! checker (enum member resolution, exhaustiveness, protocol structural matching,
 //! narrowing, gradual-typing calls, type-variable joins, inferred typed dicts,
-//! overload resolution) and times a single in-memory check of it. `SHARED_STATE`
-//! pre-initializes the stdlib once, so only the snippet's check is measured, and
-//! each case asserts its expected error count up front so a scenario that stops
-//! exercising the intended path fails loudly instead of silently measuring
-//! nothing.
+//! overload resolution, error collection) and times a single in-memory check of
+//! it. `SHARED_STATE` pre-initializes the stdlib once, so only the snippet's
+//! check is measured, and each case asserts its expected error count up front so
+//! a scenario that stops exercising the intended path fails loudly instead of
+//! silently measuring nothing.
 //!
 //! Build mode matters: must be optimized. Buck requires `@fbcode//mode/opt`
 //! (or `opt-clang-thinlto` for final numbers); Cargo `cargo bench` builds the
@@ -272,6 +272,13 @@ fn overload_resolution(overloads: usize, calls: usize) -> String {
     src
 }
 
+/// `count` annotated assignments of a string to an `int`, each one a
+/// `bad-assignment` error. Exercises building and collecting a large batch of
+/// errors, so the snippet reports exactly `count` errors.
+fn many_errors(count: usize) -> String {
+    joined(count, "\n", |i| format!("v{i}: int = \"s{i}\""))
+}
+
 /// Type-check `source` once to assert it produces `expected_errors`, then
 /// register a criterion benchmark that repeats the check. The up-front assertion
 /// guards against a scenario silently drifting to a different error count (and
@@ -338,6 +345,10 @@ fn overloads(c: &mut Criterion) {
     );
 }
 
+fn error_collection(c: &mut Criterion) {
+    measure(c, "many_errors_2048", many_errors(2048), 2048);
+}
+
 criterion_group!(
     benches,
     smoke,
@@ -350,5 +361,6 @@ criterion_group!(
     typevar_mapping,
     anon_typed_dict,
     overloads,
+    error_collection,
 );
 criterion_main!(benches);
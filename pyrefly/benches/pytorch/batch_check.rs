@@ -0,0 +1,75 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Batch-check benchmark against PyTorch: end-to-end `pyrefly check` throughput.
+//! Unlike the LSP benchmarks, which measure interactive latency, this runs the
+//! same one-shot check the CLI does — file listing, parsing, binding, solving and
+//! error collection — over a PyTorch subpackage and its import closure, across
+//! all cores. Errors are computed but not printed, so terminal output stays out of
+//! the measurement.
+//!
+//! The shared PyTorch checkout harness lives in [`crate::common`].
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use clap::Parser;
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::criterion_group;
+use pyrefly::commands::check::FullCheckArgs;
+use pyrefly_util::thread_pool::ThreadCount;
+
+use crate::common::pytorch_root_or_skip;
+
+/// Subpackage the benchmark checks. Large enough to pull in much of `torch`
+/// through its imports, small enough to keep an iteration to a few seconds.
+const CHECKED_DIR: &str = "torch/distributed/pipelining";
+
+/// Run a full `pyrefly check` of [`CHECKED_DIR`] with a fresh state.
+fn batch_check(root: &Path) {
+    let dir = root.join(CHECKED_DIR);
+    let args = FullCheckArgs::parse_from([
+        OsStr::new("check"),
+        dir.as_os_str(),
+        OsStr::new("--output-format=omit-errors"),
+        OsStr::new("--summary=none"),
+    ]);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let (_, result) = runtime
+        .block_on(args.run(None, ThreadCount::AllThreads))
+        .unwrap();
+    // An empty check means CHECKED_DIR drifted (e.g. after a pin bump) and the
+    // benchmark would silently measure nothing.
+    assert!(
+        result.is_some_and(|r| r.checked_file_count > 0),
+        "no files were checked under {CHECKED_DIR}; it likely moved after a pin bump"
+    );
+}
+
+/// End-to-end batch check time. Every iteration builds a new `State`, so each
+/// sample is a cold check. Like the other PyTorch benches this is a heavy
+/// walltime bench, so it uses Criterion's floor of 10 samples.
+fn batch_check_subpackage(c: &mut Criterion) {
+    let Some(root) = pytorch_root_or_skip() else {
+        return;
+    };
+    let mut group = c.benchmark_group("pytorch");
+    group.sample_size(10);
+    group.bench_function("batch_check", |b| {
+        b.iter_batched(
+            || root.clone(),
+            |root| batch_check(&root),
+            BatchSize::PerIteration,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, batch_check_subpackage);
@@ -104,6 +104,7 @@ pub struct TypeCheckOptions<'a> {
     errors: &'a ErrorCollector,
     context: &'a dyn Fn() -> TypeCheckContext,
     call_context: Option<&'a CallContext>,
+    detail: Option<&'a dyn Fn() -> String>,
 }
 
 impl<'a> TypeCheckOptions<'a> {
@@ -112,6 +113,7 @@ impl<'a> TypeCheckOptions<'a> {
             errors,
             context,
             call_context: None,
+            detail: None,
         }
     }
 
@@ -119,6 +121,13 @@ impl<'a> TypeCheckOptions<'a> {
         self.call_context = Some(call_context);
         self
     }
+
+    /// Add a detail line to the error reported if the check fails. It is only computed if
+    /// the check fails.
+    pub fn with_detail(mut self, detail: &'a dyn Fn() -> String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
 }
 
 /// Compactly represents the identity of a binding, for the purposes of
@@ -1170,7 +1179,7 @@ enum BindingAction {
     Calculate,
     /// A recursive placeholder exists (in SCC-local `SccNodeState::HasPlaceholder`)
     /// and we should return it.
-    /// Action: return `Arc::new(K::promote_recursive(heap, r))`
+    /// Action: return `Arc::new(K::promote_iteration_placeholder(heap, r))`
     CycleBroken(Var),
     /// An answer is available from SccNodeState::Done in the top SCC.
     /// Type-erased; will be downcast to `Arc<K::Answer>` in `get_idx`.
@@ -2119,7 +2128,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
 
         let mut result = match self.stack().push(current.dupe()) {
             BindingAction::Calculate => self.calculate_and_record_answer(current, idx, calculation),
-            BindingAction::CycleBroken(r) => Arc::new(K::promote_recursive(self.heap, r)),
+            BindingAction::CycleBroken(r) => {
+                Arc::new(K::promote_iteration_placeholder(self.heap, r))
+            }
             BindingAction::SccLocalAnswer(type_erased) => {
                 // Downcast the type-erased answer back to Arc<K::Answer>.
                 // The answer was stored as Arc::new(answer.dupe()) where answer: Arc<K::Answer>,
@@ -2133,7 +2144,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
             BindingAction::NeedsColdPlaceholder => self
                 .attempt_to_unwind_cycle_from_here(&current, idx, calculation)
-                .unwrap_or_else(|r| Arc::new(K::promote_recursive(self.heap, r))),
+                .unwrap_or_else(|r| Arc::new(K::promote_iteration_placeholder(self.heap, r))),
         };
         if let Some(scc) = self.stack().pop_and_take_completed_scc() {
             self.iterative_resolve_scc(scc);
@@ -2824,6 +2835,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         } else {
             Vec::new()
         };
+        // A cycle through imports between modules is worth pointing out, since it can often be
+        // broken at the import rather than by annotating every member.
+        let cycle_modules: SmallSet<ModuleName> = if non_convergent_members.is_empty() {
+            SmallSet::new()
+        } else {
+            scc.node_state
+                .keys()
+                .map(|calc_id| calc_id.0.module().name())
+                .collect()
+        };
 
         let did_commit = self.commit_final_answers(scc);
         if did_commit {
@@ -2837,6 +2858,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         answer,
                         previous.as_ref(),
                         member_bindings,
+                        &cycle_modules,
                         self.base_errors
                     );
                 } else {
@@ -2849,6 +2871,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         answer,
                         previous.as_ref(),
                         member_bindings,
+                        &cycle_modules,
                         &cross_errors
                     );
                     self.base_errors.extend(cross_errors);
@@ -3111,8 +3134,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.solver().record_recursive(recursive, ty)
     }
 
-    /// Check if `got` matches `want`, returning `want` if the check fails.
-    /// Convenience wrapper around `check_type_with_options`.
+    /// Check if `got` matches `want`, returning `want` if the check fails, or `Any` if the
+    /// check hit the recursion limit.
     pub fn check_and_return_type(
         &self,
         got: Type,
@@ -3121,10 +3144,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         errors: &ErrorCollector,
         tcc: &dyn Fn() -> TypeCheckContext,
     ) -> Type {
-        if self.check_type_with_options(&got, want, loc, TypeCheckOptions::new(errors, tcc)) {
-            got
-        } else {
-            want.clone()
+        match self.check_type_result(&got, want, loc, TypeCheckOptions::new(errors, tcc)) {
+            Ok(()) => got,
+            Err(SubsetError::RecursionLimit) => self.heap.mk_any_error(),
+            Err(_) => want.clone(),
         }
     }
 
@@ -3140,7 +3163,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.check_type_with_options(got, want, loc, TypeCheckOptions::new(errors, tcc))
     }
 
-    /// Check if `got` matches `want`.
+    /// Check if `got` matches `want`. A check that hits the subset recursion limit is reported
+    /// as a `recursion-limit` error, then treated as passing, as if one side were `Any`.
     pub fn check_type_with_options(
         &self,
         got: &Type,
@@ -3148,6 +3172,20 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         loc: TextRange,
         options: TypeCheckOptions,
     ) -> bool {
+        matches!(
+            self.check_type_result(got, want, loc, options),
+            Ok(()) | Err(SubsetError::RecursionLimit)
+        )
+    }
+
+    /// Check if `got` matches `want`, reporting any failure.
+    fn check_type_result(
+        &self,
+        got: &Type,
+        want: &Type,
+        loc: TextRange,
+        options: TypeCheckOptions,
+    ) -> Result<(), SubsetError> {
         // Record expected type for LSP query
         self.record_expected_type_trace(loc, want);
 
@@ -3171,11 +3209,32 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         match subset_result {
             Ok(()) => {
                 self.check_string_as_iterable(got, want, loc, options.errors);
-                true
+                Ok(())
+            }
+            Err(SubsetError::RecursionLimit) => {
+                self.solver()
+                    .error_builder(
+                        got,
+                        want,
+                        options.errors,
+                        loc,
+                        options.context,
+                        SubsetError::RecursionLimit,
+                    )
+                    .emit();
+                Err(SubsetError::RecursionLimit)
             }
             Err(error) => {
-                self.report_type_error(got, want, options.errors, loc, options.context, error);
-                false
+                self.report_type_error(
+                    got,
+                    want,
+                    options.errors,
+                    loc,
+                    options.context,
+                    options.detail,
+                    error.clone(),
+                );
+                Err(error)
             }
         }
     }
@@ -3240,11 +3299,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         errors: &ErrorCollector,
         loc: TextRange,
         tcc: &dyn Fn() -> TypeCheckContext,
+        detail: Option<&dyn Fn() -> String>,
         error: SubsetError,
     ) {
         let mut builder = self
             .solver()
             .error_builder(got, want, errors, loc, tcc, error);
+        if let Some(detail) = detail {
+            builder = builder.with_detail(detail());
+        }
         if let Some(replacement) = self.suggest_enum_member_for_value(got, want) {
             builder = builder
                 .with_detail(format!("Did you mean `{replacement}`?"))
@@ -3494,6 +3557,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         current: &Arc<dyn Any + Send + Sync>,
         previous: Option<&Arc<dyn Any + Send + Sync>>,
         member_bindings: &Bindings,
+        cycle_modules: &SmallSet<ModuleName>,
         member_errors: &ErrorCollector,
     ) where
         AnswerTable: TableKeyed<K, Value = AnswerEntry<K>>,
@@ -3526,6 +3590,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 noun, typed_answer,
             ),
         );
+        if cycle_modules.len() > 1 {
+            builder = builder.with_detail(format!(
+                "The cycle passes through imports between modules {}. Annotating the names they import from each other, or moving imports only used in annotations under `if TYPE_CHECKING:`, can break it.",
+                cycle_modules
+                    .iter()
+                    .map(|m| format!("`{m}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
         // If PYREFLY_FIXPOINT_DETAILS=1 is set, we output much more detailed information useful
         // for explaining or debugging nonconvergence in terms of Pyrefly internals.
         if Self::fixpoint_details_enabled() {
@@ -21,8 +21,10 @@ use pyrefly_types::types::Forallable;
 use pyrefly_types::types::TArgs;
 use pyrefly_types::types::Var;
 use pyrefly_util::suggest::best_suggestion;
+use ruff_python_ast::Expr;
 use ruff_python_ast::helpers::is_dunder;
 use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use starlark_map::small_set::SmallSet;
 use vec1::Vec1;
@@ -592,6 +594,22 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         errors: &ErrorCollector,
         context: Option<&dyn Fn() -> ErrorContext>,
         todo_ctx: &str,
+    ) -> Type {
+        self.type_of_attr_get_on_expr(base, None, attr_name, range, errors, context, todo_ctx)
+    }
+
+    /// Like [`Self::type_of_attr_get`], where `base_expr` is the expression `base` came from,
+    /// if any. When the attribute is only missing on the `None` member of `base`, the error
+    /// suggests checking `base_expr` for `None` first.
+    pub fn type_of_attr_get_on_expr(
+        &self,
+        base: &Type,
+        base_expr: Option<&Expr>,
+        attr_name: &Name,
+        range: TextRange,
+        errors: &ErrorCollector,
+        context: Option<&dyn Fn() -> ErrorContext>,
+        todo_ctx: &str,
     ) -> Type {
         let attr_base = self.as_attribute_base(base.clone());
         let lookup_result = attr_base.clone().map_or_else(
@@ -605,12 +623,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         // Check if we have a partial union failure (attribute exists on some union members
         // but not others) before consuming the vectors. This helps us decide whether to suggest.
         let is_partial_union_failure = !found.is_empty() && !not_found.is_empty();
+        let none_class = self.stdlib.none_type().class_object();
+        let mut only_missing_on_none = is_partial_union_failure
+            && not_found
+                .iter()
+                .all(|e| matches!(e, NotFoundOn::ClassInstance(cls, _) if cls == none_class));
         for (attr, _) in found {
             match self.resolve_get_access(attr_name, attr, range, errors, context) {
                 Ok(ty) => types.push(ty),
                 Err(err) => {
                     error_messages.push(err.to_error_msg(attr_name));
                     success = false;
+                    only_missing_on_none = false;
                 }
             }
         }
@@ -643,18 +667,63 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             {
                 msg.push(format!("Did you mean `{suggestion}`?"));
             }
+            let mut none_sources = Vec::new();
+            if only_missing_on_none
+                && self.solver().optional_attribute_hints
+                && let Some(base_expr) = base_expr
+            {
+                msg.push(self.none_check_hint(base_expr, attr_name));
+                none_sources = self.none_sources(base_expr);
+            }
             let (header, details) = msg.split_off_first();
-            errors
+            let mut builder = errors
                 .error_builder(range, ErrorKind::MissingAttribute, header)
                 .with_details(details)
-                .with_context(context)
-                .emit();
+                .with_context(context);
+            for source in none_sources {
+                let label = format!("`{}` may be `None` here", self.module().code_at(source));
+                builder = builder.with_annotation(source, label);
+            }
+            builder.emit();
             self.heap.mk_any_error()
         } else {
             self.heap.mk_any_error() // we've encountered internal errors (already logged above)
         }
     }
 
+    /// A hint for an attribute access that fails only because `base_expr` may be `None`.
+    fn none_check_hint(&self, base_expr: &Expr, attr_name: &Name) -> String {
+        let text = self.module().code_at(base_expr.range());
+        // Only suggest a check on expressions that narrowing applies to.
+        if matches!(base_expr, Expr::Name(_) | Expr::Attribute(_)) && !text.contains('\n') {
+            format!(
+                "`{text}` may be `None`. Check `if {text} is not None:` before accessing `{attr_name}`"
+            )
+        } else {
+            format!(
+                "The value may be `None`. Check that it is not `None` before accessing `{attr_name}`"
+            )
+        }
+    }
+
+    /// If `base_expr` is a name, the places it last got a value that may be `None`, which are
+    /// the nearest places it could have been checked for `None`. For a name merged from several
+    /// branches, these are the branches that may be `None`.
+    fn none_sources(&self, base_expr: &Expr) -> Vec<TextRange> {
+        let Some((_, values)) = self.name_reaching_values(base_expr) else {
+            return Vec::new();
+        };
+        values
+            .into_iter()
+            .filter(|idx| match self.get_idx(*idx).ty() {
+                Type::None => true,
+                Type::Union(u) => u.members.iter().any(Type::is_none),
+                _ => false,
+            })
+            .map(|idx| self.bindings().idx_to_key(idx).range())
+            .collect()
+    }
+
     fn add_class_fields(&self, class: &Class, candidates: &mut SmallSet<Name>) {
         let mut add_fields_for = |cls: &Class| {
             if let Some(class_fields) = self.get_class_fields(cls) {
@@ -708,6 +777,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     self.collect_attribute_candidates_from_base(b, candidates);
                 }
             }
+            AttributeBase1::Module(module) => {
+                // Only the public names, which are tracked as a dependency, unlike the full
+                // export list.
+                if let Some(wildcard) = self
+                    .exports
+                    .get_wildcard(ModuleName::from_parts(module.parts()))
+                {
+                    for name in wildcard.iter() {
+                        candidates.insert(name.clone());
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -984,7 +1065,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
-    fn check_attr_set_and_infer_narrow(
+    pub fn check_attr_set_and_infer_narrow(
         &self,
         base: &Type,
         attr_name: &Name,
@@ -37,6 +37,7 @@ use vec1::Vec1;
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::attr::NoAccessReason;
+use crate::alt::callable::ArgMap;
 use crate::alt::callable::CallArg;
 use crate::alt::callable::CallKeyword;
 use crate::alt::callable::CallWithTypes;
@@ -47,10 +48,12 @@ use crate::alt::expr::TypeOrExpr;
 use crate::alt::nn_module_specials::is_nn_sequential;
 use crate::alt::polars_specials::is_pandas_dataframe;
 use crate::alt::polars_specials::is_polars_dataframe;
+use crate::alt::stdlib_specials::StdlibSpecial;
 use crate::alt::unwrap::HintRef;
 use crate::alt::unwrap::MAX_CALL_HINT_WIDTH;
 use crate::binding::binding::Key;
 use crate::config::error_kind::ErrorKind;
+use crate::config::literal_string_sink::SinkValue;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::context::TypeCheckContext;
@@ -61,6 +64,7 @@ use crate::types::callable::Callable;
 use crate::types::callable::FuncMetadata;
 use crate::types::callable::Function;
 use crate::types::callable::FunctionKind;
+use crate::types::callable::Param;
 use crate::types::callable::ParamList;
 use crate::types::callable::Params;
 use crate::types::class::ClassType;
@@ -957,6 +961,25 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         // Priority: metaclass __call__ > overridden __new__ > __init__.
         let mut recorded_trace = false;
         let errors = self.error_collector();
+        // Looking up an enum member by value, e.g. `Color(1)`, needs the argument's type, so
+        // infer it once here and pass the inferred type on to `EnumMeta.__call__`.
+        let enum_value = if class_metadata.is_enum()
+            && constructor_kind != ConstructorKind::TypeOfSelf
+            && keywords.is_empty()
+            && let [CallArg::Arg(value)] = args
+        {
+            Some((value.infer(self, &errors), value.range()))
+        } else {
+            None
+        };
+        let enum_value_arg;
+        let args = match &enum_value {
+            Some((ty, range)) => {
+                enum_value_arg = [CallArg::ty(ty, *range)];
+                &enum_value_arg[..]
+            }
+            None => args,
+        };
         if let Some(ret) = self.call_metaclass(
             &cls,
             arguments_range,
@@ -985,6 +1008,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             if class_metadata.is_enum() {
                 let ty = if constructor_kind == ConstructorKind::TypeOfSelf {
                     self.heap.mk_self_type(cls)
+                } else if let Some((value, _)) = &enum_value
+                    && let Some(member) = self.get_enum_member_for_value(cls.class_object(), value)
+                {
+                    // Looking up a member by a literal value, e.g. `Color(1)`.
+                    member.to_implicit_type()
                 } else {
                     ret
                 };
@@ -1708,6 +1736,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         hint: Option<HintRef>,
         ctor_targs: Option<&mut TArgs>,
     ) -> Type {
+        let sink_target =
+            self.literal_string_sink_target(callable_name, &callable, self_obj.is_some());
         // First try the call without the hint to see if it succeeds.
         let mut ctor_targs_no_hint = ctor_targs.as_ref().map(|x| (**x).clone());
         let arg_errors_no_hint = self.error_collector();
@@ -1780,13 +1810,109 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         {
             *targs = chosen_targs;
         }
-        let (ty, specialization_errors, _expected_types) = chosen_res;
+        let (ty, specialization_errors, argmap) = chosen_res;
         if let Ok(errors) = Vec1::try_from_vec(specialization_errors) {
             self.add_specialization_errors(errors, arguments_range, call_errors, context);
         }
+        if let Some((qualified_name, param)) = sink_target {
+            self.check_literal_string_sink(&qualified_name, &param, &argmap, call_errors);
+        }
         ty
     }
 
+    /// If the called function is one of the configured `literal-string-sinks`, its qualified
+    /// name and the name of its first parameter (after `self` or `cls` for a bound method).
+    pub fn literal_string_sink_target(
+        &self,
+        callable_name: Option<&FunctionKind>,
+        callable: &Callable,
+        is_bound: bool,
+    ) -> Option<(String, Name)> {
+        let sinks = &self.solver().literal_string_sinks;
+        if sinks.is_empty() {
+            return None;
+        }
+        let kind = callable_name?;
+        let qualified_name = match kind.class() {
+            Some(cls) => format!(
+                "{}.{}.{}",
+                kind.module_name(),
+                cls.name(),
+                kind.function_name()
+            ),
+            None => format!("{}.{}", kind.module_name(), kind.function_name()),
+        };
+        sinks.for_function(&qualified_name).next()?;
+        let Params::List(params) = &callable.params else {
+            return None;
+        };
+        let param = params
+            .items()
+            .iter()
+            .filter(|p| matches!(p, Param::PosOnly(..) | Param::Pos(..)))
+            .nth(if is_bound { 1 } else { 0 })?
+            .name()?
+            .clone();
+        Some((qualified_name, param))
+    }
+
+    /// Error when a call to one of the configured `literal-string-sinks` passes a string that
+    /// isn't a `LiteralString` to the function's first parameter, positionally or by keyword.
+    /// A sink written as `module.func(keyword=value)` only applies to calls passing a literal
+    /// `value` for `keyword`.
+    pub fn check_literal_string_sink(
+        &self,
+        qualified_name: &str,
+        param: &Name,
+        argmap: &ArgMap,
+        errors: &ErrorCollector,
+    ) {
+        let arg_for = |name: &str| {
+            argmap
+                .range_to_param
+                .iter()
+                .find(|(_, p)| p.name.as_ref().is_some_and(|n| n.as_str() == name))
+                .and_then(|(range, p)| Some((*range, p.arg_ty.as_ref()?)))
+        };
+        let applies = self
+            .solver()
+            .literal_string_sinks
+            .for_function(qualified_name)
+            .any(|sink| match sink.condition() {
+                None => true,
+                Some((keyword, value)) => {
+                    arg_for(keyword).is_some_and(|(_, ty)| matches_sink_value(ty, value))
+                }
+            });
+        if !applies {
+            return;
+        }
+        let Some((range, arg_ty)) = arg_for(param.as_str()) else {
+            return;
+        };
+        if arg_ty.is_any()
+            || !self.is_subset_eq(arg_ty, &self.stdlib.str().clone().to_type())
+            || self.is_subset_eq(arg_ty, &Type::LiteralString(LitStyle::Implicit))
+        {
+            return;
+        }
+        errors
+            .error_builder(
+                range,
+                ErrorKind::NonLiteralStringSink,
+                format!(
+                    "`{qualified_name}` expects a `LiteralString`, but got `{}`",
+                    self.for_display(arg_ty.clone())
+                ),
+            )
+            .with_detail(
+                "Strings built from non-literal values can carry injected code. \
+                 Pass parameters separately, e.g. as query parameters or an argument list."
+                    .to_owned(),
+            )
+            .emit();
+    }
+
     pub fn call_infer(
         &self,
         call_target: CallTarget,
@@ -1966,19 +2092,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
 
     pub fn constructor_to_callable(&self, cls: &ClassType) -> Type {
         let class_type = self.heap.mk_class_type(cls.clone());
-        if let Some(metaclass_call_attr_ty) = self.get_metaclass_dunder_call(cls) {
-            // Use the metaclass __call__ directly (ignoring __new__ and __init__) when either:
-            // 1. Its return type is not a subclass of the current class, or
-            // 2. The class is an enum (enum construction is handled by EnumMeta.__call__).
-            if metaclass_call_attr_ty
-                .callable_return_type(self.heap)
-                .is_some_and(|ret| !self.is_compatible_constructor_return(&ret, cls.class_object()))
-            {
-                return metaclass_call_attr_ty;
-            }
-            if self.get_metadata_for_class(cls.class_object()).is_enum() {
-                return metaclass_call_attr_ty;
-            }
+        // Use the metaclass __call__ directly (ignoring __new__ and __init__) when either:
+        // 1. Its return type is not a subclass of the current class, or
+        // 2. The class is an enum (enum construction is handled by EnumMeta.__call__).
+        if let Some(metaclass_call_attr_ty) = self.get_overriding_metaclass_dunder_call(cls) {
+            return metaclass_call_attr_ty;
+        }
+        if self.get_metadata_for_class(cls.class_object()).is_enum()
+            && let Some(metaclass_call_attr_ty) = self.get_metaclass_dunder_call(cls)
+        {
+            return metaclass_call_attr_ty;
         }
         // Default constructor that takes no args and returns Self.
         let heap = self.heap;
@@ -2271,6 +2394,36 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         errors,
                     )
                 }
+                // Stdlib functions whose result depends on literal arguments (see `alt::stdlib_specials`).
+                Some(CalleeKind::Function(kind))
+                    if let Some(special) = StdlibSpecial::for_function(&kind) =>
+                {
+                    self.call_stdlib_special(
+                        special,
+                        ty.clone(),
+                        &args,
+                        &kws,
+                        x.func.range(),
+                        x.arguments.range(),
+                        hint,
+                        errors,
+                    )
+                }
+                Some(CalleeKind::Function(FunctionKind::SetAttr))
+                    if x.arguments.args.len() == 3
+                        && x.arguments.keywords.is_empty()
+                        && !x.arguments.args.iter().any(|arg| matches!(arg, Expr::Starred(_))) =>
+                {
+                    self.call_builtin_setattr(
+                        &args,
+                        ty.clone(),
+                        &kws,
+                        x.func.range(),
+                        x.arguments.range(),
+                        hint,
+                        errors,
+                    )
+                }
                 // `f.register(C)(impl)`: applying the tagged factory decorator by call.
                 _ if let Type::KwCall(kw) = ty
                     && matches!(&kw.func_metadata.kind, FunctionKind::SingleDispatchRegister(_))
@@ -2472,3 +2625,17 @@ fn is_special_name(x: &Expr, name: &str) -> bool {
         _ => false,
     }
 }
+
+/// Whether an argument of type `ty` is the literal `value` of a sink's keyword condition.
+fn matches_sink_value(ty: &Type, value: &SinkValue) -> bool {
+    match (ty, value) {
+        (Type::None, SinkValue::None) => true,
+        (Type::Literal(lit), _) => match (&lit.value, value) {
+            (Lit::Bool(b), SinkValue::Bool(v)) => b == v,
+            (Lit::Int(i), SinkValue::Int(v)) => i.as_i64() == Some(*v),
+            (Lit::Str(s), SinkValue::Str(v)) => s.as_str() == v.as_str(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -6,6 +6,7 @@
  */
 
 use std::collections::HashMap;
+use std::iter;
 use std::mem;
 
 use itertools::Itertools;
@@ -30,6 +31,7 @@ use ruff_python_ast::Keyword;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
+use starlark_map::Hashed;
 use starlark_map::ordered_map::OrderedMap;
 use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
@@ -42,6 +44,8 @@ use crate::alt::expr::TypeOrExpr;
 use crate::alt::solve::Iterable;
 use crate::alt::unwrap::HintRef;
 use crate::alt::unwrap::MAX_CALL_HINT_WIDTH;
+use crate::binding::binding::KeyUndecoratedFunction;
+use crate::binding::binding::KeyUndecoratedFunctionRange;
 use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
@@ -79,10 +83,12 @@ impl CallWithTypes {
         errors: &ErrorCollector,
     ) -> TypeOrExpr<'a> {
         match x {
-            TypeOrExpr::Expr(e @ (Expr::Dict(_) | Expr::List(_) | Expr::Set(_))) => {
-                // Hack: don't flatten mutable builtin containers into types before calling a
-                // function, as we know these containers often need to be contextually typed using
-                // the function's parameter types.
+            TypeOrExpr::Expr(
+                e @ (Expr::Dict(_) | Expr::List(_) | Expr::Set(_) | Expr::Lambda(_)),
+            ) => {
+                // Hack: don't flatten mutable builtin containers or lambdas into types before
+                // calling a function, as we know these often need to be contextually typed using
+                // the function's parameter types (e.g. the `key` of `sorted`).
                 TypeOrExpr::Expr(e)
             }
             TypeOrExpr::Expr(e) => {
@@ -302,7 +308,24 @@ impl<'a> CallArg<'a> {
                         | Iterable::OfTypeVarTuple(_) => {}
                     }
                 }
-                if !fixed_lens.is_empty()
+                if let [
+                    Iterable::Unpacked {
+                        prefix,
+                        middle,
+                        suffix,
+                    },
+                ] = iterables.as_slice()
+                    && !prefix.is_empty()
+                {
+                    // The prefix of an unpacked tuple has a known length, so it can be matched
+                    // positionally. The middle may be empty, so the suffix cannot.
+                    let rest = solver.unions(
+                        iter::once(middle.clone())
+                            .chain(suffix.iter().cloned())
+                            .collect(),
+                    );
+                    CallArgPreEval::PrefixedStar(prefix.clone(), 0, rest)
+                } else if !fixed_lens.is_empty()
                     && fixed_lens.len() == iterables.len()
                     && fixed_lens.iter().all(|len| *len == fixed_lens[0])
                 {
@@ -326,13 +349,15 @@ impl<'a> CallArg<'a> {
 }
 
 // Pre-evaluated args are iterable. Type/Expr/Star variants iterate once (tracked via bool field),
-// Fixed variant iterates over the vec (tracked via usize field).
+// Fixed variant iterates over the vec (tracked via usize field). PrefixedStar iterates over its
+// known prefix like Fixed, then turns into a Star of the remaining element type.
 #[derive(Clone, Debug)]
 enum CallArgPreEval<'a> {
     Type(&'a Type, bool),
     Expr(&'a Expr, bool),
     Star(Type, bool),
     Fixed(Vec<Type>, usize),
+    PrefixedStar(Vec<Type>, usize, Type),
 }
 
 impl CallArgPreEval<'_> {
@@ -340,6 +365,17 @@ impl CallArgPreEval<'_> {
         match self {
             Self::Type(_, done) | Self::Expr(_, done) | Self::Star(_, done) => !*done,
             Self::Fixed(tys, i) => *i < tys.len(),
+            Self::PrefixedStar(..) => true,
+        }
+    }
+
+    /// Move past the current element of a PrefixedStar, becoming a Star once the prefix is used up.
+    fn advance_prefix(&mut self) {
+        if let Self::PrefixedStar(tys, i, rest) = self {
+            *i += 1;
+            if *i == tys.len() {
+                *self = Self::Star(mem::replace(rest, Type::never()), false);
+            }
         }
     }
 
@@ -356,7 +392,7 @@ impl CallArgPreEval<'_> {
             Self::Type(ty, _) => (*ty).clone(),
             Self::Expr(expr, _) => solver.expr_infer(expr, arg_errors),
             Self::Star(ty, _) => ty.clone(),
-            Self::Fixed(tys, idx) => tys[*idx].clone(),
+            Self::Fixed(tys, idx) | Self::PrefixedStar(tys, idx, _) => tys[*idx].clone(),
         }
     }
 
@@ -381,6 +417,10 @@ impl CallArgPreEval<'_> {
                 TypeCheckKind::CallArgument(param_name.cloned(), callable_name.cloned())
             })
             .with_context(context.map(|ctx| ctx()))
+            .with_annotation(
+                solver.param_decl_range(callable_name, param_name),
+                "parameter declared here".to_owned(),
+            )
         };
         match self {
             Self::Type(ty, done) => {
@@ -460,6 +500,17 @@ impl CallArgPreEval<'_> {
                 *i += 1;
                 Some(arg_ty)
             }
+            Self::PrefixedStar(tys, i, _) => {
+                let arg_ty = tys[*i].clone();
+                solver.check_type_with_options(
+                    &arg_ty,
+                    hint,
+                    range,
+                    TypeCheckOptions::new(call_errors, tcc).with_call_context(call_context),
+                );
+                self.advance_prefix();
+                Some(arg_ty)
+            }
         }
     }
 
@@ -473,6 +524,7 @@ impl CallArgPreEval<'_> {
             Self::Fixed(_, i) => {
                 *i += 1;
             }
+            Self::PrefixedStar(..) => self.advance_prefix(),
         }
     }
 
@@ -485,6 +537,9 @@ impl CallArgPreEval<'_> {
             Self::Fixed(tys, i) => {
                 *i = tys.len();
             }
+            Self::PrefixedStar(_, _, rest) => {
+                *self = Self::Star(mem::replace(rest, Type::never()), true);
+            }
         }
     }
 
@@ -507,11 +562,17 @@ impl CallArgPreEval<'_> {
 pub struct MatchedParam {
     pub ty: Type,
     pub name: Option<Name>,
+    /// The type of the argument, once it has been checked against the parameter.
+    pub arg_ty: Option<Type>,
 }
 
 impl MatchedParam {
     fn new(ty: Type, name: Option<Name>) -> Self {
-        Self { ty, name }
+        Self {
+            ty,
+            name,
+            arg_ty: None,
+        }
     }
 }
 
@@ -534,6 +595,12 @@ impl ArgMap {
         self.range_to_param
             .insert(range, MatchedParam::new(ty, name))
     }
+
+    fn record_arg_ty(&mut self, range: TextRange, arg_ty: &Type) {
+        if let Some(param) = self.range_to_param.get_mut(&range) {
+            param.arg_ty = Some(arg_ty.clone());
+        }
+    }
 }
 
 /// Helps track matching of arguments against positional parameters in AnswersSolver::callable_infer_params.
@@ -592,6 +659,39 @@ enum NameOrigin<'a> {
 }
 
 impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Where parameter `param` of the called function is declared, so argument errors can
+    /// point at it. Only functions defined in this module are found, since other modules'
+    /// bindings aren't available here.
+    fn param_decl_range(
+        &self,
+        callable_name: Option<&FunctionKind>,
+        param: Option<&Name>,
+    ) -> Option<TextRange> {
+        let (Some(FunctionKind::Def(func_id)), Some(param)) = (callable_name, param) else {
+            return None;
+        };
+        if func_id.module.path() != self.module().path() {
+            return None;
+        }
+        let range_idx =
+            self.bindings()
+                .key_to_idx_hashed_opt(Hashed::new(&KeyUndecoratedFunctionRange(
+                    func_id.def_index?,
+                )))?;
+        let function = self.get_idx(range_idx).0;
+        let idx = self
+            .bindings()
+            .key_to_idx_hashed_opt(Hashed::new(&KeyUndecoratedFunction(function)))?;
+        self.bindings()
+            .get(idx)
+            .def
+            .parameters
+            .iter()
+            .map(|p| p.name())
+            .find(|name| name.id == *param)
+            .map(|name| name.range)
+    }
+
     fn is_param_spec_args(&self, x: &CallArg, q: &Quantified, errors: &ErrorCollector) -> bool {
         match x {
             CallArg::Star(x, _) => {
@@ -847,6 +947,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                             context,
                             call_context,
                         );
+                        if let Some(ty) = &arg_ty {
+                            argmap.record_arg_ty(arg.range(), ty);
+                        }
                         if let Some(name) = name
                             && let Some(ty) = unhinted_arg_ty.or(arg_ty)
                         {
@@ -952,7 +1055,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                             suffix.push(self.expr_infer(e, arg_errors))
                         }
                     }
-                    CallArgPreEval::Fixed(tys, idx) => {
+                    CallArgPreEval::Fixed(tys, idx) | CallArgPreEval::PrefixedStar(tys, idx, _) => {
                         if middle.is_empty() {
                             prefix.push(tys[idx].clone());
                         } else {
@@ -1278,6 +1381,14 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                             )
                         })
                         .with_context(context.map(|ctx| ctx()))
+                        .with_annotation(
+                            if has_matching_param {
+                                self.param_decl_range(callable_name, Some(&id.id))
+                            } else {
+                                None
+                            },
+                            "parameter declared here".to_owned(),
+                        )
                     };
                     let arg_ty = match kw.value {
                         TypeOrExpr::Expr(x) => self
@@ -1310,6 +1421,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                             (*x).clone()
                         }
                     };
+                    argmap.record_arg_ty(kw.range, &arg_ty);
                     record(bound_args, &id.id, unhinted_arg_ty.unwrap_or(arg_ty));
                 }
             }
@@ -16,7 +16,6 @@ use pyrefly_derive::VisitMut;
 use pyrefly_graph::index::Idx;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::dunder;
-use pyrefly_python::module_name::ModuleName;
 use pyrefly_types::callable::Callable;
 use pyrefly_types::callable::FuncFlags;
 use pyrefly_types::callable::FunctionKind;
@@ -69,6 +68,7 @@ use crate::binding::binding::KeyClassField;
 use crate::binding::binding::KeyClassSynthesizedFields;
 use crate::binding::binding::MethodSelfKind;
 use crate::binding::binding::MethodThatSetsAttr;
+use crate::config::base::ConstructorOverrides;
 use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
@@ -78,6 +78,7 @@ use crate::error::signature_diff::render_signature_diff;
 use crate::solver::solver::SubsetError;
 use crate::types::annotation::Annotation;
 use crate::types::annotation::Qualifier;
+use crate::types::callable::DefaultValue;
 use crate::types::callable::FuncMetadata;
 use crate::types::callable::Function;
 use crate::types::callable::Param;
@@ -3673,6 +3674,64 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         true
     }
 
+    /// Find a difference between an override and the parent method it is compatible with
+    /// that can still break callers: a parameter the parent accepts by keyword that is no
+    /// longer a named parameter (e.g. because `**kwargs` absorbs it), or a parameter whose
+    /// default value changed. Reported as `strict-override`.
+    fn strict_override_violation(got: &ClassAttribute, want: &ClassAttribute) -> Option<String> {
+        let params = |attr: &ClassAttribute| match attr {
+            ClassAttribute::ReadOnly(ty, _) | ClassAttribute::ReadWrite(ty) => {
+                match ty.callable_signatures().as_slice() {
+                    [sig] => match &sig.params {
+                        Params::List(params) => Some(params.items().to_vec()),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let (got_params, want_params) = (params(got)?, params(want)?);
+        let display_default = |default: &DefaultValue| match &default.display {
+            Some(display) => display.clone(),
+            None => default.ty.to_string(),
+        };
+        for want_param in &want_params {
+            let (Param::Pos(name, _, want_required) | Param::KwOnly(name, _, want_required)) =
+                want_param
+            else {
+                continue;
+            };
+            let got_required = got_params.iter().find_map(|p| match p {
+                Param::Pos(got_name, _, required) | Param::KwOnly(got_name, _, required)
+                    if got_name == name =>
+                {
+                    Some(required)
+                }
+                _ => None,
+            });
+            match (want_required, got_required) {
+                (_, None) => {
+                    return Some(format!(
+                        "Parameter `{name}` can no longer be passed as a named keyword argument"
+                    ));
+                }
+                (
+                    Required::Optional(Some(want_default)),
+                    Some(Required::Optional(Some(got_default))),
+                ) if want_default != got_default => {
+                    return Some(format!(
+                        "Default of parameter `{name}` changed from `{}` to `{}`",
+                        display_default(want_default),
+                        display_default(got_default),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
     fn should_check_field_for_override_consistency(
         &self,
         field_name: &Name,
@@ -3680,12 +3739,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         is_explicit_override: bool,
     ) -> bool {
         // Object construction (`__new__`, `__init__`, `__init_subclass__`) should not participate
-        // in override checks unless the user explicitly opts in with `@override`.
-        if !is_explicit_override
-            && (field_name == &dunder::NEW
-                || field_name == &dunder::INIT
-                || field_name == &dunder::INIT_SUBCLASS)
-        {
+        // in override checks unless the user explicitly opts in with `@override`. The
+        // `constructor-overrides` option can turn checks of `__new__` and `__init__` off entirely,
+        // or on even without `@override`.
+        if field_name == &dunder::NEW || field_name == &dunder::INIT {
+            match self.solver().constructor_overrides {
+                ConstructorOverrides::Ignore => return false,
+                ConstructorOverrides::Explicit if !is_explicit_override => return false,
+                ConstructorOverrides::Explicit | ConstructorOverrides::Check => {}
+            }
+        }
+        if !is_explicit_override && field_name == &dunder::INIT_SUBCLASS {
             return false;
         }
 
@@ -3866,19 +3930,40 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             parent_attr_found = true;
             if want_field.defining_class.is_builtin("object") {
                 parent_attr_is_from_object = true;
+                // With `constructor-overrides = "check"`, every class would otherwise be
+                // checked against `object.__init__`.
+                if !is_explicit_override
+                    && (field_name == &dunder::NEW || field_name == &dunder::INIT)
+                {
+                    continue;
+                }
             }
             let want_class_field = Arc::unwrap_or_clone(want_field.value);
+            let want_decl_range = self
+                .get_class_fields(&want_field.defining_class)
+                .and_then(|fields| fields.field_decl_range(field_name));
             if want_class_field.is_final() {
-                self.error(
-                    errors,
+                let mut builder = errors.error_builder(
                     range,
-                    ErrorKind::BadOverride,
+                    ErrorKind::OverridesFinal,
                     format!(
                         "`{}` is declared as final in parent class `{}`",
                         field_name,
                         parent.name()
                     ),
                 );
+                // Point at the final declaration too, which may be in another module.
+                if let Some(final_range) = want_decl_range {
+                    builder = builder.with_annotation_in(
+                        want_field.defining_class.module().dupe(),
+                        final_range,
+                        format!(
+                            "`{}.{field_name}` is final",
+                            want_field.defining_class.name()
+                        ),
+                    );
+                }
+                builder.emit();
                 continue;
             }
             if want_class_field.has_explicit_annotation() && class_field.has_explicit_annotation() {
@@ -4131,7 +4216,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         diff_lines,
                     })
                 }
-                Ok(()) => None,
+                Ok(()) => Self::strict_override_violation(
+                    got_attribute.as_ref().unwrap(),
+                    &want_attribute,
+                )
+                .map(|message| OverrideError {
+                    kind: ErrorKind::StrictOverride,
+                    message,
+                    diff_lines: Vec::new(),
+                }),
             };
             if let Some(OverrideError {
                 kind,
@@ -4154,6 +4247,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 for line in extra_lines {
                     builder = builder.with_detail(line);
                 }
+                if let Some(want_range) = want_decl_range {
+                    let label = if want_class_field.ty().is_toplevel_callable() {
+                        "overridden method here"
+                    } else {
+                        "overridden attribute here"
+                    };
+                    builder = builder.with_annotation_in(
+                        want_field.defining_class.module().dupe(),
+                        want_range,
+                        label.to_owned(),
+                    );
+                }
                 builder.emit();
             }
         }
@@ -4530,6 +4635,38 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             .map(|field| self.as_instance_attribute(name, &field, &Instance::of_class(cls)))
     }
 
+    /// The type accepted when assigning to attribute `name` on an instance of `cls`, if the
+    /// attribute is a property with a setter or a data descriptor. This may differ from the
+    /// type read back from the attribute.
+    pub fn get_instance_attribute_setter_type(&self, cls: &ClassType, name: &Name) -> Option<Type> {
+        // The setter signatures still include `self`, followed by the instance for a
+        // descriptor's `__set__`, and then the value.
+        let (setter, skip) = match self.get_instance_attribute(cls, name)? {
+            ClassAttribute::Property(_, Some(setter), _) => (setter, 1),
+            ClassAttribute::Descriptor(x, _) => (
+                self.resolve_descriptor_setter(name, &x, &self.error_swallower())?,
+                2,
+            ),
+            _ => return None,
+        };
+        let value_types = setter
+            .callable_signatures()
+            .into_iter()
+            .map(|sig| {
+                let mut sig = sig.clone();
+                for _ in 0..skip {
+                    sig = sig.strip_first_param()?;
+                }
+                sig.get_first_param()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if value_types.is_empty() {
+            None
+        } else {
+            Some(self.unions(value_types))
+        }
+    }
+
     pub fn get_self_attribute(&self, cls: &ClassType, name: &Name) -> Option<ClassAttribute> {
         self.get_class_member(cls.class_object(), name)
             .map(|field| self.as_instance_attribute(name, &field, &Instance::of_self_type(cls)))
@@ -4592,11 +4729,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             &attr,
             &Instance::of_metaclass(cls.clone(), metaclass),
         );
-        if name == &dunder::CALL
-            && metaclass
-                .class_object()
-                .has_toplevel_qname(ModuleName::builtins().as_str(), "type")
-        {
+        if name == &dunder::CALL {
+            // Calling a class object runs the whole constructor, not just the metaclass
+            // `__call__`, unless that `__call__` returns something other than an instance.
             return Some(ClassAttribute::read_write(
                 self.constructor_to_callable(cls.class_type()),
             ));
@@ -4901,6 +5036,28 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Get the metaclass `__call__` method if it replaces the normal constructor, because it
+    /// returns something other than an instance of `cls`. Callable conversion and class object
+    /// `__call__` lookups both go through here, so they agree on when a factory metaclass wins.
+    pub fn get_overriding_metaclass_dunder_call(&self, cls: &ClassType) -> Option<Type> {
+        let dunder_call = self.get_metaclass_dunder_call(cls)?;
+        // Solve `T` in e.g. `def __call__(cls: type[T]) -> T` against `cls` before looking at the
+        // return type, the same way a call would.
+        let bound = match &dunder_call {
+            Type::BoundMethod(m) => self.bind_boundmethod(m, &mut |a, b| self.is_subset_eq(a, b)),
+            _ => None,
+        };
+        let ret = bound
+            .as_ref()
+            .unwrap_or(&dunder_call)
+            .callable_return_type(self.heap)?;
+        if self.is_compatible_constructor_return(&ret, cls.class_object()) {
+            None
+        } else {
+            Some(dunder_call)
+        }
+    }
+
     pub fn resolve_named_tuple_element(&self, cls: &ClassType, name: &Name) -> Option<Type> {
         let field = self.get_class_member(cls.class_object(), name)?;
         match field.instantiate_for(self.heap, &Instance::of_class(cls)).0 {
@@ -1673,12 +1673,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                             || (metadata.is_enum()
                                 && !self.get_enum_members(&class_object).is_empty()))
                     {
-                        self.error(
-                            errors,
-                            range,
-                            ErrorKind::InvalidInheritance,
-                            format!("Cannot extend final class `{}`", class_object.name()),
-                        );
+                        errors
+                            .error_builder(
+                                range,
+                                ErrorKind::OverridesFinal,
+                                format!("Cannot extend final class `{}`", class_object.name()),
+                            )
+                            .with_annotation_in(
+                                class_object.module().dupe(),
+                                class_object.range(),
+                                format!("`{}` is final", class_object.name()),
+                            )
+                            .emit();
                     }
                     if is_new_type {
                         // TODO: raise an error for generic classes and other forbidden types such as hashable
@@ -87,6 +87,37 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             .unwrap_or_default()
     }
 
+    /// The member of enum `cls` whose value is the literal `value`, so that `MyEnum(value)`
+    /// can be narrowed to that member. `None` unless exactly one member has that value, or if
+    /// the class defines its own `__new__` (which may compute `_value_` from the arguments).
+    pub fn get_enum_member_for_value(&self, cls: &Class, value: &Type) -> Option<Lit> {
+        match value {
+            Type::Literal(lit) if !matches!(lit.value, Lit::Enum(_)) => {}
+            _ => return None,
+        }
+        let is_django = self.get_metadata_for_class(cls).enum_metadata()?.is_django;
+        if self
+            .get_class_fields(cls)
+            .is_some_and(|fields| fields.field_decl_range(&dunder::NEW).is_some())
+        {
+            return None;
+        }
+        let mut found = None;
+        for lit in self.get_enum_members(cls) {
+            let Lit::Enum(lit_enum) = &lit else {
+                unreachable!("enum members must be represented as enum literals");
+            };
+            let value_ty = self.enum_literal_to_value_type((**lit_enum).clone(), is_django);
+            if self.is_equivalent(value, &value_ty) {
+                if found.is_some() {
+                    return None;
+                }
+                found = Some(lit);
+            }
+        }
+        found
+    }
+
     fn suggest_enum_member_for_class_value(&self, cls: &Class, got: &Type) -> Option<String> {
         let is_django = self.get_metadata_for_class(cls).enum_metadata()?.is_django;
         let mut suggestion = None;
@@ -87,7 +87,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     ) {
         let fields = self.typed_dict_fields(typed_dict);
         let extra_items = self.typed_dict_extra_items(typed_dict);
-        let mut has_expansion = false;
+        // Whether an unpacked item may provide keys we can't see, e.g. `**some_dict`.
+        let mut has_unknown_expansion = false;
         let mut keys: SmallSet<Name> = SmallSet::new();
         dict_items.iter().for_each(|x| match &x.key {
             Some(key) => {
@@ -173,13 +174,23 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
             None => {
                 // This is an unpacked item (`**some_dict`).
-                has_expansion = true;
                 let partial_td_ty = self.heap.mk_partial_typed_dict(typed_dict.clone());
                 let item_ty = self.expr_infer_with_hint(
                     &x.value,
                     Some(HintRef::soft(&partial_td_ty)),
                     item_errors,
                 );
+                // Unpacking a TypedDict definitely provides its required keys.
+                if let Type::TypedDict(item_td) = &item_ty {
+                    keys.extend(
+                        self.typed_dict_fields(item_td)
+                            .into_iter()
+                            .filter(|(_, field)| field.required)
+                            .map(|(key, _)| key),
+                    );
+                } else {
+                    has_unknown_expansion = true;
+                }
                 let subset_result = self.is_subset_eq_with_reason(&item_ty, &partial_td_ty);
                 if let Some(subset_error) = subset_result.err() {
                     let tcc: &dyn Fn() -> TypeCheckContext =
@@ -206,7 +217,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         });
         // You can update a TypedDict with a subset of its items. Otherwise, all required fields must be present.
-        if !has_expansion && !is_update {
+        if !has_unknown_expansion && !is_update {
             for (key, field) in &fields {
                 if field.required && !keys.contains(key) {
                     self.error(
@@ -280,6 +291,32 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// The TypedDict key named by a literal subscript type: either a string literal, or a
+    /// member of a `str`-valued enum (e.g. a `StrEnum`), which hashes and compares equal to
+    /// its value at runtime.
+    pub fn typed_dict_literal_key(&self, ty: &Type) -> Option<Name> {
+        let Type::Literal(lit) = ty else {
+            return None;
+        };
+        match &lit.value {
+            Lit::Str(name) => Some(Name::new(name)),
+            Lit::Enum(lit_enum)
+                if self.has_superclass(
+                    lit_enum.class.class_object(),
+                    self.stdlib.str().class_object(),
+                ) =>
+            {
+                match &lit_enum.ty {
+                    Type::Literal(value) if let Lit::Str(name) = &value.value => {
+                        Some(Name::new(name))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn typed_dict_field(&self, typed_dict: &TypedDict, name: &Name) -> Option<TypedDictField> {
         match typed_dict {
             TypedDict::TypedDict(inner) => {
@@ -826,6 +863,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 values,
                 self.stdlib,
                 &|cls| self.get_enum_member_count(cls),
+                self.solver().union_limits,
                 self.heap,
             )
         }
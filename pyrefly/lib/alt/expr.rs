@@ -13,6 +13,7 @@ use std::slice;
 use dupe::Dupe;
 use itertools::Either;
 use itertools::Itertools;
+use pyrefly_graph::index::Idx;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::dunder;
 use pyrefly_python::module_name::ModuleName;
@@ -104,6 +105,7 @@ use crate::types::callable::Params;
 use crate::types::callable::Required;
 use crate::types::class::Class;
 use crate::types::class::ClassType;
+use crate::types::display::TypeDisplayContext;
 use crate::types::facet::FacetKind;
 use crate::types::literal::Lit;
 use crate::types::param_spec::ParamSpec;
@@ -415,6 +417,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             } if !want.is_any() => {
                 let got =
                     self.expr_infer_impl(x, Some(HintRef::new(want, Some(errors))), options.errors);
+                let context = &|| {
+                    let mut tcc = context();
+                    tcc.annotations.extend(self.flow_merge_provenance(x));
+                    tcc
+                };
                 let check_options = match call_context {
                     Some(call_context) => {
                         TypeCheckOptions::new(errors, context).with_call_context(call_context)
@@ -432,6 +439,78 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// If `x` is a name, the binding it reads (after following forwards) and the values that
+    /// can reach it: for a merge of control-flow branches, the value of each branch that doesn't
+    /// end in `Never`/`NoReturn`; otherwise just the binding itself.
+    pub(crate) fn name_reaching_values(&self, x: &Expr) -> Option<(Idx<Key>, Vec<Idx<Key>>)> {
+        let Expr::Name(name) = x else {
+            return None;
+        };
+        if Ast::is_synthesized_empty_name(name) {
+            return None;
+        }
+        let bindings = self.bindings();
+        let mut idx = bindings.key_to_idx_hashed_opt(Hashed::new(&Key::BoundName(
+            ShortIdentifier::expr_name(name),
+        )))?;
+        while let Binding::Forward(k) | Binding::PromoteForward(k) | Binding::ForwardToFirstUse(k) =
+            bindings.get(idx)
+        {
+            idx = *k;
+        }
+        let values = match bindings.get(idx) {
+            Binding::Phi(_, branches) => branches
+                .iter()
+                .filter(|branch| {
+                    branch
+                        .termination_key
+                        .is_none_or(|k| !self.get_idx(k).ty().is_never())
+                })
+                .map(|branch| branch.value_key)
+                .collect(),
+            _ => vec![idx],
+        };
+        Some((idx, values))
+    }
+
+    /// If `x` is a name whose value was merged from several control-flow branches,
+    /// label each branch with the type it contributed, e.g. `int` from one arm of an
+    /// `if` and `None` from the other. A branch of an `if` statement is labelled at its
+    /// condition (or `else` keyword); any other branch where it got its value.
+    fn flow_merge_provenance(&self, x: &Expr) -> Vec<(TextRange, String)> {
+        let Some((phi, values)) = self.name_reaching_values(x) else {
+            return Vec::new();
+        };
+        if values.len() < 2 {
+            return Vec::new();
+        }
+        let bindings = self.bindings();
+        let phi_range = bindings.idx_to_key(phi).range();
+        let branch_types = values
+            .into_iter()
+            .map(|value| {
+                let value_range = bindings.idx_to_key(value).range();
+                (
+                    bindings
+                        .branch_condition(phi_range, value_range.start())
+                        .unwrap_or(value_range),
+                    self.for_display(self.get_idx(value).ty().clone()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let ctx =
+            TypeDisplayContext::new(&branch_types.iter().map(|(_, ty)| ty).collect::<Vec<_>>());
+        branch_types
+            .iter()
+            .map(|(range, ty)| {
+                (
+                    *range,
+                    format!("has type `{}` on this branch", ctx.display(ty)),
+                )
+            })
+            .collect()
+    }
+
     /// The core logic for inferring a type for an expression.
     /// Returns a TypeInfo that includes narrowing information.
     fn expr_infer_impl(
@@ -637,7 +716,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     },
                     |callable| callable,
                 );
-                if let Type::Callable(c) = &callable {
+                // Only flag lambdas inferred without context: a hint that doesn't describe the
+                // lambda (e.g. a non-matching overload parameter) gets its own assignability error.
+                if hint.is_none()
+                    && let Type::Callable(c) = &callable
+                {
                     let is_implicit_any = |t: &Type| matches!(t, Type::Any(AnyStyle::Implicit));
                     // Collect the AST parameters in the same order the callable's params
                     // were built above (non-variadic, then vararg, then kwarg), so we can
@@ -1822,7 +1905,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     /// Determine the boolean behavior of a type:
     /// - `Some(true)` or `Some(false)` when it is known to be statically truthy
     ///   or falsey (as determined by some baked in rules for literals
-    ///   and looking at the `__bool__` method, if it is present).
+    ///   and looking at the `__bool__` method, or `__len__` in its absence).
     /// - `None` if it's truthiness is not statically known.
     pub fn as_bool(&self, ty: &Type, range: TextRange, errors: &ErrorCollector) -> Option<bool> {
         if let Type::TypedDict(td) = ty {
@@ -1837,14 +1920,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         }
         ty.as_bool().or_else(|| {
-            // If the object defines `__bool__`, we can check if it returns a statically known value
-            if self
-                .type_of_magic_dunder_attr(ty, &dunder::BOOL, range, errors, None, "as_bool", true)?
-                .is_never()
-            {
+            // If the object defines `__bool__`, we can check if it returns a statically known value.
+            // Otherwise `bool()` falls back to `__len__`, where a known length decides it.
+            let lookup = |method: &Name| {
+                self.type_of_magic_dunder_attr(ty, method, range, errors, None, "as_bool", true)
+                    .map(|attr| (method.clone(), attr))
+            };
+            let (method, attr) = lookup(&dunder::BOOL).or_else(|| lookup(&dunder::LEN))?;
+            if attr.is_never() {
                 return None;
             };
-            self.call_method_or_error(ty, &dunder::BOOL, range, &[], &[], errors, None)
+            self.call_method_or_error(ty, &method, range, &[], &[], errors, None)
                 .as_bool()
         })
     }
@@ -1969,7 +2055,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         errors: &ErrorCollector,
     ) -> TypeInfo {
         self.record_external_attribute_definition_index(base.ty(), x.attr.id(), x.attr.range);
-        let attr_type = self.attr_infer(base, &x.attr.id, x.range, errors, None);
+        let attr_type = TypeInfo::at_facet(base, &FacetKind::Attribute(x.attr.id.clone()), || {
+            self.type_of_attr_get_on_expr(
+                base.ty(),
+                Some(&x.value),
+                &x.attr.id,
+                x.range,
+                errors,
+                None,
+                "Expr::attr_access_infer",
+            )
+        });
         if base.ty().is_literal_string() {
             match attr_type.ty() {
                 Type::BoundMethod(method) => attr_type
@@ -3011,8 +3107,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     // Don't warn on anonymous typed dicts
                     let warn_on_not_required_access = matches!(typed_dict, TypedDict::TypedDict(_));
                     self.distribute_over_union(&key_ty, |ty| match ty {
-                        Type::Literal(lit) if let Lit::Str(field_name) = &lit.value => {
-                            let key_name = Name::new(field_name);
+                        ty if let Some(key_name) = self.typed_dict_literal_key(ty) => {
                             if let Some(field) = self.typed_dict_field(&typed_dict, &key_name) {
                                 if warn_on_not_required_access && !field.required && !key_present {
                                     errors
@@ -3042,7 +3137,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                             slice.range(),
                                             typed_dict.key_error_kind(),
                                             format!(
-                                                "{} does not have key `{field_name}`",
+                                                "{} does not have key `{key_name}`",
                                                 typed_dict.label()
                                             ),
                                         );
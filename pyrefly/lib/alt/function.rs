@@ -1141,11 +1141,36 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         }))
                     ) if i.as_i64().is_some() && matches!(inner.as_ref(), Type::Quantified(_))
                 );
-                let check: Option<(&Type, &dyn Fn() -> TypeCheckContext)> = if skip_check {
-                    None
-                } else {
-                    Some((&param_ty, &make_context))
-                };
+                // While `implicit-optional` is enabled, a `None` default that the annotation
+                // rejects is reported once, as `implicit-optional`, rather than as a bad default.
+                let mut implicit_optional = false;
+                if !skip_check
+                    && self.solver().implicit_optional
+                    && let Some(Expr::NoneLiteral(none)) = default
+                    && !self.is_subset_eq(&Type::None, &param_ty)
+                {
+                    let mut has_quantified = false;
+                    param_ty.for_each_quantified(&mut |_| has_quantified = true);
+                    if !has_quantified {
+                        implicit_optional = true;
+                        self.error(
+                            errors,
+                            annot_range.unwrap_or(none.range),
+                            ErrorKind::ImplicitOptional,
+                            format!(
+                                "Default `None` is not assignable to parameter `{}` with type `{}`, add `| None` to the annotation",
+                                name.id,
+                                self.for_display(param_ty.clone()),
+                            ),
+                        );
+                    }
+                }
+                let check: Option<(&Type, &dyn Fn() -> TypeCheckContext)> =
+                    if skip_check || implicit_optional {
+                        None
+                    } else {
+                        Some((&param_ty, &make_context))
+                    };
                 let required = self.get_requiredness(default, check, stub_or_impl, errors);
                 (param_ty, required, false)
             }
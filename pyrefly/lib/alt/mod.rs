@@ -25,6 +25,7 @@ pub mod singledispatch;
 pub mod solve;
 pub mod special_calls;
 pub mod specials;
+pub mod stdlib_specials;
 pub mod subscript;
 pub mod traits;
 pub mod types;
@@ -1916,6 +1916,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 narrowed
             }
             NarrowOp::Atomic(subject, AtomicNarrowOp::HasAttr(attr)) => {
+                if !self.solver().hasattr_narrowing {
+                    return type_info.clone();
+                }
                 let resolved_chain = subject
                     .as_ref()
                     .and_then(|s| self.resolve_facet_chain(s.chain.clone()));
@@ -425,6 +425,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     None,
                 );
             }
+            if let Some((qualified_name, param)) = self.literal_string_sink_target(
+                Some(&metadata.kind),
+                &closest_overload.func.1.signature,
+                self_obj.is_some(),
+            ) {
+                self.check_literal_string_sink(
+                    &qualified_name,
+                    &param,
+                    &closest_overload.argmap,
+                    errors,
+                );
+            }
             (
                 closest_overload.res,
                 closest_overload.func.1.signature.clone(),
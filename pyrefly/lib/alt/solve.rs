@@ -31,6 +31,7 @@ use pyrefly_types::typed_dict::TypedDict;
 use pyrefly_util::display::pluralize;
 use pyrefly_util::prelude::SliceExt;
 use pyrefly_util::prelude::VecExt;
+use pyrefly_util::suggest::best_suggestion;
 use pyrefly_util::visit::Visit;
 use pyrefly_util::visit::VisitMut;
 use ruff_python_ast::Expr;
@@ -150,6 +151,7 @@ use crate::types::annotation::Qualifier;
 use crate::types::callable::Callable;
 use crate::types::callable::Param;
 use crate::types::callable::ParamList;
+use crate::types::callable::Params;
 use crate::types::callable::Required;
 use crate::types::class::AttrsFieldSpecifierKind;
 use crate::types::class::Class;
@@ -1010,6 +1012,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             _ => {
                 let ty = self
                     .unwrap_iterable(iterable)
+                    .or_else(|| {
+                        self.iterate_via_dunders(iterable, &dunder::ITER, &dunder::NEXT, range)
+                    })
                     .or_else(|| {
                         let int_ty = self.heap.mk_class_type(self.stdlib.int().clone());
                         let arg = CallArg::ty(&int_ty, range);
@@ -1045,14 +1050,43 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
             _ => {
                 let context = || ErrorContext::AsyncIteration(self.for_display(iterable.clone()));
-                let ty = self.unwrap_async_iterable(iterable).unwrap_or_else(|| {
-                    self.error(errors, range, ErrorKind::NotIterable, context().format())
-                });
+                let ty = self
+                    .unwrap_async_iterable(iterable)
+                    .or_else(|| {
+                        let awaitable = self.iterate_via_dunders(
+                            iterable,
+                            &dunder::AITER,
+                            &dunder::ANEXT,
+                            range,
+                        )?;
+                        self.unwrap_awaitable(&awaitable)
+                    })
+                    .unwrap_or_else(|| {
+                        self.error(errors, range, ErrorKind::NotIterable, context().format())
+                    });
                 vec![Iterable::OfType(ty)]
             }
         }
     }
 
+    /// Iteration only requires the object returned by `__iter__` (`__aiter__`) to have a
+    /// `__next__` (`__anext__`) method, which is weaker than the `Iterator` protocol. Call the
+    /// two dunders directly, returning the result of the second if both calls succeed.
+    fn iterate_via_dunders(
+        &self,
+        iterable: &Type,
+        iter_method: &Name,
+        next_method: &Name,
+        range: TextRange,
+    ) -> Option<Type> {
+        let errors = self.error_collector();
+        let iterator =
+            self.call_magic_dunder_method(iterable, iter_method, range, &[], &[], &errors, None)?;
+        let next =
+            self.call_magic_dunder_method(&iterator, next_method, range, &[], &[], &errors, None)?;
+        errors.is_empty().then_some(next)
+    }
+
     pub fn get_produced_type(&self, iterables: Vec<Iterable>) -> Type {
         let mut produced_types = Vec::new();
         for iterable in iterables {
@@ -2441,7 +2475,27 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 };
                 for iterable in iterables {
                     match iterable {
-                        Iterable::OfType(_) | Iterable::Unpacked { .. } => {}
+                        Iterable::OfType(_) => {}
+                        Iterable::Unpacked { prefix, suffix, .. } => {
+                            // The middle may be empty, so the prefix and suffix give a lower bound
+                            // on the length, which can only conflict with an exact expectation.
+                            let min_len = prefix.len() + suffix.len();
+                            if let SizeExpectation::Eq(n) = expect
+                                && min_len > *n
+                            {
+                                self.error(
+                                    errors,
+                                    *range,
+                                    ErrorKind::BadUnpacking,
+                                    format!(
+                                        "Cannot unpack {} (of size >= {}) into {}",
+                                        iterable_ty,
+                                        min_len,
+                                        expect.message(),
+                                    ),
+                                );
+                            }
+                        }
                         Iterable::OfTypeVarTuple(_) => {
                             self.error(
                                 errors,
@@ -2526,9 +2580,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 has_explicit_return,
             } => {
                 let annotation = self.get_idx(*annotation).annotation.get_type().clone();
+                let fall_through = || self.implicit_return_fall_through(*implicit_return);
                 let implicit_return = self.get_idx(*implicit_return);
                 self.check_implicit_return_against_annotation(
                     implicit_return,
+                    &fall_through,
                     &annotation,
                     *is_async,
                     *is_generator,
@@ -2883,6 +2939,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             self.check_variance_for_class(cls, class_bases.as_ref(), &class_field_map, errors);
             self.check_self_in_typed_dict(cls, &class_field_map, errors);
             self.check_invalid_abstract_methods(cls, &class_field_map, errors);
+            self.check_consistent_constructor(cls, errors);
         }
         Arc::new(EmptyAnswer)
     }
@@ -2955,6 +3012,158 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Flag a class whose `__new__` and `__init__` accept incompatible arguments: every call
+    /// to the class passes the same arguments to both, so one of them is bound to raise a
+    /// `TypeError`. Only reported on classes that define one of the two methods themselves.
+    fn check_consistent_constructor(&self, cls: &Class, errors: &ErrorCollector) {
+        let metadata = self.get_metadata_for_class(cls);
+        if metadata.is_enum() || metadata.is_typed_dict() {
+            return;
+        }
+        let Some(cls_fields) = self.get_class_fields(cls) else {
+            return;
+        };
+        let Some(range) = cls_fields
+            .field_decl_range(&dunder::INIT)
+            .or_else(|| cls_fields.field_decl_range(&dunder::NEW))
+        else {
+            return;
+        };
+        let class_type = self.as_class_type_unchecked(cls);
+        let Some(mut new_ty) = self
+            .get_dunder_new(&class_type, false)
+            .and_then(|t| self.bind_dunder_new(&t, class_type.clone()))
+        else {
+            return;
+        };
+        if new_ty
+            .callable_return_type(self.heap)
+            .is_some_and(|ret| !self.is_compatible_constructor_return(&ret, cls))
+        {
+            // `__init__` isn't called when `__new__` returns something else.
+            return;
+        }
+        let Some(Type::BoundMethod(init_method)) = self.get_dunder_init(&class_type, false) else {
+            return;
+        };
+        let Some(mut init_ty) = self.bind_dunder_init_for_callable(&init_method) else {
+            return;
+        };
+        // Only the parameters need to agree, so give both the same return type.
+        let ret = self.heap.mk_class_type(class_type);
+        new_ty.transform_toplevel_callable(|c| c.ret = ret.clone());
+        init_ty.transform_toplevel_callable(|c| c.ret = ret.clone());
+        // With overloads, a call only needs one signature of each to accept its arguments.
+        let init_sigs = init_ty.callable_signatures();
+        let compatible = new_ty.callable_signatures().iter().any(|new_sig| {
+            init_sigs
+                .iter()
+                .any(|init_sig| self.constructor_params_consistent(new_sig, init_sig))
+        });
+        if !compatible {
+            self.error(
+                errors,
+                range,
+                ErrorKind::InconsistentConstructor,
+                format!(
+                    "`{}.__new__` and `{}.__init__` accept incompatible arguments",
+                    cls.name(),
+                    cls.name()
+                ),
+            );
+        }
+    }
+
+    /// Do `__new__` and `__init__` accept the same arguments? Like pyright, we compare which
+    /// arguments each parameter list accepts rather than requiring one signature to be a subtype
+    /// of the other: a parameter with a default only needs a counterpart if the other signature
+    /// has one, and a signature with both `*args` and `**kwargs` is treated as accepting anything.
+    fn constructor_params_consistent(&self, new_sig: &Callable, init_sig: &Callable) -> bool {
+        let (Params::List(new_params), Params::List(init_params)) =
+            (&new_sig.params, &init_sig.params)
+        else {
+            return true;
+        };
+        let accepts_anything = |params: &ParamList| {
+            params
+                .items()
+                .iter()
+                .any(|p| matches!(p, Param::Varargs(..)))
+                && params
+                    .items()
+                    .iter()
+                    .any(|p| matches!(p, Param::Kwargs(..)))
+        };
+        if accepts_anything(new_params) || accepts_anything(init_params) {
+            return true;
+        }
+        self.accepts_arguments_for(new_params, init_params)
+            && self.accepts_arguments_for(init_params, new_params)
+    }
+
+    /// Can `params` take every argument that a parameter of `other` can, at a type that
+    /// overlaps with `other`'s?
+    fn accepts_arguments_for(&self, params: &ParamList, other: &ParamList) -> bool {
+        let positional = params
+            .items()
+            .iter()
+            .filter(|p| matches!(p, Param::PosOnly(..) | Param::Pos(..)))
+            .collect::<Vec<_>>();
+        let varargs = params.items().iter().find_map(|p| match p {
+            Param::Varargs(_, ty) => Some(ty),
+            _ => None,
+        });
+        let kwargs = params.items().iter().find_map(|p| match p {
+            Param::Kwargs(_, ty) => Some(ty),
+            _ => None,
+        });
+        let by_name = |name: &Name| {
+            params.items().iter().find_map(|p| match p {
+                Param::Pos(n, ty, _) | Param::KwOnly(n, ty, _) if n == name => Some(ty),
+                _ => None,
+            })
+        };
+        // A parameter of `other` with a default doesn't need a counterpart, since calls can omit it.
+        let overlaps = |want: Option<&Type>, got: &Type, required: &Required| match want {
+            Some(want) => self.is_subset_eq(want, got) || self.is_subset_eq(got, want),
+            None => matches!(required, Required::Optional(_)),
+        };
+        let mut position = 0;
+        other.items().iter().all(|p| match p {
+            Param::PosOnly(_, ty, required) => {
+                position += 1;
+                overlaps(
+                    positional
+                        .get(position - 1)
+                        .map(|p| p.as_type())
+                        .or(varargs),
+                    ty,
+                    required,
+                )
+            }
+            Param::Pos(name, ty, required) => {
+                position += 1;
+                overlaps(
+                    positional
+                        .get(position - 1)
+                        .map(|p| p.as_type())
+                        .or(varargs),
+                    ty,
+                    required,
+                ) && overlaps(by_name(name).or(kwargs), ty, required)
+            }
+            Param::KwOnly(name, ty, required) => overlaps(by_name(name).or(kwargs), ty, required),
+            Param::Varargs(_, ty) | Param::Kwargs(_, ty) => {
+                let want = if matches!(p, Param::Varargs(..)) {
+                    varargs
+                } else {
+                    kwargs
+                };
+                overlaps(want, ty, &Required::Optional(None))
+            }
+        })
+    }
+
     /// Check method and attribute override consistency for a class.
     fn check_consistent_override_for_class(
         &self,
@@ -4066,10 +4275,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
-    /// Handle `Binding::ReturnImplicit` - compute the implicit return type.
-    /// The `#[inline(never)]` annotation is intentional to reduce stack frame size.
-    #[inline(never)]
-    fn binding_to_type_return_implicit(&self, x: &ReturnImplicit) -> Type {
+    /// Whether the terminal statement `last` (with binding `k`) never falls through.
+    fn last_stmt_terminates(&self, last: &LastStmt, k: Idx<Key>) -> bool {
         // Would context have caught something:
         // https://typing.python.org/en/latest/spec/exceptions.html#context-managers.
         let context_catch = |x: &Type| -> bool {
@@ -4080,29 +4287,35 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         };
 
+        let e = self.get_idx(k);
+        match last {
+            LastStmt::Expr => e.ty().is_never(),
+            LastStmt::With(kind) => {
+                let res = self.context_value_exit(
+                    e.ty(),
+                    *kind,
+                    TextRange::default(),
+                    &self.error_swallower(),
+                    None,
+                );
+                !context_catch(&res)
+            }
+            LastStmt::Exhaustive(_, _) => {
+                // Check if the Exhaustive binding at this range resolved to Never
+                e.ty().is_never()
+            }
+        }
+    }
+
+    /// Handle `Binding::ReturnImplicit` - compute the implicit return type.
+    /// The `#[inline(never)]` annotation is intentional to reduce stack frame size.
+    #[inline(never)]
+    fn binding_to_type_return_implicit(&self, x: &ReturnImplicit) -> Type {
         if self.module().path().is_interface() {
             self.heap.mk_any_implicit() // .pyi file, functions don't have bodies
         } else if x.last_exprs.as_ref().is_some_and(|xs| {
-            xs.iter().all(|(last, k)| {
-                let e = self.get_idx(*k);
-                match last {
-                    LastStmt::Expr => e.ty().is_never(),
-                    LastStmt::With(kind) => {
-                        let res = self.context_value_exit(
-                            e.ty(),
-                            *kind,
-                            TextRange::default(),
-                            &self.error_swallower(),
-                            None,
-                        );
-                        !context_catch(&res)
-                    }
-                    LastStmt::Exhaustive(_, _) => {
-                        // Check if the Exhaustive binding at this range resolved to Never
-                        e.ty().is_never()
-                    }
-                }
-            })
+            xs.iter()
+                .all(|(last, k)| self.last_stmt_terminates(last, *k))
         }) {
             self.heap.mk_never()
         } else {
@@ -4110,6 +4323,22 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// The range of the first terminal statement through which the function with implicit
+    /// return `idx` can fall through, or of its last statement if we can't tell.
+    fn implicit_return_fall_through(&self, idx: Idx<Key>) -> Option<TextRange> {
+        let Binding::ReturnImplicit(x) = self.bindings().get(idx) else {
+            return None;
+        };
+        let fall_through = x.last_exprs.as_ref().and_then(|xs| {
+            xs.iter()
+                .find(|(last, k)| !self.last_stmt_terminates(last, *k))
+        });
+        Some(match fall_through {
+            Some((_, k)) => self.bindings().idx_to_key(*k).range(),
+            None => x.last_stmt,
+        })
+    }
+
     /// Handle `Binding::ExceptionHandler` - process exception handler clause.
     /// The `#[inline(never)]` annotation is intentional to reduce stack frame size.
     #[inline(never)]
@@ -4306,7 +4535,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         for iterable in iterables {
             values.push(match iterable {
                 Iterable::OfType(ty) => match pos {
-                    UnpackedPosition::Index(_) | UnpackedPosition::ReverseIndex(_) => ty,
+                    UnpackedPosition::Index(_)
+                    | UnpackedPosition::ExactIndex(_, _)
+                    | UnpackedPosition::ReverseIndex(_) => ty,
                     UnpackedPosition::Slice(_, _) => self.heap.mk_class_type(self.stdlib.list(ty)),
                 },
                 Iterable::Unpacked {
@@ -4314,14 +4545,39 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     middle,
                     suffix,
                 } => match pos {
-                    UnpackedPosition::Index(i) => {
-                        prefix.get(*i).cloned().unwrap_or_else(|| middle.clone())
+                    // Past the known prefix (or suffix), the middle may be empty, so the element
+                    // can also come from the suffix (or prefix) on the other side of the middle.
+                    UnpackedPosition::Index(i) => match prefix.get(*i) {
+                        Some(ty) => ty.clone(),
+                        None => {
+                            let reachable = (*i - prefix.len() + 1).min(suffix.len());
+                            self.unions(
+                                iter::once(middle)
+                                    .chain(suffix.into_iter().take(reachable))
+                                    .collect(),
+                            )
+                        }
+                    },
+                    // With no starred target, the middle is exactly the positions between the
+                    // prefix and the suffix.
+                    UnpackedPosition::ExactIndex(i, len) => {
+                        let suffix_start = len.saturating_sub(suffix.len());
+                        match prefix.get(*i) {
+                            Some(ty) => ty.clone(),
+                            None if *i >= suffix_start => suffix[*i - suffix_start].clone(),
+                            None => middle,
+                        }
                     }
                     UnpackedPosition::ReverseIndex(i) => {
                         if *i > 0 && *i <= suffix.len() {
                             suffix[suffix.len() - *i].clone()
                         } else {
-                            middle.clone()
+                            let reachable = (*i - suffix.len()).min(prefix.len());
+                            self.unions(
+                                iter::once(middle)
+                                    .chain(prefix.into_iter().rev().take(reachable))
+                                    .collect(),
+                            )
                         }
                     }
                     UnpackedPosition::Slice(i, j) => {
@@ -4341,9 +4597,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     // Type var tuples can resolve to anything so we fall back to object
                     let object_type = self.heap.mk_class_type(self.stdlib.object().clone());
                     match pos {
-                        UnpackedPosition::Index(_) | UnpackedPosition::ReverseIndex(_) => {
-                            object_type
-                        }
+                        UnpackedPosition::Index(_)
+                        | UnpackedPosition::ExactIndex(_, _)
+                        | UnpackedPosition::ReverseIndex(_) => object_type,
                         UnpackedPosition::Slice(_, _) => {
                             self.heap.mk_class_type(self.stdlib.list(object_type))
                         }
@@ -4352,11 +4608,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 Iterable::FixedLen(ts) => {
                     let has_never = ts.iter().any(Type::is_never);
                     match pos {
-                        UnpackedPosition::Index(i) | UnpackedPosition::ReverseIndex(i) => {
-                            let idx = if matches!(pos, UnpackedPosition::Index(_)) {
-                                Some(*i)
-                            } else {
+                        UnpackedPosition::Index(i)
+                        | UnpackedPosition::ExactIndex(i, _)
+                        | UnpackedPosition::ReverseIndex(i) => {
+                            let idx = if matches!(pos, UnpackedPosition::ReverseIndex(_)) {
                                 ts.len().checked_sub(*i)
+                            } else {
+                                Some(*i)
                             };
                             if let Some(idx) = idx
                                 && let Some(element) = ts.get(idx)
@@ -4829,13 +5087,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
         if matches!(submodule_error, FindError::MissingImport(..)) {
             if !fallback.is_unreachable {
-                errors
-                    .error_builder(
-                        fallback.stmt_range,
-                        ErrorKind::MissingModuleAttribute,
-                        format!("Could not import `{name}` from `{m}`"),
-                    )
-                    .emit();
+                let mut builder = errors.error_builder(
+                    fallback.stmt_range,
+                    ErrorKind::MissingModuleAttribute,
+                    format!("Could not import `{name}` from `{m}`"),
+                );
+                if let Some(wildcard) = self.exports.get_wildcard(m)
+                    && let Some(suggestion) =
+                        best_suggestion(name, wildcard.iter().map(|candidate| (candidate, 0)))
+                {
+                    builder = builder.with_detail(format!("Did you mean `{suggestion}`?"));
+                }
+                builder.emit();
             }
             self.heap.mk_any_error()
         } else {
@@ -4848,14 +5111,25 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     /// only fires for bindings that are actually solved — unused
     /// `import X` in a transitive dep stays at `Step::Nothing`. The
     /// `module_exists` call still demands `Step::Load` so incremental
-    /// re-check picks up edits to `m`.
+    /// re-check picks up edits to `m`. Modules that are deliberately not
+    /// type checked are reported as `implicit-any-import`.
     fn report_module_find_error(&self, m: ModuleName, range: TextRange, errors: &ErrorCollector) {
         let result = self.exports.module_exists(m);
         let error = match &result {
             FindingOrError::Finding(f) => f.error.as_ref(),
             FindingOrError::Error(e) => Some(e),
         };
-        if let Some(error) = error
+        if let Some(FindError::Ignored) = error {
+            errors
+                .error_builder(
+                    range,
+                    ErrorKind::ImplicitAnyImport,
+                    format!(
+                        "Module `{m}` is not type checked, so names imported from it are `Any`"
+                    ),
+                )
+                .emit();
+        } else if let Some(error) = error
             && let Some(kind) = error.kind()
         {
             let (ctx, msg) = error.display();
@@ -5347,10 +5621,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         let assigned_ty = self.distribute_over_union(&base, |base| {
             self.distribute_over_union(&slice_ty, |key| {
                 match (base, key) {
-                    (Type::TypedDict(typed_dict), Type::Literal(lit))
-                        if let Lit::Str(field_name) = &lit.value =>
+                    (Type::TypedDict(typed_dict), key)
+                        if let Some(field_name) = self.typed_dict_literal_key(key) =>
                     {
-                        let field_name = Name::new(field_name);
                         self.check_assign_to_typed_dict_literal_subscript(
                             typed_dict,
                             &field_name,
@@ -5491,6 +5764,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     fn check_implicit_return_against_annotation(
         &self,
         implicit_return: Arc<TypeInfo>,
+        fall_through: &dyn Fn() -> Option<TextRange>,
         annotation: &Type,
         is_async: bool,
         is_generator: bool,
@@ -5516,16 +5790,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 self.decompose_generator(hint).map(|(_, _, r)| r)
             });
             if !return_tys.is_empty() {
-                self.check_type(
+                self.check_implicit_return_type(
                     implicit_return.ty(),
                     &self.unions(return_tys),
+                    fall_through,
+                    has_explicit_returns,
                     range,
                     errors,
-                    &|| {
-                        TypeCheckContext::of_kind(TypeCheckKind::ImplicitFunctionReturn(
-                            has_explicit_returns,
-                        ))
-                    },
                 );
             } else {
                 self.error(
@@ -5536,14 +5807,45 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 );
             }
         } else {
-            self.check_type(implicit_return.ty(), annotation, range, errors, &|| {
-                TypeCheckContext::of_kind(TypeCheckKind::ImplicitFunctionReturn(
-                    has_explicit_returns,
-                ))
-            });
+            self.check_implicit_return_type(
+                implicit_return.ty(),
+                annotation,
+                fall_through,
+                has_explicit_returns,
+                range,
+                errors,
+            );
         }
     }
 
+    /// Check the implicit return type of a function against the declared one, pointing at
+    /// the statement the function falls through, if known.
+    fn check_implicit_return_type(
+        &self,
+        got: &Type,
+        want: &Type,
+        fall_through: &dyn Fn() -> Option<TextRange>,
+        has_explicit_returns: bool,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        let tcc: &dyn Fn() -> TypeCheckContext = &|| {
+            TypeCheckContext::of_kind(TypeCheckKind::ImplicitFunctionReturn(has_explicit_returns))
+        };
+        let detail = || match fall_through() {
+            Some(fall_through) => format!(
+                "The function can fall through after the statement on line {}",
+                self.module()
+                    .display_range(fall_through)
+                    .start
+                    .line_within_cell()
+            ),
+            None => "The function can fall through".to_owned(),
+        };
+        let options = TypeCheckOptions::new(errors, tcc).with_detail(&detail);
+        self.check_type_with_options(got, want, range, options);
+    }
+
     fn check_type_form(&self, ty: &Type, allow_none: bool) -> bool {
         // TODO(stroxler, rechen): Do we want to include Type::ClassDef(_)
         // when there is no annotation, so that `mylist = list` is treated
@@ -6712,10 +7014,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 let slice_ty = self.expr_infer(&x.slice, errors);
                 self.map_over_union(&base, |base| {
                     self.map_over_union(&slice_ty, |key| match (base, key) {
-                        (Type::TypedDict(typed_dict), Type::Literal(lit))
-                            if let Lit::Str(field_name) = &lit.value =>
+                        (Type::TypedDict(typed_dict), key)
+                            if let Some(field_name) = self.typed_dict_literal_key(key) =>
                         {
-                            let field_name = Name::new(field_name);
                             self.check_del_typed_dict_literal_key(
                                 typed_dict,
                                 &field_name,
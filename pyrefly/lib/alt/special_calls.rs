@@ -14,6 +14,7 @@
 use pyrefly_python::dunder;
 use pyrefly_types::callable::FuncMetadata;
 use pyrefly_types::shaped_array::IntTuple;
+use pyrefly_util::prelude::SliceExt;
 use pyrefly_util::visit::Visit;
 use pyrefly_util::visit::VisitMut;
 use ruff_python_ast::Expr;
@@ -27,6 +28,7 @@ use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::callable::CallArg;
 use crate::alt::callable::CallKeyword;
 use crate::alt::expr::ExprOptions;
+use crate::alt::expr::TypeOrExpr;
 use crate::alt::solve::TypeFormContext;
 use crate::alt::types::decorated_function::Decorator;
 use crate::alt::unwrap::HintRef;
@@ -37,6 +39,7 @@ use crate::error::context::TypeCheckKind;
 use crate::types::callable::FunctionKind;
 use crate::types::callable::unexpected_keyword;
 use crate::types::class::Class;
+use crate::types::literal::Lit;
 use crate::types::tuple::Tuple;
 use crate::types::types::Type;
 
@@ -153,6 +156,51 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         default
     }
 
+    pub fn call_builtin_setattr(
+        &self,
+        args: &[CallArg],
+        callee_ty: Type,
+        keywords: &[CallKeyword],
+        func_range: TextRange,
+        arguments_range: TextRange,
+        hint: Option<HintRef>,
+        errors: &ErrorCollector,
+    ) -> Type {
+        let arg_tys = args.map(|arg| match arg {
+            CallArg::Arg(arg) => (arg.infer(self, errors), arg.range()),
+            CallArg::Star(_, _) => {
+                unreachable!("starred setattr arguments are excluded by the caller")
+            }
+        });
+        let typed_args = arg_tys.map(|(ty, range)| CallArg::ty(ty, *range));
+        let ret = self.freeform_call_infer(
+            callee_ty,
+            &typed_args,
+            keywords,
+            func_range,
+            arguments_range,
+            hint,
+            errors,
+        );
+        // With a literal name, check the call like the assignment `obj.name = value`.
+        if let Type::Literal(lit) = &arg_tys[1].0
+            && let Lit::Str(attr_name) = &lit.value
+        {
+            let (value_ty, value_range) = &arg_tys[2];
+            self.check_attr_set_and_infer_narrow(
+                &arg_tys[0].0,
+                &Name::new(attr_name),
+                TypeOrExpr::Type(value_ty, *value_range),
+                false,
+                arguments_range,
+                errors,
+                None,
+                "call_builtin_setattr",
+            );
+        }
+        ret
+    }
+
     pub fn call_reveal_type(
         &self,
         args: &[Expr],
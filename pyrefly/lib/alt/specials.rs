@@ -335,6 +335,121 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Apply `Callable[params, ret]`, where `params` is a list of parameter types, `...`, a
+    /// `ParamSpec`, or a `Concatenate`. Malformed forms are reported and treated as
+    /// `Callable[..., Any]`.
+    fn apply_callable_special_form(
+        &self,
+        arguments: &[Expr],
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Type {
+        let callable_error = || {
+            self.heap
+                .mk_type_of(self.heap.mk_callable_ellipsis(self.heap.mk_any_error()))
+        };
+        let [params, ret] = arguments else {
+            self.error(
+                errors,
+                range,
+                ErrorKind::BadSpecialization,
+                format!(
+                    "Expected 2 arguments for `Callable`, got {}",
+                    arguments.len()
+                ),
+            );
+            return callable_error();
+        };
+        let ret = if let Expr::EllipsisLiteral(_) = ret {
+            self.error(
+                errors,
+                ret.range(),
+                ErrorKind::InvalidAnnotation,
+                "`...` is not a valid return type".to_owned(),
+            );
+            self.heap.mk_any_error()
+        } else {
+            self.expr_untype(ret, TypeFormContext::TypeArgumentCallableReturn, errors)
+        };
+        match params {
+            Expr::List(ExprList { elts, .. }) => {
+                match self.check_args_and_construct_tuple(elts, errors) {
+                    Some((tuple, true)) => self.heap.mk_type_of(self.heap.mk_callable_from_vec(
+                        vec![Param::Varargs(
+                            None,
+                            self.heap.mk_unpack(self.heap.mk_tuple(tuple)),
+                        )],
+                        ret,
+                    )),
+                    Some((Tuple::Concrete(elts), false)) => {
+                        self.heap.mk_type_of(self.heap.mk_callable_from_vec(
+                            elts.map(|t| Param::PosOnly(None, t.clone(), Required::Required)),
+                            ret,
+                        ))
+                    }
+                    Some(_) => {
+                        self.error(
+                            errors,
+                            range,
+                            ErrorKind::BadSpecialization,
+                            "Unrecognized callable type form".to_owned(),
+                        );
+                        callable_error()
+                    }
+                    None => callable_error(),
+                }
+            }
+            Expr::EllipsisLiteral(_) => self.heap.mk_type_of(self.heap.mk_callable_ellipsis(ret)),
+            name @ (Expr::Name(_) | Expr::Attribute(_)) => {
+                let ty = self.expr_untype(name, TypeFormContext::TypeArgument, errors);
+                if ty.is_kind_param_spec() {
+                    self.heap
+                        .mk_type_of(self.heap.mk_callable_param_spec(ty, ret))
+                } else {
+                    self.error(
+                        errors,
+                        name.range(),
+                        ErrorKind::BadSpecialization,
+                        format!(
+                            "Callable types can only have `ParamSpec` in this position, got `{}`",
+                            self.for_display(ty)
+                        ),
+                    );
+                    callable_error()
+                }
+            }
+            x @ Expr::Subscript(_) => {
+                let ty = self.expr_untype(x, TypeFormContext::TypeArgument, errors);
+                match ty {
+                    Type::Concatenate(args, pspec) => self
+                        .heap
+                        .mk_type_of(self.heap.mk_callable_concatenate(args, *pspec, ret)),
+                    _ => {
+                        self.error(
+                            errors,
+                            x.range(),
+                            ErrorKind::BadSpecialization,
+                            format!(
+                                "Callable types can only have `Concatenate` in this position, got `{}`",
+                                self.for_display(ty)
+                            ),
+                        );
+                        callable_error()
+                    }
+                }
+            }
+            x => {
+                self.error(
+                    errors,
+                    x.range(),
+                    ErrorKind::InvalidSyntax,
+                    "Invalid `Callable` type: the first argument must be a list of parameter types, `...`, a `ParamSpec`, or `Concatenate`".to_owned(),
+                );
+                callable_error()
+            }
+        }
+    }
+
     pub fn apply_special_form(
         &self,
         special_form: SpecialForm,
@@ -419,107 +534,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         .mk_type_of(Type::Concatenate(args, Box::new(pspec)))
                 }
             }
-            SpecialForm::Callable if arguments.len() == 2 => {
-                let callable_error = || {
-                    self.heap
-                        .mk_type_of(self.heap.mk_callable_ellipsis(self.heap.mk_any_error()))
-                };
-                let ret = if let Expr::EllipsisLiteral(_) = &arguments[1] {
-                    self.error(
-                        errors,
-                        arguments[1].range(),
-                        ErrorKind::InvalidAnnotation,
-                        "`...` is not a valid return type".to_owned(),
-                    );
-                    self.heap.mk_any_error()
-                } else {
-                    self.expr_untype(
-                        &arguments[1],
-                        TypeFormContext::TypeArgumentCallableReturn,
-                        errors,
-                    )
-                };
-                match &arguments[0] {
-                    Expr::List(ExprList { elts, .. }) => {
-                        match self.check_args_and_construct_tuple(elts, errors) {
-                            Some((tuple, true)) => {
-                                self.heap.mk_type_of(self.heap.mk_callable_from_vec(
-                                    vec![Param::Varargs(
-                                        None,
-                                        self.heap.mk_unpack(self.heap.mk_tuple(tuple)),
-                                    )],
-                                    ret,
-                                ))
-                            }
-                            Some((Tuple::Concrete(elts), false)) => {
-                                self.heap.mk_type_of(self.heap.mk_callable_from_vec(
-                                    elts.map(|t| {
-                                        Param::PosOnly(None, t.clone(), Required::Required)
-                                    }),
-                                    ret,
-                                ))
-                            }
-                            Some(_) => {
-                                self.error(
-                                    errors,
-                                    range,
-                                    ErrorKind::BadSpecialization,
-                                    "Unrecognized callable type form".to_owned(),
-                                );
-                                callable_error()
-                            }
-                            None => callable_error(),
-                        }
-                    }
-                    Expr::EllipsisLiteral(_) => {
-                        self.heap.mk_type_of(self.heap.mk_callable_ellipsis(ret))
-                    }
-                    name @ Expr::Name(_) => {
-                        let ty = self.expr_untype(name, TypeFormContext::TypeArgument, errors);
-                        if ty.is_kind_param_spec() {
-                            self.heap
-                                .mk_type_of(self.heap.mk_callable_param_spec(ty, ret))
-                        } else {
-                            self.error(errors, name.range(),ErrorKind::BadSpecialization, format!("Callable types can only have `ParamSpec` in this position, got `{}`", self.for_display(ty)));
-                            callable_error()
-                        }
-                    }
-                    x @ Expr::Subscript(_) => {
-                        let ty = self.expr_untype(x, TypeFormContext::TypeArgument, errors);
-                        match ty {
-                            Type::Concatenate(args, pspec) => self
-                                .heap
-                                .mk_type_of(self.heap.mk_callable_concatenate(args, *pspec, ret)),
-                            _ => {
-                                self.error(errors, x.range(),ErrorKind::BadSpecialization, format!("Callable types can only have `Concatenate` in this position, got `{}`", self.for_display(ty)));
-                                callable_error()
-                            }
-                        }
-                    }
-                    x => {
-                        self.error(
-                            errors,
-                            x.range(),
-                            ErrorKind::InvalidSyntax,
-                            "Invalid `Callable` type".to_owned(),
-                        );
-                        callable_error()
-                    }
-                }
-            }
-            SpecialForm::Callable => {
-                self.error(
-                    errors,
-                    range,
-                    ErrorKind::BadSpecialization,
-                    format!(
-                        "Expected 2 arguments for `Callable`, got {}",
-                        arguments.len()
-                    ),
-                );
-                self.heap
-                    .mk_type_of(self.heap.mk_callable_ellipsis(self.heap.mk_any_error()))
-            }
+            SpecialForm::Callable => self.apply_callable_special_form(arguments, range, errors),
             SpecialForm::TypeGuard => self.apply_unary_special_form(
                 SpecialForm::TypeGuard.to_string(),
                 arguments,
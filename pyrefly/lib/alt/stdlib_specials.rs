@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Special handling for stdlib functions whose result depends on literal arguments.
+//!
+//! Typeshed can only express that kind of precision through overloads, which give up
+//! when it would take too many of them (e.g. `subprocess.run` types `stdout` as captured
+//! output even when nothing is captured, and `getattr` can't name the attribute it
+//! looks up). Each entry in `STDLIB_SPECIALS` names a
+//! function and a refinement that runs after the ordinary call: the call is checked as
+//! usual, and the refinement may replace its result based on the argument types. To add a
+//! special case, add a `StdlibSpecial` variant, a table entry, and a `refine_*` method.
+
+use pyrefly_util::prelude::SliceExt;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::callable::CallArg;
+use crate::alt::callable::CallKeyword;
+use crate::alt::callable::CallWithTypes;
+use crate::alt::expr::TypeOrExpr;
+use crate::alt::unwrap::HintRef;
+use crate::error::collector::ErrorCollector;
+use crate::types::callable::FunctionKind;
+use crate::types::literal::Lit;
+use crate::types::types::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdlibSpecial {
+    /// `subprocess.run`: `CompletedProcess[None]` when neither stdout nor stderr is captured.
+    SubprocessRun,
+    /// `os.getenv`: plain `str` when the default is a `str`.
+    OsGetenv,
+    /// `getattr`: the attribute's type when the name is a string literal.
+    GetAttr,
+}
+
+/// The module-level functions with a special case, keyed by module and function name.
+const STDLIB_SPECIALS: &[(&str, &str, StdlibSpecial)] = &[
+    ("subprocess", "run", StdlibSpecial::SubprocessRun),
+    ("os", "getenv", StdlibSpecial::OsGetenv),
+    ("builtins", "getattr", StdlibSpecial::GetAttr),
+];
+
+impl StdlibSpecial {
+    pub fn for_function(kind: &FunctionKind) -> Option<Self> {
+        if kind.class().is_some() {
+            return None;
+        }
+        let module = kind.module_name();
+        let name = kind.function_name();
+        STDLIB_SPECIALS
+            .iter()
+            .find(|(m, n, _)| module.as_str() == *m && name.as_str() == *n)
+            .map(|(_, _, special)| *special)
+    }
+}
+
+/// The arguments of a call once every argument has been inferred, if they are all
+/// plain positional or keyword arguments.
+struct StdlibCall<'a> {
+    args: Vec<&'a Type>,
+    keywords: Vec<(&'a str, &'a Type)>,
+}
+
+impl<'a> StdlibCall<'a> {
+    fn new(args: &'a [CallArg<'a>], keywords: &'a [CallKeyword<'a>]) -> Option<Self> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                CallArg::Arg(TypeOrExpr::Type(ty, _)) => Some(*ty),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        let keywords = keywords
+            .iter()
+            .map(|kw| match (kw.arg, &kw.value) {
+                (Some(name), TypeOrExpr::Type(ty, _)) => Some((name.id.as_str(), *ty)),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        Some(Self { args, keywords })
+    }
+
+    fn keyword(&self, name: &str) -> Option<&'a Type> {
+        self.keywords
+            .iter()
+            .find(|(kw, _)| *kw == name)
+            .map(|(_, ty)| *ty)
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    pub fn call_stdlib_special(
+        &self,
+        special: StdlibSpecial,
+        callee_ty: Type,
+        args: &[CallArg],
+        keywords: &[CallKeyword],
+        func_range: TextRange,
+        arguments_range: TextRange,
+        hint: Option<HintRef>,
+        errors: &ErrorCollector,
+    ) -> Type {
+        // Infer the arguments once, so the refinement can inspect their types.
+        let call = CallWithTypes::new();
+        let args = call.vec_call_arg(args, self, errors);
+        let keywords = keywords.map(|kw| call.call_keyword(kw, self, errors));
+        // The ordinary call reports any argument errors and yields typeshed's result.
+        let default = self.freeform_call_infer(
+            callee_ty,
+            &args,
+            &keywords,
+            func_range,
+            arguments_range,
+            hint,
+            errors,
+        );
+        let Some(call) = StdlibCall::new(&args, &keywords) else {
+            return default;
+        };
+        let refined = match special {
+            StdlibSpecial::SubprocessRun => self.refine_subprocess_run(&call, &default),
+            StdlibSpecial::OsGetenv => self.refine_os_getenv(&call),
+            StdlibSpecial::GetAttr => self.refine_getattr(&call, arguments_range),
+        };
+        refined.unwrap_or(default)
+    }
+
+    /// `CompletedProcess.stdout` and `stderr` are only set when the output is captured,
+    /// but typeshed types them as captured output regardless. When no argument can
+    /// capture output, both are `None`.
+    fn refine_subprocess_run(&self, call: &StdlibCall, default: &Type) -> Option<Type> {
+        // `stdout` is the fifth positional parameter.
+        if call.args.len() > 4 {
+            return None;
+        }
+        let not_captured = |name: &str| {
+            call.keyword(name)
+                .is_none_or(|ty| ty.as_bool() == Some(false))
+        };
+        if !(not_captured("stdout") && not_captured("stderr") && not_captured("capture_output")) {
+            return None;
+        }
+        let Type::ClassType(cls) = default else {
+            return None;
+        };
+        if !cls.has_qname("subprocess", "CompletedProcess") {
+            return None;
+        }
+        let mut cls = cls.clone();
+        for targ in cls.targs_mut().as_mut() {
+            *targ = Type::None;
+        }
+        Some(self.heap.mk_class_type(cls))
+    }
+
+    /// `os.getenv(key, default)` returns `str | T` for a default of type `T`; with a `str`
+    /// default that is just `str`, as it is for `os.environ.get`.
+    fn refine_os_getenv(&self, call: &StdlibCall) -> Option<Type> {
+        let default = match (call.args.as_slice(), call.keyword("default")) {
+            ([_, default], None) => *default,
+            ([_], Some(default)) => default,
+            _ => return None,
+        };
+        if default.is_any() {
+            return None;
+        }
+        let str_ty = self.stdlib.str().clone().to_type();
+        self.is_subset_eq(default, &str_ty).then_some(str_ty)
+    }
+
+    /// `getattr(x, "a")` is the type of `x.a`, and `getattr(x, "a", d)` is that unioned with
+    /// the default. A missing attribute keeps typeshed's `Any`, as `getattr` is commonly used
+    /// to probe for attributes that may not exist.
+    fn refine_getattr(&self, call: &StdlibCall, range: TextRange) -> Option<Type> {
+        if !call.keywords.is_empty() {
+            return None;
+        }
+        let (obj, name, default) = match call.args.as_slice() {
+            [obj, name] => (*obj, *name, None),
+            [obj, name, default] => (*obj, *name, Some(*default)),
+            _ => return None,
+        };
+        let Type::Literal(lit) = name else {
+            return None;
+        };
+        let Lit::Str(attr_name) = &lit.value else {
+            return None;
+        };
+        let attr_errors = self.error_collector();
+        let attr_ty = self.type_of_attr_get(
+            obj,
+            &Name::new(attr_name),
+            range,
+            &attr_errors,
+            None,
+            "refine_getattr",
+        );
+        if !attr_errors.is_empty() || attr_ty.is_error() {
+            return None;
+        }
+        Some(match default {
+            Some(default) => self.union(attr_ty, default.clone()),
+            None => attr_ty,
+        })
+    }
+}
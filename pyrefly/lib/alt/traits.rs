@@ -112,6 +112,13 @@ pub trait Solve<Ans: LookupAnswer>: Keyed {
     /// sufficient for now.
     fn promote_recursive(heap: &TypeHeap, x: Var) -> Self::Answer;
 
+    /// We hit a back-edge while iterating an SCC to a fixpoint, before this binding has an
+    /// answer from a previous iteration. Later iterations see the previous iteration's answer
+    /// instead, so this only seeds the first one.
+    fn promote_iteration_placeholder(heap: &TypeHeap, x: Var) -> Self::Answer {
+        Self::promote_recursive(heap, x)
+    }
+
     /// We solved a binding, but during its execution we gave some people back a recursive value.
     /// Record that recursive value along with the answer.
     fn record_recursive(
@@ -255,6 +262,14 @@ impl<Ans: LookupAnswer> Solve<Ans> for KeyExport {
         // KeyExport would otherwise return a Type::Var from a foreign solver).
         Type::Any(AnyStyle::Implicit)
     }
+
+    fn promote_iteration_placeholder(heap: &TypeHeap, _: Var) -> Self::Answer {
+        // A back-edge on an export comes from a module in an import cycle. Rather than
+        // seeding the iteration with `Any`, which would stick in every answer that unions
+        // with it, defer the lookup: start from `Never`, and let the next iteration see
+        // the export's actual answer.
+        heap.mk_never()
+    }
 }
 
 impl<Ans: LookupAnswer> Solve<Ans> for KeyDecorator {
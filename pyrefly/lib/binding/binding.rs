@@ -1744,8 +1744,11 @@ impl DisplayWith<ModuleInfo> for KeyYieldFrom {
 
 #[derive(Clone, Copy, Dupe, Debug)]
 pub enum UnpackedPosition {
-    /// Zero-based index
+    /// Zero-based index, before a starred target
     Index(usize),
+    /// Zero-based index into an unpacking of exactly the given number of values, i.e. one
+    /// with no starred target
+    ExactIndex(usize, usize),
     /// A negative index, counting from the back
     ReverseIndex(usize),
     /// Slice represented as an index from the front to an index from the back.
@@ -2019,6 +2022,9 @@ pub struct ReturnImplicit {
     /// the function has an implicit `None` return if there exists a non-`Never` in this
     /// list.
     pub last_exprs: Option<Box<[(LastStmt, Idx<Key>)]>>,
+    /// The last statement of the function body, which is where the function falls through
+    /// when `last_exprs` doesn't narrow it down.
+    pub last_stmt: TextRange,
 }
 
 #[derive(Clone, Debug)]
@@ -2505,7 +2511,9 @@ impl DisplayWith<Bindings> for Binding {
             }
             Self::UnpackedValue(a, x, range, pos, receiver) => {
                 let pos = match pos {
-                    UnpackedPosition::Index(i) => i.to_string(),
+                    UnpackedPosition::Index(i) | UnpackedPosition::ExactIndex(i, _) => {
+                        i.to_string()
+                    }
                     UnpackedPosition::ReverseIndex(i) => format!("-{i}"),
                     UnpackedPosition::Slice(i, j) => {
                         let end = match j {
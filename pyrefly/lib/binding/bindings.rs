@@ -9,6 +9,7 @@ use std::cell::RefCell;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::mem;
 use std::sync::Arc;
 
 use dupe::Dupe;
@@ -18,6 +19,7 @@ use pyrefly_graph::index::Index;
 use pyrefly_graph::index_map::IndexMap;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::dunder;
+use pyrefly_python::ignore::has_noqa;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::nesting_context::NestingContext;
 use pyrefly_python::short_identifier::ShortIdentifier;
@@ -32,6 +34,7 @@ use pyrefly_util::gas::Gas;
 use pyrefly_util::suggest::best_suggestion;
 use ruff_python_ast::Expr;
 use ruff_python_ast::ExprAttribute;
+use ruff_python_ast::ExprUnaryOp;
 use ruff_python_ast::Identifier;
 use ruff_python_ast::ModModule;
 use ruff_python_ast::Parameter;
@@ -40,6 +43,7 @@ use ruff_python_ast::StmtClassDef;
 use ruff_python_ast::StmtFunctionDef;
 use ruff_python_ast::TypeParam;
 use ruff_python_ast::TypeParams;
+use ruff_python_ast::UnaryOp;
 use ruff_python_ast::name::Name;
 use ruff_python_parser::semantic_errors::LazyImportContext;
 use ruff_python_parser::semantic_errors::SemanticSyntaxChecker;
@@ -209,6 +213,8 @@ struct BindingsInner {
     unused_variables: Vec<UnusedVariable>,
     pytest_info: Option<PytestBindingInfo>,
     promote_ranges: SmallSet<TextRange>,
+    /// See `BindingsBuilder::branch_conditions`.
+    branch_conditions: SmallMap<TextRange, Vec<(TextSize, TextRange)>>,
     /// Yield and yield-from indices for each lambda that contains yields,
     /// keyed by the lambda's TextRange. Populated at binding time so the
     /// solver can look up yield info without re-walking the AST.
@@ -309,6 +315,20 @@ pub struct BindingsBuilder<'a> {
     /// set by `stmts()` and consumed by namedtuple synthesis in `stmt()`.
     pub adjacent_namedtuple_defaults: Option<Vec<Expr>>,
     pub promote_ranges: SmallSet<TextRange>,
+    /// For each `if` statement, keyed by its range (which is also the range of the `Key::Phi`s
+    /// its merge produces), the start of each clause and the range of its condition (the
+    /// `else` keyword for an `else` clause). Used to point at the branches of a flow merge.
+    pub branch_conditions: SmallMap<TextRange, Vec<(TextSize, TextRange)>>,
+    /// How many `if TYPE_CHECKING:` bodies (with no `elif`/`else`) we are currently binding.
+    pub type_checking_only_depth: usize,
+    /// Idxs of imports made inside an `if TYPE_CHECKING:` body, which don't exist at runtime.
+    pub type_checking_only_imports: SmallSet<Idx<Key>>,
+    /// Names read at runtime, with the idx they resolved to. Checked against
+    /// `type_checking_only_imports` after traversal, once every definition is known.
+    pub runtime_name_uses: Vec<(Identifier, Idx<Key>)>,
+    /// Whether we are binding a parameter or return annotation, which is evaluated when the
+    /// function is defined unless annotations are deferred.
+    pub in_function_header_annotation: bool,
 }
 
 /// An enum tracking whether we are in a generator expression
@@ -368,6 +388,7 @@ impl Bindings {
             class_scopes: Vec::new(),
             subsequently_initialized: SmallSet::new(),
             promote_ranges: SmallSet::new(),
+            branch_conditions: SmallMap::new(),
         }))
     }
 
@@ -485,6 +506,20 @@ impl Bindings {
         self.0.promote_ranges.contains(&range)
     }
 
+    /// The condition of the clause of the `if` statement at `if_range` that contains `at`, if
+    /// `at` is inside one of its clauses.
+    pub fn branch_condition(&self, if_range: TextRange, at: TextSize) -> Option<TextRange> {
+        let clauses = self.0.branch_conditions.get(&if_range)?;
+        if !if_range.contains(at) {
+            return None;
+        }
+        clauses
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= at)
+            .map(|(_, condition)| *condition)
+    }
+
     pub fn key_to_idx<K: Keyed>(&self, k: &K) -> Idx<K>
     where
         BindingTable: TableKeyed<K, Value = BindingEntry<K>>,
@@ -639,6 +674,11 @@ impl Bindings {
             subsequently_initialized: SmallSet::new(),
             adjacent_namedtuple_defaults: None,
             promote_ranges: SmallSet::new(),
+            branch_conditions: SmallMap::new(),
+            type_checking_only_depth: 0,
+            type_checking_only_imports: SmallSet::new(),
+            runtime_name_uses: Vec::new(),
+            in_function_header_annotation: false,
         };
         builder.init_static_scope(&x.body, true);
         if module_info.name() != ModuleName::builtins() {
@@ -653,6 +693,7 @@ impl Bindings {
         assert_eq!(builder.scopes.loop_depth(), 0);
 
         builder.process_deferred_bound_names();
+        builder.report_type_checking_only_uses();
 
         // Validate that all entries in __all__ are defined in the module.
         // Synthesize a binding so importers resolve to Any(Error) without
@@ -687,6 +728,10 @@ impl Bindings {
         }
 
         let unused_imports = builder.scopes.collect_module_unused_imports();
+        // Imports in an `__init__.py` are usually there to re-export names from submodules.
+        if !module_info.path().is_init() {
+            builder.report_unused_imports(&unused_imports);
+        }
         builder.record_unused_imports(unused_imports);
         let scope_trace = builder.scopes.finish();
 
@@ -753,6 +798,7 @@ impl Bindings {
             class_scopes: builder.class_scopes,
             subsequently_initialized: builder.subsequently_initialized,
             promote_ranges: builder.promote_ranges,
+            branch_conditions: builder.branch_conditions,
         }))
     }
 
@@ -853,7 +899,7 @@ impl BindingTable {
             Binding::Phi(JoinStyle::SimpleMerge, Box::new([]))
         }) {
             Binding::Phi(_, branches) => {
-                let mut v = std::mem::take(branches).into_vec();
+                let mut v = mem::take(branches).into_vec();
                 v.push(new_branch);
                 *branches = v.into_boxed_slice();
             }
@@ -1002,6 +1048,29 @@ impl<'a> BindingsBuilder<'a> {
         self.unused_parameters.extend(unused);
     }
 
+    /// Report `unused-import` errors, skipping imports marked with `# noqa` or `# noqa: F401`.
+    fn report_unused_imports(&self, unused: &[UnusedImport]) {
+        for unused in unused {
+            let line = self
+                .module_info
+                .display_range(unused.range)
+                .start
+                .line_within_file();
+            let line_text = self
+                .module_info
+                .lined_buffer()
+                .content_in_line_range(line, line);
+            if has_noqa(line_text, "F401") {
+                continue;
+            }
+            self.error(
+                unused.range,
+                ErrorKind::UnusedImport,
+                format!("Import `{}` is unused", unused.name),
+            );
+        }
+    }
+
     pub fn record_unused_imports(&mut self, unused: Vec<UnusedImport>) {
         self.unused_imports.extend(unused);
     }
@@ -1289,6 +1358,43 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
+    /// Statically evaluate the test of an `if` statement. Besides the `sys_info` checks, this
+    /// understands bool literals assigned to `Final` names in other modules, e.g. `if flags.DEBUG:`
+    /// where `flags` defines `DEBUG: Final = False`. Only `Final` names are trusted, since they
+    /// can't be reassigned after import.
+    pub fn evaluate_static_test(&self, x: &Expr) -> Option<bool> {
+        if let Some(value) = self.sys_info.evaluate_bool(x) {
+            return Some(value);
+        }
+        match x {
+            Expr::UnaryOp(ExprUnaryOp {
+                op: UnaryOp::Not,
+                operand,
+                ..
+            }) => self.evaluate_static_test(operand).map(|value| !value),
+            Expr::Name(name) => match self.scopes.binding_idx_for_name(&name.id)? {
+                (_, FlowStyle::Import(module, upstream_name)) => {
+                    self.lookup
+                        .export_origin(module, &upstream_name)
+                        .final_bool_value
+                }
+                _ => None,
+            },
+            Expr::Attribute(ExprAttribute { value, attr, .. })
+                if let Expr::Name(base_name) = &**value =>
+            {
+                let module = match self.scopes.binding_idx_for_name(&base_name.id)? {
+                    // `import foo.bar` binds `foo`, so `foo.X` refers to `foo`.
+                    (_, FlowStyle::MergeableImport(_)) => ModuleName::from_name(&base_name.id),
+                    (_, FlowStyle::ImportAs(module)) => module,
+                    _ => return None,
+                };
+                self.lookup.export_origin(module, &attr.id).final_bool_value
+            }
+            _ => None,
+        }
+    }
+
     pub fn class_object_is_generic(&self, idx: Idx<Key>) -> bool {
         let Some(Binding::ClassDef(class_idx, _)) = self.idx_to_binding(idx) else {
             return false;
@@ -1645,7 +1751,7 @@ impl<'a> BindingsBuilder<'a> {
     /// follow Forward chains and detect first-use opportunities.
     fn process_deferred_bound_names(&mut self) {
         // Take the deferred bindings to avoid borrow issues
-        let deferred = std::mem::take(&mut self.deferred_bound_names);
+        let deferred = mem::take(&mut self.deferred_bound_names);
 
         // Process each deferred binding.
         for deferred_binding in deferred {
@@ -1885,6 +1991,14 @@ impl<'a> BindingsBuilder<'a> {
         }
         self.check_for_type_alias_redefinition(name, idx);
         self.check_for_imported_final_reassignment(name, idx);
+        if self.type_checking_only_depth > 0
+            && matches!(
+                style,
+                FlowStyle::MergeableImport(_) | FlowStyle::Import(..) | FlowStyle::ImportAs(_)
+            )
+        {
+            self.type_checking_only_imports.insert(idx);
+        }
         let name = Hashed::new(name);
         let write_info = self
             .scopes
@@ -1902,6 +2016,39 @@ impl<'a> BindingsBuilder<'a> {
         write_info.annotation
     }
 
+    /// Report runtime reads of names that are only imported under `if TYPE_CHECKING:`.
+    fn report_type_checking_only_uses(&mut self) {
+        for (name, idx) in mem::take(&mut self.runtime_name_uses) {
+            if self.is_type_checking_only_import(idx) {
+                self.error(
+                    name.range,
+                    ErrorKind::TypeCheckingOnlyImport,
+                    format!(
+                        "`{name}` is imported only under `if TYPE_CHECKING:` and is not available at runtime"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn is_type_checking_only_import(&self, idx: Idx<Key>) -> bool {
+        if self.type_checking_only_imports.contains(&idx) {
+            return true;
+        }
+        // Reads from function and class bodies resolve to the merge of every definition in the
+        // enclosing scope, which is only missing at runtime if all of them are.
+        if matches!(self.idx_to_key(idx), Key::Anywhere(..))
+            && let Some(Binding::Phi(_, branches)) = self.idx_to_binding(idx)
+        {
+            !branches.is_empty()
+                && branches
+                    .iter()
+                    .all(|branch| self.type_checking_only_imports.contains(&branch.value_key))
+        } else {
+            false
+        }
+    }
+
     fn check_for_type_alias_redefinition(&self, name: &Name, idx: Idx<Key>) {
         let prev_idx = self.scopes.current_flow_idx(name);
         if let Some(prev_idx) = prev_idx {
@@ -2483,7 +2630,7 @@ impl<'a> BindingsBuilder<'a> {
     }
 
     pub fn with_semantic_checker(&mut self, f: impl FnOnce(&mut SemanticSyntaxChecker, &Self)) {
-        let mut checker = std::mem::take(&mut self.semantic_checker);
+        let mut checker = mem::take(&mut self.semantic_checker);
         f(&mut checker, self);
         self.semantic_checker = checker;
     }
@@ -5,10 +5,13 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::mem;
+
 use pyrefly_graph::index::Idx;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::module_path::ModuleStyle;
 use pyrefly_python::short_identifier::ShortIdentifier;
+use pyrefly_util::visit::Visit;
 use pyrefly_util::visit::VisitMut;
 use ruff_python_ast::AtomicNodeIndex;
 use ruff_python_ast::BoolOp;
@@ -28,6 +31,7 @@ use ruff_python_ast::ExprYield;
 use ruff_python_ast::ExprYieldFrom;
 use ruff_python_ast::Identifier;
 use ruff_python_ast::Operator;
+use ruff_python_ast::PySourceType;
 use ruff_python_ast::StringLiteral;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
@@ -366,6 +370,22 @@ impl<'a> BindingsBuilder<'a> {
             usage,
             Usage::StaticTypeInformation { .. } | Usage::TypeAliasRhs
         );
+        // Before Python 3.14, annotations in executable modules are evaluated at runtime
+        // unless `from __future__ import annotations` defers them.
+        let annotations_are_evaluated = self.module_info.path().style() == ModuleStyle::Executable
+            && !self.sys_info.version().at_least(3, 14)
+            && !self.scopes.has_future_annotations();
+        let is_evaluated_annotation = is_runtime_evaluated_annotation
+            && matches!(
+                usage,
+                Usage::StaticTypeInformation {
+                    is_annotation: true
+                }
+            )
+            && annotations_are_evaluated;
+        let is_runtime_use = (!used_in_static_type && !self.module_info.path().is_interface())
+            || is_evaluated_annotation
+            || (self.in_function_header_annotation && annotations_are_evaluated);
         let lookup_result =
             if used_in_static_type && let Some((tparams_collector, tparam_id)) = tparams_lookup {
                 self.intercept_lookup(tparams_collector, tparam_id)
@@ -399,20 +419,15 @@ impl<'a> BindingsBuilder<'a> {
                         self.error(name.range, ErrorKind::UnboundName, error_message);
                     }
                 }
-                if is_runtime_evaluated_annotation
-                    && matches!(
-                        usage,
-                        Usage::StaticTypeInformation {
-                            is_annotation: true
-                        }
-                    )
-                    && self.module_info.path().style() == ModuleStyle::Executable
-                    && !self.sys_info.version().at_least(3, 14)
-                    && !self.scopes.has_future_annotations()
+                if is_evaluated_annotation
                     && let Some(error_message) = is_initialized.as_error_message(&name.id)
                 {
                     self.error(name.range, ErrorKind::UnboundName, error_message);
                 }
+                if is_runtime_use && self.type_checking_only_depth == 0 {
+                    self.runtime_name_uses
+                        .push((name.clone(), lookup_result_idx));
+                }
 
                 // TODO: `global x` reads bypass this (they use Flow, not Anywhere).
 
@@ -1171,6 +1186,19 @@ impl<'a> BindingsBuilder<'a> {
         );
     }
 
+    /// Like `ensure_type`, for a parameter or return annotation, which (unless annotations are
+    /// deferred) is evaluated when the function is defined.
+    pub fn ensure_function_header_type(
+        &mut self,
+        x: &mut Expr,
+        tparams_builder: &mut Option<LegacyTParamCollector>,
+    ) {
+        let in_function_header_annotation =
+            mem::replace(&mut self.in_function_header_annotation, true);
+        self.ensure_type(x, tparams_builder);
+        self.in_function_header_annotation = in_function_header_annotation;
+    }
+
     pub fn ensure_class_member_type(
         &mut self,
         x: &mut Expr,
@@ -1219,6 +1247,9 @@ impl<'a> BindingsBuilder<'a> {
             }
         }
         let expr_range = x.range();
+        if matches!(x, Expr::Name(_) | Expr::Attribute(_)) {
+            self.check_deprecated_typing_alias(x);
+        }
         let invalid_proxy_method_use = !allow_proxy_method
             && matches!(usage, Usage::TypeAliasRhs)
             && self.type_expr_is_proxy_method_node(x);
@@ -1332,6 +1363,9 @@ impl<'a> BindingsBuilder<'a> {
                 match Ast::parse_type_literal(literal) {
                     Ok(expr) => {
                         *x = expr;
+                        // Quoted annotations are never evaluated.
+                        let in_function_header_annotation =
+                            mem::replace(&mut self.in_function_header_annotation, false);
                         self.ensure_type_impl(
                             x,
                             tparams_builder,
@@ -1340,6 +1374,7 @@ impl<'a> BindingsBuilder<'a> {
                             usage,
                             allow_proxy_method,
                         );
+                        self.in_function_header_annotation = in_function_header_annotation;
                     }
                     Err(_) => {
                         // We don't need to emit errors here, because the solving logic expects the expression to resolve to a type, and it will fail.
@@ -1513,6 +1548,76 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
+    /// Report a `typing` alias like `List` when the builtin generic is available.
+    fn check_deprecated_typing_alias(&self, x: &Expr) {
+        if !self.sys_info.version().at_least(3, 9) {
+            return;
+        }
+        let (alias, builtin) = match self.as_special_export(x) {
+            Some(SpecialExport::TypingList) => ("List", "list"),
+            Some(SpecialExport::TypingDict) => ("Dict", "dict"),
+            Some(SpecialExport::TypingTuple) => ("Tuple", "tuple"),
+            Some(SpecialExport::TypingType) => ("Type", "type"),
+            _ => return,
+        };
+        self.error(
+            x.range(),
+            ErrorKind::DeprecatedTypingAlias,
+            format!("`typing.{alias}` is deprecated, use `{builtin}` instead"),
+        );
+    }
+
+    /// Report the quoted parts of an annotation that is never evaluated at runtime, i.e. in a
+    /// stub, under `from __future__ import annotations`, or when targeting Python 3.14+.
+    /// Must be called before `ensure_type`, which replaces string literals with their contents.
+    pub fn check_quoted_annotation(&self, x: &Expr) {
+        if self.module_info.source_type() == PySourceType::Stub
+            || self.scopes.has_future_annotations()
+            || self.sys_info.version().at_least(3, 14)
+        {
+            self.check_quoted_annotation_parts(x);
+        }
+    }
+
+    fn check_quoted_annotation_parts(&self, x: &Expr) {
+        match x {
+            Expr::StringLiteral(literal) => {
+                if let Some(literal) = literal.as_single_part_string()
+                    && !literal.flags.prefix().is_raw()
+                {
+                    self.error(
+                        literal.range(),
+                        ErrorKind::UnnecessaryQuotedAnnotation,
+                        "Quotes are unnecessary because this annotation is not evaluated at runtime"
+                            .to_owned(),
+                    );
+                }
+            }
+            Expr::Subscript(ExprSubscript { value, slice, .. }) => {
+                match self.as_special_export(value) {
+                    // Strings inside `Literal` are values, not forward references.
+                    Some(SpecialExport::Literal) => {}
+                    // Only the first argument to `Annotated` is a type.
+                    Some(SpecialExport::Annotated) => {
+                        if let Expr::Tuple(tup) = &**slice
+                            && let Some(first) = tup.elts.first()
+                        {
+                            self.check_quoted_annotation_parts(first);
+                        }
+                    }
+                    _ => {
+                        self.check_quoted_annotation_parts(value);
+                        self.check_quoted_annotation_parts(slice);
+                    }
+                }
+            }
+            Expr::Tuple(_) | Expr::List(_) | Expr::BinOp(_) => {
+                x.recurse(&mut |x| self.check_quoted_annotation_parts(x))
+            }
+            _ => {}
+        }
+    }
+
     fn type_expr_is_proxy_method_node(&self, x: &Expr) -> bool {
         match x {
             Expr::Name(_) | Expr::Attribute(_) => {
@@ -1526,16 +1631,6 @@ impl<'a> BindingsBuilder<'a> {
     }
 
     /// Execute through the expr, ensuring every name has a binding.
-    pub fn ensure_type_opt(
-        &mut self,
-        x: Option<&mut Expr>,
-        tparams_builder: &mut Option<LegacyTParamCollector>,
-    ) {
-        if let Some(x) = x {
-            self.ensure_type(x, tparams_builder);
-        }
-    }
-
     pub fn ensure_and_bind_decorators(
         &mut self,
         decorators: ThinVec<Decorator>,
@@ -332,7 +332,8 @@ impl<'a> BindingsBuilder<'a> {
         class_key: Option<Idx<KeyClass>>,
         tparams_builder: &mut Option<LegacyTParamCollector>,
     ) -> (TextRange, Idx<KeyAnnotation>) {
-        self.ensure_type(&mut x, tparams_builder);
+        self.check_quoted_annotation(&x);
+        self.ensure_function_header_type(&mut x, tparams_builder);
         (
             x.range(),
             self.insert_binding(
@@ -367,7 +368,12 @@ impl<'a> BindingsBuilder<'a> {
         // We need to bind all the parameters expressions _after_ the type params, but before the parameter names,
         // which might shadow some types.
         for (param, default) in Ast::parameters_iter_mut(&mut x.parameters) {
-            self.ensure_type_opt(param.annotation.as_deref_mut(), &mut legacy);
+            if let Some(annotation) = &param.annotation {
+                self.check_quoted_annotation(annotation);
+            }
+            if let Some(annotation) = param.annotation.as_deref_mut() {
+                self.ensure_function_header_type(annotation, &mut legacy);
+            }
             if let Some(default) = default {
                 self.ensure_expr_opt(default.as_deref_mut(), usage);
             }
@@ -492,7 +498,10 @@ impl<'a> BindingsBuilder<'a> {
         });
         self.insert_binding(
             Key::ReturnImplicit(ShortIdentifier::new(func_name)),
-            Binding::ReturnImplicit(ReturnImplicit { last_exprs }),
+            Binding::ReturnImplicit(ReturnImplicit {
+                last_exprs,
+                last_stmt: body.last().map_or(func_name.range, |x| x.range()),
+            }),
         )
     }
 
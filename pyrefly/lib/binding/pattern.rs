@@ -18,6 +18,7 @@ use ruff_python_ast::Number;
 use ruff_python_ast::Pattern;
 use ruff_python_ast::PatternKeyword;
 use ruff_python_ast::StmtMatch;
+use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use vec1::Vec1;
@@ -383,8 +384,10 @@ impl<'a> BindingsBuilder<'a> {
                         _ => {
                             let position = if seen_star {
                                 UnpackedPosition::ReverseIndex(num_patterns - i)
-                            } else {
+                            } else if num_patterns != num_non_star_patterns {
                                 UnpackedPosition::Index(i)
+                            } else {
+                                UnpackedPosition::ExactIndex(i, num_patterns)
                             };
                             let key_for_subpattern = self.insert_binding(
                                 Key::Anon(x.range()),
@@ -459,23 +462,63 @@ impl<'a> BindingsBuilder<'a> {
             Pattern::MatchMapping(x) => {
                 let mut narrow_ops = PatternNarrowOps::new();
                 let mut subject_idx = subject_idx;
-                let narrow_op = AtomicNarrowOp::IsMapping;
+                // A mapping pattern only matches if every key is present, which lets us narrow
+                // e.g. a union of TypedDicts to the ones that can have the literal keys.
+                let key_ops = x
+                    .keys
+                    .iter()
+                    .filter_map(|key| match key {
+                        Expr::StringLiteral(ExprStringLiteral { value: key, .. }) => {
+                            Some(NarrowOp::Atomic(
+                                None,
+                                AtomicNarrowOp::HasKey(Name::new(key.to_string())),
+                            ))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let is_mapping_op = NarrowOp::Atomic(None, AtomicNarrowOp::IsMapping);
+                let narrow_op = if key_ops.is_empty() {
+                    is_mapping_op.clone()
+                } else {
+                    let mut ops = vec![is_mapping_op.clone()];
+                    ops.extend(key_ops.iter().cloned());
+                    NarrowOp::And(ops)
+                };
                 subject_idx = self.insert_binding(
                     Key::PatternNarrow(x.range()),
                     Binding::Narrow(
                         subject_idx,
-                        Box::new(NarrowOp::Atomic(None, narrow_op.clone())),
+                        Box::new(narrow_op.clone()),
                         NarrowUseLocation::Span(x.range()),
                     ),
                 );
-                let subject_op = if match_subject.is_synthetic() && !x.keys.is_empty() {
-                    NarrowOp::And(vec![
-                        NarrowOp::Atomic(None, narrow_op),
-                        NarrowOp::Atomic(None, AtomicNarrowOp::Placeholder),
-                    ])
-                } else {
-                    NarrowOp::Atomic(None, narrow_op)
-                };
+                // A subject that falls through only lacks one of the keys if the values can't
+                // be what failed to match, e.g. `{"year": 2000}` falls through for any year
+                // other than 2000. Otherwise the keys only narrow the case body.
+                let all_values_irrefutable = x
+                    .patterns
+                    .iter()
+                    .all(|p| p.is_irrefutable() || p.is_wildcard());
+                // A subject can also fall through for lacking a non-literal key, which we can't
+                // narrow on, so then falling through proves nothing about the subject.
+                let all_keys_literal = key_ops.len() == x.keys.len();
+                let subject_op =
+                    if (match_subject.is_synthetic() && !x.keys.is_empty()) || !all_keys_literal {
+                        NarrowOp::And(vec![
+                            narrow_op,
+                            NarrowOp::Atomic(None, AtomicNarrowOp::Placeholder),
+                        ])
+                    } else if all_values_irrefutable || key_ops.is_empty() {
+                        narrow_op
+                    } else {
+                        narrow_ops.body_only.and_all(
+                            match_subject
+                                .subject_narrow_op(NarrowOp::And(key_ops), x.range)
+                                .scope,
+                        );
+                        is_mapping_op
+                    };
                 narrow_ops.and_all(match_subject.subject_narrow_op(subject_op, x.range));
                 x.keys
                     .into_iter()
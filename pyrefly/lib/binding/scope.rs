@@ -72,6 +72,7 @@ use crate::binding::narrow::NarrowOps;
 use crate::export::definitions::Definition;
 use crate::export::definitions::DefinitionStyle;
 use crate::export::definitions::Definitions;
+use crate::export::definitions::FinalValue;
 use crate::export::definitions::MutableCaptureKind;
 use crate::export::exports::LookupExport;
 use crate::export::special::SpecialExport;
@@ -543,7 +544,10 @@ impl Static {
         let final_string_values = d
             .final_names
             .into_iter()
-            .filter_map(|(name, value)| value.map(|v| (name, v)))
+            .filter_map(|(name, value)| match value {
+                Some(FinalValue::Str(v)) => Some((name, v)),
+                _ => None,
+            })
             .collect();
         (
             implicit_captures,
@@ -10,6 +10,7 @@ use pyrefly_python::ast::Ast;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::nesting_context::NestingContext;
 use pyrefly_python::short_identifier::ShortIdentifier;
+use pyrefly_python::sys_info::SysInfo;
 use ruff_python_ast::Arguments;
 use ruff_python_ast::AtomicNodeIndex;
 use ruff_python_ast::Expr;
@@ -27,6 +28,7 @@ use ruff_python_ast::StmtReturn;
 use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
 use starlark_map::small_set::SmallSet;
 
 use crate::binding::binding::AnnAssignHasValue;
@@ -631,6 +633,7 @@ impl<'a> BindingsBuilder<'a> {
         is_initialized: AnnAssignHasValue,
     ) -> Idx<KeyAnnotation> {
         let ann_key = KeyAnnotation::Annotation(ShortIdentifier::new(name));
+        self.check_quoted_annotation(annotation);
         if self.scopes.in_class_body() {
             self.ensure_class_member_type(annotation, &mut None);
         } else {
@@ -990,6 +993,7 @@ impl<'a> BindingsBuilder<'a> {
                 Expr::Attribute(attr) => {
                     let mut attr = attr;
                     let attr_name = attr.attr.id.clone();
+                    self.check_quoted_annotation(&x.annotation);
                     self.ensure_type(&mut x.annotation, &mut None);
                     let ann_key = self.insert_binding(
                         KeyAnnotation::AttrAnnotation(x.annotation.range()),
@@ -1232,6 +1236,9 @@ impl<'a> BindingsBuilder<'a> {
                 let is_definitely_unreachable = self.scopes.is_definitely_unreachable();
                 let mut exhaustive = false;
                 let if_range = x.range;
+                // Names imported in an `if TYPE_CHECKING:` with no fallback don't exist at runtime.
+                let is_type_checking_only =
+                    x.elif_else_clauses.is_empty() && SysInfo::is_type_checking_test(&x.test);
                 // Process the first `if` test before forking so that walrus-defined names
                 // are in the base flow and visible after the if-statement. This mirrors the
                 // fix for ternary expressions in expr.rs (Expr::If handling).
@@ -1248,6 +1255,14 @@ impl<'a> BindingsBuilder<'a> {
                 let mut contains_static_test_with_no_else = false;
                 let mut is_first_branch = true;
                 for (range, mut test, body) in Ast::if_branches_owned(x) {
+                    let condition = match &test {
+                        Some(test) => test.range(),
+                        None => TextRange::at(range.start(), TextSize::of("else")),
+                    };
+                    self.branch_conditions
+                        .entry(if_range)
+                        .or_default()
+                        .push((range.start(), condition));
                     self.start_branch();
                     self.bind_narrow_ops(
                         &negated_prev_ops,
@@ -1261,7 +1276,7 @@ impl<'a> BindingsBuilder<'a> {
                             Some(true)
                         }
                         Some(x) => {
-                            let result = self.sys_info.evaluate_bool(x);
+                            let result = self.evaluate_static_test(x);
                             if result.is_some() {
                                 contains_static_test_with_no_else = true;
                             }
@@ -1308,7 +1323,13 @@ impl<'a> BindingsBuilder<'a> {
                         &Usage::NonPinningValue(None),
                     );
                     negated_prev_ops.and_all(new_narrow_ops.negate());
-                    self.stmts(body, parent);
+                    if is_type_checking_only {
+                        self.type_checking_only_depth += 1;
+                        self.stmts(body, parent);
+                        self.type_checking_only_depth -= 1;
+                    } else {
+                        self.stmts(body, parent);
+                    }
                     self.finish_branch();
                     if this_branch_chosen == Some(true) {
                         exhaustive = true;
@@ -1544,25 +1565,13 @@ impl<'a> BindingsBuilder<'a> {
                     }
                 }
             }
-            Stmt::ImportFrom(x) => {
-                if let Some(m) = self.module_info.name().new_maybe_relative(
-                    self.module_info.path().is_init(),
-                    x.level,
-                    x.module.as_ref().map(|x| &x.id),
-                ) {
-                    self.bind_module_exports(x, m);
-                } else {
-                    self.error(
-                        x.range,
-                        ErrorKind::MissingImport,
-                        format!(
-                            "Could not resolve relative import `{}`",
-                            ".".repeat(x.level as usize)
-                        ),
-                    );
+            Stmt::ImportFrom(x) => match self.resolve_import_from(&x) {
+                Ok(m) => self.bind_module_exports(x, m),
+                Err(msg) => {
+                    self.error(x.range, ErrorKind::InvalidRelativeImport, msg);
                     self.bind_unimportable_names(&x, true);
                 }
-            }
+            },
             Stmt::Global(x) => {
                 for name in x.names {
                     self.declare_mutable_capture(&name, MutableCaptureKind::Global);
@@ -1679,6 +1688,31 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
+    /// The module a `from ... import` statement imports from, or an error message if it's a
+    /// relative import that can't refer to any module.
+    fn resolve_import_from(&self, x: &StmtImportFrom) -> Result<ModuleName, String> {
+        let name = self.module_info.name();
+        let is_init = self.module_info.path().is_init();
+        let dots = ".".repeat(x.level as usize);
+        if x.level > 0 && name == ModuleName::unknown() {
+            // We couldn't work out which package this file belongs to, so there's
+            // nothing to resolve the import relative to.
+            return Err(format!(
+                "Could not resolve relative import `{dots}` because this file isn't under any search root, so its module name is unknown. Try adding the project root with `--search-path`"
+            ));
+        }
+        match name.new_maybe_relative(is_init, x.level, x.module.as_ref().map(|x| &x.id)) {
+            Some(m) => Ok(m),
+            None if is_init || name.components().len() > 1 => Err(format!(
+                "Could not resolve relative import `{dots}` because it goes beyond the top-level package `{}`",
+                name.first_component()
+            )),
+            None => Err(format!(
+                "Could not resolve relative import `{dots}` because `{name}` is a top-level module, so it has no parent package"
+            )),
+        }
+    }
+
     fn bind_module_exports(&mut self, x: StmtImportFrom, m: ModuleName) {
         let module_range = x.range;
         // Single solve-time module-existence check per `from X import …`
@@ -1724,6 +1758,24 @@ impl<'a> BindingsBuilder<'a> {
                         Binding::Any(AnyStyle::Error)
                     };
                     let key = self.insert_binding(key, val);
+                    if !self.scopes.is_unreachable_from_static_test()
+                        && matches!(
+                            self.scopes.current_flow_style(name.key()),
+                            Some(
+                                FlowStyle::Other
+                                    | FlowStyle::FunctionDef { .. }
+                                    | FlowStyle::ClassDef { .. }
+                            )
+                        )
+                    {
+                        self.error(
+                            x.range,
+                            ErrorKind::StarImportShadowing,
+                            format!(
+                                "`from {m} import *` overwrites the existing definition of `{name}`"
+                            ),
+                        );
+                    }
                     // Register the imported name from wildcard imports
                     self.scopes.register_import_with_star(&Identifier {
                         node_index: AtomicNodeIndex::default(),
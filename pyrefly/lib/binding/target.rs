@@ -94,6 +94,7 @@ impl<'a> BindingsBuilder<'a> {
         // An unpacking has zero or one splats (starred expressions).
         let mut splat = false;
         let len = elts.len();
+        let has_splat = elts.iter().any(|e| matches!(e, Expr::Starred(_)));
         for (i, e) in elts.iter_mut().enumerate() {
             match e {
                 Expr::Starred(e) => {
@@ -116,8 +117,10 @@ impl<'a> BindingsBuilder<'a> {
                         // If we've encountered a splat, we no longer know how many values have been consumed
                         // from the front, but we know how many are left at the back.
                         UnpackedPosition::ReverseIndex(len - i)
-                    } else {
+                    } else if has_splat {
                         UnpackedPosition::Index(i)
+                    } else {
+                        UnpackedPosition::ExactIndex(i, len)
                     };
                     let make_nested_binding = |ann| {
                         Binding::UnpackedValue(ann, unpack_idx, range, unpacked_position, None)
@@ -19,14 +19,17 @@ use crate::commands::check::SnippetCheckArgs;
 use crate::commands::config_finder::ConfigConfigurerWrapper;
 use crate::commands::coverage::CoverageCommand;
 use crate::commands::coverage::report::ReportArgs;
+use crate::commands::doc::DocArgs;
 use crate::commands::dump_config::DumpConfigArgs;
 use crate::commands::infer::InferArgs;
 use crate::commands::init::InitArgs;
 use crate::commands::lsp::LspArgs;
 use crate::commands::stubgen::StubgenArgs;
 use crate::commands::suppress::SuppressArgs;
+use crate::commands::test_annotations::TestAnnotationsArgs;
 use crate::commands::tsp::TspArgs;
 use crate::commands::util::CommandExitStatus;
+use crate::commands::verify_runtime::VerifyRuntimeArgs;
 use crate::lsp::non_wasm::external_provider::NoExternalProvider;
 
 /// Subcommands to run Pyrefly with.
@@ -72,6 +75,12 @@ pub enum Command {
     Suppress(SuppressArgs),
     /// Generate .pyi stub files from Python source files.
     Stubgen(StubgenArgs),
+    /// Generate API documentation from the types pyrefly infers.
+    Doc(DocArgs),
+    /// Run `assert_type` and `reveal_type` assertions in files as a test suite.
+    TestAnnotations(TestAnnotationsArgs),
+    /// Import modules in the configured interpreter and compare them to their static types.
+    VerifyRuntime(VerifyRuntimeArgs),
 }
 
 impl Command {
@@ -124,6 +133,11 @@ impl Command {
             Command::Stubgen(args) => {
                 Ok((args.run(config_configurer_wrapper, thread_count)?, None))
             }
+            Command::Doc(args) => Ok((args.run(config_configurer_wrapper, thread_count)?, None)),
+            Command::TestAnnotations(args) => {
+                Ok((args.run(config_configurer_wrapper, thread_count)?, None))
+            }
+            Command::VerifyRuntime(args) => Ok((args.run(thread_count)?, None)),
         }
     }
 }
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::cmp::Reverse;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Display;
@@ -31,6 +32,7 @@ use percent_encoding::CONTROLS;
 use percent_encoding::utf8_percent_encode;
 use pyrefly_build::handle::Handle;
 use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::base::FollowImports;
 use pyrefly_config::config::ConfigFile;
 use pyrefly_config::config::OutputFormat;
 use pyrefly_config::config::SynthesizedPresetReason;
@@ -38,9 +40,11 @@ use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_config::finder::ConfigError;
 use pyrefly_config::migration::run::MigratedConfigSource;
 use pyrefly_config::migration::run::MigratedFromKind;
+use pyrefly_config::module_wildcard::ModuleFilter;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_name::ModuleNameWithKind;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::args::clap_env;
 use pyrefly_util::demand_tree::DemandCollector;
@@ -61,11 +65,16 @@ use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 
 use crate::commands::config_finder::ConfigConfigurerWrapper;
 use crate::commands::files::FilesArgs;
 use crate::commands::files::UpsellDecision;
 use crate::commands::files::get_config_finder_for_snippet;
+use crate::commands::saved_state;
+use crate::commands::saved_state::ErrorCategory;
+use crate::commands::saved_state::SavedState;
+use crate::commands::saved_state::SavedStateReuse;
 use crate::commands::util::CommandExitStatus;
 use crate::config::error_kind::Severity;
 use crate::config::finder::ConfigFinder;
@@ -73,9 +82,11 @@ use crate::error::code_climate::CodeClimateIssues;
 use crate::error::error::Error;
 use crate::error::error::ErrorRenderer;
 use crate::error::error::print_error_counts;
+use crate::error::fix;
 use crate::error::legacy::LegacyError;
 use crate::error::legacy::LegacyErrors;
 use crate::error::legacy::severity_to_str;
+use crate::error::sarif::SarifLog;
 use crate::error::summarize::print_error_summary;
 use crate::error::suppress;
 use crate::error::suppress::CommentLocation;
@@ -88,7 +99,10 @@ use crate::state::require::RequireLevels;
 use crate::state::state::State;
 use crate::state::state::Transaction;
 use crate::state::steps::Step;
+use crate::state::subscriber::CompositeSubscriber;
+use crate::state::subscriber::FailFastSubscriber;
 use crate::state::subscriber::ProgressBarStyle;
+use crate::state::subscriber::Subscriber;
 use crate::state::subscriber::TestSubscriber;
 
 /// Result data from a non-watch check run, used for telemetry logging.
@@ -122,7 +136,10 @@ pub struct FullCheckArgs {
 
     /// Watch for file changes and re-check them.
     /// (Warning: This mode is highly experimental!)
-    #[arg(long, conflicts_with = "check_all")]
+    #[arg(
+        long,
+        conflicts_with_all = ["check_all", "fail_fast", "read_saved_state", "write_saved_state"]
+    )]
     watch: bool,
 
     /// Type checking arguments and configuration
@@ -236,6 +253,14 @@ impl SnippetCheckArgs {
                 suppress_errors: false,
                 expectations: false,
                 remove_unused_ignores: false,
+                fix: false,
+                diff: false,
+                require: None,
+                include_modules: Vec::new(),
+                exclude_modules: Vec::new(),
+                fail_fast: false,
+                read_saved_state: None,
+                write_saved_state: None,
             },
         };
         let (status, check_result) =
@@ -263,6 +288,10 @@ struct OutputArgs {
     /// Report type traces.
     #[arg(long, value_name = "OUTPUT_FILE")]
     report_trace: Option<PathBuf>,
+    /// Write one JSON record per line for each definition in the checked files, giving its module,
+    /// qualified name, kind, signature as printed by stubgen, and location.
+    #[arg(long, value_name = "OUTPUT_FILE")]
+    dump_types: Option<PathBuf>,
     /// Experimental: generate a JSON dependency graph of all modules to the specified file. This is unstable and should only be used for debugging.
     #[arg(long, value_name = "OUTPUT_FILE")]
     dependency_graph: Option<PathBuf>,
@@ -367,6 +396,20 @@ struct OutputArgs {
     /// Errors below this severity will not be shown. Defaults to "error".
     #[arg(long, value_enum)]
     min_severity: Option<Severity>,
+
+    /// How to order the reported errors.
+    /// `path` (default) orders by file and location, `kind` groups errors by error kind,
+    /// and `count` groups them by error kind with the most frequent kinds first.
+    #[arg(long, value_enum, default_value_t)]
+    sort_errors_by: SortErrorsBy,
+
+    /// Print at most this many errors. The error count and exit code still reflect all errors.
+    #[arg(long, value_name = "N")]
+    max_errors: Option<usize>,
+
+    /// Show this many lines of source before and after each error in the full text output.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    show_error_context: u32,
 }
 
 impl OutputArgs {
@@ -407,6 +450,44 @@ enum Summary {
     Full,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum, Default, PartialEq, Eq)]
+enum SortErrorsBy {
+    #[default]
+    Path,
+    Kind,
+    Count,
+}
+
+/// Order errors for output. Whatever the grouping, ties are broken by module, path, range, kind
+/// and message, so the output doesn't depend on the order in which modules were checked.
+fn sort_errors(errors: &mut [Error], by: SortErrorsBy) {
+    let mut counts: SmallMap<ErrorKind, usize> = SmallMap::new();
+    if by == SortErrorsBy::Count {
+        for e in errors.iter() {
+            *counts.entry(e.error_kind()).or_default() += 1;
+        }
+    }
+    errors.sort_by_cached_key(|e| {
+        let group = match by {
+            SortErrorsBy::Path => None,
+            SortErrorsBy::Kind => Some((Reverse(0), e.error_kind())),
+            SortErrorsBy::Count => Some((
+                Reverse(counts.get(&e.error_kind()).copied().unwrap_or_default()),
+                e.error_kind(),
+            )),
+        };
+        (
+            group,
+            e.module().name(),
+            e.path().dupe(),
+            e.range().start(),
+            e.range().end(),
+            e.error_kind(),
+            e.msg(),
+        )
+    });
+}
+
 /// non-config type checker behavior
 #[deny(clippy::missing_docs_in_private_items)]
 #[derive(Debug, Parser, Clone)]
@@ -423,24 +504,69 @@ struct BehaviorArgs {
     /// Remove unused ignores from the input files.
     #[arg(long)]
     remove_unused_ignores: bool,
+    /// Apply the machine-applicable fixes attached to reported errors to the input files.
+    /// Fixes that overlap an earlier fix in the same file are skipped.
+    /// Only errors of enabled kinds are fixed, and the kinds with fixes are off by default:
+    /// enable them with e.g. `--warn unused-ignore` to remove unused suppression comments.
+    #[arg(long, conflicts_with_all = ["suppress_errors", "remove_unused_ignores"])]
+    fix: bool,
+    /// With `--fix`, print the fixes as a diff to stderr instead of writing them.
+    #[arg(long, requires = "fix")]
+    diff: bool,
+    /// How much to compute for modules that are not being checked (e.g. dependencies).
+    /// `exports` only computes what is needed to resolve their exported interface, which is fastest;
+    /// `errors` fully checks their bodies, so errors that affect inferred types are found.
+    /// Levels required by other flags (e.g. `--check-all` or a report) take precedence.
+    #[arg(long, value_enum)]
+    require: Option<Require>,
+    /// Only check files whose module name matches one of these patterns (e.g. `app.*`).
+    #[arg(long)]
+    include_modules: Vec<String>,
+    /// Don't check files whose module name matches one of these patterns (e.g. `proto.*`),
+    /// wherever they are on disk.
+    #[arg(long)]
+    exclude_modules: Vec<String>,
+    /// Stop checking as soon as a checked file has an error, and only report errors from the
+    /// files that were fully checked by then.
+    #[arg(long)]
+    fail_fast: bool,
+    /// Start from a saved state written by `--write-saved-state`, e.g. for a base commit, and
+    /// only recheck the files that changed since, or that import a file that changed. Errors in
+    /// the other files are reported from the saved state.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["check_all", "fail_fast"])]
+    read_saved_state: Option<PathBuf>,
+    /// Write a saved state for a later `--read-saved-state` to this file after checking.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["check_all", "fail_fast"])]
+    write_saved_state: Option<PathBuf>,
 }
 
+impl BehaviorArgs {
+    fn module_filter(&self) -> anyhow::Result<ModuleFilter> {
+        ModuleFilter::new(&self.include_modules, &self.exclude_modules)
+    }
+}
+
+/// `context_lines` is the number of source lines to show around each error in full text output.
 fn write_errors_to_file(
     format: OutputFormat,
     path: &Path,
     relative_to: &Path,
     errors: &[Error],
+    context_lines: u32,
 ) -> anyhow::Result<()> {
     match format {
-        OutputFormat::MinText => write_error_text_to_file(path, relative_to, errors, false),
-        OutputFormat::FullText => write_error_text_to_file(path, relative_to, errors, true),
+        OutputFormat::MinText => write_error_text_to_file(path, relative_to, errors, false, 0),
+        OutputFormat::FullText => {
+            write_error_text_to_file(path, relative_to, errors, true, context_lines)
+        }
         OutputFormat::FullTextWithGithub => {
-            write_error_full_text_with_github_to_file(path, relative_to, errors)
+            write_error_full_text_with_github_to_file(path, relative_to, errors, context_lines)
         }
         OutputFormat::Json => write_error_json_to_file(path, relative_to, errors),
         OutputFormat::Github => write_error_github_to_file(path, errors),
         OutputFormat::JunitXml => write_error_junit_xml_to_file(path, relative_to, errors),
         OutputFormat::CodeClimate => write_error_codeclimate_to_file(path, relative_to, errors),
+        OutputFormat::Sarif => write_error_sarif_to_file(path, relative_to, errors),
         OutputFormat::OmitErrors => Ok(()),
     }
 }
@@ -449,17 +575,21 @@ pub(crate) fn write_errors_to_console(
     format: OutputFormat,
     relative_to: &Path,
     errors: &[Error],
+    context_lines: u32,
 ) -> anyhow::Result<()> {
     match format {
-        OutputFormat::MinText => write_error_text_to_console(relative_to, errors, false),
-        OutputFormat::FullText => write_error_text_to_console(relative_to, errors, true),
+        OutputFormat::MinText => write_error_text_to_console(relative_to, errors, false, 0),
+        OutputFormat::FullText => {
+            write_error_text_to_console(relative_to, errors, true, context_lines)
+        }
         OutputFormat::FullTextWithGithub => {
-            write_error_full_text_with_github_to_console(relative_to, errors)
+            write_error_full_text_with_github_to_console(relative_to, errors, context_lines)
         }
         OutputFormat::Json => write_error_json_to_console(relative_to, errors),
         OutputFormat::Github => write_error_github_to_console(errors),
         OutputFormat::JunitXml => write_error_junit_xml_to_console(relative_to, errors),
         OutputFormat::CodeClimate => write_error_codeclimate_to_console(relative_to, errors),
+        OutputFormat::Sarif => write_error_sarif_to_console(relative_to, errors),
         OutputFormat::OmitErrors => Ok(()),
     }
 }
@@ -469,8 +599,10 @@ fn write_error_text_to_file(
     relative_to: &Path,
     errors: &[Error],
     verbose: bool,
+    context_lines: u32,
 ) -> anyhow::Result<()> {
-    let mut renderer = ErrorRenderer::plain(BufWriter::new(File::create(path)?));
+    let mut renderer =
+        ErrorRenderer::plain(BufWriter::new(File::create(path)?)).with_context_lines(context_lines);
     for e in errors {
         renderer.write(e, relative_to, verbose)?;
     }
@@ -482,10 +614,12 @@ fn write_error_text_to_console(
     relative_to: &Path,
     errors: &[Error],
     verbose: bool,
+    context_lines: u32,
 ) -> anyhow::Result<()> {
     let stdout = stdout();
     let color_choice = stdout.current_choice();
-    let mut renderer = ErrorRenderer::new(BufWriter::new(stdout.lock()), color_choice);
+    let mut renderer = ErrorRenderer::new(BufWriter::new(stdout.lock()), color_choice)
+        .with_context_lines(context_lines);
     for error in errors {
         renderer.write(error, relative_to, verbose)?;
         renderer.flush()?;
@@ -578,10 +712,12 @@ fn write_error_full_text_with_github(
     color_choice: ColorChoice,
     relative_to: &Path,
     errors: &[Error],
+    context_lines: u32,
 ) -> anyhow::Result<()> {
     let mut writer = BufWriter::new(writer);
     {
-        let mut renderer = ErrorRenderer::new(&mut writer, color_choice);
+        let mut renderer =
+            ErrorRenderer::new(&mut writer, color_choice).with_context_lines(context_lines);
         for error in errors {
             renderer.write(error, relative_to, true)?;
         }
@@ -596,17 +732,31 @@ fn write_error_full_text_with_github_to_file(
     path: &Path,
     relative_to: &Path,
     errors: &[Error],
+    context_lines: u32,
 ) -> anyhow::Result<()> {
-    write_error_full_text_with_github(File::create(path)?, ColorChoice::Never, relative_to, errors)
+    write_error_full_text_with_github(
+        File::create(path)?,
+        ColorChoice::Never,
+        relative_to,
+        errors,
+        context_lines,
+    )
 }
 
 fn write_error_full_text_with_github_to_console(
     relative_to: &Path,
     errors: &[Error],
+    context_lines: u32,
 ) -> anyhow::Result<()> {
     let stdout = stdout();
     let color_choice = stdout.current_choice();
-    write_error_full_text_with_github(stdout.lock(), color_choice, relative_to, errors)
+    write_error_full_text_with_github(
+        stdout.lock(),
+        color_choice,
+        relative_to,
+        errors,
+        context_lines,
+    )
 }
 
 /// True for characters allowed by the XML 1.0 `Char` production. Everything else
@@ -806,17 +956,58 @@ fn write_error_codeclimate_to_console(relative_to: &Path, errors: &[Error]) -> a
     buffered_write_error_codeclimate(stdout(), relative_to, errors)
 }
 
+fn write_error_sarif(
+    writer: &mut impl Write,
+    relative_to: &Path,
+    errors: &[Error],
+) -> anyhow::Result<()> {
+    let log = SarifLog::from_errors(relative_to, errors);
+    serde_json::to_writer_pretty(writer, &log)?;
+    Ok(())
+}
+
+fn buffered_write_error_sarif(
+    writer: impl Write,
+    relative_to: &Path,
+    errors: &[Error],
+) -> anyhow::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    write_error_sarif(&mut writer, relative_to, errors)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_error_sarif_to_file(
+    path: &Path,
+    relative_to: &Path,
+    errors: &[Error],
+) -> anyhow::Result<()> {
+    fn f(path: &Path, relative_to: &Path, errors: &[Error]) -> anyhow::Result<()> {
+        let file = File::create(path)?;
+        buffered_write_error_sarif(file, relative_to, errors)
+    }
+    f(path, relative_to, errors)
+        .with_context(|| format!("while writing SARIF log to `{}`", path.display()))
+}
+
+fn write_error_sarif_to_console(relative_to: &Path, errors: &[Error]) -> anyhow::Result<()> {
+    buffered_write_error_sarif(stdout(), relative_to, errors)
+}
+
 /// A data structure to facilitate the creation of handles for all the files we want to check.
 pub struct Handles {
     /// A mapping from a file to all other information needed to create a `Handle`.
     /// The value type is basically everything else in `Handle` except for the file path.
     path_data: HashSet<ModulePath>,
+    /// Which modules to keep, once the module names of the files are known.
+    module_filter: ModuleFilter,
 }
 
 impl Handles {
     pub fn new(files: impl IntoIterator<Item = PathBuf>) -> Self {
         let mut handles = Self {
             path_data: HashSet::new(),
+            module_filter: ModuleFilter::default(),
         };
         for file in files {
             handles.path_data.insert(ModulePath::filesystem(file));
@@ -824,6 +1015,11 @@ impl Handles {
         handles
     }
 
+    pub fn with_module_filter(mut self, module_filter: ModuleFilter) -> Self {
+        self.module_filter = module_filter;
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         self.path_data.is_empty()
     }
@@ -850,6 +1046,7 @@ impl Handles {
         let result = configs
             .iter()
             .flat_map(|(c, files)| files.iter().map(|p| c.handle_from_module_path(p.dupe())))
+            .filter(|h| self.module_filter.allows(h.module()))
             .collect();
         let reloaded_configs = configs
             .into_iter()
@@ -985,6 +1182,38 @@ fn decide_upsell(
     }
 }
 
+/// The Python source files reached by following imports from those of `handles` that have
+/// `follow-imports = "normal"`, whose errors should also be reported, each paired with the
+/// checked file it was reached from. Stubs, including the bundled ones, are never reported.
+fn followed_dependencies(handles: &[Handle], transaction: &Transaction) -> Vec<(Handle, Handle)> {
+    let checked: HashSet<&Handle> = handles.iter().collect();
+    let mut seen: HashSet<Handle> = HashSet::new();
+    let mut work_list: Vec<(Handle, Handle)> = handles
+        .iter()
+        .filter(|h| {
+            transaction.get_config(h).is_some_and(|config| {
+                config.follow_imports(Some(h.path().as_path())) == FollowImports::Normal
+            })
+        })
+        .map(|h| (h.dupe(), h.dupe()))
+        .collect();
+    let mut dependencies = Vec::new();
+    while let Some((handle, importer)) = work_list.pop() {
+        for dep in transaction.get_deps(&handle) {
+            if checked.contains(&dep) || !seen.insert(dep.dupe()) {
+                continue;
+            }
+            if matches!(dep.path().details(), ModulePathDetails::FileSystem(_))
+                && !dep.path().is_interface()
+            {
+                dependencies.push((dep.dupe(), importer.dupe()));
+            }
+            work_list.push((dep, importer.dupe()));
+        }
+    }
+    dependencies
+}
+
 /// Write the "no pyrefly.toml found" upsell for a single
 /// `SynthesizedPresetReason`. Pure function of the reason — trivial to
 /// unit-test against a `Vec<u8>` without spinning up a real check run.
@@ -1043,7 +1272,8 @@ impl CheckArgs {
         let list_files_start = Instant::now();
         let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
         timings.list_files = list_files_start.elapsed();
-        let handles = Handles::new(expanded_file_list);
+        let handles =
+            Handles::new(expanded_file_list).with_module_filter(self.behavior.module_filter()?);
         debug!(
             "Checking {} files (listing took {})",
             handles.len(),
@@ -1062,11 +1292,16 @@ impl CheckArgs {
 
         let state = Forgetter::new(State::new(config_finder, thread_count), true);
         let require_levels = self.get_required_levels();
+        debug!(
+            "Computing {:?} for checked files and {:?} for other modules",
+            require_levels.specified, require_levels.default,
+        );
         let mut transaction = Forgetter::new(
             state.as_ref().new_transaction(require_levels.default, None),
             true,
         );
-        let (loaded_handles, _, sourcedb_errors) = handles.all(state.as_ref().config_finder());
+        let (loaded_handles, configs, sourcedb_errors) =
+            handles.all(state.as_ref().config_finder());
 
         // Project-level output settings can come from config when CLI flags are absent.
         if (self.output.baseline.is_none()
@@ -1082,6 +1317,33 @@ impl CheckArgs {
         }
 
         let checked_file_count = loaded_handles.len();
+        let (loaded_handles, saved_state) = if self.behavior.read_saved_state.is_some()
+            || self.behavior.write_saved_state.is_some()
+        {
+            let root = std::env::current_dir().unwrap_or_default();
+            let config_hash = saved_state::config_hash(configs.iter(), &root)?;
+            match &self.behavior.read_saved_state {
+                Some(path) => match SavedState::read(path, &config_hash) {
+                    Ok(saved_state) => {
+                        let (recheck, reuse) =
+                            saved_state.plan(&loaded_handles, &root, transaction.as_ref());
+                        info!(
+                            "Rechecking {} of {} affected by changes since the saved state",
+                            number_thousands(recheck.len()),
+                            count(checked_file_count, "file"),
+                        );
+                        (recheck, Some(reuse))
+                    }
+                    Err(e) => {
+                        warn!("Checking all files, could not use the saved state: {e:#}");
+                        (loaded_handles, Some(SavedStateReuse::none(config_hash)))
+                    }
+                },
+                None => (loaded_handles, Some(SavedStateReuse::none(config_hash))),
+            }
+        } else {
+            (loaded_handles, None)
+        };
         let relative_to = resolve_relative_to(self.output.relative_to.as_ref());
         let (status, errors) = self.run_inner(
             timings,
@@ -1090,6 +1352,7 @@ impl CheckArgs {
             sourcedb_errors,
             require_levels.specified,
             upsell,
+            saved_state.as_ref(),
         )?;
         let check_result = CheckResult::from_errors(&errors, &relative_to, checked_file_count);
         Ok((status, errors, check_result))
@@ -1147,6 +1410,7 @@ impl CheckArgs {
             require_levels.specified,
             // Snippet checks are interactive ad-hoc inputs — never upsell.
             UpsellDecision::Skip,
+            None,
         )?;
         Ok((status, CheckResult::from_errors(&errors, &relative_to, 1)))
     }
@@ -1163,7 +1427,8 @@ impl CheckArgs {
         // - Config search is stable across incremental runs.
         let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
         let require_levels = self.get_required_levels();
-        let mut handles = Handles::new(expanded_file_list);
+        let mut handles =
+            Handles::new(expanded_file_list).with_module_filter(self.behavior.module_filter()?);
         let state = State::new(config_finder, thread_count);
 
         // Track which output settings were explicitly set on the CLI.
@@ -1207,6 +1472,7 @@ impl CheckArgs {
                 sourcedb_errors,
                 require_levels.specified,
                 upsell,
+                None,
             );
             // The upsell is a one-time CTA. Re-nagging on every file
             // save during a long watch session is noise — clamp to
@@ -1236,23 +1502,29 @@ impl CheckArgs {
         let retain = self.output.report_binding_memory.is_some()
             || self.output.debug_info.is_some()
             || self.output.report_trace.is_some()
+            || self.output.dump_types.is_some()
             || self.output.report_glean.is_some();
+        // The lowest level that still lets us produce the requested output for every module.
+        let minimum = if retain {
+            Require::Everything
+        } else if self.behavior.check_all
+            || self.output.report_pysa.is_some()
+            || self.output.report_cinderx.is_some()
+        {
+            Require::Errors
+        } else {
+            Require::Exports
+        };
         RequireLevels {
             specified: if retain {
                 Require::Everything
             } else {
                 Require::Errors
             },
-            default: if retain {
-                Require::Everything
-            } else if self.behavior.check_all
-                || stdlib_search_path().is_some()
-                || self.output.report_pysa.is_some()
-                || self.output.report_cinderx.is_some()
-            {
-                Require::Errors
-            } else {
-                Require::Exports
+            default: match self.behavior.require {
+                Some(require) => require.max(minimum),
+                None if stdlib_search_path().is_some() => Require::Errors.max(minimum),
+                None => minimum,
             },
         }
     }
@@ -1265,6 +1537,7 @@ impl CheckArgs {
         mut sourcedb_errors: Vec<ConfigError>,
         require: Require,
         upsell: UpsellDecision,
+        saved_state: Option<&SavedStateReuse>,
     ) -> anyhow::Result<(CommandExitStatus, Vec<Error>)> {
         let mut memory_trace = MemoryUsageTrace::start(Duration::from_secs_f32(0.1));
 
@@ -1293,23 +1566,65 @@ impl CheckArgs {
             transaction.set_cinderx_reporter(Some(cinderx_reporter));
         }
 
+        let min_severity = self.output.min_severity.unwrap_or(Severity::Error);
         let type_check_start = Instant::now();
-        let demand_tree_subscriber = if self.output.report_demand_tree.is_some() {
+        let (subscriber, demand_tree_subscriber) = if self.output.report_demand_tree.is_some() {
             transaction.set_demand_collector(Some(DemandCollector::new()));
             let sub = TestSubscriber::new();
-            transaction.set_subscriber(Some(Box::new(sub.dupe())));
-            Some(sub)
+            (Some(Box::new(sub.dupe()) as Box<dyn Subscriber>), Some(sub))
         } else {
-            transaction.set_subscriber(self.output.progress_bar_style().make_subscriber());
-            None
+            (self.output.progress_bar_style().make_subscriber(), None)
         };
-        transaction.run(handles, require, None);
+        let fail_fast = self
+            .behavior
+            .fail_fast
+            .then(|| FailFastSubscriber::new(handles, min_severity));
+        if let Some(fail_fast) = &fail_fast {
+            let subscribers = subscriber
+                .into_iter()
+                .chain([Box::new(fail_fast.dupe()) as Box<dyn Subscriber>])
+                .collect();
+            transaction.set_subscriber(Some(Box::new(CompositeSubscriber::new(subscribers))));
+        } else {
+            transaction.set_subscriber(subscriber);
+        }
+        let stopped_early = transaction.run_cancellable(handles, require, None).is_err();
+        let dependencies = if self.behavior.check_all || stopped_early {
+            Vec::new()
+        } else {
+            followed_dependencies(handles, transaction)
+        };
+        if !dependencies.is_empty() {
+            let dependency_handles = dependencies
+                .iter()
+                .map(|(dep, _)| dep.dupe())
+                .collect::<Vec<_>>();
+            transaction.run(&dependency_handles, Require::Errors, None);
+        }
         transaction.set_subscriber(None);
 
-        let loads = if self.behavior.check_all {
+        let loads = if let Some(fail_fast) = &fail_fast
+            && stopped_early
+        {
+            // Only modules that were fully checked have complete errors (and ignore comments
+            // that are known to be unused).
+            let finished = fail_fast.finished();
+            info!(
+                "Stopped checking after finding an error (--fail-fast), {} of {} checked",
+                finished.len(),
+                count(handles.len(), "module"),
+            );
+            transaction.get_errors(&finished)
+        } else if self.behavior.check_all {
             transaction.get_all_errors()
         } else {
-            transaction.get_errors(handles)
+            transaction.get_errors_with_importers(
+                handles.iter().map(|h| (h, None)).chain(
+                    dependencies
+                        .iter()
+                        .map(|(dep, importer)| (dep, Some(importer))),
+                ),
+            )
         };
         timings.type_check = type_check_start.elapsed();
 
@@ -1330,9 +1645,32 @@ impl CheckArgs {
         );
         let output_format = self.output.output_format();
 
-        let collected = loads.collect_errors();
+        let mut collected = loads.collect_errors();
         // Pass pre-collected errors to avoid redundant error collection.
-        let unused_ignore_errors = loads.collect_unused_ignore_errors_for_display(&collected);
+        let mut unused_ignore_errors = loads.collect_unused_ignore_errors_for_display(&collected);
+        if let Some(saved_state) = saved_state {
+            let root = std::env::current_dir().unwrap_or_default();
+            let checked: Vec<Handle> = handles.iter().chain(&dependencies).cloned().collect();
+            saved_state.extend_errors(&checked, &root, &mut collected, &mut unused_ignore_errors);
+            if let Some(path) = &self.behavior.write_saved_state {
+                SavedState::new(
+                    transaction,
+                    &checked,
+                    &[
+                        (ErrorCategory::Ordinary, collected.ordinary.as_slice()),
+                        (ErrorCategory::Directive, collected.directives.as_slice()),
+                        (ErrorCategory::Suppressed, collected.suppressed.as_slice()),
+                        (
+                            ErrorCategory::UnusedIgnore,
+                            unused_ignore_errors.ordinary.as_slice(),
+                        ),
+                    ],
+                    saved_state,
+                    &root,
+                )
+                .write(path)?;
+            }
+        }
         let errors = loads.apply_baseline(
             collected,
             self.output.baseline.as_deref(),
@@ -1376,7 +1714,6 @@ impl CheckArgs {
         // the user's severity threshold: a finding the user asked to hide
         // via `--min-severity` should not get a suppression comment written
         // into source.
-        let min_severity = self.output.min_severity.unwrap_or(Severity::Error);
         let (ordinary_errors, hidden_errors): (Vec<_>, Vec<_>) = ordinary_errors
             .into_iter()
             .partition(|e| e.severity() >= min_severity);
@@ -1398,6 +1735,35 @@ impl CheckArgs {
             let unused_errors = loads.collect_unused_ignore_errors(&collected);
             suppress::remove_unused_ignores(unused_errors);
         }
+        if self.behavior.fix {
+            let outcome = fix::apply_fixes(&ordinary_errors, !self.behavior.diff);
+            // The diff goes to stderr, so it doesn't mix with errors printed in a
+            // machine-readable `--output-format`.
+            if self.behavior.diff {
+                eprint!("{}", outcome.diff);
+            }
+            let fixes = |n: usize| format!("{n} {}", if n == 1 { "fix" } else { "fixes" });
+            info!(
+                "{} {} in {}",
+                if self.behavior.diff {
+                    "Would apply"
+                } else {
+                    "Applied"
+                },
+                fixes(outcome.applied),
+                count(outcome.files, "file"),
+            );
+            if outcome.skipped > 0 {
+                info!(
+                    "Skipped {} that overlapped an earlier fix; run again to apply them",
+                    fixes(outcome.skipped)
+                );
+            } else if outcome.applied == 0 {
+                info!(
+                    "Error kinds with fixes, such as `unused-ignore`, are off by default; enable them (e.g. `--warn unused-ignore`) to fix them"
+                );
+            }
+        }
 
         // We update the baseline file if requested, after reporting any new
         // errors using the old baseline. Directives are structurally excluded
@@ -1428,24 +1794,37 @@ impl CheckArgs {
         // (e.g. reveal_type) do not contribute to the error count.
         let ordinary_errors_count = config_errors_count + ordinary_errors.len();
 
-        // Merge directives into the display list, re-sorting by module
-        // name, path, and source range so output preserves file/line
-        // interleaving across modules.
+        // Merge directives into the display list, re-sorting so that by
+        // default output preserves file/line interleaving across modules.
         let mut output_errors = ordinary_errors;
         output_errors.extend(directives);
-        output_errors.sort_by_cached_key(|e| {
-            (
-                e.module().name(),
-                e.path().dupe(),
-                e.range().start(),
-                e.range().end(),
-            )
-        });
+        sort_errors(&mut output_errors, self.output.sort_errors_by);
 
+        let shown_errors = match self.output.max_errors {
+            Some(max) if output_errors.len() > max => {
+                info!(
+                    "{} not shown (--max-errors={max})",
+                    count(output_errors.len() - max, "more error"),
+                );
+                &output_errors[..max]
+            }
+            _ => &output_errors[..],
+        };
         if let Some(path) = &self.output.output {
-            write_errors_to_file(output_format, path, relative_to.as_path(), &output_errors)?;
+            write_errors_to_file(
+                output_format,
+                path,
+                relative_to.as_path(),
+                shown_errors,
+                self.output.show_error_context,
+            )?;
         } else {
-            write_errors_to_console(output_format, relative_to.as_path(), &output_errors)?;
+            write_errors_to_console(
+                output_format,
+                relative_to.as_path(),
+                shown_errors,
+                self.output.show_error_context,
+            )?;
         }
         memory_trace.stop();
         if let Some(limit) = self.output.count_errors {
@@ -1565,6 +1944,12 @@ impl CheckArgs {
         if let Some(path) = &self.output.report_trace {
             fs_anyhow::write(path, report::trace::trace(transaction))?;
         }
+        if let Some(path) = &self.output.dump_types {
+            fs_anyhow::write(
+                path,
+                report::type_database::type_database(transaction, handles)?,
+            )?;
+        }
         if let Some(path) = &self.output.dependency_graph {
             fs_anyhow::write(
                 path,
@@ -1682,7 +2067,7 @@ mod tests {
     fn full_text_with_github_output_format_writes_both() {
         let errors = vec![sample_error("bad".into())];
         let mut buf = Vec::new();
-        write_error_full_text_with_github(&mut buf, ColorChoice::Never, Path::new("/"), &errors)
+        write_error_full_text_with_github(&mut buf, ColorChoice::Never, Path::new("/"), &errors, 0)
             .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("ERROR bad [bad-assignment]"));
@@ -1859,4 +2244,78 @@ mod tests {
         let s = upsell_string(SynthesizedPresetReason::UserOverride);
         assert!(s.is_empty(), "expected no upsell, got {s:?}");
     }
+
+    fn error_at(path: &str, start: u32, kind: ErrorKind, msg: &str) -> Error {
+        let module = Module::new(
+            ModuleName::from_str(path.trim_end_matches(".py")),
+            ModulePath::filesystem(PathBuf::from(path)),
+            Arc::new("x = 1\ny = 2\n".to_owned()),
+        );
+        Error::new(
+            module,
+            TextRange::new(TextSize::from(start), TextSize::from(start + 1)),
+            msg.to_owned(),
+            Vec::new(),
+            kind,
+        )
+    }
+
+    fn describe(errors: &[Error]) -> Vec<String> {
+        errors
+            .iter()
+            .map(|e| format!("{} {} {}", e.path(), e.range().start().to_u32(), e.msg()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let errors = vec![
+            error_at("b.py", 0, ErrorKind::BadReturn, "b1"),
+            error_at("a.py", 6, ErrorKind::BadAssignment, "a2"),
+            error_at("a.py", 0, ErrorKind::BadReturn, "a1 second"),
+            error_at("a.py", 0, ErrorKind::BadReturn, "a1 first"),
+        ];
+
+        // Errors at the same location are ordered by message, whatever order they arrive in.
+        let mut by_path = errors.clone();
+        sort_errors(&mut by_path, SortErrorsBy::Path);
+        let mut reversed = errors.iter().rev().cloned().collect::<Vec<_>>();
+        sort_errors(&mut reversed, SortErrorsBy::Path);
+        assert_eq!(describe(&by_path), describe(&reversed));
+        assert_eq!(
+            describe(&by_path),
+            [
+                "a.py 0 a1 first",
+                "a.py 0 a1 second",
+                "a.py 6 a2",
+                "b.py 0 b1"
+            ]
+        );
+
+        let mut by_kind = errors.clone();
+        sort_errors(&mut by_kind, SortErrorsBy::Kind);
+        assert_eq!(
+            describe(&by_kind),
+            [
+                "a.py 6 a2",
+                "a.py 0 a1 first",
+                "a.py 0 a1 second",
+                "b.py 0 b1"
+            ]
+        );
+
+        let mut by_count = errors.clone();
+        by_count.push(error_at("c.py", 0, ErrorKind::BadAssignment, "c1"));
+        sort_errors(&mut by_count, SortErrorsBy::Count);
+        assert_eq!(
+            describe(&by_count),
+            [
+                "a.py 0 a1 first",
+                "a.py 0 a1 second",
+                "b.py 0 b1",
+                "a.py 6 a2",
+                "c.py 0 c1"
+            ]
+        );
+    }
 }
@@ -78,6 +78,7 @@ impl CheckArgs {
             self.public_only,
             Some(self.strict),
             thread_count,
+            |_, _| {},
         )?;
 
         let total = module_reports
@@ -107,7 +108,7 @@ impl CheckArgs {
             Ok(CommandExitStatus::Success)
         } else {
             let root = std::env::current_dir().unwrap_or_default();
-            write_errors_to_console(self.output_format.unwrap_or_default(), &root, &errors)?;
+            write_errors_to_console(self.output_format.unwrap_or_default(), &root, &errors, 0)?;
             eprintln!(
                 "{} {summary} is below the {:.2}% threshold",
                 Severity::Error.painted(),
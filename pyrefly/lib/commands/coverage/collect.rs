@@ -1628,6 +1628,8 @@ fn collect_untyped_errors(
     }
 }
 
+/// `inspect` is called with the checked targets before the transaction is dropped, so a
+/// caller can read results that the module reports don't carry.
 pub fn collect_module_reports(
     files_to_check: Box<dyn Includes>,
     config_finder: ConfigFinder,
@@ -1636,6 +1638,7 @@ pub fn collect_module_reports(
     public_only: bool,
     untyped_strict: Option<bool>,
     thread_count: ThreadCount,
+    inspect: impl FnOnce(&Transaction, &[Handle]),
 ) -> anyhow::Result<(Vec<ModuleReport>, Vec<Error>)> {
     let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
     let state = State::new(config_finder, thread_count);
@@ -1782,6 +1785,8 @@ pub fn collect_module_reports(
         fqns
     });
 
+    inspect(transaction, &targets);
+
     let mut module_reports: Vec<ModuleReport> = Vec::new();
     let mut errors: Vec<Error> = Vec::new();
     let mut collected = collected.lock();
@@ -3109,6 +3114,7 @@ def g(x: int) -> int:
                 public_only,
                 None,
                 TEST_THREAD_COUNT,
+                |_, _| {},
             )
             .unwrap();
             let mut private: Vec<_> = reports
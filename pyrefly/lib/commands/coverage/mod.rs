@@ -8,6 +8,7 @@
 mod check;
 mod collect;
 pub(crate) mod report;
+mod trend;
 mod types;
 
 use clap::Subcommand;
@@ -16,6 +17,7 @@ use pyrefly_util::thread_pool::ThreadCount;
 use crate::commands::config_finder::ConfigConfigurerWrapper;
 use crate::commands::coverage::check::CheckArgs;
 use crate::commands::coverage::report::ReportArgs;
+use crate::commands::coverage::trend::TrendArgs;
 use crate::commands::util::CommandExitStatus;
 
 /// Subcommands of `pyrefly coverage`.
@@ -26,6 +28,9 @@ pub enum CoverageCommand {
     Report(ReportArgs),
     /// Check that type coverage from pyrefly type checking results meets a minimum threshold.
     Check(CheckArgs),
+    /// Summarize per-directory error counts, ignore counts and type coverage as JSON, optionally
+    /// compared to a previous summary.
+    Trend(TrendArgs),
 }
 
 impl CoverageCommand {
@@ -37,6 +42,7 @@ impl CoverageCommand {
         match self {
             CoverageCommand::Report(args) => args.run(config_configurer_wrapper, thread_count),
             CoverageCommand::Check(args) => args.run(config_configurer_wrapper, thread_count),
+            CoverageCommand::Trend(args) => args.run(config_configurer_wrapper, thread_count),
         }
     }
 }
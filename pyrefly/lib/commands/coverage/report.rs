@@ -73,6 +73,7 @@ impl ReportArgs {
             self.public_only,
             None,
             thread_count,
+            |_, _| {},
         )?;
         let full_report = FullReport {
             schema_version: format!("{}.{}", REPORT_SCHEMA_VERSION.0, REPORT_SCHEMA_VERSION.1),
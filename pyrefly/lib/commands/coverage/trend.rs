@@ -0,0 +1,401 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use pyrefly_config::args::EnvironmentArgs;
+use pyrefly_config::error_kind::Severity;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::thread_pool::ThreadCount;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::coverage::collect::collect_module_reports;
+use crate::commands::coverage::types::ModuleReport;
+use crate::commands::coverage::types::SlotCounts;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::config::config::ConfigScope;
+
+/// `(major, minor)` version for the trend JSON schema.
+const TREND_SCHEMA_VERSION: (u32, u32) = (0, 1);
+
+/// Summarize error counts, ignore counts and type coverage per directory.
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Clone, Parser)]
+pub struct TrendArgs {
+    /// Which files to check.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    #[command(flatten)]
+    config_override: EnvironmentArgs,
+
+    /// When enabled, `.py` files are skipped if a corresponding `.pyi`
+    /// file is also present in the set of files to check.
+    #[clap(long, default_value_t = true, action = clap::ArgAction::Set)]
+    prefer_stubs: bool,
+
+    /// Group modules by the first N components of their directory, relative to
+    /// the current directory. By default modules are grouped by their full directory.
+    #[clap(long, value_name = "N")]
+    depth: Option<usize>,
+
+    /// A report previously written by `pyrefly coverage trend`. Each directory
+    /// then also reports how its numbers changed since that report.
+    #[clap(long, value_name = "FILE")]
+    compare_to: Option<PathBuf>,
+}
+
+/// Counts that are summed across the modules of a directory.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct TrendCounts {
+    n_modules: usize,
+    n_errors: usize,
+    n_type_ignores: usize,
+    #[serde(flatten)]
+    slots: SlotCounts,
+}
+
+impl TrendCounts {
+    fn of_module(report: &ModuleReport, n_errors: usize) -> Self {
+        TrendCounts {
+            n_modules: 1,
+            n_errors,
+            n_type_ignores: report.symbols.n_type_ignores,
+            slots: report.slots,
+        }
+    }
+
+    fn merge(self, other: TrendCounts) -> TrendCounts {
+        TrendCounts {
+            n_modules: self.n_modules + other.n_modules,
+            n_errors: self.n_errors + other.n_errors,
+            n_type_ignores: self.n_type_ignores + other.n_type_ignores,
+            slots: self.slots.merge(other.slots),
+        }
+    }
+}
+
+/// The change in a [`TrendEntry`] since a previous report.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct TrendDelta {
+    n_modules: i64,
+    n_errors: i64,
+    n_type_ignores: i64,
+    n_typable: i64,
+    n_typed: i64,
+    n_any: i64,
+    n_untyped: i64,
+    coverage: f64,
+    strict_coverage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrendEntry {
+    #[serde(flatten)]
+    counts: TrendCounts,
+    coverage: f64,
+    strict_coverage: f64,
+    /// Only present with `--compare-to`, and only if the previous report has this entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delta: Option<TrendDelta>,
+}
+
+impl TrendEntry {
+    fn new(counts: TrendCounts) -> Self {
+        TrendEntry {
+            counts,
+            coverage: counts.slots.coverage(),
+            strict_coverage: counts.slots.strict_coverage(),
+            delta: None,
+        }
+    }
+
+    fn delta_from(&self, previous: &TrendEntry) -> TrendDelta {
+        let diff = |now: usize, before: usize| now as i64 - before as i64;
+        let (now, before) = (&self.counts, &previous.counts);
+        TrendDelta {
+            n_modules: diff(now.n_modules, before.n_modules),
+            n_errors: diff(now.n_errors, before.n_errors),
+            n_type_ignores: diff(now.n_type_ignores, before.n_type_ignores),
+            n_typable: diff(now.slots.n_typable, before.slots.n_typable),
+            n_typed: diff(now.slots.n_typed, before.slots.n_typed),
+            n_any: diff(now.slots.n_any, before.slots.n_any),
+            n_untyped: diff(now.slots.n_untyped, before.slots.n_untyped),
+            coverage: self.coverage - previous.coverage,
+            strict_coverage: self.strict_coverage - previous.strict_coverage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DirectoryTrend {
+    directory: String,
+    #[serde(flatten)]
+    entry: TrendEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TrendReport {
+    /// `"{major}.{minor}"` version for this report format.
+    schema_version: String,
+    /// Sorted by directory.
+    directories: Vec<DirectoryTrend>,
+    summary: TrendEntry,
+    /// With `--compare-to`, directories missing from the previous report.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    added_directories: Vec<String>,
+    /// With `--compare-to`, directories missing from this report.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    removed_directories: Vec<String>,
+}
+
+impl TrendReport {
+    fn new(modules: impl IntoIterator<Item = (String, TrendCounts)>) -> Self {
+        let mut by_directory: BTreeMap<String, TrendCounts> = BTreeMap::new();
+        let mut total = TrendCounts::default();
+        for (directory, counts) in modules {
+            let entry = by_directory.entry(directory).or_default();
+            *entry = entry.merge(counts);
+            total = total.merge(counts);
+        }
+        TrendReport {
+            schema_version: format!("{}.{}", TREND_SCHEMA_VERSION.0, TREND_SCHEMA_VERSION.1),
+            directories: by_directory
+                .into_iter()
+                .map(|(directory, counts)| DirectoryTrend {
+                    directory,
+                    entry: TrendEntry::new(counts),
+                })
+                .collect(),
+            summary: TrendEntry::new(total),
+            added_directories: Vec::new(),
+            removed_directories: Vec::new(),
+        }
+    }
+
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let report: Self = serde_json::from_str(&fs_anyhow::read_to_string(path)?)
+            .with_context(|| format!("failed to parse trend report `{}`", path.display()))?;
+        let major = report.schema_version.split('.').next();
+        if major != Some(TREND_SCHEMA_VERSION.0.to_string().as_str()) {
+            anyhow::bail!(
+                "trend report `{}` has schema version {}, expected {}.x",
+                path.display(),
+                report.schema_version,
+                TREND_SCHEMA_VERSION.0
+            );
+        }
+        Ok(report)
+    }
+
+    /// Fill in the deltas against `previous`.
+    fn compare_to(&mut self, previous: &TrendReport) {
+        let before: BTreeMap<&str, &TrendEntry> = previous
+            .directories
+            .iter()
+            .map(|d| (d.directory.as_str(), &d.entry))
+            .collect();
+        for directory in &mut self.directories {
+            match before.get(directory.directory.as_str()) {
+                Some(entry) => directory.entry.delta = Some(directory.entry.delta_from(entry)),
+                None => self.added_directories.push(directory.directory.clone()),
+            }
+        }
+        self.removed_directories = previous
+            .directories
+            .iter()
+            .filter(|d| {
+                self.directories
+                    .binary_search_by(|x| x.directory.cmp(&d.directory))
+                    .is_err()
+            })
+            .map(|d| d.directory.clone())
+            .collect();
+        self.summary.delta = Some(self.summary.delta_from(&previous.summary));
+    }
+}
+
+/// The directory a module at `path` is grouped under: its parent directory relative
+/// to `root`, truncated to `depth` components. Modules directly in `root` are grouped
+/// under `.`.
+fn directory_of(path: &Path, root: &Path, depth: Option<usize>) -> String {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let relative = parent.strip_prefix(root).unwrap_or(parent);
+    let directory: PathBuf = relative
+        .components()
+        .take(depth.unwrap_or(usize::MAX))
+        .collect();
+    if directory.as_os_str().is_empty() {
+        ".".to_owned()
+    } else {
+        directory.display().to_string()
+    }
+}
+
+impl TrendArgs {
+    pub fn run(
+        self,
+        wrapper: Option<ConfigConfigurerWrapper>,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        // Read the previous report up front, so a bad path fails before the slow part.
+        let previous = self
+            .compare_to
+            .as_deref()
+            .map(TrendReport::load)
+            .transpose()?;
+
+        let (files_to_check, config_finder, _) = self.files.resolve_scoped(
+            self.config_override.into(),
+            wrapper,
+            ConfigScope::Coverage,
+        )?;
+        // Error-severity type errors per module path.
+        let mut n_errors: HashMap<String, usize> = HashMap::new();
+        let (module_reports, _) = collect_module_reports(
+            files_to_check,
+            config_finder,
+            self.prefer_stubs,
+            None,
+            false,
+            None,
+            thread_count,
+            |transaction, handles| {
+                for handle in handles {
+                    let count = transaction
+                        .get_errors([handle])
+                        .collect_errors()
+                        .ordinary
+                        .iter()
+                        .filter(|e| e.severity() >= Severity::Error)
+                        .count();
+                    n_errors.insert(handle.path().as_path().display().to_string(), count);
+                }
+            },
+        )?;
+        let root = std::env::current_dir().unwrap_or_default();
+        let mut report = TrendReport::new(module_reports.iter().map(|m| {
+            (
+                directory_of(Path::new(&m.path), &root, self.depth),
+                TrendCounts::of_module(m, n_errors.get(&m.path).copied().unwrap_or_default()),
+            )
+        }));
+        if let Some(previous) = &previous {
+            report.compare_to(previous);
+        }
+        println!("{}", serde_json::to_string_pretty(&report)?);
+
+        Ok(CommandExitStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(n_errors: usize, n_type_ignores: usize, n_typed: usize, n_any: usize) -> TrendCounts {
+        TrendCounts {
+            n_modules: 1,
+            n_errors,
+            n_type_ignores,
+            slots: SlotCounts {
+                n_typable: n_typed + n_any,
+                n_typed,
+                n_any,
+                n_untyped: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_directory_of() {
+        let root = Path::new("/repo");
+        let path = Path::new("/repo/pkg/sub/mod.py");
+        assert_eq!(directory_of(path, root, None), "pkg/sub");
+        assert_eq!(directory_of(path, root, Some(1)), "pkg");
+        assert_eq!(directory_of(path, root, Some(0)), ".");
+        assert_eq!(directory_of(Path::new("/repo/main.py"), root, None), ".");
+        assert_eq!(
+            directory_of(Path::new("/elsewhere/mod.py"), root, None),
+            "/elsewhere"
+        );
+    }
+
+    #[test]
+    fn test_trend_groups_by_directory() {
+        let report = TrendReport::new([
+            ("b".to_owned(), counts(1, 0, 2, 2)),
+            ("a".to_owned(), counts(0, 1, 1, 0)),
+            ("b".to_owned(), counts(2, 3, 4, 0)),
+        ]);
+        let directories: Vec<_> = report
+            .directories
+            .iter()
+            .map(|d| {
+                let c = &d.entry.counts;
+                (
+                    d.directory.as_str(),
+                    c.n_modules,
+                    c.n_errors,
+                    c.n_type_ignores,
+                )
+            })
+            .collect();
+        assert_eq!(directories, vec![("a", 1, 0, 1), ("b", 2, 3, 3)]);
+        assert_eq!(report.directories[1].entry.strict_coverage, 75.0);
+        assert_eq!(report.summary.counts.n_modules, 3);
+        assert_eq!(report.summary.counts.slots.n_any, 2);
+    }
+
+    #[test]
+    fn test_trend_compare_to_previous() {
+        let previous = TrendReport::new([
+            ("a".to_owned(), counts(4, 2, 1, 1)),
+            ("gone".to_owned(), counts(1, 0, 0, 0)),
+        ]);
+        // The previous report is read back from JSON, so round-trip it.
+        let previous: TrendReport =
+            serde_json::from_str(&serde_json::to_string(&previous).unwrap()).unwrap();
+        let mut report = TrendReport::new([
+            ("a".to_owned(), counts(1, 2, 2, 0)),
+            ("new".to_owned(), counts(0, 0, 1, 0)),
+        ]);
+        report.compare_to(&previous);
+        assert_eq!(
+            report.directories[0].entry.delta,
+            Some(TrendDelta {
+                n_modules: 0,
+                n_errors: -3,
+                n_type_ignores: 0,
+                n_typable: 0,
+                n_typed: 1,
+                n_any: -1,
+                n_untyped: 0,
+                coverage: 0.0,
+                strict_coverage: 50.0,
+            })
+        );
+        assert_eq!(report.directories[1].entry.delta, None);
+        assert_eq!(report.added_directories, vec!["new"]);
+        assert_eq!(report.removed_directories, vec!["gone"]);
+        assert_eq!(report.summary.delta.unwrap().n_errors, -4);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["directories"][0]["delta"]["n_errors"], -3);
+        assert_eq!(json["directories"][0]["n_any"], 0);
+        assert!(json["directories"][1].get("delta").is_none());
+    }
+}
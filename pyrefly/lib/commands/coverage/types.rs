@@ -8,6 +8,7 @@
 use pyrefly_python::ignore::Tool;
 use pyrefly_types::callable::PropertyRole;
 use ruff_text_size::TextRange;
+use serde::Deserialize;
 use serde::Serialize;
 
 /// Slot-level annotation counts for a symbol.
@@ -17,7 +18,7 @@ use serde::Serialize;
 /// three buckets: typed (concrete annotation with no `Any`), any (annotation
 /// that resolves to or contains `Any`), or untyped (no annotation at all).
 /// `n_typable` is always the sum of the other three.
-#[derive(Debug, Serialize, Default, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy)]
 pub struct SlotCounts {
     /// Total number of annotation sites.
     pub n_typable: usize,
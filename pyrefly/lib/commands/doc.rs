@@ -0,0 +1,112 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::thread_pool::ThreadCount;
+
+use crate::commands::check::Handles;
+use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::stubgen::doc::emit_markdown;
+use crate::stubgen::doc::extract_module_doc;
+
+#[derive(Clone, Copy, Debug, ValueEnum, Default, PartialEq, Eq)]
+enum DocFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+/// Generate API documentation from the types pyrefly infers.
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct DocArgs {
+    /// Which files to document.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration.
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+
+    /// Format of the generated documentation.
+    #[arg(long, value_enum, default_value_t)]
+    output_format: DocFormat,
+
+    /// Write one file per module, named after the module, to this directory.
+    /// By default all documentation is printed to stdout.
+    #[arg(short = 'o', long)]
+    output_dir: Option<PathBuf>,
+}
+
+impl DocArgs {
+    pub fn run(
+        self,
+        wrapper: Option<ConfigConfigurerWrapper>,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let (files_to_check, config_finder, _) =
+            self.files.resolve(self.config_override, wrapper)?;
+
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
+        let state = State::new(config_finder, thread_count);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Everything, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+
+        transaction.run(&handles, Require::Everything, None);
+        let mut docs: Vec<_> = handles
+            .iter()
+            .filter_map(|handle| extract_module_doc(transaction, handle))
+            .collect();
+        docs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        match &self.output_dir {
+            Some(output_dir) => {
+                fs_anyhow::create_dir_all(output_dir)?;
+                for doc in &docs {
+                    let (text, extension) = match self.output_format {
+                        DocFormat::Markdown => (emit_markdown(doc), "md"),
+                        DocFormat::Json => (serde_json::to_string_pretty(doc)?, "json"),
+                    };
+                    fs_anyhow::write(&output_dir.join(format!("{}.{extension}", doc.name)), text)?;
+                }
+            }
+            None => match self.output_format {
+                DocFormat::Markdown => {
+                    let pages: Vec<String> = docs.iter().map(emit_markdown).collect();
+                    print!("{}", pages.join("\n"));
+                }
+                DocFormat::Json => println!("{}", serde_json::to_string_pretty(&docs)?),
+            },
+        }
+
+        Ok(CommandExitStatus::Success)
+    }
+}
@@ -34,6 +34,7 @@ use crate::state::state::State;
 use crate::types::class::Class;
 use crate::types::display::TypeDisplayContext;
 use crate::types::heap::TypeHeap;
+use crate::types::simplify::UnionLimits;
 use crate::types::simplify::unions_with_literals;
 use crate::types::stdlib::Stdlib;
 use crate::types::types::Type;
@@ -272,7 +273,13 @@ fn hint_to_string(
     let hint = hint.promote_implicit_literals(stdlib);
     let hint = hint.explicit_any().clean_var();
     let hint = match hint {
-        Type::Union(u) => unions_with_literals(u.members, stdlib, enum_members, heap),
+        Type::Union(u) => unions_with_literals(
+            u.members,
+            stdlib,
+            enum_members,
+            UnionLimits::default(),
+            heap,
+        ),
         _ => hint,
     };
     let mut ctx = TypeDisplayContext::new(&[&hint]);
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::fs;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -17,6 +18,7 @@ use pyrefly_config::migration::run::config_migration;
 use pyrefly_config::pyproject::PyProject;
 use pyrefly_util::absolutize::Absolutize as _;
 use pyrefly_util::fs_anyhow;
+use pyrefly_util::globs::Globs;
 use pyrefly_util::thread_pool::ThreadCount;
 use tracing::error;
 use tracing::info;
@@ -280,10 +282,15 @@ impl InitArgs {
 
         // Generate a basic config with a couple sensible defaults.
         // This prevents us from simply outputting an empty file, and gives the user somewhere to start if they want to customize.
-        let cfg = ConfigFile {
+        let mut cfg = ConfigFile {
             project_includes: ConfigFile::default_project_includes(),
             ..Default::default()
         };
+        if let Some(dir) = dir {
+            let (search_path, extra_search_roots) = detect_source_roots(dir);
+            cfg.search_path_from_file = search_path;
+            cfg.extra_search_roots = Globs::new(extra_search_roots)?;
+        }
 
         // 3. Initialize pyproject.toml configuration in the case that there are no existing Mypy or Pyright configurations but user specified a pyproject.toml
         if InitArgs::check_for_pyproject_file(&path) {
@@ -377,6 +384,81 @@ impl InitArgs {
     }
 }
 
+/// Directories that are never source roots.
+const NON_SOURCE_DIRS: &[&str] = &["node_modules", "build", "dist", "venv", "site-packages"];
+
+fn is_candidate_dir(path: &Path) -> bool {
+    path.is_dir()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                !name.starts_with('.') && !name.starts_with('_') && !NON_SOURCE_DIRS.contains(&name)
+            })
+}
+
+/// Whether `dir` contains Python files, directly or in a package directly inside it.
+fn contains_python(dir: &Path) -> bool {
+    let is_python = |path: &Path| {
+        path.extension()
+            .is_some_and(|ext| ext == "py" || ext == "pyi")
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        let path = entry.path();
+        is_python(&path)
+            || (is_candidate_dir(&path)
+                && fs::read_dir(&path).is_ok_and(|mut children| {
+                    children.any(|child| child.is_ok_and(|child| is_python(&child.path())))
+                }))
+    })
+}
+
+fn sorted_subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| is_candidate_dir(path))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Detect the `src/` layouts of the project at `dir`. A `src/` directory at the top
+/// becomes the search path. `src/` directories of subprojects, such as the packages of a
+/// monorepo, become extra search roots: `lib/src` for a subproject directly in the
+/// project, or `packages/*/src` for subprojects grouped in a directory.
+fn detect_source_roots(dir: &Path) -> (Vec<PathBuf>, Vec<String>) {
+    let is_src = |path: &Path| path.is_dir() && contains_python(path);
+    let search_path = if is_src(&dir.join("src")) {
+        vec![PathBuf::from("src")]
+    } else {
+        Vec::new()
+    };
+    let mut extra_search_roots = Vec::new();
+    for child in sorted_subdirs(dir) {
+        let Some(name) = child.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name == "src" {
+            continue;
+        }
+        if is_src(&child.join("src")) {
+            extra_search_roots.push(format!("{name}/src"));
+        } else if sorted_subdirs(&child)
+            .iter()
+            .any(|grandchild| is_src(&grandchild.join("src")))
+        {
+            extra_search_roots.push(format!("{name}/*/src"));
+        }
+    }
+    (search_path, extra_search_roots)
+}
+
 #[cfg(test)]
 mod test {
     use pyrefly_util::thread_pool::TEST_THREAD_COUNT;
@@ -761,6 +843,46 @@ k = [\"v\"]
         check_file_in(tmp.path(), "pyrefly.toml", &["project-includes"])
     }
 
+    #[test]
+    fn test_detects_src_layouts() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        for dir in [
+            "src/app",
+            "packages/core/src/core",
+            "packages/web/src/web",
+            "tools/src",
+            "docs/src",
+            ".venv/src/dep",
+        ] {
+            fs_anyhow::create_dir_all(&tmp.path().join(dir))?;
+        }
+        for file in [
+            "src/app/__init__.py",
+            "packages/core/src/core/__init__.py",
+            "tools/src/tool.py",
+            "docs/src/index.md",
+            ".venv/src/dep/__init__.py",
+        ] {
+            create_file_in(tmp.path(), file, None)?;
+        }
+        let status = run_init_non_interactive(&tmp)?;
+        assert_success(status);
+        check_file_in(
+            tmp.path(),
+            "pyrefly.toml",
+            &[
+                "search-path = [\"src\"]",
+                "extra-search-roots = [",
+                "\"packages/*/src\"",
+                "\"tools/src\"",
+            ],
+        )?;
+        let raw_cfg = fs_anyhow::read_to_string(&tmp.path().join("pyrefly.toml"))?;
+        assert!(!raw_cfg.contains("docs/src"), "{raw_cfg}");
+        assert!(!raw_cfg.contains(".venv"), "{raw_cfg}");
+        Ok(())
+    }
+
     #[test]
     fn test_non_interactive_with_mypy_config() -> anyhow::Result<()> {
         let tmp = tempfile::tempdir()?;
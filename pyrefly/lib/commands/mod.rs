@@ -12,6 +12,8 @@ pub mod check;
 pub mod config_finder;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod coverage;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod doc;
 pub mod dump_config;
 pub mod files;
 #[cfg(not(target_arch = "wasm32"))]
@@ -20,11 +22,16 @@ pub mod infer;
 pub mod init;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod lsp;
+pub mod saved_state;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod stubgen;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod suppress;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod test_annotations;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod tsp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod util;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod verify_runtime;
@@ -0,0 +1,724 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Saved states for `pyrefly check`: a snapshot of a check, typically at a base commit, that
+//! a later check can start from so it only rechecks the modules affected by local changes.
+//!
+//! A saved state records every module the check loaded from disk, with a hash of its contents,
+//! the modules it imports and where each import resolved to, plus the errors reported for each
+//! checked module. A check that reads a saved state hashes those files again and resolves their
+//! imports again. It rechecks a file only if it, or something it transitively imports, changed,
+//! or if one of those imports now resolves somewhere else (e.g. to a newly added file that
+//! shadows it, or to a newly installed package). The saved errors of every other file are
+//! reported as they are. Types are not saved, so the rechecked files still solve their imports
+//! from source.
+//!
+//! Paths under the current directory are stored relative to it, so a saved state written on
+//! one machine can be read from a checkout in another directory. A saved state is only read by
+//! the pyrefly version that wrote it, and everything is rechecked if the configuration, the
+//! interpreter or the import search path changed since.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::config::ConfigFile;
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_name::ModuleNameWithKind;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_util::arc_id::ArcId;
+use pyrefly_util::fs_anyhow;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use serde::Deserialize;
+use serde::Serialize;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::config::error_kind::Severity;
+use crate::error::collector::CollectedErrors;
+use crate::error::error::Error;
+use crate::error::error::ErrorQuickFix;
+use crate::state::state::Transaction;
+
+/// Bumped whenever the format changes incompatibly.
+const SAVED_STATE_VERSION: u32 = 2;
+
+/// Where an error was reported in [`CollectedErrors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCategory {
+    Ordinary,
+    Directive,
+    Suppressed,
+    UnusedIgnore,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum SavedQuickFix {
+    ReplaceWithEnumMember {
+        replacement: String,
+    },
+    TextEdits {
+        title: String,
+        edits: Vec<(u32, u32, String)>,
+    },
+}
+
+impl SavedQuickFix {
+    fn new(fix: &ErrorQuickFix) -> Self {
+        match fix {
+            ErrorQuickFix::ReplaceWithEnumMember { replacement } => Self::ReplaceWithEnumMember {
+                replacement: replacement.clone(),
+            },
+            ErrorQuickFix::TextEdits { title, edits } => Self::TextEdits {
+                title: title.clone(),
+                edits: edits
+                    .iter()
+                    .map(|(range, text)| {
+                        (range.start().to_u32(), range.end().to_u32(), text.clone())
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn to_quick_fix(&self) -> ErrorQuickFix {
+        match self {
+            Self::ReplaceWithEnumMember { replacement } => ErrorQuickFix::ReplaceWithEnumMember {
+                replacement: replacement.clone(),
+            },
+            Self::TextEdits { title, edits } => ErrorQuickFix::TextEdits {
+                title: title.clone(),
+                edits: edits
+                    .iter()
+                    .map(|(start, end, text)| (text_range(*start, *end), text.clone()))
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SavedError {
+    category: ErrorCategory,
+    start: u32,
+    end: u32,
+    kind: ErrorKind,
+    severity: Severity,
+    header: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<(u32, u32, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    quick_fixes: Vec<SavedQuickFix>,
+}
+
+fn text_range(start: u32, end: u32) -> TextRange {
+    TextRange::new(TextSize::new(start), TextSize::new(end))
+}
+
+impl SavedError {
+    fn new(category: ErrorCategory, error: &Error) -> Self {
+        Self {
+            category,
+            start: error.range().start().to_u32(),
+            end: error.range().end().to_u32(),
+            kind: error.error_kind(),
+            severity: error.severity(),
+            header: error.msg_header().to_owned(),
+            details: error.msg_details().map(str::to_owned),
+            // Annotations in other modules aren't saved, since their ranges could be stale by
+            // the time the saved state is read.
+            annotations: error
+                .secondary_annotations()
+                .iter()
+                .filter(|a| a.module.is_none())
+                .map(|a| {
+                    (
+                        a.range.start().to_u32(),
+                        a.range.end().to_u32(),
+                        a.label.to_string(),
+                    )
+                })
+                .collect(),
+            quick_fixes: error.quick_fixes().iter().map(SavedQuickFix::new).collect(),
+        }
+    }
+
+    fn to_error(&self, module: &Module) -> Error {
+        // The details are stored as rendered, with each line indented.
+        let details = self.details.as_deref().map_or_else(Vec::new, |details| {
+            details
+                .lines()
+                .map(|line| line.strip_prefix("  ").unwrap_or(line).to_owned())
+                .collect()
+        });
+        let mut error = Error::new(
+            module.clone(),
+            text_range(self.start, self.end),
+            self.header.clone(),
+            details,
+            self.kind,
+        )
+        .with_severity(self.severity);
+        for (start, end, label) in &self.annotations {
+            error = error.with_annotation(text_range(*start, *end), label.clone());
+        }
+        for fix in &self.quick_fixes {
+            error = error.with_quick_fix(fix.to_quick_fix());
+        }
+        error
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedModule {
+    /// Relative to the current directory if it is under it.
+    path: PathBuf,
+    name: String,
+    /// Hash of the file contents.
+    hash: String,
+    /// Paths of the modules this module imports, stored like `path`.
+    deps: Vec<PathBuf>,
+    /// The modules this module imports by name, with the path each resolved to, stored like
+    /// `path`, or `None` if it couldn't be found.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    imports: Vec<(String, Option<PathBuf>)>,
+    /// The errors reported in this module, or `None` if it was only loaded as a dependency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<SavedError>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedState {
+    version: u32,
+    pyrefly_version: String,
+    /// See [`config_hash`].
+    config_hash: String,
+    /// Sorted by path.
+    modules: Vec<SavedModule>,
+}
+
+/// What a check can reuse from a saved state, decided by [`SavedState::plan`].
+pub struct SavedStateReuse {
+    /// The hash of the configurations this check uses, see [`config_hash`].
+    config_hash: String,
+    /// The saved state, if there is one and it was written with the same configurations.
+    previous: Option<SavedState>,
+    /// Files whose saved errors are still valid, with their current contents.
+    unaffected: SmallMap<PathBuf, Module>,
+}
+
+fn contents_hash(contents: &str) -> String {
+    blake3::hash(contents.as_bytes()).to_hex().to_string()
+}
+
+/// The path a module is stored under: relative to `root` if it is under it.
+fn stored_path(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_owned()
+}
+
+/// A hash of everything besides the source files that the errors of a check depend on: the
+/// configurations the files are checked with, including the interpreter, the Python version
+/// and platform, and the full import search path. Paths under `root` are hashed relative to it.
+pub fn config_hash<'a>(
+    configs: impl IntoIterator<Item = &'a ArcId<ConfigFile>>,
+    root: &Path,
+) -> anyhow::Result<String> {
+    let stored_paths = |paths: &mut dyn Iterator<Item = &PathBuf>| -> Vec<PathBuf> {
+        paths.map(|path| stored_path(path, root)).collect()
+    };
+    let mut configs: Vec<String> = configs
+        .into_iter()
+        .map(|config| {
+            // The serialized config spells out the paths it was configured with, so make them
+            // relative the same way.
+            let serialized = serde_json::to_string(&**config)
+                .context("failed to serialize the configuration for the saved state")?
+                .replace(&*root.to_string_lossy(), ".");
+            Ok(format!(
+                "{serialized}\n{:?}\n{:?}\n{:?}",
+                stored_paths(&mut config.search_path()),
+                stored_paths(&mut config.site_package_path()),
+                config.get_sys_info(),
+            ))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    configs.sort();
+    Ok(contents_hash(&configs.join("\n")))
+}
+
+/// Find `module` as imported by the module `importer` at `path`.
+fn resolve_import(
+    transaction: &Transaction,
+    importer: ModuleName,
+    path: &Path,
+    module: ModuleName,
+) -> Option<ModulePath> {
+    let path = ModulePath::filesystem(path.to_owned());
+    let importer = transaction
+        .config_finder()
+        .python_file(ModuleNameWithKind::guaranteed(importer), &path)
+        .handle_from_module_path(path);
+    transaction
+        .import_handle(&importer, module, None)
+        .finding()
+        .map(|found| found.path().dupe())
+}
+
+/// Whether a module is a plain file on disk, the only kind a saved state records.
+fn is_saved_kind(path: &ModulePath) -> bool {
+    matches!(path.details(), ModulePathDetails::FileSystem(_))
+        && path.as_path().extension().is_none_or(|ext| ext != "ipynb")
+}
+
+impl SavedState {
+    /// Read a saved state, which must have been written by this version of pyrefly with
+    /// configurations that hash to `config_hash`.
+    pub fn read(path: &Path, config_hash: &str) -> anyhow::Result<Self> {
+        let state: Self = serde_json::from_str(&fs_anyhow::read_to_string(path)?)
+            .with_context(|| format!("failed to parse saved state `{}`", path.display()))?;
+        if state.version != SAVED_STATE_VERSION {
+            anyhow::bail!(
+                "saved state `{}` has version {}, expected {SAVED_STATE_VERSION}",
+                path.display(),
+                state.version
+            );
+        }
+        if state.pyrefly_version != env!("CARGO_PKG_VERSION") {
+            anyhow::bail!(
+                "saved state `{}` was written by pyrefly {}, not {}",
+                path.display(),
+                state.pyrefly_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        if state.config_hash != config_hash {
+            anyhow::bail!(
+                "saved state `{}` was written with a different configuration, interpreter or search path",
+                path.display()
+            );
+        }
+        Ok(state)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        fs_anyhow::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// Record a finished check. `checked` are the modules that were checked this time, and
+    /// `errors` holds their errors. Everything else is carried over from `reuse`.
+    pub fn new(
+        transaction: &Transaction,
+        checked: &[Handle],
+        errors: &[(ErrorCategory, &[Error])],
+        reuse: &SavedStateReuse,
+        root: &Path,
+    ) -> Self {
+        let checked: SmallSet<&Path> = checked
+            .iter()
+            .filter(|h| is_saved_kind(h.path()))
+            .map(|h| h.path().as_path())
+            .collect();
+        let mut modules: SmallMap<PathBuf, SavedModule> = SmallMap::new();
+        if let Some(previous) = &reuse.previous {
+            for module in &previous.modules {
+                let path = root.join(&module.path);
+                if checked.contains(path.as_path()) {
+                    continue;
+                }
+                let mut module = module.clone();
+                if !reuse.unaffected.contains_key(&path) {
+                    // Affected but not checked, e.g. because it was deleted.
+                    module.errors = None;
+                }
+                modules.insert(path, module);
+            }
+        }
+        for handle in transaction.handles() {
+            if !is_saved_kind(handle.path()) {
+                continue;
+            }
+            let path = handle.path().as_path();
+            let Some(info) = transaction.get_module_info(&handle) else {
+                continue;
+            };
+            let hash = contents_hash(info.contents());
+            // A module that wasn't checked may only have been loaded far enough to solve its
+            // exports, so if it is unchanged its saved imports are more complete.
+            if modules.get(path).is_some_and(|m| m.hash == hash) {
+                continue;
+            }
+            let mut deps: Vec<PathBuf> = transaction
+                .get_deps(&handle)
+                .iter()
+                .filter(|dep| is_saved_kind(dep.path()))
+                .map(|dep| stored_path(dep.path().as_path(), root))
+                .collect();
+            deps.sort();
+            deps.dedup();
+            let mut imports: Vec<(String, Option<PathBuf>)> = transaction
+                .get_imports(&handle)
+                .into_iter()
+                .map(|(module, path)| {
+                    (
+                        module.to_string(),
+                        path.map(|path| stored_path(path.as_path(), root)),
+                    )
+                })
+                .collect();
+            imports.sort();
+            modules.insert(
+                path.to_owned(),
+                SavedModule {
+                    path: stored_path(path, root),
+                    name: handle.module().to_string(),
+                    hash,
+                    deps,
+                    imports,
+                    errors: checked.contains(path).then(Vec::new),
+                },
+            );
+        }
+        for (category, errors) in errors {
+            for error in *errors {
+                let path = error.path().as_path();
+                if checked.contains(path)
+                    && let Some(module) = modules.get_mut(path)
+                    && let Some(saved) = &mut module.errors
+                {
+                    saved.push(SavedError::new(*category, error));
+                }
+            }
+        }
+        let mut modules: Vec<SavedModule> = modules.into_values().collect();
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+        SavedState {
+            version: SAVED_STATE_VERSION,
+            pyrefly_version: env!("CARGO_PKG_VERSION").to_owned(),
+            config_hash: reuse.config_hash.clone(),
+            modules,
+        }
+    }
+
+    /// Decide which of `handles` to recheck: those that changed since the saved state was
+    /// written, or that transitively import a file that changed or an import that now resolves
+    /// somewhere else. The rest can reuse their saved errors.
+    /// Imports are resolved again with `transaction`.
+    pub fn plan(
+        self,
+        handles: &[Handle],
+        root: &Path,
+        transaction: &Transaction,
+    ) -> (Vec<Handle>, SavedStateReuse) {
+        // Unchanged files that have saved errors, i.e. were checked when the saved state was
+        // written.
+        let mut unchanged: SmallMap<PathBuf, Module> = SmallMap::new();
+        let mut changed: Vec<PathBuf> = Vec::new();
+        for module in &self.modules {
+            let path = root.join(&module.path);
+            let name = ModuleName::from_str(&module.name);
+            let contents = match fs_anyhow::read_to_string(&path) {
+                Ok(contents) if contents_hash(&contents) == module.hash => contents,
+                _ => {
+                    changed.push(path);
+                    continue;
+                }
+            };
+            // A new file may shadow an import, or resolve one that failed before.
+            let moved = module.imports.iter().any(|(import, saved)| {
+                let resolved =
+                    resolve_import(transaction, name, &path, ModuleName::from_str(import))
+                        .map(|resolved| stored_path(resolved.as_path(), root));
+                resolved != *saved
+            });
+            if moved {
+                changed.push(path);
+            } else if module.errors.is_some() {
+                let module = Module::new(
+                    name,
+                    ModulePath::filesystem(path.clone()),
+                    Arc::new(contents),
+                );
+                unchanged.insert(path, module);
+            }
+        }
+
+        let mut rdeps: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for module in &self.modules {
+            for dep in &module.deps {
+                rdeps
+                    .entry(root.join(dep))
+                    .or_default()
+                    .push(root.join(&module.path));
+            }
+        }
+        let mut affected: SmallSet<PathBuf> = SmallSet::new();
+        while let Some(path) = changed.pop() {
+            if let Some(importers) = rdeps.get(&path) {
+                changed.extend(importers.iter().filter(|p| !affected.contains(*p)).cloned());
+            }
+            affected.insert(path);
+        }
+        unchanged.retain(|path, _| !affected.contains(path));
+
+        let recheck = handles
+            .iter()
+            .filter(|h| !unchanged.contains_key(h.path().as_path()))
+            .cloned()
+            .collect();
+        (
+            recheck,
+            SavedStateReuse {
+                config_hash: self.config_hash.clone(),
+                previous: Some(self),
+                unaffected: unchanged,
+            },
+        )
+    }
+}
+
+impl SavedStateReuse {
+    /// Nothing to reuse, e.g. because there is no saved state yet.
+    pub fn none(config_hash: String) -> Self {
+        Self {
+            config_hash,
+            previous: None,
+            unaffected: SmallMap::new(),
+        }
+    }
+
+    /// Add the saved errors of the unaffected files that weren't checked this time, i.e.
+    /// aren't in `checked`.
+    pub fn extend_errors(
+        &self,
+        checked: &[Handle],
+        root: &Path,
+        collected: &mut CollectedErrors,
+        unused_ignores: &mut CollectedErrors,
+    ) {
+        let Some(previous) = &self.previous else {
+            return;
+        };
+        let checked: SmallSet<&Path> = checked.iter().map(|h| h.path().as_path()).collect();
+        for saved in &previous.modules {
+            let path = root.join(&saved.path);
+            let (Some(errors), Some(module)) = (&saved.errors, self.unaffected.get(&path)) else {
+                continue;
+            };
+            if checked.contains(path.as_path()) {
+                continue;
+            }
+            for error in errors {
+                let target = match error.category {
+                    ErrorCategory::Ordinary => &mut collected.ordinary,
+                    ErrorCategory::Directive => &mut collected.directives,
+                    ErrorCategory::Suppressed => &mut collected.suppressed,
+                    ErrorCategory::UnusedIgnore => &mut unused_ignores.ordinary,
+                };
+                target.push(error.to_error(module));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dupe::Dupe;
+    use pyrefly_python::sys_info::SysInfo;
+    use pyrefly_util::thread_pool::TEST_THREAD_COUNT;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::config::finder::ConfigFinder;
+    use crate::state::require::Require;
+    use crate::state::state::State;
+
+    fn config(root: &Path) -> ArcId<ConfigFile> {
+        let mut config = ConfigFile::default();
+        config.python_environment.set_empty_to_default();
+        config.search_path_from_args = vec![root.to_path_buf()];
+        config.configure();
+        ArcId::new(config)
+    }
+
+    fn state(root: &Path) -> State {
+        State::new(ConfigFinder::new_constant(config(root)), TEST_THREAD_COUNT)
+    }
+
+    fn handle(root: &Path, name: &str) -> Handle {
+        Handle::new(
+            ModuleName::from_str(name),
+            ModulePath::filesystem(root.join(format!("{name}.py"))),
+            SysInfo::default(),
+        )
+    }
+
+    /// Check `names` in `root`, returning the saved state and the ordinary errors.
+    fn check(root: &Path, names: &[&str]) -> (SavedState, Vec<Handle>, Vec<String>) {
+        let state = state(root);
+        let handles: Vec<Handle> = names.iter().map(|name| handle(root, name)).collect();
+        let mut transaction = state.new_transaction(Require::Exports, None);
+        transaction.run(&handles, Require::Errors, None);
+        let collected = transaction.get_errors(&handles).collect_errors();
+        let saved = SavedState::new(
+            &transaction,
+            &handles,
+            &[(ErrorCategory::Ordinary, collected.ordinary.as_slice())],
+            &SavedStateReuse::none(config_hash([&config(root)], root).unwrap()),
+            root,
+        );
+        let errors = describe(&collected.ordinary);
+        (saved, handles, errors)
+    }
+
+    /// Plan a check of `handles` in `root` from `saved`, returning the modules to recheck.
+    fn plan(saved: SavedState, handles: &[Handle], root: &Path) -> (Vec<String>, SavedStateReuse) {
+        let state = state(root);
+        let transaction = state.new_transaction(Require::Exports, None);
+        let (recheck, reuse) = saved.plan(handles, root, &transaction);
+        let recheck = recheck.iter().map(|h| h.module().to_string()).collect();
+        (recheck, reuse)
+    }
+
+    fn describe(errors: &[Error]) -> Vec<String> {
+        let mut errors: Vec<String> = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.path().as_path().display(), e.msg_header()))
+            .collect();
+        errors.sort();
+        errors
+    }
+
+    fn write(root: &Path, name: &str, contents: &str) {
+        fs_anyhow::write(&root.join(format!("{name}.py")), contents).unwrap();
+    }
+
+    #[test]
+    fn test_saved_state_rechecks_affected_files() {
+        let tdir = TempDir::new().unwrap();
+        let root = tdir.path();
+        write(root, "a", "def f() -> int: return 1\n");
+        write(root, "b", "from a import f\nx: int = f()\n");
+        write(root, "c", "y: int = ''\n");
+        let names = ["a", "b", "c"];
+        let (saved, handles, errors) = check(root, &names);
+        assert_eq!(errors.len(), 1);
+        // Paths under the root are stored relative to it.
+        assert!(
+            saved
+                .modules
+                .iter()
+                .any(|m| m.path == Path::new("b.py") && m.deps.contains(&PathBuf::from("a.py")))
+        );
+
+        // Round-trip through the file format.
+        let file = root.join("saved.json");
+        saved.write(&file).unwrap();
+        let hash = config_hash([&config(root)], root).unwrap();
+        let saved = SavedState::read(&file, &hash).unwrap();
+
+        // Nothing changed, so nothing is rechecked and the errors come from the saved state.
+        let (recheck, reuse) = plan(saved, &handles, root);
+        assert!(recheck.is_empty());
+        let mut collected = CollectedErrors::default();
+        reuse.extend_errors(&[], root, &mut collected, &mut CollectedErrors::default());
+        assert_eq!(describe(&collected.ordinary), errors);
+
+        // Changing `a` affects `b`, which imports it, but not `c`.
+        write(root, "a", "def f() -> str: return ''\n");
+        let (recheck, reuse) = plan(SavedState::read(&file, &hash).unwrap(), &handles, root);
+        assert_eq!(recheck, vec!["a", "b"]);
+        assert_eq!(
+            reuse.unaffected.keys().collect::<Vec<_>>(),
+            vec![&root.join("c.py")]
+        );
+    }
+
+    #[test]
+    fn test_saved_state_rechecks_missing_imports_after_adding_files() {
+        let tdir = TempDir::new().unwrap();
+        let root = tdir.path();
+        write(root, "a", "import b\n");
+        let (saved, handles, errors) = check(root, &["a"]);
+        assert_eq!(errors.len(), 1);
+        write(root, "b", "");
+        let (recheck, _) = plan(saved, &[handles[0].dupe(), handle(root, "b")], root);
+        assert_eq!(recheck, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_saved_state_rechecks_shadowed_imports() {
+        let tdir = TempDir::new().unwrap();
+        let root = tdir.path();
+        write(root, "a", "import json\n");
+        write(root, "b", "x: int = 1\n");
+        let (saved, handles, errors) = check(root, &["a", "b"]);
+        assert!(errors.is_empty());
+        // A file on the search path shadows the stdlib module, without adding a checked file.
+        write(root, "json", "");
+        let (recheck, _) = plan(saved, &handles, root);
+        assert_eq!(recheck, vec!["a"]);
+    }
+
+    #[test]
+    fn test_saved_state_requires_same_config() {
+        let tdir = TempDir::new().unwrap();
+        let root = tdir.path();
+        write(root, "a", "");
+        let (saved, _, _) = check(root, &["a"]);
+        let file = root.join("saved.json");
+        saved.write(&file).unwrap();
+        let mut other = ConfigFile::default();
+        other.python_environment.set_empty_to_default();
+        other.search_path_from_args = vec![root.join("src")];
+        other.configure();
+        assert!(SavedState::read(&file, &config_hash([&config(root)], root).unwrap()).is_ok());
+        assert!(
+            SavedState::read(&file, &config_hash([&ArcId::new(other)], root).unwrap()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_saved_error_keeps_quick_fixes() {
+        let module = Module::new(
+            ModuleName::from_str("a"),
+            ModulePath::filesystem(PathBuf::from("a.py")),
+            Arc::new("x = 1\n".to_owned()),
+        );
+        let range = TextRange::new(TextSize::new(0), TextSize::new(1));
+        let error = Error::new(
+            module.dupe(),
+            range,
+            "Message".to_owned(),
+            Vec::new(),
+            ErrorKind::BadAssignment,
+        )
+        .with_quick_fix(ErrorQuickFix::TextEdits {
+            title: "Rename".to_owned(),
+            edits: vec![(range, "y".to_owned())],
+        })
+        .with_quick_fix(ErrorQuickFix::ReplaceWithEnumMember {
+            replacement: "E.A".to_owned(),
+        });
+        let saved = SavedError::new(ErrorCategory::Ordinary, &error);
+        let saved: SavedError =
+            serde_json::from_str(&serde_json::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(saved.to_error(&module).quick_fixes(), error.quick_fixes());
+    }
+}
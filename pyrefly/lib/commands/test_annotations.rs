@@ -0,0 +1,171 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Run files containing `assert_type` and `reveal_type` calls as a test suite, so that
+//! library authors can keep regression tests for how their types behave.
+
+use std::path::Path;
+
+use clap::Parser;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_config::error_kind::Severity;
+use pyrefly_util::display::count;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::thread_pool::ThreadCount;
+use tracing::info;
+
+use crate::commands::check::Handles;
+use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::error::error::Error;
+use crate::state::require::Require;
+use crate::state::state::State;
+
+/// The comment that gives the expected result of a `reveal_type` call on the same line,
+/// e.g. `reveal_type(x)  # revealed: int`.
+const REVEALED_COMMENT: &str = "# revealed:";
+
+/// Run `assert_type` and `reveal_type` assertions in the given files as tests.
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct TestAnnotationsArgs {
+    /// Which files to run.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration.
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+}
+
+/// Why an error fails the test suite.
+enum Failure<'a> {
+    /// A `reveal_type` whose revealed type differs from its `# revealed:` comment.
+    Reveal {
+        error: &'a Error,
+        expected: &'a str,
+        actual: &'a str,
+    },
+    /// A failed `assert_type`, or any other error.
+    Error(&'a Error),
+}
+
+impl Failure<'_> {
+    fn error(&self) -> &Error {
+        match self {
+            Self::Reveal { error, .. } | Self::Error(error) => error,
+        }
+    }
+
+    fn print(&self, project_root: &Path) {
+        let error = self.error();
+        let location = format!(
+            "{}:{}",
+            error.path_string_with_fragment(project_root),
+            error.display_range().start
+        );
+        match self {
+            Self::Reveal {
+                expected, actual, ..
+            } => {
+                println!("FAIL {location}: reveal_type");
+                println!("  - {expected}");
+                println!("  + {actual}");
+            }
+            Self::Error(error) => {
+                println!(
+                    "FAIL {location}: {} [{}]",
+                    error.msg_header(),
+                    error.error_kind().to_name()
+                );
+            }
+        }
+    }
+}
+
+/// The expectation in a `# revealed:` comment on the last line of `error`, if any.
+fn expected_reveal(error: &Error) -> Option<&str> {
+    let line = error.display_range().end.line_within_file();
+    let text = error.lined_buffer().content_in_line_range(line, line);
+    let (_, expected) = text.split_once(REVEALED_COMMENT)?;
+    Some(expected.trim())
+}
+
+impl TestAnnotationsArgs {
+    pub fn run(
+        self,
+        wrapper: Option<ConfigConfigurerWrapper>,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let (files_to_check, config_finder, _) =
+            self.files.resolve(self.config_override, wrapper)?;
+
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
+        let state = State::new(config_finder, thread_count);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter =
+            Forgetter::new(holder.as_ref().new_transaction(Require::Errors, None), true);
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        transaction.run(&handles, Require::Errors, None);
+        let errors = transaction.get_errors(&handles).collect_errors();
+
+        let mut failures = Vec::new();
+        for error in &errors.directives {
+            if error.error_kind() == ErrorKind::RevealType
+                && let Some(expected) = expected_reveal(error)
+                && let Some(actual) = error.msg_header().strip_prefix("revealed type: ")
+                && actual != expected
+            {
+                failures.push(Failure::Reveal {
+                    error,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        failures.extend(
+            errors
+                .ordinary
+                .iter()
+                .filter(|e| e.severity() >= Severity::Error)
+                .map(Failure::Error),
+        );
+        failures.sort_by(|a, b| {
+            let (a, b) = (a.error(), b.error());
+            (a.path(), a.display_range().start.line_within_file())
+                .cmp(&(b.path(), b.display_range().start.line_within_file()))
+        });
+
+        let project_root = std::env::current_dir().unwrap_or_default();
+        for failure in &failures {
+            failure.print(&project_root);
+        }
+        if failures.is_empty() {
+            info!("All assertions passed in {}", count(handles.len(), "file"));
+            Ok(CommandExitStatus::Success)
+        } else {
+            info!(
+                "{} in {}",
+                count(failures.len(), "failure"),
+                count(handles.len(), "file")
+            );
+            Ok(CommandExitStatus::UserError)
+        }
+    }
+}
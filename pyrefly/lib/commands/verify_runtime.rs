@@ -0,0 +1,333 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Import modules in the configured Python interpreter and compare what exists at runtime
+//! against what Pyrefly sees statically, as a lightweight stubtest for local stubs and
+//! `replace-imports-with-any` candidates.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context as _;
+use clap::Parser;
+use dupe::Dupe as _;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_name::ModuleNameWithKind;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_types::callable::Param;
+use pyrefly_types::callable::Params;
+use pyrefly_types::types::Type;
+use pyrefly_util::display::count;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::thread_pool::ThreadCount;
+use ruff_python_ast::name::Name;
+use serde::Deserialize;
+use starlark_map::Hashed;
+use tracing::info;
+
+use crate::binding::binding::KeyExport;
+use crate::commands::files::get_config_finder_for_snippet;
+use crate::commands::util::CommandExitStatus;
+use crate::export::exports::ExportLocation;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::state::state::Transaction;
+
+/// Imports every module named on the command line and prints a JSON object mapping each
+/// module to either an import error or its public attributes. Callables get their
+/// parameter names from `inspect.signature`, with `*` and `**` marking variadic parameters.
+const RUNTIME_SCRIPT: &str = r#"
+import importlib, inspect, json, sys
+
+def params(value):
+    if not (inspect.isfunction(value) or inspect.isbuiltin(value)):
+        return None
+    try:
+        signature = inspect.signature(value)
+    except (TypeError, ValueError):
+        return None
+    prefixes = {inspect.Parameter.VAR_POSITIONAL: "*", inspect.Parameter.VAR_KEYWORD: "**"}
+    return [prefixes.get(p.kind, "") + p.name for p in signature.parameters.values()]
+
+result = {}
+for name in sys.argv[1:]:
+    try:
+        module = importlib.import_module(name)
+    except BaseException as e:
+        result[name] = {"error": f"{type(e).__name__}: {e}"}
+        continue
+    attributes = {}
+    for attr in dir(module):
+        if attr.startswith("_"):
+            continue
+        try:
+            value = getattr(module, attr)
+        except BaseException:
+            continue
+        if inspect.ismodule(value):
+            defined_here = False
+        elif inspect.isclass(value) or inspect.isroutine(value):
+            defined_here = getattr(value, "__module__", None) == name
+        else:
+            defined_here = True
+        attributes[attr] = {"defined_here": defined_here, "params": params(value)}
+    result[name] = {"attributes": attributes}
+print(json.dumps(result))
+"#;
+
+/// Compare runtime objects of modules against their statically determined types.
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct VerifyRuntimeArgs {
+    /// Names of the modules to import and verify, e.g. `foo.bar`.
+    #[arg(required = true)]
+    modules: Vec<String>,
+
+    /// Explicitly set the Pyrefly configuration to use.
+    #[arg(long, short)]
+    config: Option<PathBuf>,
+
+    /// Type checking arguments and configuration.
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+}
+
+/// What the interpreter reported for one module.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RuntimeModule {
+    Error {
+        error: String,
+    },
+    Attributes {
+        attributes: BTreeMap<String, RuntimeAttribute>,
+    },
+}
+
+/// What the interpreter reported for one public attribute of a module.
+#[derive(Debug, Deserialize)]
+struct RuntimeAttribute {
+    /// Whether the attribute is defined in the module, rather than imported into it.
+    defined_here: bool,
+    /// Parameter names from `inspect.signature`, if the attribute is a function with one.
+    params: Option<Vec<String>>,
+}
+
+/// Run `RUNTIME_SCRIPT` in `interpreter` and parse what it reports.
+fn query_runtime(
+    interpreter: &Path,
+    modules: &[String],
+) -> anyhow::Result<BTreeMap<String, RuntimeModule>> {
+    let output = Command::new(interpreter)
+        .arg("-c")
+        .arg(RUNTIME_SCRIPT)
+        .args(modules)
+        .output()
+        .with_context(|| format!("Failed to run `{}`", interpreter.display()))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "`{}` failed to import modules: {}",
+            interpreter.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout).with_context(|| {
+        format!(
+            "Failed to parse runtime information from `{}`",
+            interpreter.display()
+        )
+    })
+}
+
+/// The parameter names of `ty`, in the same format as `RUNTIME_SCRIPT`, if it is a function
+/// with a single signature.
+fn static_params(ty: &Type) -> Option<Vec<String>> {
+    if !matches!(ty, Type::Function(_) | Type::Forall(_)) {
+        return None;
+    }
+    let [signature] = ty.callable_signatures()[..] else {
+        return None;
+    };
+    let Params::List(params) = &signature.params else {
+        return None;
+    };
+    params
+        .items()
+        .iter()
+        .map(|param| {
+            let prefix = match param {
+                Param::Varargs(..) => "*",
+                Param::Kwargs(..) => "**",
+                _ => "",
+            };
+            Some(format!("{prefix}{}", param.name()?))
+        })
+        .collect()
+}
+
+/// The type of the export `name` defined in `handle`.
+fn export_type(transaction: &Transaction, handle: &Handle, name: &Name) -> Option<Type> {
+    let bindings = transaction.get_bindings(handle)?;
+    let answers = transaction.get_answers(handle)?;
+    let idx = bindings.key_to_idx_hashed_opt(Hashed::new(&KeyExport(name.clone())))?;
+    answers.get_idx(idx).map(|ty| (*ty).clone())
+}
+
+/// Compare a single module, returning a message for each mismatch.
+fn verify_module(
+    transaction: &Transaction,
+    handle: &Handle,
+    runtime: &BTreeMap<String, RuntimeAttribute>,
+) -> Vec<String> {
+    let exports = transaction.get_exports(handle);
+    let mut mismatches = Vec::new();
+    for (name, location) in exports.iter() {
+        if name.starts_with('_') {
+            continue;
+        }
+        let Some(attribute) = runtime.get(name.as_str()) else {
+            mismatches.push(format!("`{name}` is not present at runtime"));
+            continue;
+        };
+        if let ExportLocation::ThisModule(_) = location
+            && let Some(runtime_params) = &attribute.params
+            && let Some(ty) = export_type(transaction, handle, name)
+            && let Some(params) = static_params(&ty)
+            && &params != runtime_params
+        {
+            mismatches.push(format!(
+                "`{name}` has parameters ({}) at runtime, but ({}) statically",
+                runtime_params.join(", "),
+                params.join(", ")
+            ));
+        }
+    }
+    for (name, attribute) in runtime {
+        if attribute.defined_here && !exports.contains_key(&Name::new(name)) {
+            mismatches.push(format!("`{name}` exists at runtime but not statically"));
+        }
+    }
+    mismatches
+}
+
+impl VerifyRuntimeArgs {
+    pub fn run(self, thread_count: ThreadCount) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let config_finder = get_config_finder_for_snippet(self.config, self.config_override)?;
+
+        // Resolve the modules as if they were imported from a file in the current directory.
+        let importer_path = ModulePath::memory(PathBuf::from("verify_runtime"));
+        let importer_name = ModuleName::from_str("__main__");
+        let config = config_finder.python_file(
+            ModuleNameWithKind::guaranteed(importer_name),
+            &importer_path,
+        );
+        let interpreter = config
+            .interpreters
+            .python_interpreter()
+            .context("No Python interpreter found to import the modules with")?
+            .to_path_buf();
+        let importer = Handle::new(importer_name, importer_path, config.get_sys_info());
+
+        let runtime = query_runtime(&interpreter, &self.modules)?;
+
+        let state = State::new(config_finder, thread_count);
+        let holder = Forgetter::new(state, false);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Everything, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let mut failures = 0;
+        for module in &self.modules {
+            let handle = transaction
+                .import_handle(&importer, ModuleName::from_str(module), None)
+                .finding();
+            let mismatches = match (handle, runtime.get(module)) {
+                (None, _) => vec!["cannot be found statically".to_owned()],
+                (_, None) => vec!["was not reported by the interpreter".to_owned()],
+                (_, Some(RuntimeModule::Error { error })) => {
+                    vec![format!("cannot be imported at runtime: {error}")]
+                }
+                (Some(handle), Some(RuntimeModule::Attributes { attributes })) => {
+                    transaction.run(&[handle.dupe()], Require::Everything, None);
+                    verify_module(transaction, &handle, attributes)
+                }
+            };
+            for mismatch in &mismatches {
+                println!("FAIL {module}: {mismatch}");
+            }
+            failures += mismatches.len();
+        }
+
+        if failures == 0 {
+            info!(
+                "No differences found in {}",
+                count(self.modules.len(), "module")
+            );
+            Ok(CommandExitStatus::Success)
+        } else {
+            info!(
+                "{} in {}",
+                count(failures, "difference"),
+                count(self.modules.len(), "module")
+            );
+            Ok(CommandExitStatus::UserError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    fn runtime(attributes: &[(&str, Option<&[&str]>)]) -> BTreeMap<String, RuntimeAttribute> {
+        attributes
+            .iter()
+            .map(|(name, params)| {
+                let attribute = RuntimeAttribute {
+                    defined_here: true,
+                    params: params.map(|ps| ps.iter().map(|p| (*p).to_owned()).collect()),
+                };
+                ((*name).to_owned(), attribute)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_module() {
+        let (state, handle) = TestEnv::one(
+            "foo",
+            "def parse(text: str, *args: int) -> None: ...\nclass Token: ...\n",
+        )
+        .to_state();
+        let transaction = state.transaction();
+        let handle = handle("foo");
+
+        let matching = runtime(&[("parse", Some(&["text", "*args"])), ("Token", None)]);
+        assert_eq!(
+            verify_module(&transaction, &handle, &matching),
+            Vec::<String>::new()
+        );
+
+        let drifted = runtime(&[("parse", Some(&["text", "strict"])), ("Lexer", None)]);
+        assert_eq!(
+            verify_module(&transaction, &handle, &drifted),
+            vec![
+                "`parse` has parameters (text, strict) at runtime, but (text, *args) statically",
+                "`Token` is not present at runtime",
+                "`Lexer` exists at runtime but not statically",
+            ]
+        );
+    }
+}
@@ -11,12 +11,14 @@ use std::mem;
 use dupe::Dupe;
 use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_python::ignore::Suppression;
-use pyrefly_python::ignore::Tool;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
 use pyrefly_util::lined_buffer::LineNumber;
 use pyrefly_util::lock::Mutex;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
+use starlark_map::small_set::SmallSet;
 
 use crate::config::error::ErrorConfig;
 use crate::config::error_kind::Severity;
@@ -165,6 +167,7 @@ impl ErrorCollector {
             details: Vec::new(),
             context: None,
             annotations: Vec::new(),
+            other_module_annotations: Vec::new(),
             quick_fixes: Vec::new(),
         }
     }
@@ -203,6 +206,16 @@ impl ErrorCollector {
         self.errors.lock().len()
     }
 
+    /// The other modules that the secondary annotations of these errors point into.
+    pub fn annotation_modules(&self) -> SmallSet<(ModuleName, ModulePath)> {
+        self.errors
+            .lock()
+            .items
+            .iter()
+            .flat_map(|err| err.annotation_modules().cloned())
+            .collect()
+    }
+
     /// Count of errors excluding soft diagnostics (which should not
     /// influence overload selection or type-inference decisions).
     pub fn len_hard(&self) -> usize {
@@ -228,20 +241,18 @@ impl ErrorCollector {
         if err.error_kind() != ErrorKind::UnusedIgnore
             && err.error_kind().suppression_names().any(|kind| {
                 ignore_all.iter().any(|supp| {
-                    error_config.enabled_ignores.contains(&supp.tool())
-                        && match supp.tool() {
-                            Tool::Pyrefly => {
-                                supp.error_codes().is_empty()
-                                    || supp.error_codes().iter().any(|x| x == kind)
-                            }
-                            _ => true,
-                        }
+                    supp.suppresses(
+                        kind,
+                        &error_config.enabled_ignores,
+                        error_config.require_ignore_codes,
+                    )
                 })
             })
         {
             return true;
         }
-        if err.is_ignored(&error_config.enabled_ignores) {
+        let require_codes = error_config.require_ignore_codes;
+        if err.is_ignored(&error_config.enabled_ignores, require_codes) {
             return true;
         }
         // Check if the error is inside a multi-line f/t-string. If so, a
@@ -252,10 +263,10 @@ impl ErrorCollector {
             let enabled = &error_config.enabled_ignores;
             // Check both this kind's name and any parent kind's name.
             for kind in err.error_kind().suppression_names() {
-                if fs_start != line && ignore.is_ignored(fs_start, kind, enabled) {
+                if fs_start != line && ignore.is_ignored(fs_start, kind, enabled, require_codes) {
                     return true;
                 }
-                if fs_end != line && ignore.is_ignored(fs_end, kind, enabled) {
+                if fs_end != line && ignore.is_ignored(fs_end, kind, enabled, require_codes) {
                     return true;
                 }
             }
@@ -296,6 +307,7 @@ impl ErrorCollector {
                 }
             }
             self.collect_misplaced_ignores(misplaced, error_config, result);
+            self.collect_missing_ignore_codes(ignore_all, error_config, result);
         }
     }
 
@@ -348,6 +360,57 @@ impl ErrorCollector {
         }
     }
 
+    /// Emit a `missing-ignore-code` diagnostic for each enabled suppression comment,
+    /// line-level or file-level, that does not name the error codes it suppresses.
+    /// Like `misplaced-ignore`, it is emitted directly rather than being routed
+    /// through `is_error_suppressed`.
+    fn collect_missing_ignore_codes(
+        &self,
+        ignore_all: &[Suppression],
+        error_config: &ErrorConfig,
+        result: &mut CollectedErrors,
+    ) {
+        let severity = error_config
+            .display_config
+            .severity(ErrorKind::MissingIgnoreCode);
+        let buffer = self.module_info.lined_buffer();
+        // A `# type: ignore` at the top of a file is both a file-level and a line-level
+        // suppression, so only report each comment once.
+        let mut seen = SmallSet::new();
+        let suppressions = self
+            .module_info
+            .ignore()
+            .iter()
+            .flat_map(|(_, supps)| supps)
+            .chain(ignore_all);
+        for supp in suppressions {
+            if !error_config.enabled_ignores.contains(&supp.tool())
+                || !supp.error_codes().is_empty()
+                || !seen.insert((supp.comment_line(), supp.comment_offset()))
+            {
+                continue;
+            }
+            let line = supp.comment_line();
+            let line_start = buffer.line_start(line);
+            let line_text = buffer.content_in_line_range(line, line);
+            let range = TextRange::new(
+                line_start + TextSize::new(supp.comment_offset() as u32),
+                line_start + TextSize::new(line_text.trim_end().len() as u32),
+            );
+            let err = Error::new(
+                self.module_info.dupe(),
+                range,
+                "Suppression comment does not name the error codes it suppresses".to_owned(),
+                Vec::new(),
+                ErrorKind::MissingIgnoreCode,
+            );
+            match severity {
+                Severity::Ignore => result.disabled.push(err),
+                sev => result.ordinary.push(err.with_severity(sev)),
+            }
+        }
+    }
+
     pub fn collect(&self, error_config: &ErrorConfig) -> CollectedErrors {
         let mut result = CollectedErrors::default();
         self.collect_into(error_config, &[], &[], &[], &mut result);
@@ -372,6 +435,7 @@ pub struct ErrorBuilder<'a> {
     details: Vec<String>,
     context: Option<ErrorContext>,
     annotations: Vec<(TextRange, String)>,
+    other_module_annotations: Vec<(ModuleInfo, TextRange, String)>,
     quick_fixes: Vec<ErrorQuickFix>,
 }
 
@@ -408,6 +472,19 @@ impl ErrorBuilder<'_> {
         self
     }
 
+    /// Add a secondary labeled span in `module`, which may be another file.
+    pub fn with_annotation_in(
+        mut self,
+        module: ModuleInfo,
+        range: TextRange,
+        label: String,
+    ) -> Self {
+        if self.active {
+            self.other_module_annotations.push((module, range, label));
+        }
+        self
+    }
+
     /// Add a structured quick fix.
     pub fn with_quick_fix(mut self, fix: ErrorQuickFix) -> Self {
         if self.active {
@@ -452,6 +529,9 @@ impl ErrorBuilder<'_> {
         for (range, label) in annotations {
             err = err.with_annotation(range, label);
         }
+        for (module, range, label) in self.other_module_annotations {
+            err = err.with_annotation_in(module, range, label);
+        }
         for fix in self.quick_fixes {
             err = err.with_quick_fix(fix);
         }
@@ -59,9 +59,17 @@ impl ErrorContext {
 
 impl TypeCheckKind {
     /// Note: `got` and `want` should be processed through `AnswersSolver::for_display` before calling this function
-    /// otherwise printed type representations may be non-deterministic due to unsolved vars
-    pub fn format_error(&self, got: &Type, want: &Type, current_module: ModuleName) -> String {
+    /// otherwise printed type representations may be non-deterministic due to unsolved vars.
+    /// Aliased unions with more than `union_display_limit` members are shown by their alias name.
+    pub fn format_error(
+        &self,
+        got: &Type,
+        want: &Type,
+        current_module: ModuleName,
+        union_display_limit: usize,
+    ) -> String {
         let mut ctx = TypeDisplayContext::new(&[got, want]);
+        ctx.collapse_aliased_unions_over(union_display_limit);
         match self {
             Self::MagicMethodReturn(cls, func) => {
                 ctx.add(cls);
@@ -11,14 +11,19 @@ use std::io;
 use std::io::Write;
 use std::path::Path;
 
+use dupe::Dupe;
 use itertools::Itertools;
 use lsp_types::CodeDescription;
 use lsp_types::Diagnostic;
+use lsp_types::DiagnosticRelatedInformation;
 use lsp_types::DiagnosticTag;
+use lsp_types::Location;
 use lsp_types::Url;
 use pyrefly_python::ignore::Tool;
 use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
 use pyrefly_util::display::number_thousands;
 use pyrefly_util::lined_buffer::DisplayRange;
 use pyrefly_util::lined_buffer::LineNumber;
@@ -36,17 +41,33 @@ use yansi::Paint;
 use crate::config::error_kind::ErrorKind;
 use crate::config::error_kind::Severity;
 
-/// A secondary annotation that labels a span in the same file as the primary error.
-/// Used to show additional context, e.g. the types of both operands in a binary operation.
+/// A secondary annotation that labels a span, usually in the same file as the primary error.
+/// Used to show additional context, e.g. the types of both operands in a binary operation,
+/// or the parent class method that an override is checked against.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SecondaryAnnotation {
+    /// The module the span is in, if it isn't the module of the error. Only its name and path
+    /// are stored, so an error that is kept around doesn't pin an old version of that file.
+    pub module: Option<(ModuleName, ModulePath)>,
+    /// The current version of `module`, attached when errors are collected
+    /// (see `Error::resolve_annotation_modules`). Annotations in another module are only
+    /// rendered once it is resolved.
+    resolved: Option<Module>,
     pub range: TextRange,
     pub label: Box<str>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ErrorQuickFix {
-    ReplaceWithEnumMember { replacement: String },
+    ReplaceWithEnumMember {
+        replacement: String,
+    },
+    /// A machine-applicable fix: replace each range in the error's module with its text.
+    /// Surfaced as an LSP quick fix and in JSON output.
+    TextEdits {
+        title: String,
+        edits: Vec<(TextRange, String)>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -61,7 +82,7 @@ pub struct Error {
     /// The rest of the error message after the first line.
     /// Note that this is formatted for pretty-printing, with two spaces at the beginning and after every newline.
     msg_details: Option<Box<str>>,
-    /// Additional labeled spans in the same file for richer diagnostics.
+    /// Additional labeled spans for richer diagnostics.
     secondary_annotations: Vec<SecondaryAnnotation>,
     /// Structured fixes that can be exposed by editor integrations.
     quick_fixes: Vec<ErrorQuickFix>,
@@ -78,6 +99,8 @@ pub struct ErrorRenderer<W> {
     writer: W,
     mode: ErrorRenderMode,
     snippets: Renderer,
+    /// Lines of source to show before and after the primary span of a verbose error.
+    context_lines: u32,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -101,6 +124,7 @@ impl<W: Write> ErrorRenderer<W> {
             writer,
             mode: ErrorRenderMode::Plain,
             snippets: Renderer::plain(),
+            context_lines: 0,
         }
     }
 
@@ -109,9 +133,15 @@ impl<W: Write> ErrorRenderer<W> {
             writer,
             mode: ErrorRenderMode::Color,
             snippets: Renderer::styled(),
+            context_lines: 0,
         }
     }
 
+    pub fn with_context_lines(mut self, context_lines: u32) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
     pub fn write(&mut self, error: &Error, project_root: &Path, verbose: bool) -> io::Result<()> {
         if !error.severity.is_enabled() {
             return Ok(());
@@ -119,7 +149,13 @@ impl<W: Write> ErrorRenderer<W> {
         let origin = error.path_string_with_fragment(project_root);
         if verbose {
             self.write_header(error)?;
-            let snippet = error.get_source_snippet(&origin);
+            // Annotations in other modules are shown with their own path.
+            let other_origins = error
+                .secondary_annotations
+                .iter()
+                .filter_map(|ann| Some(module_origin(ann.resolved.as_ref()?, project_root)))
+                .collect::<Vec<_>>();
+            let snippet = error.get_source_snippet(&origin, &other_origins, self.context_lines);
             self.write_snippet(snippet)?;
             if let Some(details) = &error.msg_details {
                 writeln!(self.writer, "{details}")?;
@@ -181,6 +217,15 @@ impl<W: Write> ErrorRenderer<W> {
     }
 }
 
+/// The path of `module` to show in a snippet header.
+fn module_origin(module: &Module, project_root: &Path) -> String {
+    let path = module.path().as_path();
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
 impl Error {
     /// Return the path with a cell fragment if the error is in a notebook cell.
     pub fn path_string_with_fragment(&self, project_root: &Path) -> String {
@@ -193,7 +238,13 @@ impl Error {
         }
     }
 
-    fn get_source_snippet<'a>(&'a self, origin: &'a str) -> Message<'a> {
+    /// `other_origins` holds the path of each annotation in another module, in order.
+    fn get_source_snippet<'a>(
+        &'a self,
+        origin: &'a str,
+        other_origins: &'a [String],
+        context_lines: u32,
+    ) -> Message<'a> {
         // Maximum number of lines to show in a single snippet. Annotations further apart
         // than this are shown as separate snippets rather than dumping all lines in between.
         // The primary span is also capped to this many lines for very large multi-line spans.
@@ -203,15 +254,34 @@ impl Error {
         // and distant (shown as separate snippets to avoid printing excessive context).
         let primary_start_line = self.display_range.start.line_within_file();
         let primary_end_line = self.display_range.end.line_within_file();
-        let mut start_line = primary_start_line;
+        let mut start_line = LineNumber::from_zero_indexed(
+            primary_start_line
+                .to_zero_indexed()
+                .saturating_sub(context_lines),
+        );
         // Cap the primary span to MAX_LINES to avoid dumping huge multi-line spans.
+        let last_line = LineNumber::from_zero_indexed(
+            (self.module.lined_buffer().line_count() as u32).saturating_sub(1),
+        );
         let mut end_line = cmp::min(
-            LineNumber::from_zero_indexed(primary_start_line.to_zero_indexed() + MAX_LINES),
-            primary_end_line,
+            LineNumber::from_zero_indexed(
+                cmp::min(
+                    primary_start_line.to_zero_indexed() + MAX_LINES,
+                    primary_end_line.to_zero_indexed(),
+                ) + context_lines,
+            ),
+            cmp::max(last_line, primary_end_line),
         );
         let mut nearby_annotations = Vec::new();
         let mut distant_annotations = Vec::new();
+        let mut other_annotations = Vec::new();
         for ann in &self.secondary_annotations {
+            if ann.module.is_some() {
+                if let Some(module) = &ann.resolved {
+                    other_annotations.push((ann, module));
+                }
+                continue;
+            }
             let ann_display = self.module.display_range(ann.range);
             let ann_start = ann_display.start.line_within_file();
             let ann_end = ann_display.end.line_within_file();
@@ -241,6 +311,7 @@ impl Error {
 
         // Primary snippet with nearby annotations inline.
         let primary_snippet = self.make_snippet(
+            &self.module,
             origin,
             start_line,
             end_line,
@@ -254,6 +325,7 @@ impl Error {
             let ann_start_line = ann_display.start.line_within_file();
             let ann_end_line = ann_display.end.line_within_file();
             message = message.snippet(self.make_snippet(
+                &self.module,
                 origin,
                 ann_start_line,
                 ann_end_line,
@@ -261,6 +333,18 @@ impl Error {
                 &[ann],
             ));
         }
+        // Annotations in other modules each get a snippet from their own file.
+        for ((ann, module), origin) in other_annotations.into_iter().zip(other_origins) {
+            let ann_display = module.display_range(ann.range);
+            message = message.snippet(self.make_snippet(
+                module,
+                origin,
+                ann_display.start.line_within_file(),
+                ann_display.end.line_within_file(),
+                None,
+                &[ann],
+            ));
+        }
         message
     }
 
@@ -268,6 +352,7 @@ impl Error {
     /// secondary annotations. Used for both the main error snippet and distant annotation snippets.
     fn make_snippet<'a>(
         &'a self,
+        module: &'a Module,
         origin: &'a str,
         from_line: LineNumber,
         to_line: LineNumber,
@@ -275,13 +360,11 @@ impl Error {
         annotations: &[&'a SecondaryAnnotation],
     ) -> Snippet<'a> {
         // Warning: The SourceRange is char indexed, while the snippet is byte indexed.
-        let source = self
-            .module
+        let source = module
             .lined_buffer()
             .content_in_line_range(from_line, to_line);
-        let line_start = self.module.lined_buffer().line_start(from_line);
-        let cell_line = self
-            .module
+        let line_start = module.lined_buffer().line_start(from_line);
+        let cell_line = module
             .display_range(TextRange::new(line_start, line_start))
             .start
             .line_within_cell()
@@ -316,14 +399,14 @@ impl Error {
         self.severity
     }
 
-    /// Create a diagnostic suitable for use in LSP.
-    pub fn to_diagnostic(&self) -> Diagnostic {
+    /// Create a diagnostic suitable for use in LSP. `uri` is the document the
+    /// diagnostic is published for (the cell, for notebooks); when given, secondary
+    /// annotations in that document become related information.
+    pub fn to_diagnostic(&self, uri: Option<&Url>) -> Diagnostic {
         let code = self.error_kind().to_name().to_owned();
         let code_description = Url::parse(&self.error_kind().docs_url())
             .ok()
             .map(|href| CodeDescription { href });
-        // TODO: Map secondary_annotations to DiagnosticRelatedInformation for LSP clients.
-        // This requires constructing a Url from the module path, which may not always succeed.
         Diagnostic {
             range: self.module.to_lsp_range(self.range()),
             severity: Some(match self.severity() {
@@ -342,10 +425,55 @@ impl Error {
             } else {
                 None
             },
+            related_information: uri.and_then(|uri| self.to_related_information(uri)),
             ..Default::default()
         }
     }
 
+    fn to_related_information(&self, uri: &Url) -> Option<Vec<DiagnosticRelatedInformation>> {
+        // `uri` only names one notebook cell, so drop annotations in other cells.
+        let cell = self.get_notebook_cell();
+        let related = self
+            .secondary_annotations
+            .iter()
+            .filter_map(|ann| {
+                let location = match (&ann.module, &ann.resolved) {
+                    (None, _) => {
+                        if self.module.to_cell_for_lsp(ann.range.start()) != cell {
+                            return None;
+                        }
+                        Location {
+                            uri: uri.clone(),
+                            range: self.module.to_lsp_range(ann.range),
+                        }
+                    }
+                    // Only point at other files that exist on disk (or are open as such).
+                    (Some(_), Some(module)) => match module.path().details() {
+                        ModulePathDetails::FileSystem(path) | ModulePathDetails::Memory(path)
+                            if !module.is_notebook() =>
+                        {
+                            Location {
+                                uri: Url::from_file_path(&**path).ok()?,
+                                range: module.to_lsp_range(ann.range),
+                            }
+                        }
+                        _ => return None,
+                    },
+                    (Some(_), None) => return None,
+                };
+                Some(DiagnosticRelatedInformation {
+                    location,
+                    message: ann.label.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if related.is_empty() {
+            None
+        } else {
+            Some(related)
+        }
+    }
+
     pub fn get_notebook_cell(&self) -> Option<usize> {
         self.module.to_cell_for_lsp(self.range().start())
     }
@@ -434,6 +562,21 @@ impl Error {
     /// underlined spans with labels in the source snippet.
     pub fn with_annotation(mut self, range: TextRange, label: String) -> Self {
         self.secondary_annotations.push(SecondaryAnnotation {
+            module: None,
+            resolved: None,
+            range,
+            label: label.into_boxed_str(),
+        });
+        self
+    }
+
+    /// Add a secondary labeled annotation in `module`, which may be another file.
+    pub fn with_annotation_in(mut self, module: Module, range: TextRange, label: String) -> Self {
+        let module =
+            (module.path() != self.module.path()).then(|| (module.name(), module.path().dupe()));
+        self.secondary_annotations.push(SecondaryAnnotation {
+            module,
+            resolved: None,
             range,
             label: label.into_boxed_str(),
         });
@@ -473,7 +616,7 @@ impl Error {
         }
     }
 
-    pub fn is_ignored(&self, enabled_ignores: &SmallSet<Tool>) -> bool {
+    pub fn is_ignored(&self, enabled_ignores: &SmallSet<Tool>, require_codes: bool) -> bool {
         // UnusedIgnore errors cannot be suppressed - this prevents infinite loops
         // where suppressing an unused-ignore creates another unused-ignore.
         if self.error_kind == ErrorKind::UnusedIgnore {
@@ -483,7 +626,7 @@ impl Error {
         // `# pyrefly: ignore[bad-override]` also suppresses `bad-override-mutable-attribute`.
         self.error_kind.suppression_names().any(|name| {
             self.module
-                .is_ignored(&self.display_range, name, enabled_ignores)
+                .is_ignored(&self.display_range, name, enabled_ignores, require_codes)
         })
     }
 
@@ -499,6 +642,22 @@ impl Error {
     pub fn quick_fixes(&self) -> &[ErrorQuickFix] {
         &self.quick_fixes
     }
+
+    /// The other modules that secondary annotations point into.
+    pub fn annotation_modules(&self) -> impl Iterator<Item = &(ModuleName, ModulePath)> {
+        self.secondary_annotations
+            .iter()
+            .filter_map(|ann| ann.module.as_ref())
+    }
+
+    /// Attach the current version of each module that a secondary annotation points into.
+    pub fn resolve_annotation_modules(&mut self, lookup: impl Fn(&ModulePath) -> Option<Module>) {
+        for ann in &mut self.secondary_annotations {
+            if let Some((_, path)) = &ann.module {
+                ann.resolved = lookup(path);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -507,7 +666,6 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::Arc;
 
-    use pyrefly_python::module_name::ModuleName;
     use ruff_text_size::TextSize;
 
     use super::*;
@@ -636,6 +794,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_with_context_lines() {
+        let module_info = Module::new(
+            ModuleName::from_str("test"),
+            ModulePath::filesystem(PathBuf::from("test.py")),
+            Arc::new("a = 1\nb = 2\nc = d\ne = 4\n".to_owned()),
+        );
+        let error = Error::new(
+            module_info,
+            TextRange::new(TextSize::new(16), TextSize::new(17)),
+            "oops".to_owned(),
+            Vec::new(),
+            ErrorKind::UnknownName,
+        );
+        let mut output = Vec::new();
+        ErrorRenderer::plain(&mut output)
+            .with_context_lines(1)
+            .write(&error, Path::new(""), true)
+            .unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"ERROR oops [unknown-name]
+ --> test.py:3:5
+  |
+2 | b = 2
+3 | c = d
+  |     ^
+4 | e = 4
+  |
+"#,
+        );
+    }
+
+    #[test]
+    fn test_error_with_annotation_in_other_module() {
+        let module = |name: &str, contents: &str| {
+            Module::new(
+                ModuleName::from_str(name),
+                ModulePath::filesystem(PathBuf::from(format!("{name}.py"))),
+                Arc::new(contents.to_owned()),
+            )
+        };
+        let child = module("child", "x = 1\n");
+        let base = module("base", "y = 2\n");
+        let mut error = Error::new(
+            child.clone(),
+            TextRange::new(TextSize::new(0), TextSize::new(1)),
+            "oops".to_owned(),
+            Vec::new(),
+            ErrorKind::BadOverride,
+        )
+        .with_annotation_in(
+            child,
+            TextRange::new(TextSize::new(4), TextSize::new(5)),
+            "same module".to_owned(),
+        )
+        .with_annotation_in(
+            base.clone(),
+            TextRange::new(TextSize::new(0), TextSize::new(1)),
+            "other module".to_owned(),
+        );
+        assert_eq!(error.secondary_annotations()[0].module, None);
+        assert_eq!(
+            error.secondary_annotations()[1].module,
+            Some((base.name(), base.path().dupe()))
+        );
+        // Until the other module is resolved, its annotation isn't rendered.
+        let output = render_error(&error, Path::new(""), true);
+        assert!(!output.contains("other module"), "{output}");
+        error.resolve_annotation_modules(|path| (path == base.path()).then(|| base.dupe()));
+        let output = render_error(&error, Path::new(""), true);
+        assert!(output.contains("base.py:1:1"), "{output}");
+        assert!(output.contains("other module"), "{output}");
+    }
+
+    /// Argument errors point at the parameter when the function is in the same module, and
+    /// override errors at the overridden method, which may be in another module.
+    #[test]
+    fn test_related_location_annotations() {
+        let mut env = TestEnv::new();
+        env.add("base", "class A:\n    def f(self) -> int: ...\n");
+        env.add(
+            "main",
+            r#"
+from base import A
+class B(A):
+    def f(self) -> str: ...  # E: overrides parent class `A` in an inconsistent manner
+def g(x: int) -> None: ...
+g("")  # E: Argument `Literal['']` is not assignable to parameter `x`
+"#,
+        );
+        let (state, handle) = env.to_state();
+        let errors = state
+            .transaction()
+            .get_errors(&[handle("main")])
+            .collect_errors()
+            .ordinary;
+        assert_eq!(errors.len(), 2);
+        let annotations = errors[0].secondary_annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(&*annotations[0].label, "overridden method here");
+        assert_eq!(
+            annotations[0].module.as_ref().map(|(name, _)| *name),
+            Some(ModuleName::from_str("base"))
+        );
+        let annotations = errors[1].secondary_annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(&*annotations[0].label, "parameter declared here");
+        assert_eq!(annotations[0].module, None);
+        assert_eq!(errors[1].module().code_at(annotations[0].range), "x");
+    }
+
     /// Integration test: verify that binary operator errors from the type checker
     /// produce secondary annotations labeling both operands with their types.
     #[test]
@@ -657,4 +927,84 @@ def f(x: None) -> None:
         assert_eq!(&*annotations[0].label, "has type `None`");
         assert_eq!(&*annotations[1].label, "has type `Literal[2]`");
     }
+
+    /// A type mismatch on a name merged from several branches labels the
+    /// assignment in each branch with the type it contributed.
+    #[test]
+    fn test_flow_merge_error_has_branch_annotations() {
+        let code = r#"
+def f(b: bool, i: int) -> None:
+    if b:
+        x = i
+    else:
+        x = None
+    y: str = x  # E: `int | None` is not assignable to `str`
+"#;
+        let (state, handle) = TestEnv::one("main", code).to_state();
+        let errors = state
+            .transaction()
+            .get_errors(&[handle("main")])
+            .collect_errors()
+            .ordinary;
+        assert_eq!(errors.len(), 1);
+        let annotations = errors[0].secondary_annotations();
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(&*annotations[0].label, "has type `int` on this branch");
+        assert_eq!(&*annotations[1].label, "has type `None` on this branch");
+        // Each label points at the condition that picks its branch.
+        assert_eq!(errors[0].module().code_at(annotations[0].range), "b");
+        assert_eq!(errors[0].module().code_at(annotations[1].range), "else");
+    }
+
+    /// A missing attribute on an optional name labels where it last got a value that may be
+    /// `None`, which is where a `None` check could go.
+    #[test]
+    fn test_optional_attribute_error_has_none_source_annotations() {
+        let code = r#"
+def f(b: bool, s: str | None) -> None:
+    if b:
+        x = s
+    else:
+        x = "hello"
+    x.upper()  # E: Object of class `NoneType` has no attribute `upper`
+    s.upper()  # E: Object of class `NoneType` has no attribute `upper`
+"#;
+        let (state, handle) = TestEnv::one("main", code).to_state();
+        let errors = state
+            .transaction()
+            .get_errors(&[handle("main")])
+            .collect_errors()
+            .ordinary;
+        assert_eq!(errors.len(), 2);
+        let lines = |error: &Error| {
+            error
+                .secondary_annotations()
+                .iter()
+                .map(|ann| {
+                    (
+                        error
+                            .module()
+                            .display_range(ann.range)
+                            .start
+                            .line_within_file(),
+                        ann.label.to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines(&errors[0]),
+            vec![(
+                LineNumber::from_zero_indexed(3),
+                "`x` may be `None` here".to_owned()
+            )]
+        );
+        assert_eq!(
+            lines(&errors[1]),
+            vec![(
+                LineNumber::from_zero_indexed(1),
+                "`s` may be `None` here".to_owned()
+            )]
+        );
+    }
 }
@@ -0,0 +1,261 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Applying the machine-applicable fixes attached to errors, for `pyrefly check --fix`.
+//!
+//! Only [`ErrorQuickFix::TextEdits`] fixes are applied. A fix is applied all-or-nothing:
+//! if any of its edits overlaps an edit of a fix already chosen for the same file, the
+//! whole fix is skipped and left for a later run. A file is only rewritten if its
+//! contents on disk still match what was checked and the fixed source still parses,
+//! so a second run over an unchanged tree finds nothing left to do.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_util::fs_anyhow;
+use ruff_python_ast::PySourceType;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::small_map::SmallMap;
+use tracing::warn;
+
+use crate::error::error::Error;
+use crate::error::error::ErrorQuickFix;
+
+/// What [`apply_fixes`] did.
+#[derive(Debug, Default)]
+pub struct FixOutcome {
+    /// A unified diff of every change, without context lines.
+    pub diff: String,
+    /// Number of fixes applied (or, when not writing, that would be applied).
+    pub applied: usize,
+    /// Number of fixes skipped because they conflicted with another fix.
+    pub skipped: usize,
+    /// Number of files changed.
+    pub files: usize,
+}
+
+/// Apply the text-edit fixes attached to `errors`. When `write` is false, only the diff
+/// is computed and no file is touched.
+pub fn apply_fixes(errors: &[Error], write: bool) -> FixOutcome {
+    let mut fixes_by_module: SmallMap<&Module, Vec<&[(TextRange, String)]>> = SmallMap::new();
+    for error in errors {
+        for fix in error.quick_fixes() {
+            if let ErrorQuickFix::TextEdits { edits, .. } = fix
+                && !edits.is_empty()
+            {
+                fixes_by_module
+                    .entry(error.module())
+                    .or_default()
+                    .push(edits.as_slice());
+            }
+        }
+    }
+    let mut outcome = FixOutcome::default();
+    for (module, fixes) in fixes_by_module {
+        if !matches!(module.path().details(), ModulePathDetails::FileSystem(_))
+            || module.is_notebook()
+            || module.is_generated()
+        {
+            continue;
+        }
+        let path = module.path().as_path();
+        let source = module.contents().as_str();
+        if fs_anyhow::read_to_string(path).ok().as_deref() != Some(source) {
+            warn!(
+                "Not fixing `{}`: it changed since it was checked",
+                path.display()
+            );
+            continue;
+        }
+        let (edits, applied, skipped) = resolve_conflicts(fixes);
+        let fixed = apply_edits(source, &edits);
+        let source_type = if module.path().is_interface() {
+            PySourceType::Stub
+        } else {
+            PySourceType::Python
+        };
+        let (_, parse_errors, _) = Ast::parse(&fixed, source_type);
+        if !parse_errors.is_empty() {
+            warn!(
+                "Not fixing `{}`: the fixed file would not parse",
+                path.display()
+            );
+            continue;
+        }
+        if write && let Err(e) = fs_anyhow::write(path, fixed) {
+            warn!("{e:#}");
+            continue;
+        }
+        outcome.diff.push_str(&render_diff(path, source, &edits));
+        outcome.applied += applied;
+        outcome.skipped += skipped;
+        outcome.files += 1;
+    }
+    outcome
+}
+
+fn conflicts(a: TextRange, b: TextRange) -> bool {
+    // Two insertions at the same point conflict too, since their order would be arbitrary.
+    a.start() == b.start() || (a.start() < b.end() && b.start() < a.end())
+}
+
+/// Choose the fixes to apply to one file, in source order, skipping any fix with an edit
+/// that conflicts with an edit already chosen. Returns the chosen edits sorted by position,
+/// and the number of fixes applied and skipped.
+fn resolve_conflicts(
+    mut fixes: Vec<&[(TextRange, String)]>,
+) -> (Vec<(TextRange, String)>, usize, usize) {
+    fixes.sort_by_key(|edits| edits.iter().map(|(range, _)| range.start()).min());
+    let mut chosen: Vec<(TextRange, String)> = Vec::new();
+    let mut skipped = 0;
+    let mut applied = 0;
+    for edits in fixes {
+        let clashes = edits.iter().enumerate().any(|(i, (range, _))| {
+            edits[..i]
+                .iter()
+                .any(|(other, _)| conflicts(*range, *other))
+                || chosen.iter().any(|(other, _)| conflicts(*range, *other))
+        });
+        if clashes {
+            skipped += 1;
+        } else {
+            applied += 1;
+            chosen.extend(edits.iter().cloned());
+        }
+    }
+    chosen.sort_by_key(|(range, _)| range.start());
+    (chosen, applied, skipped)
+}
+
+/// Apply sorted, non-overlapping `edits` to `source`.
+fn apply_edits(source: &str, edits: &[(TextRange, String)]) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+    for (range, text) in edits {
+        result.push_str(&source[last..range.start().to_usize()]);
+        result.push_str(text);
+        last = range.end().to_usize();
+    }
+    result.push_str(&source[last..]);
+    result
+}
+
+/// The 0-indexed line containing byte `offset`.
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].bytes().filter(|b| *b == b'\n').count()
+}
+
+/// Byte offset of the start of 0-indexed `line`, or the end of `source` if past it.
+fn line_start(source: &str, line: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+    source
+        .match_indices('\n')
+        .nth(line - 1)
+        .map_or(source.len(), |(i, _)| i + 1)
+}
+
+/// A unified diff of the lines touched by `edits`.
+fn render_diff(path: &Path, source: &str, edits: &[(TextRange, String)]) -> String {
+    // Group edits into hunks of whole lines: (first line, last line, edits). Edits on
+    // the same or adjacent lines share a hunk.
+    let mut hunks: Vec<(usize, usize, Vec<&(TextRange, String)>)> = Vec::new();
+    for edit in edits {
+        let (range, _) = edit;
+        let first = line_of(source, range.start().to_usize());
+        let end = range.end().to_usize();
+        // An edit that ends just after a newline doesn't touch the next line.
+        let last = if end > range.start().to_usize() && source[..end].ends_with('\n') {
+            line_of(source, end - 1)
+        } else {
+            line_of(source, end)
+        };
+        match hunks.last_mut() {
+            Some((_, hunk_last, hunk_edits)) if first <= *hunk_last + 1 => {
+                *hunk_last = (*hunk_last).max(last);
+                hunk_edits.push(edit);
+            }
+            _ => hunks.push((first, last, vec![edit])),
+        }
+    }
+    let mut out = String::new();
+    if hunks.is_empty() {
+        return out;
+    }
+    let path = path.display();
+    writeln!(out, "--- a/{path}\n+++ b/{path}").unwrap();
+    let mut delta: isize = 0;
+    for (first, last, hunk_edits) in hunks {
+        let start = line_start(source, first);
+        let end = line_start(source, last + 1);
+        let old = &source[start..end];
+        // `start` is a line start within `source`, whose edit ranges are `TextSize`s already.
+        let start_offset =
+            TextSize::try_from(start).expect("line start is within a source addressed by TextSize");
+        let shifted = hunk_edits
+            .iter()
+            .map(|(range, text)| (*range - start_offset, text.clone()))
+            .collect::<Vec<_>>();
+        let new = apply_edits(old, &shifted);
+        let old_count = old.lines().count();
+        let new_count = new.lines().count();
+        let new_first = (first as isize + delta) as usize;
+        writeln!(
+            out,
+            "@@ -{},{old_count} +{},{new_count} @@",
+            first + 1,
+            new_first + 1
+        )
+        .unwrap();
+        for line in old.lines() {
+            writeln!(out, "-{line}").unwrap();
+        }
+        for line in new.lines() {
+            writeln!(out, "+{line}").unwrap();
+        }
+        delta += new_count as isize - old_count as isize;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(start: u32, end: u32, text: &str) -> (TextRange, String) {
+        (
+            TextRange::new(TextSize::new(start), TextSize::new(end)),
+            text.to_owned(),
+        )
+    }
+
+    #[test]
+    fn test_conflicting_fix_is_skipped_whole() {
+        let first = [edit(0, 3, "a")];
+        let second = [edit(10, 12, ""), edit(2, 4, "b")];
+        let third = [edit(5, 6, "c")];
+        let (edits, applied, skipped) = resolve_conflicts(vec![&second, &third, &first]);
+        assert_eq!(edits, vec![edit(0, 3, "a"), edit(5, 6, "c")]);
+        assert_eq!((applied, skipped), (2, 1));
+    }
+
+    #[test]
+    fn test_apply_and_diff() {
+        let source = "x = 1  # type: ignore\n# pyrefly: ignore\ny = 2\n";
+        let edits = vec![edit(5, 21, ""), edit(22, 40, "")];
+        assert_eq!(apply_edits(source, &edits), "x = 1\ny = 2\n");
+        assert_eq!(
+            render_diff(Path::new("m.py"), source, &edits),
+            "--- a/m.py\n+++ b/m.py\n@@ -1,2 +1,1 @@\n-x = 1  # type: ignore\n-# pyrefly: ignore\n+x = 1\n"
+        );
+    }
+}
@@ -14,6 +14,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::error::error::Error;
+use crate::error::error::ErrorQuickFix;
 
 pub(crate) fn severity_to_str(severity: Severity) -> String {
     match severity {
@@ -51,6 +52,46 @@ pub struct LegacyError {
     /// Optional notebook cell number for errors in notebook files
     #[serde(skip_serializing_if = "Option::is_none")]
     cell: Option<usize>,
+    /// Machine-applicable fixes. Not part of the Pyre1 error format.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<LegacyFix>,
+}
+
+/// A fix for a [`LegacyError`], as replacements in the same file.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LegacyFix {
+    title: String,
+    edits: Vec<LegacyEdit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LegacyEdit {
+    line: usize,
+    column: usize,
+    stop_line: usize,
+    stop_column: usize,
+    replacement: String,
+}
+
+impl LegacyFix {
+    fn from_quick_fix(error: &Error, fix: &ErrorQuickFix) -> Option<Self> {
+        let ErrorQuickFix::TextEdits { title, edits } = fix else {
+            return None;
+        };
+        Some(Self {
+            title: title.clone(),
+            edits: edits.map(|(range, replacement)| {
+                let range = error.module().display_range(*range);
+                LegacyEdit {
+                    line: range.start.line_within_cell().get() as usize,
+                    column: range.start.column().get() as usize,
+                    stop_line: range.end.line_within_cell().get() as usize,
+                    stop_column: range.end.column().get() as usize,
+                    replacement: replacement.clone(),
+                }
+            }),
+        })
+    }
 }
 
 impl LegacyError {
@@ -73,6 +114,11 @@ impl LegacyError {
             description: error.msg(),
             concise_description: error.msg_header().to_owned(),
             severity: severity_to_str(error.severity()),
+            fixes: error
+                .quick_fixes()
+                .iter()
+                .filter_map(|fix| LegacyFix::from_quick_fix(error, fix))
+                .collect(),
         }
     }
 }
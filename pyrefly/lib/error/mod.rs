@@ -12,7 +12,9 @@ pub mod context;
 pub mod display;
 pub mod error;
 pub mod expectation;
+pub mod fix;
 pub mod legacy;
+pub mod sarif;
 pub mod signature_diff;
 pub mod style;
 pub mod summarize;
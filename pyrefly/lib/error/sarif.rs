@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use pyrefly_config::error_kind::Severity;
+use pyrefly_util::unix_path::str_path_to_unix_string;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::error::error::Error;
+
+pub(crate) fn severity_to_sarif_level(severity: Severity) -> Option<&'static str> {
+    match severity {
+        Severity::Ignore => None,
+        Severity::Info => Some("note"),
+        Severity::Warn => Some("warning"),
+        Severity::Error => Some("error"),
+    }
+}
+
+/// A SARIF 2.1.0 log with a single run
+/// <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>.
+///
+/// Used to upload errors to tools that consume SARIF, like GitHub code scanning
+/// <https://docs.github.com/en/code-security/code-scanning/integrating-with-code-scanning/sarif-support-for-code-scanning>.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: String,
+    information_uri: String,
+    rules: Vec<SarifRule>,
+}
+
+/// One rule per error kind that occurs in the results.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    help_uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    level: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+/// Lines and columns are 1-based, and `end_column` is exclusive.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+}
+
+impl SarifResult {
+    fn from_error(relative_to: &Path, error: &Error) -> Option<Self> {
+        let level = severity_to_sarif_level(error.severity())?.to_owned();
+        let range = error.display_range();
+        Some(Self {
+            rule_id: error.error_kind().to_name().to_owned(),
+            level,
+            message: SarifMessage { text: error.msg() },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: str_path_to_unix_string(error.path_string_with_fragment(relative_to)),
+                    },
+                    region: SarifRegion {
+                        start_line: range.start.line_within_cell().get(),
+                        start_column: range.start.column().get(),
+                        end_line: range.end.line_within_cell().get(),
+                        end_column: range.end.column().get(),
+                    },
+                },
+            }],
+        })
+    }
+}
+
+impl SarifLog {
+    pub fn from_errors(relative_to: &Path, errors: &[Error]) -> Self {
+        let mut rules = BTreeMap::new();
+        let mut results = Vec::new();
+        for error in errors {
+            if let Some(result) = SarifResult::from_error(relative_to, error) {
+                let kind = error.error_kind();
+                rules
+                    .entry(kind.to_name())
+                    .or_insert_with(|| kind.docs_url());
+                results.push(result);
+            }
+        }
+        Self {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json".to_owned(),
+            version: "2.1.0".to_owned(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "pyrefly".to_owned(),
+                        information_uri: "https://pyrefly.org".to_owned(),
+                        rules: rules
+                            .into_iter()
+                            .map(|(id, help_uri)| SarifRule {
+                                id: id.to_owned(),
+                                help_uri,
+                            })
+                            .collect(),
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use pyrefly_config::error_kind::ErrorKind;
+    use pyrefly_python::module::Module;
+    use pyrefly_python::module_name::ModuleName;
+    use pyrefly_python::module_path::ModulePath;
+    use ruff_text_size::TextRange;
+    use ruff_text_size::TextSize;
+
+    use super::*;
+
+    fn sample_error(kind: ErrorKind, severity: Severity) -> Error {
+        let module = Module::new(
+            ModuleName::from_str("sample"),
+            ModulePath::filesystem(PathBuf::from("/repo/pkg/foo.py")),
+            Arc::new("x = 1\n".to_owned()),
+        );
+        Error::new(
+            module,
+            TextRange::new(TextSize::from(0), TextSize::from(1)),
+            "Sample error message".to_owned(),
+            vec!["Additional details".to_owned()],
+            kind,
+        )
+        .with_severity(severity)
+    }
+
+    #[test]
+    fn from_errors_includes_location_and_rules() {
+        let errors = [
+            sample_error(ErrorKind::BadAssignment, Severity::Error),
+            sample_error(ErrorKind::BadArgumentType, Severity::Warn),
+            sample_error(ErrorKind::BadAssignment, Severity::Info),
+        ];
+        let log = SarifLog::from_errors(Path::new("/repo"), &errors);
+        let run = &log.runs[0];
+        assert_eq!(
+            run.tool.driver.rules,
+            vec![
+                SarifRule {
+                    id: "bad-argument-type".to_owned(),
+                    help_uri: ErrorKind::BadArgumentType.docs_url(),
+                },
+                SarifRule {
+                    id: "bad-assignment".to_owned(),
+                    help_uri: ErrorKind::BadAssignment.docs_url(),
+                },
+            ]
+        );
+        let levels: Vec<_> = run.results.iter().map(|r| r.level.as_str()).collect();
+        assert_eq!(levels, vec!["error", "warning", "note"]);
+        let result = &run.results[0];
+        assert_eq!(result.rule_id, "bad-assignment");
+        assert_eq!(
+            result.message.text,
+            "Sample error message\n  Additional details"
+        );
+        let location = &result.locations[0].physical_location;
+        assert_eq!(location.artifact_location.uri, "pkg/foo.py");
+        assert_eq!(
+            location.region,
+            SarifRegion {
+                start_line: 1,
+                start_column: 1,
+                end_line: 1,
+                end_column: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn from_errors_skips_ignored() {
+        let errors = [sample_error(ErrorKind::BadAssignment, Severity::Ignore)];
+        let log = SarifLog::from_errors(Path::new("/repo"), &errors);
+        assert!(log.runs[0].results.is_empty());
+        assert!(log.runs[0].tool.driver.rules.is_empty());
+    }
+}
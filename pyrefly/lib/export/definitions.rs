@@ -95,6 +95,13 @@ impl DefinitionStyle {
     }
 }
 
+/// The literal value assigned to a `Final` name, for the literals we track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FinalValue {
+    Str(String),
+    Bool(bool),
+}
+
 #[derive(Debug, Clone)]
 pub struct Definition {
     /// If the definition occurs multiple times, the lowest `DefinitionStyle` is used (e.g. prefer `Local`).
@@ -168,10 +175,11 @@ pub struct Definitions {
     pub implicitly_imported_submodules: SmallSet<Name>,
     /// Deprecated names that are defined in this module.
     pub deprecated: SmallMap<Name, Deprecation>,
-    /// Names that are marked `Final`, with their string literal value if assigned one.
-    /// The `Option<String>` is `Some` when the RHS is a string literal (e.g. `X: Final = "x"`),
-    /// used to resolve Final variable references in synthesized class field names.
-    pub final_names: SmallMap<Name, Option<String>>,
+    /// Names that are marked `Final`, with their literal value if assigned a string or bool
+    /// literal (e.g. `X: Final = "x"` or `DEBUG: Final = False`). String values resolve Final
+    /// variable references in synthesized class field names, and bool values let importers
+    /// statically evaluate `if` tests on them.
+    pub final_names: SmallMap<Name, Option<FinalValue>>,
     /// Special exports defined in this module
     pub special_exports: SmallMap<Name, SpecialExport>,
     /// Names that are read (not just defined) in this scope.
@@ -691,9 +699,10 @@ impl DefinitionsBuilder {
                     }
                 }
                 let has_final_annotation = is_final_annotation(&x.annotation);
-                let final_string_value = if has_final_annotation {
+                let final_value = if has_final_annotation {
                     match x.value.as_deref() {
-                        Some(Expr::StringLiteral(s)) => Some(s.value.to_string()),
+                        Some(Expr::StringLiteral(s)) => Some(FinalValue::Str(s.value.to_string())),
+                        Some(Expr::BooleanLiteral(b)) => Some(FinalValue::Bool(b.value)),
                         _ => None,
                     }
                 } else {
@@ -710,9 +719,7 @@ impl DefinitionsBuilder {
                             ),
                         );
                         if has_final_annotation {
-                            self.inner
-                                .final_names
-                                .insert(x.id.clone(), final_string_value);
+                            self.inner.final_names.insert(x.id.clone(), final_value);
                         }
                     }
                     _ => self.expr_lvalue(&x.target),
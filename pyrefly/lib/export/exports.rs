@@ -28,6 +28,7 @@ use crate::export::definitions::DefinitionStyle;
 use crate::export::definitions::Definitions;
 use crate::export::definitions::DunderAllEntry;
 use crate::export::definitions::DunderAllKind;
+use crate::export::definitions::FinalValue;
 use crate::export::special::SpecialExport;
 use crate::module::module_info::ModuleInfo;
 use crate::state::loader::FindingOrError;
@@ -74,6 +75,8 @@ pub struct ExportOrigin {
     /// The module and name where the export is ultimately defined.
     pub origin: (ModuleName, Name),
     pub is_final: bool,
+    /// If the export is `Final` and assigned a bool literal, its value.
+    pub final_bool_value: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -271,6 +274,14 @@ impl Exports {
         self.docstring_range
     }
 
+    /// If `name` is defined in this module as `Final` and assigned a bool literal, its value.
+    pub fn final_bool_value(&self, name: &Name) -> Option<bool> {
+        match self.definitions.final_names.get(name)? {
+            Some(FinalValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
     /// If `position` is inside a user-specified `__all__` string entry, return its range and name.
     pub fn dunder_all_name_at(&self, position: TextSize) -> Option<(TextRange, Name)> {
         if self.definitions.dunder_all.kind != DunderAllKind::Specified {
@@ -446,6 +457,28 @@ impl Exports {
             .is_some_and(|definition| matches!(definition.style, DefinitionStyle::ImportAsEq(_)))
     }
 
+    /// The range in this module that publicly re-exports `name` from module `from`: the imported
+    /// name, or the `*` of a wildcard import. An import is a public re-export if it is written
+    /// `from x import y as y`, if the name is listed in `__all__`, or if this is an `__init__`
+    /// module. `None` if `name` isn't re-exported here.
+    pub fn reexport_range(&self, name: &Name, from: ModuleName) -> Option<TextRange> {
+        let public = self.is_init
+            || self
+                .get_explicit_dunder_all_names_iter()
+                .is_some_and(|mut names| names.any(|x| x == name));
+        if let Some(definition) = self.definitions.definitions.get(name)
+            && definition.style.is_import()
+        {
+            return (public || matches!(definition.style, DefinitionStyle::ImportAsEq(_)))
+                .then_some(definition.range);
+        }
+        if public {
+            self.definitions.import_all.get(&from).copied()
+        } else {
+            None
+        }
+    }
+
     /// Returns the range of the unresolvable `__all__` RHS, if applicable.
     pub fn unresolvable_dunder_all_range(&self) -> Option<TextRange> {
         match self.definitions.dunder_all.kind {
@@ -517,6 +550,7 @@ mod tests {
             ExportOrigin {
                 origin: (module, name.clone()),
                 is_final: false,
+                final_bool_value: None,
             }
         }
     }
@@ -596,6 +630,20 @@ _x = 2
         eq_wildcards(&b, &imports, &["a"]);
     }
 
+    #[test]
+    fn test_reexport_range() {
+        let from = ModuleName::from_str("a");
+        let exports = mk_exports(
+            "from a import x as x\nfrom a import y\nfrom a import z\n__all__ = ['z']",
+            ModuleStyle::Executable,
+        );
+        assert!(exports.reexport_range(&Name::new("x"), from).is_some());
+        assert!(exports.reexport_range(&Name::new("y"), from).is_none());
+        assert!(exports.reexport_range(&Name::new("z"), from).is_some());
+        let exports = mk_exports("from a import *", ModuleStyle::Executable);
+        assert!(exports.reexport_range(&Name::new("w"), from).is_none());
+    }
+
     #[test]
     fn test_cyclic() {
         let a = mk_exports("from b import *", ModuleStyle::Interface);
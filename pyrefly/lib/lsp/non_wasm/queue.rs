@@ -62,6 +62,9 @@ pub enum LspEvent {
     /// Inform the server that some configs' find caches are now invalid (stored in
     /// `server.invalidated_configs`), and that a new type check must occur.
     InvalidateConfigFind,
+    /// Validate open files now that a debounced burst of edits has settled. Only produced by
+    /// `recv` once the instant passed to [`LspQueue::schedule_validation`] has elapsed.
+    ValidateOpenFiles,
     LspResponse(Response),
     LspRequest(Request),
     Exit,
@@ -73,6 +76,7 @@ impl LspEvent {
             Self::RecheckFinished => "RecheckFinished".to_owned(),
             Self::CancelRequest(_) => "CancelRequest".to_owned(),
             Self::InvalidateConfigFind => "InvalidateConfigFind".to_owned(),
+            Self::ValidateOpenFiles => "ValidateOpenFiles".to_owned(),
             Self::DidOpenTextDocument(_) => "DidOpenTextDocument".to_owned(),
             Self::DidChangeTextDocument(_) => "DidChangeTextDocument".to_owned(),
             Self::DidCloseTextDocument(_) => "DidCloseTextDocument".to_owned(),
@@ -128,6 +132,7 @@ impl LspEvent {
             | Self::DidSaveNotebookDocument(_)
             | Self::DidChangeNotebookDocument(_)
             | Self::InvalidateConfigFind
+            | Self::ValidateOpenFiles
             | Self::Exit => LspEventKind::Mutation,
             Self::LspRequest(_) => LspEventKind::Query,
         }
@@ -153,6 +158,9 @@ pub struct LspQueue {
     /// reflect the full debounce wait (matching how `send` timestamps events).
     /// Only one is held at a time; see [`LspQueue::send_delayed`].
     delayed: Mutex<Option<(Request, Instant, Instant)>>,
+    /// A pending validation of open files, as the instant it becomes ready and the instant it
+    /// was scheduled. See [`LspQueue::schedule_validation`].
+    delayed_validation: Mutex<Option<(Instant, Instant)>>,
     normal: (
         Sender<(usize, LspEvent, Instant)>,
         Receiver<(usize, LspEvent, Instant)>,
@@ -170,6 +178,7 @@ impl LspQueue {
             last_mutation: AtomicUsize::new(0),
             last_edit_time: Mutex::new(None),
             delayed: Mutex::new(None),
+            delayed_validation: Mutex::new(None),
             normal: crossbeam_channel::unbounded(),
             priority: crossbeam_channel::unbounded(),
         }
@@ -206,15 +215,20 @@ impl LspQueue {
     /// Due to race conditions, we might say false when there is a subsequent mutation,
     /// but we will never say true when there is not.
     pub fn recv(&self) -> Result<(bool, LspEvent, Instant), RecvError> {
-        // If a delayed request is held, wake once its window expires so we can
-        // deliver it. The slot is only written by the same thread that calls
-        // `recv` (via `send_delayed` during event processing), so it can't change
-        // while we block here.
-        let deadline = self
+        // If a delayed request or validation is held, wake once its window
+        // expires so we can deliver it. The slots are only written by the same
+        // thread that calls `recv` (via `send_delayed` and `schedule_validation`
+        // during event processing), so they can't change while we block here.
+        let request_deadline = self
             .delayed
             .lock()
             .as_ref()
             .map(|(_, ready_at, _)| *ready_at);
+        let validation_deadline = self.delayed_validation.lock().map(|(ready_at, _)| ready_at);
+        let deadline = request_deadline
+            .into_iter()
+            .chain(validation_deadline)
+            .min();
 
         let mut event_receiver_selector = Select::new_biased();
         // Biased selector will pick the receiver with lower index over higher ones,
@@ -226,12 +240,25 @@ impl LspQueue {
             Some(deadline) => match event_receiver_selector.select_deadline(deadline) {
                 Ok(selected) => selected,
                 Err(_) => {
+                    let last_mutation = self.last_mutation.load(Ordering::Relaxed);
+                    // Whichever deadline came first has now passed.
+                    if validation_deadline == Some(deadline) {
+                        let (_ready_at, scheduled_at) = self
+                            .delayed_validation
+                            .lock()
+                            .take()
+                            .expect("a validation deadline is only set while one is scheduled");
+                        return Ok((
+                            last_mutation != 0,
+                            LspEvent::ValidateOpenFiles,
+                            scheduled_at,
+                        ));
+                    }
                     let (request, _ready_at, enqueued_at) = self
                         .delayed
                         .lock()
                         .take()
                         .expect("a deadline is only set while a delayed request is held");
-                    let last_mutation = self.last_mutation.load(Ordering::Relaxed);
                     // Report the enqueue instant (not `ready_at`) as the queue
                     // time so downstream latency metrics see the full wait.
                     return Ok((
@@ -269,6 +296,14 @@ impl LspQueue {
             .map(|(request, ..)| request)
     }
 
+    /// Deliver [`LspEvent::ValidateOpenFiles`] from `recv` at `ready_at`. This
+    /// debounces validation after edits: scheduling again replaces the pending
+    /// validation, so a burst of keystrokes results in a single validation once
+    /// typing pauses.
+    pub fn schedule_validation(&self, ready_at: Instant) {
+        *self.delayed_validation.lock() = Some((ready_at, Instant::now()));
+    }
+
     /// How long since the most recent document edit was enqueued, or `None` if
     /// no edit has happened yet.
     pub fn time_since_last_edit(&self) -> Option<Duration> {
@@ -428,4 +463,23 @@ mod tests {
             "a new edit should reset the debounce clock"
         );
     }
+
+    #[test]
+    fn test_schedule_validation_coalesces() {
+        let queue = LspQueue::new();
+        let start = Instant::now();
+        queue.schedule_validation(start + Duration::from_secs(60));
+        // Rescheduling replaces the pending validation rather than adding another.
+        queue.schedule_validation(start + Duration::from_millis(20));
+
+        let (_, event, _) = queue.recv().unwrap();
+        assert!(matches!(event, LspEvent::ValidateOpenFiles));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // Queued events are still delivered first, and nothing else is pending.
+        queue.send(non_edit()).unwrap();
+        let (_, event, _) = queue.recv().unwrap();
+        assert!(matches!(event, LspEvent::DidChangeConfiguration(_)));
+        assert!(queue.delayed_validation.lock().is_none());
+    }
 }
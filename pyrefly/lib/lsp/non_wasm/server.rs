@@ -210,6 +210,7 @@ use lsp_types::request::WorkspaceSymbolRequest;
 use pyrefly_build::handle::Handle;
 use pyrefly_build::source_db::SourceDatabase;
 use pyrefly_config::config::ConfigSource;
+use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_config::error_kind::Severity;
 use pyrefly_python::PYTHON_EXTENSIONS;
 use pyrefly_python::ast::Ast;
@@ -326,7 +327,7 @@ use crate::lsp::non_wasm::workspace::Workspace;
 use crate::lsp::non_wasm::workspace::Workspaces;
 use crate::lsp::wasm::completion::CompletionOptions as CompletionRequestOptions;
 use crate::lsp::wasm::completion::supports_snippet_completions;
-use crate::lsp::wasm::hover::get_hover;
+use crate::lsp::wasm::hover::get_hover_with_signature_line_width;
 use crate::lsp::wasm::notebook::DidChangeNotebookDocument;
 use crate::lsp::wasm::notebook::DidChangeNotebookDocumentParams;
 use crate::lsp::wasm::notebook::DidCloseNotebookDocument;
@@ -569,6 +570,11 @@ const PROGRESS_REPORT_INTERVAL: Duration = Duration::from_millis(100);
 /// `analysis.inlayHintDebounceMs`. See [`Server::inlay_hint_debounce_remaining`].
 const DEFAULT_INLAY_HINT_DEBOUNCE_MS: u64 = 150;
 
+/// Default validation debounce window, applied when the client doesn't set
+/// `analysis.validationDebounceMs`. Off by default, since edits that are already
+/// queued are coalesced anyway and a newer edit cancels an in-flight validation.
+const DEFAULT_VALIDATION_DEBOUNCE_MS: u64 = 0;
+
 struct LspProgressSubscriber<'a> {
     server: &'a Server,
     token: ProgressToken,
@@ -896,6 +902,11 @@ pub struct Server {
     /// we rely on file watchers to catch up.
     indexed_workspaces: Mutex<HashSet<PathBuf>>,
     cancellation_handles: Mutex<HashMap<RequestId, CancellationHandle>>,
+    /// The cancellation handle of the in-memory validation of open files running on the LSP
+    /// thread, if any. An incoming edit cancels it, since its results would be immediately stale.
+    in_memory_validation: Mutex<Option<CancellationHandle>>,
+    /// The most recently edited open file, which is checked ahead of the other open files.
+    last_edited_file: Mutex<Option<PathBuf>>,
     /// A thread pool for transactions run in the lsp_loop to avoid possibly waiting on thread pool
     /// operations in another thread.
     lsp_thread_pool: ThreadPool,
@@ -1166,6 +1177,7 @@ pub fn dispatch_lsp_events(server: &Server, reader: &mut MessageReader) {
                         .lsp_queue()
                         .send(LspEvent::DidOpenTextDocument(params))
                 } else if let Some(Ok(params)) = as_notification::<DidChangeTextDocument>(&x) {
+                    server.cancel_in_memory_validation();
                     server
                         .lsp_queue()
                         .send(LspEvent::DidChangeTextDocument(params))
@@ -1188,6 +1200,7 @@ pub fn dispatch_lsp_events(server: &Server, reader: &mut MessageReader) {
                         .lsp_queue()
                         .send(LspEvent::DidOpenNotebookDocument(params))
                 } else if let Some(Ok(params)) = as_notification::<DidChangeNotebookDocument>(&x) {
+                    server.cancel_in_memory_validation();
                     server
                         .lsp_queue()
                         .send(LspEvent::DidChangeNotebookDocument(params))
@@ -1799,6 +1812,13 @@ impl Server {
                 }
                 canceled_requests.insert(id);
             }
+            LspEvent::ValidateOpenFiles => {
+                self.validate_in_memory_and_commit_if_possible(
+                    ide_transaction_manager,
+                    telemetry_event,
+                    Some(&self.lsp_thread_pool),
+                );
+            }
             LspEvent::InvalidateConfigFind => {
                 let mut lock = self.invalidated_source_dbs.lock();
                 let invalidated_source_dbs = std::mem::take(&mut *lock);
@@ -2593,16 +2613,25 @@ impl Server {
         thread_count: ThreadCount,
         lsp_start_time: Instant,
     ) -> Self {
-        let folders = if let Some(capability) = &initialize_params.capabilities.workspace
+        let folders: Vec<PathBuf> = if let Some(capability) =
+            &initialize_params.capabilities.workspace
             && let Some(true) = capability.workspace_folders
-            && let Some(folders) = &initialize_params.workspace_folders
         {
-            folders
+            initialize_params
+                .workspace_folders
                 .iter()
+                .flatten()
                 .filter_map(|x| x.uri.to_file_path().ok())
                 .collect()
         } else {
-            Vec::new()
+            // Clients without workspace folder support open a single root, which they report
+            // through the deprecated `rootUri`. Treat it as the only workspace folder.
+            #[allow(deprecated)]
+            let root_uri = &initialize_params.root_uri;
+            root_uri
+                .iter()
+                .filter_map(|x| x.to_file_path().ok())
+                .collect()
         };
 
         let workspaces = Arc::new(Workspaces::new(Workspace::default(), &folders));
@@ -2647,6 +2676,8 @@ impl Server {
             indexed_configs: Mutex::new(HashSet::new()),
             indexed_workspaces: Mutex::new(HashSet::new()),
             cancellation_handles: Mutex::new(HashMap::new()),
+            in_memory_validation: Mutex::new(None),
+            last_edited_file: Mutex::new(None),
             lsp_thread_pool: ThreadPool::new(ThreadCount::NumThreads(
                 NonZeroUsize::new(8).unwrap(),
             )),
@@ -2686,11 +2717,11 @@ impl Server {
                 init_options.clone(),
                 server_mode,
             );
-            if let Some(workspace_folders) = &s.initialize_params.workspace_folders {
-                for folder in workspace_folders {
+            for root in s.workspaces.roots() {
+                if let Ok(uri) = Url::from_file_path(root) {
                     s.workspaces.apply_client_configuration(
                         &mut modified,
-                        &Some(folder.uri.clone()),
+                        &Some(uri),
                         init_options.clone(),
                         server_mode,
                     );
@@ -2809,8 +2840,21 @@ impl Server {
         telemetry: &mut TelemetryEvent,
         custom_thread_pool: Option<&ThreadPool>,
     ) -> Vec<Handle> {
-        let validate_start = Instant::now();
         let handles = self.get_open_file_handles();
+        let _ = self.run_open_files(transaction, &handles, telemetry, custom_thread_pool);
+        handles
+    }
+
+    /// Load the in-memory content of open files into the transaction and check `handles`,
+    /// stopping early if the transaction is cancelled.
+    fn run_open_files(
+        &self,
+        transaction: &mut Transaction<'_>,
+        handles: &[Handle],
+        telemetry: &mut TelemetryEvent,
+        custom_thread_pool: Option<&ThreadPool>,
+    ) -> Result<(), Cancelled> {
+        let validate_start = Instant::now();
         transaction.set_memory(
             self.open_files
                 .read()
@@ -2818,16 +2862,31 @@ impl Server {
                 .map(|x| (x.0.clone(), Some(Arc::new(x.1.to_file_contents()))))
                 .collect::<Vec<_>>(),
         );
-        transaction.run(&handles, Require::Everything, custom_thread_pool);
+        let result = transaction.run_cancellable(handles, Require::Everything, custom_thread_pool);
         telemetry.set_validate_duration(validate_start.elapsed());
-        handles
+        result
     }
 
-    /// Get handles for all currently open files.
+    /// Called from the dispatch thread when an edit arrives, so that the LSP thread stops
+    /// validating the previous contents and gets to the edit promptly.
+    fn cancel_in_memory_validation(&self) {
+        if let Some(handle) = self.in_memory_validation.lock().take() {
+            handle.cancel();
+        }
+    }
+
+    /// Get handles for all currently open files, with the most recently edited one first.
     fn get_open_file_handles(&self) -> Vec<Handle> {
-        self.open_files
-            .read()
-            .keys()
+        let last_edited = self.last_edited_file.lock().clone();
+        let open_files = self.open_files.read();
+        let last_edited = last_edited.filter(|x| open_files.contains_key(x));
+        last_edited
+            .iter()
+            .chain(
+                open_files
+                    .keys()
+                    .filter(|x| Some(*x) != last_edited.as_ref()),
+            )
             .map(|x| make_open_handle(&self.state, x))
             .collect()
     }
@@ -2874,10 +2933,16 @@ impl Server {
                         {
                             None
                         } else {
-                            Some((PathBuf::from(error_cell_uri.to_string()), e.to_diagnostic()))
+                            Some((
+                                PathBuf::from(error_cell_uri.to_string()),
+                                e.to_diagnostic(Some(error_cell_uri)),
+                            ))
                         }
                     }
-                    LspFile::Source(_) => Some((path.to_path_buf(), e.to_diagnostic())),
+                    LspFile::Source(_) => Some((
+                        path.to_path_buf(),
+                        e.to_diagnostic(Url::from_file_path(&path).ok().as_ref()),
+                    )),
                 };
             }
 
@@ -2892,7 +2957,10 @@ impl Server {
                 && !config.project_excludes.covers(&path)
                 && type_error_status.is_enabled()
             {
-                return Some((path.to_path_buf(), e.to_diagnostic()));
+                return Some((
+                    path.to_path_buf(),
+                    e.to_diagnostic(Url::from_file_path(&path).ok().as_ref()),
+                ));
             }
         }
         None
@@ -3120,8 +3188,19 @@ impl Server {
             Ok(transaction) => transaction.as_mut(),
             Err(transaction) => transaction,
         };
-        let handles =
-            self.validate_in_memory_for_transaction(transaction, telemetry, custom_thread_pool);
+        let handles = self.get_open_file_handles();
+        *self.in_memory_validation.lock() = Some(transaction.get_cancellation_handle());
+        let result = self.run_open_files(transaction, &handles, telemetry, custom_thread_pool);
+        let cancelled = self.in_memory_validation.lock().take().is_none();
+        if result.is_err() {
+            // The transaction dropped its remaining work, so throw it away. The edit that
+            // cancelled it will validate again from the latest state.
+            info!("Validation of open files was cancelled by a newer edit.");
+            return;
+        } else if cancelled {
+            // The edit arrived after the run finished, so the results are complete.
+            transaction.reset_cancellation();
+        }
         match possibly_committable_transaction {
             Ok(transaction) => {
                 self.state.commit_transaction(transaction, Some(telemetry));
@@ -3681,6 +3760,7 @@ impl Server {
         drop(lock);
         // Update version_info only after the mutation has fully succeeded.
         self.version_info.lock().insert(file_path.clone(), version);
+        *self.last_edited_file.lock() = Some(file_path.clone());
         if !subsequent_mutation {
             info!(
                 "File {} changed, prepare to validate open files.",
@@ -3694,11 +3774,24 @@ impl Server {
                     .as_mut()
                     .map(|handles| handles.shift_remove(&handle));
             }
-            self.validate_in_memory_and_commit_if_possible(
-                ide_transaction_manager,
-                telemetry,
-                Some(&self.lsp_thread_pool),
-            );
+            let debounce_ms = self
+                .workspaces
+                .get_with(file_path, |(_, workspace)| {
+                    workspace
+                        .lsp_analysis_config
+                        .and_then(|c| c.validation_debounce_ms)
+                })
+                .unwrap_or(DEFAULT_VALIDATION_DEBOUNCE_MS);
+            if debounce_ms == 0 {
+                self.validate_in_memory_and_commit_if_possible(
+                    ide_transaction_manager,
+                    telemetry,
+                    Some(&self.lsp_thread_pool),
+                );
+            } else {
+                self.lsp_queue
+                    .schedule_validation(Instant::now() + Duration::from_millis(debounce_ms));
+            }
         }
         Ok(())
     }
@@ -4246,13 +4339,23 @@ impl Server {
         params: GotoDefinitionParams,
     ) -> Result<Option<GotoDefinitionResponse>, EmptyResponseReason> {
         let uri = &params.text_document_position_params.text_document.uri;
-        let handle = self.make_handle_if_enabled(uri, Some(GotoDefinition::METHOD))?;
+        let (handle, lsp_config) = self
+            .make_handle_with_lsp_analysis_config_if_enabled(uri, Some(GotoDefinition::METHOD))?;
         let info = transaction
             .get_module_info(&handle)
             .ok_or(EmptyResponseReason::ModuleInfoNotFound)?;
         let range =
             self.from_lsp_position(uri, &info, params.text_document_position_params.position);
-        let targets = transaction.goto_definition(&handle, range)?;
+        let import_behavior = if lsp_config
+            .and_then(|c| c.goto_definition_stop_at_reexports)
+            .unwrap_or(false)
+        {
+            ImportBehavior::StopAtReexports
+        } else {
+            ImportBehavior::JumpThroughEverything
+        };
+        let targets =
+            transaction.goto_definition_with_import_behavior(&handle, range, import_behavior)?;
         let mut lsp_targets = targets
             .iter()
             .filter_map(|x| self.to_lsp_location(x))
@@ -5115,7 +5218,14 @@ impl Server {
         let show_go_to_links = lsp_config
             .and_then(|c| c.show_hover_go_to_links)
             .unwrap_or(true);
-        Ok(get_hover(transaction, &handle, position, show_go_to_links))
+        let signature_line_width = lsp_config.and_then(|c| c.hover_signature_line_width);
+        Ok(get_hover_with_signature_line_width(
+            transaction,
+            &handle,
+            position,
+            show_go_to_links,
+            signature_line_width,
+        ))
     }
 
     /// How long an inlay hint request should be deferred to debounce it, or
@@ -5473,6 +5583,15 @@ impl Server {
         handle: &Handle,
         items: &mut Vec<Diagnostic>,
     ) {
+        // Once the `unused-import` error is enabled, it replaces the hint.
+        if transaction.get_config(handle).is_some_and(|config| {
+            config
+                .errors(handle.path().as_path())
+                .severity(ErrorKind::UnusedImport)
+                != Severity::Ignore
+        }) {
+            return;
+        }
         if let Some(bindings) = transaction.get_bindings(handle) {
             let module_info = bindings.module();
             for unused in bindings.unused_imports() {
@@ -5530,14 +5649,9 @@ impl Server {
         let module = transaction.get_module_info(&handle)?;
         let docstring_ranges = transaction.docstring_ranges(&handle)?;
         Some(
-            docstring_ranges
-                .into_iter()
-                .filter(|range| {
-                    maybe_cell_idx.is_none()
-                        || module.to_cell_for_lsp(range.start()) == maybe_cell_idx
-                })
-                .map(|range| module.to_lsp_range(range))
-                .collect(),
+            module.to_lsp_ranges(docstring_ranges.into_iter().filter(|range| {
+                maybe_cell_idx.is_none() || module.to_cell_for_lsp(range.start()) == maybe_cell_idx
+            })),
         )
     }
 
@@ -354,6 +354,16 @@ pub struct LspAnalysisConfig {
     // TODO: this is not a pylance setting. it should be in pyrefly settings
     #[serde(default)]
     pub show_hover_go_to_links: Option<bool>,
+    /// When true, go-to-definition on a re-exported name stops at the public
+    /// re-export (e.g. in a package's `__init__.py`) instead of following the
+    /// chain to the original definition. Defaults to false.
+    #[serde(default)]
+    pub goto_definition_stop_at_reexports: Option<bool>,
+    /// When set, hover puts a signature's parameters on separate lines only if the
+    /// signature is wider than this many characters. By default, every signature with
+    /// several parameters is broken over multiple lines.
+    #[serde(default)]
+    pub hover_signature_line_width: Option<usize>,
     /// Debounce window (milliseconds) for inlay hint requests. VS Code exposes
     /// no client-side inlay-hint debounce (microsoft/vscode#133730), so without
     /// this, hints recompute on every keystroke and their widths jitter
@@ -362,6 +372,12 @@ pub struct LspAnalysisConfig {
     // TODO: this is not a pylance setting. it should be in pyrefly settings
     #[serde(default)]
     pub inlay_hint_debounce_ms: Option<u64>,
+    /// Pyrefly-specific setting (`analysis.validationDebounceMs`): how long, in
+    /// milliseconds, to wait after an edit before validating open files, so that
+    /// edits made within this window of each other are validated together once
+    /// editing pauses. Unset or `0` (the default) validates after every edit.
+    #[serde(default)]
+    pub validation_debounce_ms: Option<u64>,
 }
 
 fn deserialize_analysis<'de, D>(deserializer: D) -> Result<Option<LspAnalysisConfig>, D::Error>
@@ -24,15 +24,11 @@ use pyrefly_python::ignore::Tool;
 use pyrefly_python::ignore::find_comment_start_in_line;
 use pyrefly_python::short_identifier::ShortIdentifier;
 use pyrefly_python::symbol_kind::SymbolKind;
-use pyrefly_types::callable::Callable;
 use pyrefly_types::callable::FunctionKind;
-use pyrefly_types::callable::Param;
-use pyrefly_types::callable::ParamList;
-use pyrefly_types::callable::Params;
-use pyrefly_types::callable::Required;
 use pyrefly_types::class::Class;
 use pyrefly_types::class::ClassType;
 use pyrefly_types::display::LspDisplayMode;
+use pyrefly_types::display::LspDisplayOptions;
 use pyrefly_types::type_var::Variance;
 use pyrefly_types::types::Type;
 use pyrefly_util::absolutize::Absolutize as _;
@@ -53,6 +49,7 @@ use crate::error::error::Error;
 use crate::lsp::module_helpers::collect_symbol_def_paths;
 use crate::lsp::module_helpers::to_real_path;
 use crate::lsp::wasm::signature_help::CallInfo;
+use crate::lsp::wasm::signature_help::expand_callable_kwargs;
 use crate::lsp::wasm::signature_help::is_constructor_call;
 use crate::lsp::wasm::signature_help::override_constructor_return_type;
 use crate::lsp::wasm::type_source::set_display_pos_fragment;
@@ -80,6 +77,7 @@ pub struct HoverValue {
     pub type_sources: Vec<String>,
     pub display: Option<String>,
     pub show_go_to_links: bool,
+    pub display_options: LspDisplayOptions,
 }
 
 impl HoverValue {
@@ -273,8 +271,11 @@ impl HoverValue {
             section
         };
         let type_display = self.display.clone().unwrap_or_else(|| {
-            self.type_
-                .as_lsp_string_with_fallback_name(self.name.as_deref(), LspDisplayMode::Hover)
+            self.type_.as_lsp_string_with_options(
+                self.name.as_deref(),
+                LspDisplayMode::Hover,
+                self.display_options,
+            )
         });
 
         Hover {
@@ -427,50 +428,6 @@ fn identifier_text_at(
         .map(|id| id.identifier.id.to_string())
 }
 
-fn collect_typed_dict_fields_for_hover<'a>(
-    solver: &AnswersSolver<TransactionHandle<'a>>,
-    ty: &Type,
-) -> Option<Vec<(Name, Type, Required)>> {
-    match ty {
-        Type::Unpack(inner) => match inner.as_ref() {
-            Type::TypedDict(typed_dict) => {
-                let fields = solver.type_order().typed_dict_kw_param_info(typed_dict);
-                if fields.is_empty() {
-                    None
-                } else {
-                    Some(fields)
-                }
-            }
-            _ => None,
-        },
-        _ => None,
-    }
-}
-
-fn expand_callable_kwargs_for_hover<'a>(
-    solver: &AnswersSolver<TransactionHandle<'a>>,
-    callable: &mut Callable,
-) {
-    if let Params::List(param_list) = &mut callable.params {
-        let mut expanded = Vec::with_capacity(param_list.len());
-        let mut changed = false;
-        for param in param_list.items() {
-            if let Param::Kwargs(_, ty) = param
-                && let Some(fields) = collect_typed_dict_fields_for_hover(solver, ty)
-            {
-                changed = true;
-                for (field_name, field_type, required) in fields {
-                    expanded.push(Param::KwOnly(field_name, field_type, required));
-                }
-            }
-            expanded.push(param.clone());
-        }
-        if changed {
-            *param_list = ParamList::new(expanded);
-        }
-    }
-}
-
 /// Given the position, if it corresponds to a class-scoped PEP695 type var declaration
 /// return the class. This only applies to the declaration of the type var, not to
 /// any usages.
@@ -536,6 +493,7 @@ fn class_hover_display(
     solver: &AnswersSolver<TransactionHandle<'_>>,
     type_: &Type,
     name_for_display: Option<&str>,
+    display_options: LspDisplayOptions,
 ) -> Option<String> {
     let enum_class = match type_ {
         Type::ClassDef(cls) => Some(cls),
@@ -559,10 +517,11 @@ fn class_hover_display(
         } else {
             solver.heap.mk_union(members)
         };
-        return Some(
-            enum_display_type
-                .as_lsp_string_with_fallback_name(name_for_display, LspDisplayMode::Hover),
-        );
+        return Some(enum_display_type.as_lsp_string_with_options(
+            name_for_display,
+            LspDisplayMode::Hover,
+            display_options,
+        ));
     }
 
     let mut constructor = match type_ {
@@ -577,9 +536,37 @@ fn class_hover_display(
         },
         _ => None,
     }?;
-    constructor.transform_toplevel_callable(|c| expand_callable_kwargs_for_hover(solver, c));
+    constructor.transform_toplevel_callable(|c| expand_callable_kwargs(solver, c));
     constructor = solver.for_display(constructor);
-    Some(constructor.as_lsp_string_with_fallback_name(name_for_display, LspDisplayMode::Hover))
+    Some(constructor.as_lsp_string_with_options(
+        name_for_display,
+        LspDisplayMode::Hover,
+        display_options,
+    ))
+}
+
+/// If the attribute being hovered is a property or descriptor whose setter accepts a different
+/// type than its getter returns, a line showing the type accepted on assignment.
+fn attribute_setter_hover_display(
+    solver: &AnswersSolver<TransactionHandle<'_>>,
+    attribute_base: Option<(Type, Name)>,
+    getter_type: &Type,
+    name_for_display: Option<&str>,
+) -> Option<String> {
+    let (base, attr_name) = attribute_base?;
+    let cls = match base {
+        Type::ClassType(cls) | Type::SelfType(cls) => cls,
+        _ => return None,
+    };
+    let setter_type = solver.get_instance_attribute_setter_type(&cls, &attr_name)?;
+    if &setter_type == getter_type {
+        return None;
+    }
+    let setter_type = solver.for_display(setter_type);
+    Some(match name_for_display {
+        Some(name) => format!("(setter) {name}: {setter_type}"),
+        None => format!("(setter) {setter_type}"),
+    })
 }
 
 fn parameter_documentation_for_callee(
@@ -689,6 +676,18 @@ pub fn get_hover(
     handle: &Handle,
     position: TextSize,
     show_go_to_links: bool,
+) -> Option<Hover> {
+    get_hover_with_signature_line_width(transaction, handle, position, show_go_to_links, None)
+}
+
+/// Like `get_hover`, but signatures are only broken over multiple lines when they are wider
+/// than `signature_line_width`, if given.
+pub fn get_hover_with_signature_line_width(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    position: TextSize,
+    show_go_to_links: bool,
+    signature_line_width: Option<usize>,
 ) -> Option<Hover> {
     // Handle hovering over an ignore comment
     if let Some(module) = transaction.get_module_info(handle) {
@@ -843,6 +842,23 @@ pub fn get_hover(
         && hover_identifier
             .as_ref()
             .is_some_and(|id| !matches!(id.context, IdentifierContext::ClassDef { .. }));
+    // For an attribute access, the base type and attribute name, so that we can show the
+    // setter type of properties and descriptors.
+    let attribute_base = hover_identifier.as_ref().and_then(|id| match &id.context {
+        IdentifierContext::Attribute { base_range, .. } => Some((
+            transaction
+                .get_answers(handle)?
+                .get_type_trace(*base_range)?,
+            id.identifier.id.clone(),
+        )),
+        _ => None,
+    });
+    let display_options = LspDisplayOptions {
+        signature_line_width,
+        union_display_limit: transaction.get_config(handle).map_or(0, |config| {
+            config.union_display_limit(handle.path().as_path())
+        }),
+    };
     let type_display = transaction.ad_hoc_solve(handle, "hover_display", {
         let mut cloned = type_.clone();
         move |solver| {
@@ -852,16 +868,30 @@ pub fn get_hover(
                 return display;
             }
             if show_constructor
-                && let Some(display) =
-                    class_hover_display(&solver, &cloned, name_for_display.as_deref())
+                && let Some(display) = class_hover_display(
+                    &solver,
+                    &cloned,
+                    name_for_display.as_deref(),
+                    display_options,
+                )
             {
                 return display;
             }
-            cloned.transform_toplevel_callable(|c| expand_callable_kwargs_for_hover(&solver, c));
-            cloned.as_lsp_string_with_fallback_name(
+            cloned.transform_toplevel_callable(|c| expand_callable_kwargs(&solver, c));
+            let display = cloned.as_lsp_string_with_options(
                 name_for_display.as_deref(),
                 LspDisplayMode::Hover,
-            )
+                display_options,
+            );
+            match attribute_setter_hover_display(
+                &solver,
+                attribute_base,
+                &cloned,
+                name_for_display.as_deref(),
+            ) {
+                Some(setter) => format!("{display}\n{setter}"),
+                None => display,
+            }
         }
     });
 
@@ -906,6 +936,7 @@ pub fn get_hover(
             type_sources: type_sources_for_hover(transaction, handle, position),
             display: type_display,
             show_go_to_links,
+            display_options,
         }
         .format(transaction, handle),
     )
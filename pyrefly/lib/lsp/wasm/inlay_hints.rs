@@ -328,7 +328,7 @@ impl<'a> Transaction<'a> {
         // Extract the element at the given position
         // This mirrors the logic in solve.rs for Binding::UnpackedValue
         match pos {
-            UnpackedPosition::Index(i) => elts.get(i),
+            UnpackedPosition::Index(i) | UnpackedPosition::ExactIndex(i, _) => elts.get(i),
             UnpackedPosition::ReverseIndex(i) => {
                 elts.len().checked_sub(i).and_then(|idx| elts.get(idx))
             }
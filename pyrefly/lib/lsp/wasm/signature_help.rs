@@ -31,11 +31,16 @@ use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 use vec1::Vec1;
 
+use crate::alt::answers_solver::AnswersSolver;
 use crate::state::lsp::FindPreference;
 use crate::state::lsp::visit_keyword_arguments_until_match;
 use crate::state::state::Transaction;
+use crate::state::state::TransactionHandle;
+use crate::types::callable::Callable;
 use crate::types::callable::Param;
+use crate::types::callable::ParamList;
 use crate::types::callable::Params;
+use crate::types::callable::Required;
 use crate::types::types::Type;
 
 /// Information about a call site at the cursor position, returned by
@@ -55,6 +60,52 @@ pub(crate) struct CallInfo {
     pub provided_arg_ranges: Vec<TextRange>,
 }
 
+fn unpacked_typed_dict_fields<'a>(
+    solver: &AnswersSolver<TransactionHandle<'a>>,
+    ty: &Type,
+) -> Option<Vec<(Name, Type, Required)>> {
+    match ty {
+        Type::Unpack(inner) => match inner.as_ref() {
+            Type::TypedDict(typed_dict) => {
+                let fields = solver.type_order().typed_dict_kw_param_info(typed_dict);
+                if fields.is_empty() {
+                    None
+                } else {
+                    Some(fields)
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Show the fields of a `**kwargs: Unpack[TypedDict]` parameter as keyword-only parameters,
+/// ahead of the `**kwargs` itself.
+pub(crate) fn expand_callable_kwargs<'a>(
+    solver: &AnswersSolver<TransactionHandle<'a>>,
+    callable: &mut Callable,
+) {
+    if let Params::List(param_list) = &mut callable.params {
+        let mut expanded = Vec::with_capacity(param_list.len());
+        let mut changed = false;
+        for param in param_list.items() {
+            if let Param::Kwargs(_, ty) = param
+                && let Some(fields) = unpacked_typed_dict_fields(solver, ty)
+            {
+                changed = true;
+                for (field_name, field_type, required) in fields {
+                    expanded.push(Param::KwOnly(field_name, field_type, required));
+                }
+            }
+            expanded.push(param.clone());
+        }
+        if changed {
+            *param_list = ParamList::new(expanded);
+        }
+    }
+}
+
 pub(crate) fn is_constructor_call(callee_type: Type) -> bool {
     matches!(callee_type, Type::ClassDef(_))
         || matches!(callee_type, Type::Type(t) if matches!(*t, Type::ClassType(_)))
@@ -503,6 +554,20 @@ impl Transaction<'_> {
                     .and_then(|ans| ans.get_type_trace(callee_range))
                     .is_some_and(is_constructor_call);
 
+                let callables = self
+                    .ad_hoc_solve(handle, "signature_help_kwargs", |solver| {
+                        callables
+                            .clone()
+                            .into_iter()
+                            .map(|mut t| {
+                                t.transform_toplevel_callable(|c| {
+                                    expand_callable_kwargs(&solver, c)
+                                });
+                                t
+                            })
+                            .collect_vec()
+                    })
+                    .unwrap_or(callables);
                 let signatures = callables
                     .into_iter()
                     .map(|t| {
@@ -14,9 +14,12 @@ use pyrefly_build::module_resolver::FindResult;
 use pyrefly_build::module_resolver::ModuleResolutionObserver;
 use pyrefly_build::module_resolver::find_module_prefixes;
 use pyrefly_build::module_resolver::find_module_results;
+use pyrefly_build::module_resolver::is_extension_module_stub;
+use pyrefly_build::module_resolver::is_partial_stub_package;
 use pyrefly_build::module_resolver::package_has_py_typed;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
 use pyrefly_python::module_path::ModuleStyle;
 use pyrefly_util::locked_map::LockedMap;
 use pyrefly_util::suggest::best_suggestion;
@@ -28,6 +31,7 @@ use vec1::Vec1;
 static STDLIB_SUGGESTION_CACHE: LazyLock<LockedMap<ModuleName, Option<ModuleName>>> =
     LazyLock::new(LockedMap::new);
 
+use crate::config::base::FollowImports;
 use crate::config::config::ConfigFile;
 use crate::module::bundled::BundledStub;
 use crate::module::third_party::get_bundled_third_party;
@@ -59,6 +63,12 @@ fn observer(timing: Option<&TransactionTimingCounters>) -> Option<&dyn ModuleRes
     timing.map(|x| x as &dyn ModuleResolutionObserver)
 }
 
+/// Whether the package `result` was found in ships its own types: it has a `py.typed`
+/// marker, or `result` is a stub shipped next to a compiled extension module.
+fn package_is_typed(module: ModuleName, result: &FindResult, dir_cache: &DirEntryCache) -> bool {
+    is_extension_module_stub(result, dir_cache) || package_has_py_typed(module, result, dir_cache)
+}
+
 fn find_result_module_path(result: FindResult) -> FindingOrError<ModulePath> {
     match result {
         FindResult::CompiledModule(_) => FindingOrError::Error(FindError::Ignored),
@@ -91,7 +101,7 @@ fn resolve_third_party_stub(
     if let Some(ref bundled) = bundled_stub
         && from_real_config_file
         && let Some(normal_result) = normal_result
-        && !package_has_py_typed(module, normal_result, dir_cache)
+        && !package_is_typed(module, normal_result, dir_cache)
         && stub_result.is_none()
     {
         if let Some(pip_package) = recommended_stubs_package(module) {
@@ -153,7 +163,7 @@ fn combine_normal_and_stub_results(
         }
         (Some(normal_result), None) => {
             if let Some(missing_stub_result) = recommended_stubs_package(module)
-                && !package_has_py_typed(module, &normal_result, dir_cache)
+                && !package_is_typed(module, &normal_result, dir_cache)
             {
                 Some(
                     find_result_module_path(normal_result).with_error(FindError::UntypedImport(
@@ -200,7 +210,7 @@ fn find_module<'a, I>(
 where
     I: Iterator<Item = &'a PathBuf> + Clone,
 {
-    let results = find_module_results(
+    let mut results = find_module_results(
         module,
         include,
         style_filter,
@@ -208,6 +218,18 @@ where
         dir_cache,
         observer(timing),
     );
+    // A partial `-stubs` package only overlays the installed package. If all it has for
+    // this module is a directory without stubs, use the installed module instead, which
+    // may be a compiled extension module.
+    if let Some(stub @ FindResult::ImplicitNamespacePackage(_)) = &results.stub_result
+        && results
+            .normal_result
+            .as_ref()
+            .is_some_and(|normal| !matches!(normal, FindResult::ImplicitNamespacePackage(_)))
+        && is_partial_stub_package(module, stub, dir_cache)
+    {
+        results.stub_result = None;
+    }
     if let Some(result) = resolve_third_party_stub(
         module,
         results.stub_result.as_ref(),
@@ -429,6 +451,10 @@ pub fn find_import_internal(
 
     if module != ModuleName::builtins() && config.replace_imports_with_any(origin, module) {
         FindingOrError::Error(FindError::Ignored)
+    } else if config.dynamic_modules.declares(module)
+        && let Some(path) = config.dynamic_module_path(module)
+    {
+        FindingOrError::new_finding(path)
     } else if let Some(build_system) = config.build_system.as_ref()
         && let Some(path) = find_module(
             module,
@@ -459,6 +485,18 @@ pub fn find_import_internal(
         timing,
     ) {
         path
+    } else if let Some(path) = find_module(
+        module,
+        config.stub_path.iter(),
+        &mut namespaces_found,
+        style_filter,
+        None,
+        false,
+        phantom_paths,
+        dir_cache,
+        timing,
+    ) {
+        path
     } else if let Some(custom_typeshed_path) = &config.typeshed_path
         && let Some(path) = find_module(
             module,
@@ -529,14 +567,102 @@ pub fn find_import_internal(
     style_filter.is_none()
     {
         FindingOrError::new_finding(ModulePath::namespace(namespace))
+    } else if let Some(path) = config.dynamic_module_path(module) {
+        // A package containing a module declared in `dynamic-modules`.
+        FindingOrError::new_finding(path)
     } else if config.ignore_missing_imports(origin, module) {
         FindingOrError::Error(FindError::Ignored)
     } else {
-        FindingOrError::Error(FindError::import_lookup_path(
+        let error = FindError::import_lookup_path(
             config.structured_import_lookup_path(origin),
             module,
             &config.source,
-        ))
+        );
+        FindingOrError::Error(match missing_import_hint(config, module, dir_cache) {
+            Some(hint) => error.with_hint(hint),
+            None => error,
+        })
+    }
+}
+
+/// A hint for an import that couldn't be found, from the metadata of the distributions
+/// installed in the site package paths and the packages we bundle third-party stubs for.
+fn missing_import_hint(
+    config: &ConfigFile,
+    module: ModuleName,
+    dir_cache: &DirEntryCache,
+) -> Option<String> {
+    let top_level = module.first_component();
+    let mut installed = Vec::new();
+    for site_packages in config.site_package_path() {
+        let distributions = dir_cache.installed_distributions(site_packages);
+        if let Some(distribution) = distributions.iter().find(|d| d.provides(module)) {
+            let mut hint = if module.as_str() == top_level.as_str() {
+                format!(
+                    "Hint: `{module}` is installed by the `{}` distribution, but its files are missing from `{}`",
+                    distribution.name,
+                    site_packages.display()
+                )
+            } else {
+                format!(
+                    "Hint: `{top_level}` is installed by the `{}` distribution, which doesn't provide `{module}`",
+                    distribution.name
+                )
+            };
+            let package_root = site_packages.join(top_level.as_str());
+            if dir_cache.dir_exists(&package_root)
+                && !dir_cache.file_exists(&package_root.join("py.typed"))
+            {
+                hint.push_str(". It has no `py.typed` file, so it doesn't ship type information");
+                if let Some(stubs) = recommended_stubs_package(ModuleName::from_name(&top_level)) {
+                    hint.push_str(&format!("; try installing `{stubs}`"));
+                }
+            }
+            return Some(hint);
+        }
+        installed.push(distributions);
+    }
+    let package = suggest_package_to_install(module)?;
+    if installed
+        .iter()
+        .flat_map(|d| d.iter())
+        .any(|d| d.is_named(package.as_str()))
+    {
+        return Some(format!(
+            "Hint: `{module}` is provided by the `{package}` package, which is installed but doesn't include it"
+        ));
+    }
+    let status = if installed.is_empty() {
+        "may not be installed"
+    } else {
+        "is not installed"
+    };
+    Some(format!(
+        "Hint: `{module}` is provided by the `{package}` package, which {status} in the selected Python environment"
+    ))
+}
+
+/// Under `follow-imports = "skip"` or `"error"`, don't analyze Python source files outside the
+/// project. Stubs are still used, so that typeshed and stub packages keep working.
+fn apply_follow_imports(
+    config: &ConfigFile,
+    module: ModuleName,
+    origin: Option<&ModulePath>,
+    result: FindingOrError<ModulePath>,
+) -> FindingOrError<ModulePath> {
+    let error = match config.follow_imports(origin.map(|p| p.as_path())) {
+        FollowImports::Normal | FollowImports::Silent => return result,
+        FollowImports::Skip => FindError::Ignored,
+        FollowImports::Error => FindError::NotFollowed(module),
+    };
+    if let FindingOrError::Finding(found) = &result
+        && let ModulePathDetails::FileSystem(path) = found.finding.details()
+        && !found.finding.is_interface()
+        && !(config.project_includes.covers(path) && !config.project_excludes.covers(path))
+    {
+        FindingOrError::Error(error)
+    } else {
+        result
     }
 }
 
@@ -553,7 +679,7 @@ pub fn find_import(
     dir_cache: &DirEntryCache,
     timing: Option<&TransactionTimingCounters>,
 ) -> FindingOrError<ModulePath> {
-    find_import_internal(
+    let result = find_import_internal(
         config,
         module,
         origin,
@@ -561,7 +687,8 @@ pub fn find_import(
         &mut phantom_paths,
         dir_cache,
         timing,
-    )
+    );
+    apply_follow_imports(config, module, origin, result)
 }
 
 pub fn find_import_filtered(
@@ -572,7 +699,7 @@ pub fn find_import_filtered(
     dir_cache: &DirEntryCache,
     timing: Option<&TransactionTimingCounters>,
 ) -> FindingOrError<ModulePath> {
-    find_import_internal(
+    let result = find_import_internal(
         config,
         module,
         origin,
@@ -580,14 +707,18 @@ pub fn find_import_filtered(
         &mut None,
         dir_cache,
         timing,
-    )
+    );
+    apply_follow_imports(config, module, origin, result)
 }
 
 /// Find all legitimate imports that start with `module`
 pub fn find_import_prefixes(config: &ConfigFile, module: ModuleName) -> Vec<ModuleName> {
     let mut results = find_module_prefixes(
         module,
-        config.search_path().chain(config.site_package_path()),
+        config
+            .search_path()
+            .chain(config.stub_path.iter())
+            .chain(config.site_package_path()),
     );
 
     if let Ok(ts) = typeshed() {
@@ -629,6 +760,16 @@ fn recommended_stubs_package(module: ModuleName) -> Option<ModuleName> {
     }
 }
 
+/// Suggest the package to install for a missing import, if it is one we bundle third-party
+/// stubs for. The package name often differs from the module name (e.g. module `yaml` comes
+/// from package `PyYAML`), which makes it hard to guess.
+fn suggest_package_to_install(missing: ModuleName) -> Option<ModuleName> {
+    let ts = typeshed_third_party().ok()?;
+    ts.package_name(missing)
+        .or_else(|| ts.package_name(ModuleName::from_name(&missing.first_component())))
+        .copied()
+}
+
 /// Suggest a similar stdlib module name for a mistyped import.
 /// Uses Levenshtein distance to find the closest match from typeshed's stdlib modules.
 /// Results are cached globally since typeshed doesn't change during a session.
@@ -658,7 +799,7 @@ mod tests {
     use pyrefly_config::config::ConfigSource;
     use pyrefly_config::environment::environment::PythonEnvironment;
     use pyrefly_config::environment::interpreters::Interpreters;
-    use pyrefly_python::module_path::ModulePathDetails;
+    use pyrefly_util::globs::Globs;
     use pyrefly_util::test_path::TestPath;
 
     use super::*;
@@ -2119,6 +2260,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_site_package_path_partial_stubs_over_compiled_package() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir(
+                    "foo",
+                    vec![
+                        TestPath::file("__init__.py"),
+                        TestPath::file("_speedups.cpython-311-x86_64-linux-gnu.so"),
+                        TestPath::dir(
+                            "api",
+                            vec![
+                                TestPath::file("__init__.py"),
+                                TestPath::file("core.cpython-311-x86_64-linux-gnu.so"),
+                            ],
+                        ),
+                    ],
+                ),
+                TestPath::dir(
+                    "foo-stubs",
+                    vec![
+                        TestPath::file_with_contents("py.typed", "partial\n"),
+                        TestPath::dir("api", vec![TestPath::file("core.pyi")]),
+                    ],
+                ),
+            ],
+        );
+        let find = |module: &str| {
+            find_module(
+                ModuleName::from_str(module),
+                [root.to_path_buf()].iter(),
+                &mut vec![],
+                None,
+                None,
+                false,
+                &mut None,
+                &DirEntryCache::new(),
+                None,
+            )
+        };
+        // The stub package has no `__init__.pyi`s, so it doesn't hide the installed ones.
+        assert_eq!(
+            find("foo").unwrap(),
+            FindingOrError::new_finding(ModulePath::filesystem(root.join("foo/__init__.py"))),
+        );
+        assert_eq!(
+            find("foo.api").unwrap(),
+            FindingOrError::new_finding(ModulePath::filesystem(root.join("foo/api/__init__.py"))),
+        );
+        // The stubs describe the compiled extension module.
+        assert_eq!(
+            find("foo.api.core").unwrap(),
+            FindingOrError::new_finding(ModulePath::filesystem(
+                root.join("foo-stubs/api/core.pyi")
+            )),
+        );
+        // Extension modules without stubs are found, but can't be analyzed.
+        assert_eq!(
+            find("foo._speedups").unwrap(),
+            FindingOrError::Error(FindError::Ignored),
+        );
+    }
+
     #[test]
     fn test_find_site_package_path_no_stubs_with_py_typed() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -2800,6 +3007,93 @@ mod tests {
         if let FindError::MissingImport(module, _) = error {
             assert_eq!(module, ModuleName::from_str("requests"));
         }
+        assert!(
+            error
+                .display()
+                .1
+                .iter()
+                .any(|line| line.contains("provided by the `requests` package")),
+            "Expected a hint to install `requests`"
+        );
+    }
+
+    fn missing_import_hint_lines(config: &ConfigFile, module: &str) -> Vec<String> {
+        let result = find_import_filtered(
+            config,
+            ModuleName::from_str(module),
+            None,
+            None,
+            &DirEntryCache::new(),
+            None,
+        );
+        let error = result.error().expect("Expected error to be present");
+        assert!(matches!(error, FindError::MissingImport(_, _)));
+        error.display().1.into_vec()
+    }
+
+    #[test]
+    fn test_missing_import_hint_from_installed_distribution() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::dir(
+                "site_packages",
+                vec![
+                    TestPath::dir("requests", vec![TestPath::file("__init__.py")]),
+                    TestPath::dir(
+                        "requests-2.32.0.dist-info",
+                        vec![
+                            TestPath::file_with_contents(
+                                "METADATA",
+                                "Metadata-Version: 2.1\nName: requests\nVersion: 2.32.0\n\nName: not a field\n",
+                            ),
+                            TestPath::file_with_contents(
+                                "RECORD",
+                                "requests/__init__.py,,\nrequests-2.32.0.dist-info/METADATA,,\n",
+                            ),
+                        ],
+                    ),
+                    TestPath::dir(
+                        "PyYAML-6.0.dist-info",
+                        vec![
+                            TestPath::file_with_contents("METADATA", "Name: PyYAML\n"),
+                            TestPath::file_with_contents("top_level.txt", "_yaml\nyaml\n"),
+                        ],
+                    ),
+                ],
+            )],
+        );
+        let mut config = get_config(ConfigSource::File("".into()));
+        config.python_environment.site_package_path = Some(vec![root.join("site_packages")]);
+        config.configure();
+
+        let lines = missing_import_hint_lines(&config, "requests.missing");
+        assert_eq!(
+            lines[0],
+            "Hint: `requests` is installed by the `requests` distribution, which doesn't provide `requests.missing`. It has no `py.typed` file, so it doesn't ship type information; try installing `types-requests`"
+        );
+        let lines = missing_import_hint_lines(&config, "yaml");
+        assert!(
+            lines[0].starts_with("Hint: `yaml` is installed by the `PyYAML` distribution, but its files are missing from"),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn test_missing_import_hint_for_uninstalled_package() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(root, vec![TestPath::dir("site_packages", vec![])]);
+        let mut config = get_config(ConfigSource::File("".into()));
+        config.python_environment.site_package_path = Some(vec![root.join("site_packages")]);
+        config.configure();
+
+        let lines = missing_import_hint_lines(&config, "requests");
+        assert_eq!(
+            lines[0],
+            "Hint: `requests` is provided by the `requests` package, which is not installed in the selected Python environment"
+        );
     }
 
     #[test]
@@ -2924,6 +3218,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_typeshed_third_party_with_real_config_and_extension_stub_no_recommendation() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+
+        // Set up site package directory with 'requests' installed as a compiled extension
+        // module with a stub shipped next to it, as in a binary wheel
+        TestPath::setup_test_directory(
+            root,
+            vec![TestPath::dir(
+                "site_packages",
+                vec![
+                    TestPath::file("requests.cpython-311-x86_64-linux-gnu.so"),
+                    TestPath::file("requests.pyi"),
+                ],
+            )],
+        );
+
+        let mut config = get_config(ConfigSource::File("".into()));
+        config.python_environment.site_package_path = Some(vec![root.join("site_packages")]);
+        config.configure();
+
+        let result = find_import_filtered(
+            &config,
+            ModuleName::from_str("requests"),
+            None,
+            None,
+            &DirEntryCache::new(),
+            None,
+        );
+
+        if let FindingOrError::Finding(finding) = &result {
+            assert!(
+                finding.error.is_none(),
+                "Expected no UntypedImport error for a stubbed extension module, got: {:?}",
+                finding.error
+            );
+        } else {
+            panic!("Expected Finding, got: {:?}", result);
+        }
+    }
+
     #[test]
     fn test_typeshed_third_party_with_real_config_and_py_typed_submodule_no_recommendation() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -3133,6 +3469,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_follow_imports_skip_and_error() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir("project", vec![TestPath::file("local.py")]),
+                TestPath::dir(
+                    "site_packages",
+                    vec![TestPath::file("dep.py"), TestPath::file("typed.pyi")],
+                ),
+            ],
+        );
+        let find = |follow_imports: FollowImports, module: &str| {
+            let mut config = get_config(ConfigSource::Synthetic);
+            config.project_includes =
+                Globs::new(vec![root.join("project/**").to_string_lossy().into_owned()]).unwrap();
+            config.search_path_from_file = vec![root.join("project")];
+            config.python_environment.site_package_path = Some(vec![root.join("site_packages")]);
+            config.root.follow_imports = Some(follow_imports);
+            config.configure();
+            find_import_filtered(
+                &config,
+                ModuleName::from_str(module),
+                None,
+                None,
+                &DirEntryCache::new(),
+                None,
+            )
+        };
+
+        assert!(find(FollowImports::Silent, "dep").finding().is_some());
+        assert_eq!(
+            find(FollowImports::Skip, "dep"),
+            FindingOrError::Error(FindError::Ignored)
+        );
+        assert_eq!(
+            find(FollowImports::Error, "dep"),
+            FindingOrError::Error(FindError::NotFollowed(ModuleName::from_str("dep")))
+        );
+        // Project files and stubs are always followed.
+        assert!(find(FollowImports::Error, "local").finding().is_some());
+        assert!(find(FollowImports::Error, "typed").finding().is_some());
+    }
+
+    #[test]
+    fn test_stub_path_takes_precedence_over_site_packages() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::dir("stubs", vec![TestPath::file("dep.pyi")]),
+                TestPath::dir(
+                    "site_packages",
+                    vec![TestPath::file("dep.py"), TestPath::file("other.py")],
+                ),
+            ],
+        );
+        let mut config = get_config(ConfigSource::Synthetic);
+        config.stub_path = vec![root.join("stubs")];
+        config.python_environment.site_package_path = Some(vec![root.join("site_packages")]);
+        config.configure();
+        let find = |module: &str| {
+            find_import(
+                &config,
+                ModuleName::from_str(module),
+                None,
+                None,
+                &DirEntryCache::new(),
+                None,
+            )
+            .finding()
+            .unwrap()
+        };
+        assert_eq!(
+            find("dep"),
+            ModulePath::filesystem(root.join("stubs/dep.pyi"))
+        );
+        assert_eq!(
+            find("other"),
+            ModulePath::filesystem(root.join("site_packages/other.py"))
+        );
+    }
+
     #[test]
     fn test_find_third_party_stub_prioritizes_typeshed_over_bundled() {
         // 'requests' exists in typeshed third party stubs, so it should
@@ -170,6 +170,10 @@ pub struct Query {
     files: Mutex<SmallSet<(ModuleName, ModulePath)>>,
     /// Cache for type resolution
     type_cache: TypeCache,
+    /// If set, files are only deeply analyzed when a query asks about them, rather than
+    /// eagerly on every `change_files`. Dependencies of a queried file are analyzed only
+    /// as far as that file demands (i.e. their exports).
+    lazy: bool,
 }
 
 const CALLEE_KIND_FUNCTION: &str = "function";
@@ -1631,6 +1635,16 @@ impl Query {
             sys_info: SysInfo::default(),
             files: Mutex::new(SmallSet::new()),
             type_cache: TypeCache::new(),
+            lazy: false,
+        }
+    }
+
+    /// Create a `Query` that analyzes files on demand, for single-file queries against large
+    /// projects where eagerly rechecking every previously loaded file would be too slow.
+    pub fn new_lazy(config_finder: ConfigFinder, thread_count: ThreadCount) -> Self {
+        Self {
+            lazy: true,
+            ..Self::new(config_finder, thread_count)
         }
     }
 
@@ -1658,10 +1672,36 @@ impl Query {
         new_transaction_mut.invalidate_events(events);
         new_transaction_mut.run(&[], Require::Exports, None);
         self.state.commit_transaction(transaction, None);
+        if self.lazy {
+            // Files are brought up to date when they are next queried.
+            return;
+        }
         let all_files = self.files.lock().iter().cloned().collect::<Vec<_>>();
         self.add_files(all_files);
     }
 
+    /// Deeply analyze `handle` if it is out of date, leaving everything else at `Require::Exports`,
+    /// so only what the module demands of its dependencies is computed.
+    fn ensure_analyzed(&self, handle: &Handle) {
+        let mut transaction = self
+            .state
+            .new_committable_transaction(Require::Exports, None);
+        transaction
+            .as_mut()
+            .run(&[handle.dupe()], Require::Everything, None);
+        self.state.commit_transaction(transaction, None);
+    }
+
+    /// The handle of a file being queried. In lazy mode, the file is analyzed first, so
+    /// every query sees up-to-date answers for it.
+    fn queried_handle(&self, name: ModuleName, path: ModulePath) -> Handle {
+        let handle = self.make_handle(name, path);
+        if self.lazy {
+            self.ensure_analyzed(&handle);
+        }
+        handle
+    }
+
     /// Load the given files and return any errors associated with them
     pub fn add_files(&self, files: Vec<(ModuleName, ModulePath)>) -> Vec<String> {
         self.files.lock().extend(files.iter().cloned());
@@ -1696,8 +1736,8 @@ impl Query {
         path: ModulePath,
         class_name: &str,
     ) -> Option<Vec<Attribute>> {
+        let handle = self.queried_handle(name, path);
         let transaction = self.state.transaction();
-        let handle = self.make_handle(name, path);
         let ast = transaction.get_ast(&handle)?;
 
         // find last declaration of class with specified name in file
@@ -1829,8 +1869,8 @@ impl Query {
         path: ModulePath,
         location: Option<PythonASTRange>,
     ) -> Option<Vec<(PythonASTRange, Callee)>> {
+        let handle = self.queried_handle(name, path);
         let transaction = self.state.transaction();
-        let handle = self.make_handle(name, path);
         let find_callees = CalleesWithLocation::new(self, transaction, handle)?;
         Some(find_callees.process(location))
     }
@@ -2009,7 +2049,7 @@ impl Query {
         F: Fn(&TypeShapeContext, &Type, String) -> T,
     {
         let setup_start = timing.as_ref().map(|_| Instant::now());
-        let handle = self.make_handle(name, path);
+        let handle = self.queried_handle(name, path);
 
         let transaction = self.state.transaction();
         let ast = transaction.get_ast(&handle)?;
@@ -2350,8 +2390,8 @@ impl Query {
                 ));
             } else {
                 let super_ty = cached_gt.unwrap();
+                let h = self.queried_handle(name, ModulePath::filesystem(path));
                 let t = self.state.transaction();
-                let h = self.make_handle(name, ModulePath::filesystem(path));
                 let result = t
                     .ad_hoc_solve(&h, "query_is_subset_eq", |solver| {
                         solver.is_subset_eq(&sub_ty, &super_ty)
@@ -12,3 +12,4 @@ pub mod dependency_graph;
 pub mod glean;
 pub mod pysa;
 pub mod trace;
+pub mod type_database;
@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A machine-readable dump of every definition in the checked modules, written as JSON
+//! lines for documentation generators and code search tools. Definitions are printed by
+//! stubgen, so each signature matches the line of the `.pyi` stub that stubgen would emit.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use ruff_python_ast::Stmt;
+use ruff_text_size::TextRange;
+use serde::Serialize;
+
+use crate::state::state::Transaction;
+use crate::stubgen::emit::class_header;
+use crate::stubgen::emit::function_signature;
+use crate::stubgen::emit::variable_signature;
+use crate::stubgen::extract::ExtractConfig;
+use crate::stubgen::extract::StubItem;
+use crate::stubgen::extract::extract_module_stub;
+
+#[derive(Serialize)]
+struct Record {
+    module: ModuleName,
+    /// The dotted name of the definition within its module, e.g. `C.method`.
+    name: String,
+    kind: &'static str,
+    /// The definition as it appears in a stub, e.g. `def method(self, x: int) -> str`.
+    signature: String,
+    path: String,
+    /// Absent for definitions that have no statement of their own, such as the `__init__`
+    /// synthesized for a dataclass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<String>,
+}
+
+struct ModuleContext<'a> {
+    handle: &'a Handle,
+    info: &'a Module,
+    /// The ranges at which each dotted name is defined, in source order. A name defined more
+    /// than once, e.g. an overload or a property setter, is matched to its stub items in order.
+    ranges: HashMap<String, VecDeque<TextRange>>,
+}
+
+impl ModuleContext<'_> {
+    fn add_range(&mut self, prefix: &str, name: &str, range: TextRange) {
+        self.ranges
+            .entry(format!("{prefix}{name}"))
+            .or_default()
+            .push_back(range);
+    }
+
+    /// Collect the ranges of definitions in module and class bodies. Function bodies are not
+    /// descended into, as their locals are not part of the module's API.
+    fn collect_ranges(&mut self, stmts: &[Stmt], prefix: &str) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::FunctionDef(x) => self.add_range(prefix, &x.name.id, x.name.range),
+                Stmt::ClassDef(x) => {
+                    self.add_range(prefix, &x.name.id, x.name.range);
+                    self.collect_ranges(&x.body, &format!("{prefix}{}.", x.name.id));
+                }
+                Stmt::AnnAssign(x) => Ast::expr_lvalue(&x.target, &mut |name| {
+                    self.add_range(prefix, &name.id, name.range);
+                }),
+                Stmt::Assign(x) => {
+                    for target in &x.targets {
+                        Ast::expr_lvalue(target, &mut |name| {
+                            self.add_range(prefix, &name.id, name.range);
+                        });
+                    }
+                }
+                Stmt::TypeAlias(x) => Ast::expr_lvalue(&x.name, &mut |name| {
+                    self.add_range(prefix, &name.id, name.range);
+                }),
+                Stmt::If(x) => {
+                    self.collect_ranges(&x.body, prefix);
+                    for clause in &x.elif_else_clauses {
+                        self.collect_ranges(&clause.body, prefix);
+                    }
+                }
+                Stmt::Try(x) => {
+                    self.collect_ranges(&x.body, prefix);
+                    self.collect_ranges(&x.orelse, prefix);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn record(
+        &mut self,
+        prefix: &str,
+        name: &str,
+        kind: &'static str,
+        signature: String,
+    ) -> Record {
+        let name = format!("{prefix}{name}");
+        let range = self
+            .ranges
+            .get_mut(&name)
+            .and_then(VecDeque::pop_front)
+            .map(|range| self.info.display_range(range).to_string());
+        Record {
+            module: self.handle.module(),
+            name,
+            kind,
+            signature,
+            path: self.handle.path().to_string(),
+            range,
+        }
+    }
+
+    fn collect(&mut self, items: &[StubItem], prefix: &str, out: &mut Vec<Record>) {
+        for item in items {
+            match item {
+                StubItem::Import(_) => {}
+                StubItem::Function(func) => {
+                    let record =
+                        self.record(prefix, &func.name, "function", function_signature(func));
+                    out.push(record);
+                }
+                StubItem::Class(cls) => {
+                    let record = self.record(prefix, &cls.name, "class", class_header(cls));
+                    out.push(record);
+                    self.collect(&cls.body, &format!("{prefix}{}.", cls.name), out);
+                }
+                StubItem::Variable(var) => {
+                    let record =
+                        self.record(prefix, &var.name, "variable", variable_signature(var));
+                    out.push(record);
+                }
+                StubItem::TypeAlias(alias) => {
+                    let record =
+                        self.record(prefix, alias.name(), "type_alias", alias.text.clone());
+                    out.push(record);
+                }
+            }
+        }
+    }
+}
+
+/// Produce one JSON record per line for each definition in `handles`.
+pub fn type_database(transaction: &Transaction, handles: &[Handle]) -> anyhow::Result<String> {
+    let config = ExtractConfig {
+        include_private: true,
+        include_docstrings: false,
+    };
+    let mut records = Vec::new();
+    for handle in handles {
+        let (Some(info), Some(ast), Some(stub)) = (
+            transaction.get_module_info(handle),
+            transaction.get_ast(handle),
+            extract_module_stub(transaction, handle, &config),
+        ) else {
+            continue;
+        };
+        let mut ctx = ModuleContext {
+            handle,
+            info: &info,
+            ranges: HashMap::new(),
+        };
+        ctx.collect_ranges(&ast.body, "");
+        ctx.collect(&stub.items, "", &mut records);
+    }
+    let mut out = String::new();
+    for record in &records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
@@ -16,11 +16,14 @@ use std::fmt;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::mem;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use dupe::Dupe;
 use itertools::Either;
 use itertools::Itertools;
 use pyrefly_python::qname::QName;
@@ -43,6 +46,7 @@ use pyrefly_util::gas::Gas;
 use pyrefly_util::lock::Mutex;
 use pyrefly_util::lock::RwLock;
 use pyrefly_util::prelude::SliceExt;
+use pyrefly_util::prelude::VecExt;
 use pyrefly_util::recurser::Guard;
 use pyrefly_util::recurser::Recurser;
 use pyrefly_util::uniques::UniqueFactory;
@@ -52,12 +56,17 @@ use ruff_text_size::TextRange;
 use starlark_map::small_map::Entry;
 use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
+use tracing::debug;
 use vec1::Vec1;
 
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::attr::AttrSubsetError;
+use crate::config::base::ConstructorOverrides;
+use crate::config::config::ConfigFile;
 use crate::config::error_kind::ErrorKind;
+use crate::config::error_kind::Severity;
+use crate::config::literal_string_sink::LiteralStringSinks;
 use crate::error::collector::ErrorBuilder;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::TypeCheckContext;
@@ -71,7 +80,9 @@ use crate::types::callable::Params;
 use crate::types::callable::PrefixParam;
 use crate::types::callable::Required;
 use crate::types::class::Class;
+use crate::types::display::TypeDisplayContext;
 use crate::types::module::ModuleType;
+use crate::types::simplify::UnionLimits;
 use crate::types::simplify::simplify_tuples;
 use crate::types::simplify::unions;
 use crate::types::simplify::unions_with_literals;
@@ -87,12 +98,6 @@ use crate::types::types::Var;
 /// in the output. The usual cause is that we failed to visit all the necessary `Type` fields.
 const VAR_LEAK: &str = "Internal error: a variable has leaked from one module to another.";
 
-/// A number chosen such that all practical types are less than this depth,
-/// but low enough to avoid stack overflow. Rust's default stack size is 8MB,
-/// and each recursive call to is_subset_eq can use several KB of stack space
-/// due to large enums (Type) and lock guards.
-const INITIAL_GAS: Gas = Gas::new(200);
-
 /// Normalize a candidate answer for an `IntVar`.
 ///
 /// Existing `IntVar` leaves stay as bare quantified/type-var values so
@@ -186,8 +191,26 @@ mod tests {
 
     use super::*;
 
+    fn test_solver() -> Solver {
+        Solver::new(SolverOptions {
+            infer_with_first_use: false,
+            tensor_shapes: true,
+            strict_callable_subtyping: false,
+            strict_partial_subtyping: false,
+            spec_compliant_overloads: false,
+            hasattr_narrowing: true,
+            union_display_limit: 0,
+            union_limits: UnionLimits::default(),
+            subset_recursion_limit: ConfigFile::DEFAULT_SUBSET_RECURSION_LIMIT,
+            literal_string_sinks: LiteralStringSinks::default(),
+            constructor_overrides: ConstructorOverrides::default(),
+            optional_attribute_hints: true,
+            implicit_optional: false,
+        })
+    }
+
     fn solver_with_answer(answer: Type) -> (Solver, Var) {
-        let solver = Solver::new(false, true, false, false, false);
+        let solver = test_solver();
         let uniques = UniqueFactory::new();
         let var = Var::new(&uniques);
         solver
@@ -639,7 +662,7 @@ mod tests {
         ];
         for (index, (v1_quantified, k1, r1, v2_quantified, k2, r2)) in cases.into_iter().enumerate()
         {
-            let solver = Solver::new(false, true, false, false, false);
+            let solver = test_solver();
             let uniques = UniqueFactory::new();
             let v1 = Var::new(&uniques);
             let v2 = Var::new(&uniques);
@@ -1042,6 +1065,67 @@ pub struct Solver {
     pub strict_callable_subtyping: bool,
     pub strict_partial_subtyping: bool,
     pub spec_compliant_overloads: bool,
+    pub hasattr_narrowing: bool,
+    /// Aliased unions with more members than this are shown by name in error messages.
+    pub union_display_limit: usize,
+    /// Caps on the size of inferred unions.
+    pub union_limits: UnionLimits,
+    /// How many levels deep a single subset query may recurse before giving up.
+    pub subset_recursion_limit: usize,
+    /// Functions whose first parameter must be passed a `LiteralString`.
+    pub literal_string_sinks: LiteralStringSinks,
+    /// Whether overrides of `__init__` and `__new__` are checked against the parent class.
+    pub constructor_overrides: ConstructorOverrides,
+    /// Whether a missing attribute on `X | None` comes with a hint to check for `None`.
+    pub optional_attribute_hints: bool,
+    /// Whether a `None` default the annotation rejects is reported as `implicit-optional`
+    /// rather than as a bad default.
+    pub implicit_optional: bool,
+}
+
+/// The config settings a `Solver` uses, as they apply to one module.
+#[derive(Debug, Clone)]
+pub struct SolverOptions {
+    pub infer_with_first_use: bool,
+    pub tensor_shapes: bool,
+    pub strict_callable_subtyping: bool,
+    pub strict_partial_subtyping: bool,
+    pub spec_compliant_overloads: bool,
+    pub hasattr_narrowing: bool,
+    pub union_display_limit: usize,
+    pub union_limits: UnionLimits,
+    pub subset_recursion_limit: usize,
+    pub literal_string_sinks: LiteralStringSinks,
+    pub constructor_overrides: ConstructorOverrides,
+    pub optional_attribute_hints: bool,
+    pub implicit_optional: bool,
+}
+
+impl SolverOptions {
+    /// Read the options for the module at `path` from `config`. Whether tensor shapes are
+    /// available depends on the module's imports rather than the config, so it is passed in.
+    pub fn from_config(config: &ConfigFile, path: &Path, tensor_shapes: bool) -> Self {
+        Self {
+            infer_with_first_use: config.infer_with_first_use(path),
+            tensor_shapes,
+            strict_callable_subtyping: config.strict_callable_subtyping(path),
+            strict_partial_subtyping: config.strict_partial_subtyping(path),
+            spec_compliant_overloads: config.spec_compliant_overloads(path),
+            hasattr_narrowing: config.hasattr_narrowing(path),
+            union_display_limit: config.union_display_limit(path),
+            union_limits: UnionLimits {
+                literals: config.literal_union_limit(path),
+                enum_members: config.enum_union_limit(path),
+                members: config.union_limit(path),
+            },
+            subset_recursion_limit: config.subset_recursion_limit(path),
+            literal_string_sinks: config.literal_string_sinks(path),
+            constructor_overrides: config.constructor_overrides(path),
+            optional_attribute_hints: config.optional_attribute_hints(path),
+            implicit_optional: config.errors(path).severity(ErrorKind::ImplicitOptional)
+                != Severity::Ignore,
+        }
+    }
 }
 
 impl Display for Solver {
@@ -1094,13 +1178,22 @@ impl SubsetWithSnapshotResult {
 
 impl Solver {
     /// Create a new solver.
-    pub fn new(
-        infer_with_first_use: bool,
-        tensor_shapes: bool,
-        strict_callable_subtyping: bool,
-        strict_partial_subtyping: bool,
-        spec_compliant_overloads: bool,
-    ) -> Self {
+    pub fn new(options: SolverOptions) -> Self {
+        let SolverOptions {
+            infer_with_first_use,
+            tensor_shapes,
+            strict_callable_subtyping,
+            strict_partial_subtyping,
+            spec_compliant_overloads,
+            hasattr_narrowing,
+            union_display_limit,
+            union_limits,
+            subset_recursion_limit,
+            literal_string_sinks,
+            constructor_overrides,
+            optional_attribute_hints,
+            implicit_optional,
+        } = options;
         Self {
             variables: Default::default(),
             instantiation_errors: Default::default(),
@@ -1112,6 +1205,14 @@ impl Solver {
             strict_callable_subtyping,
             strict_partial_subtyping,
             spec_compliant_overloads,
+            hasattr_narrowing,
+            union_display_limit,
+            union_limits,
+            subset_recursion_limit,
+            literal_string_sinks,
+            constructor_overrides,
+            optional_attribute_hints,
+            implicit_optional,
         }
     }
 
@@ -2886,13 +2987,52 @@ impl Solver {
             // Optimization: return early to avoid evaluating `tcc`.
             return errors.error_builder(loc, ErrorKind::InternalError, String::new());
         }
+        if matches!(subset_error, SubsetError::RecursionLimit) {
+            let got = self.for_display(got.clone());
+            let want = self.for_display(want.clone());
+            let ctx = TypeDisplayContext::new(&[&got, &want]);
+            // Name the class whose structure we were expanding, which is usually the recursive one.
+            let definition = [&want, &got]
+                .into_iter()
+                .find_map(|ty| match ty {
+                    Type::ClassType(cls) => Some(format!(
+                        " (in the definition of `{}.{}`)",
+                        cls.class_object().module_name(),
+                        cls.name()
+                    )),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            return errors.error_builder(
+                loc,
+                ErrorKind::RecursionLimit,
+                format!(
+                    "Recursion limit exceeded while checking whether `{}` is assignable to `{}`{definition}; falling back to `Any`",
+                    ctx.display(&got),
+                    ctx.display(&want),
+                ),
+            );
+        }
         let tcc = tcc();
         let msg = tcc.kind.format_error(
             &self.for_display(got.clone()),
             &self.for_display(want.clone()),
             errors.module().name(),
+            self.union_display_limit,
+        );
+        let is_call_argument = matches!(
+            tcc.kind,
+            TypeCheckKind::CallArgument(..) | TypeCheckKind::CallVarArgs(..)
         );
-        let mut builder = errors.error_builder(loc, tcc.kind.as_error_kind(), msg);
+        let is_str_for_literal_string = matches!(want, Type::LiteralString(_))
+            && matches!(got, Type::ClassType(cls) if cls.is_builtin("str"))
+            && tcc.kind.as_error_kind() == ErrorKind::BadArgumentType;
+        let kind = if is_str_for_literal_string {
+            ErrorKind::BadArgumentTypeLiteralString
+        } else {
+            tcc.kind.as_error_kind()
+        };
+        let mut builder = errors.error_builder(loc, kind, msg);
         builder = builder.with_context(tcc.context.map(|ctx| || ctx));
         for (range, label) in tcc.annotations {
             builder = builder.with_annotation(range, label);
@@ -2900,9 +3040,41 @@ impl Solver {
         if let Some(detail) = subset_error.to_error_msg() {
             builder = builder.with_detail(detail);
         }
+        if is_call_argument {
+            builder = builder.with_details(self.describe_inferred_quantifieds(want));
+        }
+        if is_str_for_literal_string {
+            builder = builder.with_detail(
+                "Only strings built from literals are accepted. Pass a string literal, or pass untrusted values separately (e.g. as query parameters) rather than formatting them into the string.".to_owned(),
+            );
+        }
         builder
     }
 
+    /// Describe what the type variables of an in-progress generic call that appear in `ty` have
+    /// been inferred as so far, so users can see why an argument didn't match its parameter.
+    fn describe_inferred_quantifieds(&self, ty: &Type) -> Vec<String> {
+        let inferred = {
+            let variables = self.variables.lock();
+            ty.collect_maybe_placeholder_vars()
+                .into_iter()
+                .unique()
+                .filter_map(|v| match &*variables.get(v) {
+                    Variable::Quantified { quantified, bounds } if !bounds.lower.is_empty() => {
+                        Some((quantified.name().clone(), v))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        inferred.into_map(|(name, v)| {
+            format!(
+                "Type variable `{name}` was inferred as `{}`",
+                self.for_display(v.to_type(self.heap))
+            )
+        })
+    }
+
     /// Union a list of types together. In the process may cause some variables to be forced.
     pub fn unions<Ans: LookupAnswer>(
         &self,
@@ -2939,12 +3111,80 @@ impl Solver {
             })
             .collect::<Vec<_>>();
         branches.extend(modules.into_values().map(Type::Module));
-        unions_with_literals(
+        let literal_count = |t: &Type| match t {
+            Type::Literal(_) => 1,
+            Type::Union(u) => u
+                .members
+                .iter()
+                .filter(|x| matches!(x, Type::Literal(_)))
+                .count(),
+            _ => 0,
+        };
+        let literals_before: usize = branches.iter().map(literal_count).sum();
+        let limits = self.union_limits;
+        let res = unions_with_literals(
             branches,
             type_order.stdlib(),
             &|cls| type_order.get_enum_member_count(cls),
+            limits,
             &self.heap,
-        )
+        );
+        if literals_before > limits.literals.min(limits.enum_members)
+            && literal_count(&res) < literals_before
+        {
+            debug!(
+                "Widened a union of {literals_before} literals to `{res}`, as it exceeds the literal union limit of {} or the enum union limit of {}",
+                limits.literals, limits.enum_members
+            );
+        }
+        match res {
+            Type::Union(u) if u.members.len() > limits.members => {
+                let widened = self.join_for_widening(&u.members, type_order);
+                debug!(
+                    "Widened a union of {} members to `{widened}`, as it exceeds the union limit of {}",
+                    u.members.len(),
+                    limits.members
+                );
+                widened
+            }
+            res => res,
+        }
+    }
+
+    /// The closest superclass shared by all of `members`, used to widen a union that exceeds the
+    /// union limit. Literals count as their class. If some member is not a class instance, or
+    /// the members only share `object`, this is `object`.
+    fn join_for_widening<Ans: LookupAnswer>(
+        &self,
+        members: &[Type],
+        type_order: TypeOrder<Ans>,
+    ) -> Type {
+        let stdlib = type_order.stdlib();
+        let classes = members
+            .iter()
+            .map(|t| match t {
+                Type::ClassType(cls) => Some(cls.clone()),
+                Type::Literal(lit) => Some(lit.value.general_class_type(stdlib).clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(classes) = classes
+            && let Some(first) = classes.first()
+        {
+            let candidates = iter::once(first.class_object().dupe())
+                .chain(type_order.ancestors(first.class_object()));
+            for want in candidates {
+                if let Some(supers) = classes
+                    .iter()
+                    .map(|cls| type_order.as_superclass(cls, &want))
+                    .collect::<Option<Vec<_>>>()
+                    && supers.iter().all_equal()
+                {
+                    return self.heap.mk_class_type(supers[0].clone());
+                }
+            }
+        }
+        self.heap.mk_class_type(stdlib.object().clone())
     }
 
     /// Record a variable that is used recursively.
@@ -3046,11 +3286,16 @@ impl Solver {
         call_context: Option<&CallContext>,
     ) -> Result<(), SubsetError> {
         let mut subset = self.subset(type_order);
-        if let Some(cc) = call_context {
+        let res = if let Some(cc) = call_context {
             subset.with_active_call_context(cc.clone(), |me| me.is_subset_eq(got, want))
         } else {
             subset.is_subset_eq(got, want)
+        };
+        if res.is_err() && subset.gas_exhausted {
+            // The check was cut short, so we don't know whether it would have failed.
+            return Err(SubsetError::RecursionLimit);
         }
+        res
     }
 
     pub fn is_consistent<Ans: LookupAnswer>(
@@ -3077,7 +3322,8 @@ impl Solver {
         Subset {
             solver: self,
             type_order,
-            gas: INITIAL_GAS,
+            gas: Gas::new(self.subset_recursion_limit as isize),
+            gas_exhausted: false,
             active_call_context: CallContext::outside(),
             subset_cache: SmallMap::new(),
             class_protocol_assumptions: SmallSet::new(),
@@ -3121,6 +3367,7 @@ impl TypeVarSpecializationError {
                     &ans.for_display(got),
                     &ans.for_display(want),
                     ans.module().name(),
+                    ans.solver().union_display_limit,
                 )
             }
             Self::BadConstraintSpecialization { name, got, want } => {
@@ -3297,6 +3544,8 @@ pub enum SubsetError {
     /// A function without **kwargs is not assignable to a function with Unpack-ed TypedDict **kwargs
     /// unless the TypedDict is closed.
     OpenTypedDictKwargs(Name),
+    /// The check recursed too deeply to finish, so whether `got` is assignable to `want` is unknown.
+    RecursionLimit,
     // TODO(rechen): replace this with specific reasons
     Other,
 }
@@ -3335,7 +3584,7 @@ impl SubsetError {
             SubsetError::OpenTypedDictKwargs(td) => Some(format!(
                 "Callable without `**kwargs` cannot be assigned to callable with `**kwargs: Unpack[{td}]`, because `{td}` is not closed and may have additional unknown keys"
             )),
-            SubsetError::Other => None,
+            SubsetError::RecursionLimit | SubsetError::Other => None,
         }
     }
 }
@@ -3670,6 +3919,9 @@ pub struct Subset<'a, Ans: LookupAnswer> {
     pub(crate) solver: &'a Solver,
     pub type_order: TypeOrder<'a, Ans>,
     gas: Gas,
+    /// Whether `gas` ran out at some point during the query, in which case a
+    /// failure may be spurious and must not be cached across queries.
+    pub(crate) gas_exhausted: bool,
     /// Invariant: there is a single active call context for a subset query.
     /// Nested work is recursive subset checking inside the same call, not a
     /// nested full call pipeline with independent call-scoped solving.
@@ -3764,6 +4016,7 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
 
     pub fn is_subset_eq(&mut self, got: &Type, want: &Type) -> Result<(), SubsetError> {
         if self.gas.stop() {
+            self.gas_exhausted = true;
             return Err(SubsetError::Other);
         }
         // Normalize before var solving so decorator metadata does not get pinned as part of a type.
@@ -671,8 +671,10 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
         // 2. No coinductive assumptions were used during this check
         //    (otherwise the result may be contingent on an assumption
         //    that could be invalidated by rollback)
+        // 3. The query has not run out of gas (otherwise a failure may only
+        //    mean the check was cut short)
         let used_coinductive = self.coinductive_assumptions_used;
-        if has_no_vars && !used_coinductive {
+        if has_no_vars && !used_coinductive && !self.gas_exhausted {
             self.solver.store_protocol_cache(got, want, res.clone());
         }
 
@@ -1205,7 +1207,7 @@ impl<'a, Ans: LookupAnswer> Subset<'a, Ans> {
         self.coinductive_assumptions_used = false;
         let res = self.is_subset_typed_dict_inner(got, want);
         let used_coinductive = self.coinductive_assumptions_used;
-        if cacheable && !used_coinductive {
+        if cacheable && !used_coinductive && !self.gas_exhausted {
             self.solver
                 .store_typed_dict_cache(got.clone(), want.clone(), res.clone());
         }
@@ -9,6 +9,7 @@ use std::sync::Arc;
 
 use dupe::Clone_;
 use dupe::Copy_;
+use dupe::Dupe;
 use dupe::Dupe_;
 use pyrefly_types::type_alias::TypeAlias;
 use pyrefly_types::type_alias::TypeAliasData;
@@ -70,6 +71,15 @@ impl<'a, Ans: LookupAnswer> TypeOrder<'a, Ans> {
         self.0.as_superclass(class, want)
     }
 
+    /// The classes `cls` inherits from, in method resolution order, ending with `object`.
+    pub fn ancestors(self, cls: &Class) -> Vec<Class> {
+        self.0
+            .get_mro_for_class(cls)
+            .ancestors(self.0.stdlib)
+            .map(|ancestor| ancestor.class_object().dupe())
+            .collect()
+    }
+
     pub fn as_class_type_unchecked(self, class: &Class) -> ClassType {
         self.0.as_class_type_unchecked(class)
     }
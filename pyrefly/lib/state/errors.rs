@@ -9,6 +9,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use dupe::Dupe;
+use dupe::OptionDupedExt;
 use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_config::error_kind::Severity;
 use pyrefly_python::ignore::Ignore;
@@ -36,6 +37,7 @@ use crate::config::config::ConfigFile;
 use crate::error::baseline::BaselineProcessor;
 use crate::error::collector::CollectedErrors;
 use crate::error::error::Error;
+use crate::error::error::ErrorQuickFix;
 use crate::error::expectation::Expectation;
 use crate::error::style::ErrorStyle;
 use crate::state::load::Load;
@@ -161,6 +163,41 @@ pub fn find_containing_range(
     }
 }
 
+/// A fix that deletes an unused suppression comment: the whole line if the comment is all
+/// it contains, otherwise the comment and the whitespace before it. Returns `None` when
+/// another comment follows, since deleting to the end of the line would remove it too.
+fn remove_suppression_fix(module: &Module, supp: &Suppression) -> Option<ErrorQuickFix> {
+    let line = supp.comment_line();
+    let line_start = module.lined_buffer().line_start(line);
+    let full_line = module.lined_buffer().content_in_line_range(line, line);
+    let content = full_line.trim_end_matches(['\n', '\r']);
+    let comment = content.get(supp.comment_offset()..)?;
+    if comment.get(1..)?.contains('#') {
+        return None;
+    }
+    let code = &content[..supp.comment_offset()];
+    let (start, end) = if code.trim().is_empty() {
+        (0, full_line.len())
+    } else {
+        (code.trim_end().len(), content.len())
+    };
+    let range = TextRange::new(
+        line_start + TextSize::try_from(start).ok()?,
+        line_start + TextSize::try_from(end).ok()?,
+    );
+    Some(ErrorQuickFix::TextEdits {
+        title: "Remove unused suppression comment".to_owned(),
+        edits: vec![(range, String::new())],
+    })
+}
+
+fn with_removal_fix(error: Error, module: &Module, supp: &Suppression) -> Error {
+    match remove_suppression_fix(module, supp) {
+        Some(fix) => error.with_quick_fix(fix),
+        None => error,
+    }
+}
+
 /// Extracts `(start_line, end_line)` line ranges for multi-line expressions
 /// where a suppression comment placed on the line *above* an interior line
 /// would be relocated by a formatter (e.g. Black) out of the bracketed group,
@@ -263,12 +300,39 @@ impl ModuleRanges {
 pub struct Errors {
     // Sorted by module name and path (so deterministic display order)
     loads: Vec<(Arc<Load>, Option<Arc<ModuleRanges>>, ArcId<ConfigFile>)>,
+    /// The current version of each other module that a secondary annotation points into.
+    annotation_modules: SmallMap<ModulePath, Module>,
 }
 
 impl Errors {
-    pub fn new(mut loads: Vec<(Arc<Load>, Option<Arc<ModuleRanges>>, ArcId<ConfigFile>)>) -> Self {
+    /// Each load comes with the module that imported it, if it was reached by following
+    /// imports, which decides whether its errors are reported (see `reports_errors_for`).
+    /// `annotation_modules` holds the modules that secondary annotations point into, which are
+    /// attached to the collected errors.
+    pub fn new(
+        loads: Vec<(
+            Arc<Load>,
+            Option<Arc<ModuleRanges>>,
+            ArcId<ConfigFile>,
+            Option<ModulePath>,
+        )>,
+        annotation_modules: SmallMap<ModulePath, Module>,
+    ) -> Self {
+        let mut loads = loads
+            .into_iter()
+            .filter(|(load, _, config, importer)| {
+                config.reports_errors_for(
+                    load.module_info.path(),
+                    importer.as_ref().map(|importer| importer.as_path()),
+                )
+            })
+            .map(|(load, module_ranges, config, _)| (load, module_ranges, config))
+            .collect::<Vec<_>>();
         loads.sort_by_key(|x| (x.0.module_info.name(), x.0.module_info.path().dupe()));
-        Self { loads }
+        Self {
+            loads,
+            annotation_modules,
+        }
     }
 
     fn merge_display_errors(mut ordinary: Vec<Error>, directives: Vec<Error>) -> Vec<Error> {
@@ -302,6 +366,15 @@ impl Errors {
                 &mut errors,
             );
         }
+        for error in errors
+            .ordinary
+            .iter_mut()
+            .chain(&mut errors.directives)
+            .chain(&mut errors.suppressed)
+            .chain(&mut errors.disabled)
+        {
+            error.resolve_annotation_modules(|path| self.annotation_modules.get(path).duped());
+        }
         errors
     }
 
@@ -429,21 +502,25 @@ impl Errors {
             })
             .collect();
 
-        let enabled_ignores_by_module: SmallMap<&ModulePath, SmallSet<Tool>> = self
+        let enabled_ignores_by_module: SmallMap<&ModulePath, (SmallSet<Tool>, bool)> = self
             .loads
             .iter()
             .map(|(load, _, config)| {
                 let path = load.module_info.path();
-                (path, config.enabled_ignores(path.as_path()).clone())
+                let ignores = (
+                    config.enabled_ignores(path.as_path()).clone(),
+                    config.require_ignore_codes(path.as_path()),
+                );
+                (path, ignores)
             })
             .collect();
 
         for error in &collected.suppressed {
             let module_path = error.path();
-            let enabled_ignores = enabled_ignores_by_module
+            let (enabled_ignores, require_codes) = enabled_ignores_by_module
                 .get(&module_path)
                 .cloned()
-                .unwrap_or_else(Tool::default_enabled);
+                .unwrap_or_else(|| (Tool::default_enabled(), false));
             let start_line = error.display_range().start.line_within_file();
             let end_line = error.display_range().end.line_within_file();
 
@@ -451,14 +528,14 @@ impl Errors {
                 .get(&module_path)
                 .and_then(|ranges| find_containing_range(ranges, start_line));
 
-            let is_ignored = error.is_ignored(&enabled_ignores)
+            let is_ignored = error.is_ignored(&enabled_ignores, require_codes)
                 || containing_range.is_some_and(|(fs_start, fs_end)| {
                     let ignore = error.module().ignore();
                     error.error_kind().suppression_names().any(|kind| {
                         (fs_start != start_line
-                            && ignore.is_ignored(fs_start, kind, &enabled_ignores))
+                            && ignore.is_ignored(fs_start, kind, &enabled_ignores, require_codes))
                             || (fs_end != start_line
-                                && ignore.is_ignored(fs_end, kind, &enabled_ignores))
+                                && ignore.is_ignored(fs_end, kind, &enabled_ignores, require_codes))
                     })
                 });
 
@@ -539,12 +616,16 @@ impl Errors {
                         let comment_line = supp.comment_line();
                         let line_start = module.lined_buffer().line_start(comment_line);
                         let range = TextRange::new(line_start, line_start + TextSize::new(1));
-                        unused_errors.push(Error::new(
-                            module.dupe(),
-                            range,
-                            "Unused pyre-fixme comment".to_owned(),
-                            Vec::new(),
-                            ErrorKind::UnusedIgnore,
+                        unused_errors.push(with_removal_fix(
+                            Error::new(
+                                module.dupe(),
+                                range,
+                                "Unused pyre-fixme comment".to_owned(),
+                                Vec::new(),
+                                ErrorKind::UnusedIgnore,
+                            ),
+                            module,
+                            supp,
                         ));
                         continue;
                     }
@@ -557,12 +638,16 @@ impl Errors {
                         let comment_line = supp.comment_line();
                         let line_start = module.lined_buffer().line_start(comment_line);
                         let range = TextRange::new(line_start, line_start + TextSize::new(1));
-                        unused_errors.push(Error::new(
-                            module.dupe(),
-                            range,
-                            "Unused `# type: ignore` comment".to_owned(),
-                            Vec::new(),
-                            ErrorKind::UnusedTypeIgnore,
+                        unused_errors.push(with_removal_fix(
+                            Error::new(
+                                module.dupe(),
+                                range,
+                                "Unused `# type: ignore` comment".to_owned(),
+                                Vec::new(),
+                                ErrorKind::UnusedTypeIgnore,
+                            ),
+                            module,
+                            supp,
                         ));
                         continue;
                     }
@@ -611,13 +696,20 @@ impl Errors {
                         )
                     };
 
-                    unused_errors.push(Error::new(
+                    let error = Error::new(
                         module.dupe(),
                         range,
                         msg,
                         Vec::new(),
                         ErrorKind::UnusedIgnore,
-                    ));
+                    );
+                    // Only a fully unused comment can be deleted outright; dropping some of
+                    // its codes would need the comment rewritten.
+                    unused_errors.push(if unused_codes.len() == declared_codes.len() {
+                        with_removal_fix(error, module, supp)
+                    } else {
+                        error
+                    });
                 }
             }
         }
@@ -702,6 +794,8 @@ mod tests {
 
     use crate::config::config::ConfigFile;
     use crate::config::finder::ConfigFinder;
+    use crate::error::error::Error;
+    use crate::error::error::ErrorQuickFix;
     use crate::state::errors::Errors;
     use crate::state::load::FileContents;
     use crate::state::require::Require;
@@ -889,6 +983,50 @@ def f() -> int:
         assert!(unused[0].msg().contains("type: ignore"));
     }
 
+    /// Apply the text edits of the first quick fix on `error` to `contents`.
+    fn apply_fix(contents: &str, error: &Error) -> String {
+        let Some(ErrorQuickFix::TextEdits { edits, .. }) = error.quick_fixes().first() else {
+            panic!("expected a text edit fix");
+        };
+        let mut result = contents.to_owned();
+        for (range, text) in edits.iter().rev() {
+            result.replace_range(range.start().to_usize()..range.end().to_usize(), text);
+        }
+        result
+    }
+
+    #[test]
+    fn test_unused_ignore_removal_fix() {
+        let contents = r#"
+def f() -> int:
+    # pyrefly: ignore
+    return 1  # type: ignore
+"#;
+        let (errors, _tdir) = get_errors(contents);
+        let collected = errors.collect_errors();
+        let unused = errors.collect_unused_ignore_errors(&collected);
+        assert_eq!(unused.len(), 2);
+        let fixed = unused
+            .iter()
+            .rev()
+            .fold(contents.to_owned(), |acc, e| apply_fix(&acc, e));
+        assert_eq!(fixed, "\ndef f() -> int:\n    return 1\n");
+    }
+
+    #[test]
+    fn test_partially_used_ignore_has_no_fix() {
+        let contents = r#"
+def f() -> int:
+    # pyrefly: ignore [bad-return, bad-override]
+    return "hello"
+"#;
+        let (errors, _tdir) = get_errors(contents);
+        let collected = errors.collect_errors();
+        let unused = errors.collect_unused_ignore_errors(&collected);
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].quick_fixes().is_empty());
+    }
+
     #[test]
     fn test_used_ignore_multiline_fstring() {
         let contents = r#"
@@ -13,6 +13,8 @@ use std::time::Instant;
 use anyhow::anyhow;
 use dupe::Dupe;
 use dupe::OptionDupedExt;
+use pyrefly_python::encoding::DecodeError;
+use pyrefly_python::encoding::decode_source;
 use pyrefly_python::module::Module;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
@@ -83,9 +85,12 @@ pub struct Load {
 
 impl Load {
     /// Return the code for this module, optional notebook cell mapping, and whether there was an error while loading (a self-error).
+    /// An in-memory path with no contents falls back to `dynamic_module_source`, the stub
+    /// synthesized for a module declared in the `dynamic-modules` config.
     pub fn load_from_path(
         path: &ModulePath,
         memory_lookup: &MemoryFilesLookup,
+        dynamic_module_source: Option<String>,
         timing: Option<&TransactionTimingCounters>,
     ) -> (FileContents, Option<anyhow::Error>) {
         let res = match path.details() {
@@ -96,6 +101,7 @@ impl Load {
                 .duped()
                 .as_deref()
                 .duped()
+                .or_else(|| dynamic_module_source.map(FileContents::from_source))
                 .ok_or_else(|| anyhow!("memory path not found")),
             ModulePathDetails::BundledTypeshed(path) => typeshed().and_then(|x| {
                 x.load(path)
@@ -122,6 +128,7 @@ impl Load {
     }
 
     /// Load from filesystem, handling both regular Python files and Jupyter notebooks.
+    /// Source files are decoded according to any PEP 263 encoding declaration.
     /// Returns the code and optional notebook.
     fn load_from_filesystem(
         path: &Path,
@@ -129,7 +136,7 @@ impl Load {
     ) -> anyhow::Result<FileContents> {
         let content = {
             let start = Instant::now();
-            let result = fs_anyhow::read_to_string(path);
+            let result = fs_anyhow::read(path);
             if let Some(t) = timing {
                 let elapsed_ns = start.elapsed().as_nanos() as u64;
                 t.total_read_count.fetch_add(1, Ordering::Relaxed);
@@ -138,7 +145,7 @@ impl Load {
                     t.slow_read_ns.fetch_add(elapsed_ns, Ordering::Relaxed);
                 }
             }
-            result?
+            decode_source(result?)?
         };
 
         // Check if this is a Jupyter notebook by file extension
@@ -167,16 +174,20 @@ impl Load {
         };
         let errors = ErrorCollector::new(module_info.dupe(), error_style);
         if let Some(err) = self_error {
-            errors
-                .error_builder(
-                    TextRange::default(),
+            let (kind, msg) = match err.downcast_ref::<DecodeError>() {
+                Some(err) => (
+                    ErrorKind::ParseError,
+                    format!("Could not decode `{}`: {err}", module_info.path()),
+                ),
+                None => (
                     ErrorKind::MissingImport,
                     format!(
                         "Failed to load `{name}` from `{}`, got {err:#}",
                         module_info.path()
                     ),
-                )
-                .emit();
+                ),
+            };
+            errors.error_builder(TextRange::default(), kind, msg).emit();
         }
         Self {
             errors,
@@ -45,9 +45,13 @@ pub enum FindError {
     UntypedImport(ModuleName, Arc<String>),
     /// This is the condition where we are using stubs but we do not have the source files
     MissingSourceForStubs(ModuleName),
+    /// This module was found outside the project, but `follow-imports` is set to `error`
+    NotFollowed(ModuleName),
 }
 
 impl FindError {
+    const HINT_PREFIX: &str = "Hint: ";
+
     pub fn missing_import(err: anyhow::Error, module: ModuleName) -> Self {
         Self::MissingImport(module, Arc::new(vec1![format!("{err:#}")]))
     }
@@ -92,12 +96,27 @@ impl FindError {
         FindError::MissingImport(module, Arc::new(explanation))
     }
 
+    /// Put `hint` before the explanation of a missing import.
+    pub fn with_hint(self, hint: String) -> Self {
+        match self {
+            Self::MissingImport(module, err) => {
+                let mut lines = (*err).clone();
+                lines.insert(0, hint);
+                Self::MissingImport(module, Arc::new(lines))
+            }
+            err => err,
+        }
+    }
+
     pub fn display(&self) -> (Option<Box<dyn Fn() -> ErrorContext + '_>>, Vec1<String>) {
         match self {
             Self::MissingImport(module, err) => {
                 let mut lines = (**err).clone();
                 // Compute suggestion lazily at display time, using global cache
-                if let Some(suggested) = suggest_stdlib_import(*module) {
+                // A module we have an install hint for wasn't mistyped, so prefer that hint.
+                if !lines.first().starts_with(Self::HINT_PREFIX)
+                    && let Some(suggested) = suggest_stdlib_import(*module)
+                {
                     lines.insert(0, format!("Did you mean `{suggested}`?"));
                 }
                 (
@@ -119,6 +138,12 @@ impl FindError {
                     "Stubs for `{module}` are bundled with Pyrefly but the source files for the package are not found."
                 )],
             ),
+            Self::NotFollowed(module) => (
+                None,
+                vec1![format!(
+                    "Import of `{module}` is not followed because `follow-imports` is set to `error`"
+                )],
+            ),
             Self::UntypedImport(source_package, stubs_package) => (
                 Some(Box::new(|| ErrorContext::ImportNotTyped(*source_package))),
                 vec1![format!("Hint: install the `{stubs_package}` package")],
@@ -132,6 +157,7 @@ impl FindError {
             Self::MissingSource(..) => Some(ErrorKind::MissingSource),
             Self::MissingSourceForStubs(..) => Some(ErrorKind::MissingSourceForStubs),
             Self::UntypedImport(..) => Some(ErrorKind::UntypedImport),
+            Self::NotFollowed(..) => Some(ErrorKind::ImportNotFollowed),
             Self::Ignored => None,
         }
     }
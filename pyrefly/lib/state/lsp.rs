@@ -72,6 +72,7 @@ use crate::alt::attr::AttrDefinition;
 use crate::alt::attr::AttrInfo;
 use crate::binding::binding::Key;
 use crate::config::error_kind::ErrorKind;
+use crate::error::error::ErrorQuickFix;
 use crate::error::suppress::detect_line_ending;
 use crate::export::exports::Export;
 use crate::export::exports::ExportLocation;
@@ -257,6 +258,10 @@ pub enum ImportBehavior {
     /// Jump through all imports. For non-Python files, this means selecting the definition file,
     /// even if we can't parse/process that definition file.
     JumpThroughEverything,
+    /// Jump through the imports in the current file, but stop at the first module that
+    /// re-exports the name from elsewhere (e.g. `from ._impl import foo as foo` in a
+    /// package's `__init__.py`), rather than following the chain to the original definition.
+    StopAtReexports,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1500,6 +1505,8 @@ impl<'a> Transaction<'a> {
         let mut m = module_name;
         let mut gas = RESOLVE_EXPORT_INITIAL_GAS;
         let mut name = name;
+        // Under `StopAtReexports`, the module, name and range of the first re-export we pass through.
+        let mut first_reexport: Option<(Handle, Name, TextRange)> = None;
         while !gas.stop() {
             let (hop_handle, location) =
                 match self.lookup_export_location_with_pyi_fallback(handle, m, &name, preference) {
@@ -1544,9 +1551,24 @@ impl<'a> Transaction<'a> {
                 };
             match location {
                 ExportLocation::ThisModule(export) => {
+                    if let Some((reexport_handle, reexport_name, location)) = first_reexport {
+                        return Some((
+                            reexport_handle,
+                            reexport_name,
+                            Export { location, ..export },
+                        ));
+                    }
                     return Some((hop_handle, name, export));
                 }
                 ExportLocation::OtherModule(module, aliased_name) => {
+                    if matches!(preference.import_behavior, ImportBehavior::StopAtReexports)
+                        && first_reexport.is_none()
+                        && let Some(range) = self
+                            .get_exports_data(&hop_handle)
+                            .reexport_range(&name, module)
+                    {
+                        first_reexport = Some((hop_handle.dupe(), name.clone(), range));
+                    }
                     if let Some(aliased_name) = aliased_name {
                         name = aliased_name;
                     }
@@ -1659,7 +1681,9 @@ impl<'a> Transaction<'a> {
                 let should_stop_at_import = match preference.import_behavior {
                     ImportBehavior::StopAtEverything => true,
                     ImportBehavior::StopAtRenamedImports => original_name_range.is_some(),
-                    ImportBehavior::JumpThroughEverything => false,
+                    ImportBehavior::JumpThroughEverything | ImportBehavior::StopAtReexports => {
+                        false
+                    }
                 };
                 if should_stop_at_import {
                     Some((
@@ -2832,11 +2856,26 @@ impl<'a> Transaction<'a> {
         &self,
         handle: &Handle,
         position: TextSize,
+    ) -> Result<Vec<TextRangeWithModule>, EmptyResponseReason> {
+        self.goto_definition_with_import_behavior(
+            handle,
+            position,
+            ImportBehavior::JumpThroughEverything,
+        )
+    }
+
+    /// Like `goto_definition`, but with control over how far to follow imports.
+    pub fn goto_definition_with_import_behavior(
+        &self,
+        handle: &Handle,
+        position: TextSize,
+        import_behavior: ImportBehavior,
     ) -> Result<Vec<TextRangeWithModule>, EmptyResponseReason> {
         let definitions = self.find_definition(
             handle,
             position,
             FindPreference {
+                import_behavior,
                 prefer_pyi: false,
                 ..Default::default()
             },
@@ -2950,7 +2989,14 @@ impl<'a> Transaction<'a> {
     ) -> Option<Vec<(String, Vec<(Module, TextRange, String)>)>> {
         let module_info = self.get_module_info(handle)?;
         let ast = self.get_ast(handle)?;
-        let errors = self.get_errors(vec![handle]).collect_errors().ordinary;
+        let handle_errors = self.get_errors(vec![handle]);
+        let collected = handle_errors.collect_errors();
+        // Unused ignores are reported separately, but carry fixes that remove them.
+        let unused_ignores = handle_errors.collect_unused_ignore_errors_for_display(&collected);
+        let errors = collected
+            .ordinary
+            .into_iter()
+            .chain(unused_ignores.ordinary);
         let mut import_actions = Vec::new();
         let mut generate_actions = Vec::new();
         let mut other_actions = Vec::new();
@@ -2983,6 +3029,20 @@ impl<'a> Transaction<'a> {
                     other_actions.push(action);
                 }
             }
+            for fix in error.quick_fixes() {
+                if let ErrorQuickFix::TextEdits { title, edits } = fix
+                    && (error_range.contains_range(range)
+                        || edits.iter().any(|(r, _)| r.contains_range(range)))
+                {
+                    multi_actions.push((
+                        title.clone(),
+                        edits
+                            .iter()
+                            .map(|(r, text)| (module_info.dupe(), *r, text.clone()))
+                            .collect(),
+                    ));
+                }
+            }
             match error.error_kind() {
                 ErrorKind::UnknownName | ErrorKind::UnimportedDirective
                     if error_range.contains_range(range) =>
@@ -3085,6 +3145,38 @@ impl<'a> Transaction<'a> {
                         }
                     }
                 }
+                ErrorKind::ImplicitOptional if error_range.contains_range(range) => {
+                    if let Some(action) =
+                        quick_fixes::annotation_style::implicit_optional_code_action(
+                            &module_info,
+                            handle.sys_info(),
+                            error_range,
+                        )
+                    {
+                        other_actions.push(action);
+                    }
+                }
+                ErrorKind::DeprecatedTypingAlias if error_range.contains_range(range) => {
+                    if let Some(action) =
+                        quick_fixes::annotation_style::deprecated_typing_alias_code_action(
+                            &module_info,
+                            error_range,
+                        )
+                    {
+                        other_actions.push(action);
+                    }
+                }
+                ErrorKind::UnnecessaryQuotedAnnotation if error_range.contains_range(range) => {
+                    if let Some(action) =
+                        quick_fixes::annotation_style::unnecessary_quoted_annotation_code_action(
+                            &module_info,
+                            &ast,
+                            error_range,
+                        )
+                    {
+                        other_actions.push(action);
+                    }
+                }
                 ErrorKind::MissingOverrideDecorator if error_range.contains_range(range) => {
                     if let Some((title, module, decorator_range, insert_text)) =
                         quick_fixes::add_override::add_override_code_action(
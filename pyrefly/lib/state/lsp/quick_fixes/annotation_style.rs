@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Quick fixes for the annotation style lints: `implicit-optional`,
+//! `deprecated-typing-alias` and `unnecessary-quoted-annotation`.
+
+use dupe::Dupe;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::sys_info::SysInfo;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::ModModule;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+
+use crate::ModuleInfo;
+
+/// Rewrite the annotation `T` of a parameter with a `None` default to `T | None`. A quoted
+/// annotation `"T"` becomes `"T | None"`, since `"T" | None` fails at runtime.
+pub(crate) fn implicit_optional_code_action(
+    module_info: &ModuleInfo,
+    sys_info: &SysInfo,
+    error_range: TextRange,
+) -> Option<(String, Module, TextRange, String)> {
+    // `X | None` can't be evaluated at runtime before Python 3.10.
+    if !sys_info.version().at_least(3, 10) {
+        return None;
+    }
+    let annotation = module_info.code_at(error_range);
+    let replacement = match annotation.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let contents = annotation.strip_prefix(quote)?.strip_suffix(quote)?;
+            // Leave triple-quoted, escaped and nested quotes alone.
+            if contents.is_empty() || contents.contains(['"', '\'', '\\']) {
+                return None;
+            }
+            format!("{quote}{contents} | None{quote}")
+        }
+        _ => format!("{annotation} | None"),
+    };
+    Some((
+        "Add `| None` to annotation".to_owned(),
+        module_info.dupe(),
+        error_range,
+        replacement,
+    ))
+}
+
+/// Replace `typing.List` and friends with the corresponding builtin generic.
+pub(crate) fn deprecated_typing_alias_code_action(
+    module_info: &ModuleInfo,
+    error_range: TextRange,
+) -> Option<(String, Module, TextRange, String)> {
+    let code = module_info.code_at(error_range);
+    let alias = code.rsplit('.').next()?;
+    // Aliased imports like `from typing import List as L` aren't rewritten.
+    let builtin = match alias {
+        "List" => "list",
+        "Dict" => "dict",
+        "Tuple" => "tuple",
+        "Type" => "type",
+        _ => return None,
+    };
+    Some((
+        format!("Replace `{code}` with `{builtin}`"),
+        module_info.dupe(),
+        error_range,
+        builtin.to_owned(),
+    ))
+}
+
+/// Remove the quotes around an annotation that is never evaluated at runtime.
+pub(crate) fn unnecessary_quoted_annotation_code_action(
+    module_info: &ModuleInfo,
+    ast: &ModModule,
+    error_range: TextRange,
+) -> Option<(String, Module, TextRange, String)> {
+    let literal = Ast::locate_node(ast, error_range.start())
+        .into_iter()
+        .find_map(|node| match node {
+            AnyNodeRef::ExprStringLiteral(literal) if literal.range() == error_range => {
+                Some(literal)
+            }
+            _ => None,
+        })?;
+    let contents = literal.value.to_str();
+    // Unquoting would change the meaning of escapes, and a multi-line string
+    // isn't a valid annotation outside of quotes.
+    if module_info.code_at(error_range).contains('\\') || contents.contains('\n') {
+        return None;
+    }
+    Some((
+        "Remove quotes from annotation".to_owned(),
+        module_info.dupe(),
+        error_range,
+        contents.to_owned(),
+    ))
+}
@@ -8,6 +8,7 @@
 use std::sync::atomic::AtomicU8;
 use std::sync::atomic::Ordering;
 
+use clap::ValueEnum;
 use dupe::Dupe;
 
 #[derive(Debug, Clone, Dupe, Copy)]
@@ -22,7 +23,9 @@ const INDEXING: u8 = 2;
 const EVERYTHING: u8 = 3;
 
 /// How much information do we require about a module?
-#[derive(Debug, Clone, Dupe, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Dupe, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum
+)]
 pub enum Require {
     /// We require nothing about the module.
     /// It's only purpose is to provide information about dependencies, namely Exports.
@@ -31,6 +34,7 @@ pub enum Require {
     Errors = ERRORS as isize,
     /// We want to retain enough information about a file (e.g. references),
     /// so that IDE features that require an index can work.
+    #[value(skip)]
     Indexing = INDEXING as isize,
     /// We want to retain all information about this module in memory,
     /// including the AST and bindings/answers.
@@ -117,6 +117,7 @@ use crate::module::bundled::BundledStub;
 use crate::module::finder::find_import_prefixes;
 use crate::module::typeshed::BundledTypeshedStdlib;
 use crate::module::typeshed::custom_typeshed_stdlib_config;
+use crate::solver::solver::SolverOptions;
 use crate::solver::solver::VarRecurser;
 use crate::state::epoch::Epoch;
 use crate::state::errors::Errors;
@@ -625,7 +626,7 @@ pub struct TransactionTimingCounters {
     pub total_stat_count: AtomicU64,
     pub slow_stat_count: AtomicU64,
     pub slow_stat_ns: AtomicU64,
-    // Filesystem read latency (read_to_string in load, pkgutil detection in finder)
+    // Filesystem read latency (file reads in load, pkgutil detection in finder)
     pub total_read_count: AtomicU64,
     pub slow_read_count: AtomicU64,
     pub slow_read_ns: AtomicU64,
@@ -919,31 +920,86 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn get_errors<'b>(&self, handles: impl IntoIterator<Item = &'b Handle>) -> Errors {
-        Errors::new(
-            handles
-                .into_iter()
-                .filter_map(|handle| {
-                    self.with_module_config_inner(handle, |config, x| {
-                        let load = x.get_load()?;
-                        let module_ranges = x.module_ranges();
-                        Some((load, module_ranges, config.dupe()))
-                    })
+        self.get_errors_with_importers(handles.into_iter().map(|handle| (handle, None)))
+    }
+
+    /// Like `get_errors`, where each handle may come with the module that imported it, for
+    /// modules reached by following imports.
+    pub fn get_errors_with_importers<'b>(
+        &self,
+        handles: impl IntoIterator<Item = (&'b Handle, Option<&'b Handle>)>,
+    ) -> Errors {
+        let loads = handles
+            .into_iter()
+            .filter_map(|(handle, importer)| {
+                self.with_module_config_inner(handle, |config, x| {
+                    let load = x.get_load()?;
+                    let module_ranges = x.module_ranges();
+                    Some((
+                        handle.sys_info().dupe(),
+                        (
+                            load,
+                            module_ranges,
+                            config.dupe(),
+                            importer.map(|importer| importer.path().dupe()),
+                        ),
+                    ))
                 })
-                .collect(),
+            })
+            .collect::<Vec<_>>();
+        let annotation_modules = self.annotation_modules(
+            loads
+                .iter()
+                .map(|(sys_info, (load, ..))| (&**load, sys_info)),
+        );
+        Errors::new(
+            loads.into_iter().map(|(_, load)| load).collect(),
+            annotation_modules,
         )
     }
 
+    /// Look up the current version of each other module that the errors of `loads` point into
+    /// with a secondary annotation. Errors only store the name and path of such modules, so
+    /// they don't keep an old version of another file alive.
+    fn annotation_modules<'b>(
+        &self,
+        loads: impl IntoIterator<Item = (&'b Load, &'b SysInfo)>,
+    ) -> SmallMap<ModulePath, Module> {
+        let mut res = SmallMap::new();
+        for (load, sys_info) in loads {
+            for (name, path) in load.errors.annotation_modules() {
+                if !res.contains_key(&path)
+                    && let Some(module) =
+                        self.get_module_info(&Handle::new(name, path.dupe(), sys_info.dupe()))
+                {
+                    res.insert(path, module);
+                }
+            }
+        }
+        res
+    }
+
     pub fn get_all_errors(&self) -> Errors {
+        // Every module is included, so annotations can be resolved against the loads themselves.
+        let errors = |loads: Vec<_>| {
+            let annotation_modules = loads
+                .iter()
+                .map(|(load, ..): &(Arc<Load>, _, _, _)| {
+                    (load.module_info.path().dupe(), load.module_info.dupe())
+                })
+                .collect();
+            Errors::new(loads, annotation_modules)
+        };
         if self.data.updated_modules.is_empty() {
             // Optimized path
-            return Errors::new(
+            return errors(
                 self.readable
                     .modules
                     .values()
                     .filter_map(|x| {
                         let load = x.state.get_load()?;
                         let module_ranges = x.state.module_ranges();
-                        Some((load, module_ranges, x.config.dupe()))
+                        Some((load, module_ranges, x.config.dupe(), None))
                     })
                     .collect(),
             );
@@ -955,7 +1011,7 @@ impl<'a> Transaction<'a> {
             .filter_map(|x| {
                 let load = x.1.state.get_load()?;
                 let module_ranges = x.1.state.module_ranges();
-                Some((load, module_ranges, x.1.config.read().dupe()))
+                Some((load, module_ranges, x.1.config.read().dupe(), None))
             })
             .collect::<Vec<_>>();
         for (k, v) in self.readable.modules.iter() {
@@ -963,10 +1019,10 @@ impl<'a> Transaction<'a> {
                 && let Some(load) = v.state.get_load()
             {
                 let module_ranges = v.state.module_ranges();
-                res.push((load, module_ranges, v.config.dupe()));
+                res.push((load, module_ranges, v.config.dupe(), None));
             }
         }
-        Errors::new(res)
+        errors(res)
     }
 
     pub fn config_finder(&self) -> &ConfigFinder {
@@ -1063,6 +1119,27 @@ impl<'a> Transaction<'a> {
         transitive_rdeps
     }
 
+    /// The modules `handle` directly depends on, in a non-deterministic order.
+    pub fn get_deps(&self, handle: &Handle) -> Vec<Handle> {
+        self.get_module(handle)
+            .deps
+            .read()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// The modules `handle` imports by name, with the path each was found at, or `None` if it
+    /// couldn't be found, in a non-deterministic order.
+    pub fn get_imports(&self, handle: &Handle) -> Vec<(ModuleName, Option<ModulePath>)> {
+        self.get_module(handle)
+            .imports
+            .read()
+            .iter()
+            .map(|(module, finding)| (*module, finding.dupe().finding()))
+            .collect()
+    }
+
     /// Return all handles for which there is data, in a non-deterministic order.
     pub fn handles(&self) -> Vec<Handle> {
         if self.data.updated_modules.is_empty() {
@@ -1238,6 +1315,10 @@ impl<'a> Transaction<'a> {
             let (file_contents, self_error) = Load::load_from_path(
                 module_data.handle.path(),
                 &self.memory_lookup(),
+                module_data
+                    .config
+                    .read()
+                    .dynamic_module_source(module_data.handle.path()),
                 Some(&self.timing),
             );
             if self_error.is_some()
@@ -1436,21 +1517,18 @@ impl<'a> Transaction<'a> {
                 path: module_data.handle.path(),
                 sys_info: module_data.handle.sys_info(),
                 memory: &self.memory_lookup(),
+                dynamic_module_source: config.dynamic_module_source(module_data.handle.path()),
                 uniques: &self.data.state.uniques,
                 stdlib: &stdlib,
                 lookup: &self.lookup(module_data),
                 check_unannotated_defs: config
                     .check_unannotated_defs(module_data.handle.path().as_path()),
                 infer_return_types: config.infer_return_types(module_data.handle.path().as_path()),
-                infer_with_first_use: config
-                    .infer_with_first_use(module_data.handle.path().as_path()),
-                tensor_shapes,
-                strict_callable_subtyping: config
-                    .strict_callable_subtyping(module_data.handle.path().as_path()),
-                strict_partial_subtyping: config
-                    .strict_partial_subtyping(module_data.handle.path().as_path()),
-                spec_compliant_overloads: config
-                    .spec_compliant_overloads(module_data.handle.path().as_path()),
+                solver_options: SolverOptions::from_config(
+                    &config,
+                    module_data.handle.path().as_path(),
+                    tensor_shapes,
+                ),
                 recursion_limit_config: config.recursion_limit_config(),
                 pysa_context,
                 cinderx_enabled: self.data.cinderx_reporter.is_some(),
@@ -1993,12 +2071,14 @@ impl<'a> Transaction<'a> {
         let mut todo_count = 0;
         let dirty_count;
         {
-            let dirty = mem::take(&mut *self.data.dirty.lock());
+            let mut dirty = mem::take(&mut *self.data.dirty.lock());
             dirty_count = dirty.len();
+            // Schedule `handles` in the order given, ahead of other dirty modules, so callers
+            // can put the modules they care most about first.
             for h in handles {
                 let (m, created) = self.get_module_ex(h, require);
                 let dirty_require = m.state.increase_require(require);
-                if (created || dirty_require) && !dirty.contains(m) {
+                if dirty.shift_remove(m) || created || dirty_require {
                     self.data.todo.push_fifo(Step::first(), m.dupe());
                     todo_count += 1;
                 }
@@ -2186,6 +2266,18 @@ impl<'a> Transaction<'a> {
         let _ = self.run_internal(handles, require, custom_thread_pool);
     }
 
+    /// Like `run`, but gives up between module steps once the transaction's cancellation handle
+    /// is cancelled. Work that was queued when cancellation happened is dropped, so a cancelled
+    /// transaction should be discarded rather than run again.
+    pub fn run_cancellable(
+        &mut self,
+        handles: &[Handle],
+        require: Require,
+        custom_thread_pool: Option<&ThreadPool>,
+    ) -> Result<(), Cancelled> {
+        self.run_internal(handles, require, custom_thread_pool)
+    }
+
     pub(crate) fn ad_hoc_solve<R: Sized, F: FnOnce(AnswersSolver<TransactionHandle>) -> R>(
         &self,
         handle: &Handle,
@@ -2304,6 +2396,10 @@ impl<'a> Transaction<'a> {
         for (handle, module_data) in self.data.updated_modules.iter_unordered() {
             let config2 = self.data.state.get_config(handle);
             if config2 != *module_data.config.read() {
+                // The stub of a module declared in `dynamic-modules` comes from the config.
+                if config2.dynamic_module_source(handle.path()).is_some() {
+                    module_data.state.set_dirty_load();
+                }
                 *module_data.config.write() = config2;
                 module_data.state.set_dirty_find();
                 dirty_set.insert(module_data.dupe());
@@ -2314,6 +2410,9 @@ impl<'a> Transaction<'a> {
                 let config2 = self.data.state.get_config(handle);
                 if module_data.config != config2 {
                     let module_data = self.get_module(handle);
+                    if config2.dynamic_module_source(handle.path()).is_some() {
+                        module_data.state.set_dirty_load();
+                    }
                     *module_data.config.write() = config2;
                     module_data.state.set_dirty_find();
                     dirty_set.insert(module_data.dupe());
@@ -2445,21 +2544,20 @@ impl<'a> Transaction<'a> {
                 path: m.handle.path(),
                 sys_info: m.handle.sys_info(),
                 memory: &self.memory_lookup(),
+                dynamic_module_source: config.dynamic_module_source(m.handle.path()),
                 uniques: &self.data.state.uniques,
                 stdlib: &stdlib,
                 lookup: &self.lookup(m),
                 check_unannotated_defs: config.check_unannotated_defs(m.handle.path().as_path()),
                 infer_return_types: config.infer_return_types(m.handle.path().as_path()),
-                infer_with_first_use: config.infer_with_first_use(m.handle.path().as_path()),
                 // This is a one-shot timing/diagnostic dump, so we intentionally do not
-                // store the bit on `module_data` (no later dirty.find() re-check applies).
-                tensor_shapes: self.tensor_shapes_available(&config, &m.handle, None),
-                strict_callable_subtyping: config
-                    .strict_callable_subtyping(m.handle.path().as_path()),
-                strict_partial_subtyping: config
-                    .strict_partial_subtyping(m.handle.path().as_path()),
-                spec_compliant_overloads: config
-                    .spec_compliant_overloads(m.handle.path().as_path()),
+                // store the `tensor_shapes` bit on `module_data` (no later dirty.find() re-check
+                // applies).
+                solver_options: SolverOptions::from_config(
+                    &config,
+                    m.handle.path().as_path(),
+                    self.tensor_shapes_available(&config, &m.handle, None),
+                ),
                 recursion_limit_config: config.recursion_limit_config(),
                 pysa_context: None,
                 cinderx_enabled: false,
@@ -2954,27 +3052,29 @@ impl<'a> LookupExport for TransactionHandle<'a> {
         let mut seen = HashSet::new();
         let mut name = name.clone();
 
-        let is_final = loop {
+        let (is_final, final_bool_value) = loop {
             if !seen.insert(module) {
-                break false; // Cycle detected
+                break (false, None); // Cycle detected
             }
 
             let next = self
                 .with_exports(
                     module,
                     |exports, lookup| match exports.exports(lookup).get(&name) {
-                        Some(ExportLocation::ThisModule(Export { is_final, .. })) => Err(*is_final),
+                        Some(ExportLocation::ThisModule(Export { is_final, .. })) => {
+                            Err((*is_final, exports.final_bool_value(&name)))
+                        }
                         Some(ExportLocation::OtherModule(other_module, original_name)) => {
                             Ok((*other_module, original_name.clone()))
                         }
-                        None => Err(false),
+                        None => Err((false, None)),
                     },
                     ModuleDep::ExportOrigin(name.clone()),
                 )
-                .unwrap_or(Err(false));
+                .unwrap_or(Err((false, None)));
 
             match next {
-                Err(is_final) => break is_final,
+                Err(result) => break result,
                 Ok((other_module, original_name)) => {
                     if let Some(original_name) = original_name {
                         name = original_name;
@@ -2987,6 +3087,7 @@ impl<'a> LookupExport for TransactionHandle<'a> {
         ExportOrigin {
             origin: (module, name),
             is_final,
+            final_bool_value,
         }
     }
 }
@@ -3238,7 +3339,8 @@ impl<'a> AsMut<Transaction<'a>> for CancellableTransaction<'a> {
 ///
 ///     1. There can be as many concurrent reads over state as possible,
 ///        but they will block committing.
-///     2. During the committing of `Transaction`, all reads will be blocked.
+///     2. During the committing of `Transaction`, all reads will be blocked
+///        (`try_new_transaction` returns `None` instead of waiting).
 pub struct State {
     threads: ThreadPool,
     uniques: UniqueFactory,
@@ -3293,6 +3395,27 @@ impl State {
         let start = Timer::start();
         let readable = self.state.read();
         let state_lock_blocked = start.elapsed();
+        self.transaction_over(readable, state_lock_blocked, default_require, subscriber)
+    }
+
+    /// Like `new_transaction`, but returns `None` instead of blocking while a committing
+    /// transaction is swapping in its results, so a reader can retry or fall back later.
+    pub fn try_new_transaction<'a>(
+        &'a self,
+        default_require: Require,
+        subscriber: Option<Box<dyn Subscriber + 'a>>,
+    ) -> Option<Transaction<'a>> {
+        let readable = self.state.try_read()?;
+        Some(self.transaction_over(readable, Duration::ZERO, default_require, subscriber))
+    }
+
+    fn transaction_over<'a>(
+        &'a self,
+        readable: RwLockReadGuard<'a, StateData>,
+        state_lock_blocked: Duration,
+        default_require: Require,
+        subscriber: Option<Box<dyn Subscriber + 'a>>,
+    ) -> Transaction<'a> {
         let now = readable.now;
         let stdlib = readable.stdlib.clone();
         Transaction {
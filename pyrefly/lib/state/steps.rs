@@ -34,6 +34,7 @@ use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
 use crate::module::parse::module_parse;
 use crate::solver::solver::Solver;
+use crate::solver::solver::SolverOptions;
 use crate::state::load::Load;
 use crate::state::memory::MemoryFilesLookup;
 use crate::state::require::Require;
@@ -53,16 +54,14 @@ pub struct Context<'a, Lookup> {
     pub path: &'a ModulePath,
     pub sys_info: &'a SysInfo,
     pub memory: &'a MemoryFilesLookup<'a>,
+    /// The stub synthesized for this module, if it is declared in `dynamic-modules`.
+    pub dynamic_module_source: Option<String>,
     pub uniques: &'a UniqueFactory,
     pub stdlib: &'a Stdlib,
     pub lookup: &'a Lookup,
     pub check_unannotated_defs: bool,
     pub infer_return_types: InferReturnTypes,
-    pub infer_with_first_use: bool,
-    pub tensor_shapes: bool,
-    pub strict_callable_subtyping: bool,
-    pub strict_partial_subtyping: bool,
-    pub spec_compliant_overloads: bool,
+    pub solver_options: SolverOptions,
     pub recursion_limit_config: Option<RecursionLimitConfig>,
     /// Pysa context for building PysaSolutions during the Solutions step.
     pub pysa_context: Option<PysaContext<'a>>,
@@ -446,7 +445,12 @@ impl Step {
         } else {
             ErrorStyle::Never
         };
-        let (file_contents, self_error) = Load::load_from_path(ctx.path, ctx.memory, ctx.timing);
+        let (file_contents, self_error) = Load::load_from_path(
+            ctx.path,
+            ctx.memory,
+            ctx.dynamic_module_source.clone(),
+            ctx.timing,
+        );
         Arc::new(Load::load_from_data(
             ctx.module,
             ctx.path.dupe(),
@@ -484,13 +488,7 @@ impl Step {
         ast: Arc<ModModule>,
         exports: Arc<Exports>,
     ) -> Arc<(Bindings, Arc<Answers>)> {
-        let solver = Solver::new(
-            ctx.infer_with_first_use,
-            ctx.tensor_shapes,
-            ctx.strict_callable_subtyping,
-            ctx.strict_partial_subtyping,
-            ctx.spec_compliant_overloads,
-        );
+        let solver = Solver::new(ctx.solver_options.clone());
         let enable_index = ctx.require.keep_index();
         let enable_trace =
             ctx.require.keep_answers_trace() || ctx.pysa_context.is_some() || ctx.cinderx_enabled;
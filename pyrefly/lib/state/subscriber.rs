@@ -17,7 +17,9 @@ use pyrefly_util::lock::Mutex;
 use pyrefly_util::panic::has_panicked;
 use starlark_map::small_map::Entry;
 use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
 
+use crate::config::error_kind::Severity;
 use crate::state::load::Load;
 use crate::state::state::Transaction;
 use crate::state::steps::Step;
@@ -286,6 +288,54 @@ impl Subscriber for SimpleProgressBarSubscriber {
     }
 }
 
+/// A subscriber that cancels the transaction as soon as one of the checked handles finishes with
+/// an error at or above `min_severity`, for `--fail-fast`.
+#[derive(Clone, Dupe)]
+pub struct FailFastSubscriber(Arc<FailFastState>);
+
+struct FailFastState {
+    handles: SmallSet<Handle>,
+    min_severity: Severity,
+    /// The checked handles that finished, in the order they did.
+    finished: Mutex<SmallSet<Handle>>,
+}
+
+impl FailFastSubscriber {
+    pub fn new(handles: &[Handle], min_severity: Severity) -> Self {
+        Self(Arc::new(FailFastState {
+            handles: handles.iter().cloned().collect(),
+            min_severity,
+            finished: Mutex::new(SmallSet::new()),
+        }))
+    }
+
+    /// The checked handles that finished before checking stopped. Errors from other handles
+    /// are incomplete, so shouldn't be reported.
+    pub fn finished(&self) -> Vec<Handle> {
+        self.0.finished.lock().iter().cloned().collect()
+    }
+}
+
+impl Subscriber for FailFastSubscriber {
+    fn start_work(&self, _handle: &Handle) {}
+
+    fn finish_work(&self, transaction: &Transaction<'_>, handle: &Handle, _: &Arc<Load>, _: bool) {
+        if !self.0.handles.contains(handle) {
+            return;
+        }
+        self.0.finished.lock().insert(handle.dupe());
+        if transaction
+            .get_errors([handle])
+            .collect_errors()
+            .ordinary
+            .iter()
+            .any(|e| e.severity() >= self.0.min_severity)
+        {
+            transaction.get_cancellation_handle().cancel();
+        }
+    }
+}
+
 pub struct PublishDiagnosticsSubscriber<F>
 where
     F: Fn(&Transaction<'_>, &Handle, bool) + Send + Sync,
@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Generates API documentation for a type-checked module.
+//!
+//! Documentation is built from the same `ModuleStub` that stubgen emits, so the
+//! documented signatures are the ones the checker inferred, including return
+//! types that aren't annotated in the source.
+
+use std::fmt::Write as _;
+
+use pyrefly_build::handle::Handle;
+use pyrefly_python::docstring::Docstring;
+use serde::Serialize;
+
+use crate::state::state::Transaction;
+use crate::stubgen::emit::class_header;
+use crate::stubgen::emit::function_signature;
+use crate::stubgen::emit::variable_signature;
+use crate::stubgen::extract::ExtractConfig;
+use crate::stubgen::extract::StubItem;
+use crate::stubgen::extract::extract_module_stub;
+
+/// The public API of a module.
+#[derive(Debug, Serialize)]
+pub struct ModuleDoc {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docstring: Option<String>,
+    pub items: Vec<DocItem>,
+}
+
+/// A documented declaration, in source order.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DocItem {
+    Function {
+        name: String,
+        /// e.g. `def f(x: int) -> str`.
+        signature: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        decorators: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        docstring: Option<String>,
+    },
+    Class {
+        name: String,
+        /// e.g. `class A(B)`.
+        signature: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        decorators: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        docstring: Option<String>,
+        members: Vec<DocItem>,
+    },
+    Variable {
+        name: String,
+        /// e.g. `x: int`.
+        signature: String,
+    },
+    TypeAlias {
+        name: String,
+        /// e.g. `type Vector = list[float]`.
+        signature: String,
+    },
+}
+
+impl DocItem {
+    fn name(&self) -> &str {
+        match self {
+            Self::Function { name, .. }
+            | Self::Class { name, .. }
+            | Self::Variable { name, .. }
+            | Self::TypeAlias { name, .. } => name,
+        }
+    }
+}
+
+/// Extract the documentation of a type-checked module. Private names are left out.
+pub fn extract_module_doc(transaction: &Transaction, handle: &Handle) -> Option<ModuleDoc> {
+    let config = ExtractConfig {
+        include_private: false,
+        include_docstrings: true,
+    };
+    let stub = extract_module_stub(transaction, handle, &config)?;
+    let ast = transaction.get_ast(handle)?;
+    let module_info = transaction.get_module_info(handle)?;
+    let docstring =
+        Docstring::range_from_stmts(&ast.body).map(|range| Docstring(range, module_info).resolve());
+    Some(ModuleDoc {
+        name: handle.module().to_string(),
+        docstring,
+        items: doc_items(&stub.items),
+    })
+}
+
+fn doc_items(items: &[StubItem]) -> Vec<DocItem> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            StubItem::Import(_) => None,
+            StubItem::Function(func) => Some(DocItem::Function {
+                name: func.name.clone(),
+                signature: function_signature(func),
+                decorators: func.decorators.clone(),
+                docstring: func.docstring.as_deref().map(Docstring::clean),
+            }),
+            StubItem::Class(cls) => Some(DocItem::Class {
+                name: cls.name.clone(),
+                signature: class_header(cls),
+                decorators: cls.decorators.clone(),
+                docstring: cls.docstring.as_deref().map(Docstring::clean),
+                members: doc_items(&cls.body),
+            }),
+            StubItem::Variable(var) => Some(DocItem::Variable {
+                name: var.name.clone(),
+                signature: variable_signature(var),
+            }),
+            StubItem::TypeAlias(alias) => Some(DocItem::TypeAlias {
+                name: alias.name().to_owned(),
+                signature: alias.text.clone(),
+            }),
+        })
+        .collect()
+}
+
+/// Render a module's documentation as Markdown.
+pub fn emit_markdown(doc: &ModuleDoc) -> String {
+    let mut out = format!("# `{}`\n", doc.name);
+    if let Some(docstring) = &doc.docstring {
+        write!(out, "\n{docstring}\n").unwrap();
+    }
+    emit_markdown_items(&doc.items, "", 2, &mut out);
+    out
+}
+
+fn emit_markdown_items(items: &[DocItem], prefix: &str, level: usize, out: &mut String) {
+    let heading = "#".repeat(level.min(6));
+    for item in items {
+        let qualified = format!("{prefix}{}", item.name());
+        write!(out, "\n{heading} `{qualified}`\n\n```python\n").unwrap();
+        let (decorators, signature, docstring) = match item {
+            DocItem::Function {
+                signature,
+                decorators,
+                docstring,
+                ..
+            }
+            | DocItem::Class {
+                signature,
+                decorators,
+                docstring,
+                ..
+            } => (decorators.as_slice(), signature, docstring.as_deref()),
+            DocItem::Variable { signature, .. } | DocItem::TypeAlias { signature, .. } => {
+                (&[][..], signature, None)
+            }
+        };
+        for decorator in decorators {
+            writeln!(out, "{decorator}").unwrap();
+        }
+        write!(out, "{signature}\n```\n").unwrap();
+        if let Some(docstring) = docstring {
+            write!(out, "\n{docstring}\n").unwrap();
+        }
+        if let DocItem::Class { members, .. } = item {
+            emit_markdown_items(members, &format!("{qualified}."), level + 1, out);
+        }
+    }
+}
@@ -88,6 +88,24 @@ fn emit_function(func: &StubFunction, out: &mut String, indent: &str) {
     }
 
     out.push_str(indent);
+    out.push_str(&function_signature(func));
+
+    if let Some(ds) = &func.docstring {
+        out.push_str(":\n");
+        let body_indent = format!("{}    ", indent);
+        out.push_str(&body_indent);
+        out.push_str(ds);
+        out.push('\n');
+        out.push_str(&body_indent);
+        out.push_str("...\n");
+    } else {
+        out.push_str(": ...\n");
+    }
+}
+
+/// The `def` line of a function without the trailing colon, e.g. `async def f(x: int) -> str`.
+pub fn function_signature(func: &StubFunction) -> String {
+    let mut out = String::new();
     if func.is_async {
         out.push_str("async ");
     }
@@ -97,25 +115,13 @@ fn emit_function(func: &StubFunction, out: &mut String, indent: &str) {
         out.push_str(tp);
     }
     out.push('(');
-    emit_params(&func.params, out);
+    emit_params(&func.params, &mut out);
     out.push(')');
-
     if let Some(ret) = &func.return_type {
         out.push_str(" -> ");
         out.push_str(ret);
     }
-
-    if let Some(ds) = &func.docstring {
-        out.push_str(":\n");
-        let body_indent = format!("{}    ", indent);
-        out.push_str(&body_indent);
-        out.push_str(ds);
-        out.push('\n');
-        out.push_str(&body_indent);
-        out.push_str("...\n");
-    } else {
-        out.push_str(": ...\n");
-    }
+    out
 }
 
 fn emit_params(params: &[StubParam], out: &mut String) {
@@ -156,16 +162,7 @@ fn emit_class(cls: &StubClass, out: &mut String, indent: &str) {
     }
 
     out.push_str(indent);
-    out.push_str("class ");
-    out.push_str(&cls.name);
-    if let Some(tp) = &cls.type_params {
-        out.push_str(tp);
-    }
-    if !cls.bases.is_empty() {
-        out.push('(');
-        out.push_str(&cls.bases);
-        out.push(')');
-    }
+    out.push_str(&class_header(cls));
     out.push_str(":\n");
 
     let body_indent = format!("{}    ", indent);
@@ -191,13 +188,23 @@ fn emit_class(cls: &StubClass, out: &mut String, indent: &str) {
     }
 }
 
+/// The `class` line of a class without the trailing colon, e.g. `class A[T](B)`.
+pub fn class_header(cls: &StubClass) -> String {
+    let mut out = format!("class {}", cls.name);
+    if let Some(tp) = &cls.type_params {
+        out.push_str(tp);
+    }
+    if !cls.bases.is_empty() {
+        out.push('(');
+        out.push_str(&cls.bases);
+        out.push(')');
+    }
+    out
+}
+
 fn emit_variable(var: &StubVariable, out: &mut String, indent: &str) {
     out.push_str(indent);
-    out.push_str(&var.name);
-    if let Some(ann) = &var.annotation {
-        out.push_str(": ");
-        out.push_str(ann);
-    }
+    out.push_str(&variable_signature(var));
     if let Some(val) = &var.value {
         out.push_str(" = ");
         out.push_str(val);
@@ -205,6 +212,14 @@ fn emit_variable(var: &StubVariable, out: &mut String, indent: &str) {
     out.push('\n');
 }
 
+/// The name and annotation of a variable, without its value, e.g. `x: int`.
+pub fn variable_signature(var: &StubVariable) -> String {
+    match &var.annotation {
+        Some(ann) => format!("{}: {ann}", var.name),
+        None => var.name.clone(),
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ItemKind {
     Import,
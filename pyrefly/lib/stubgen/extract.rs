@@ -114,6 +114,16 @@ pub struct StubTypeAlias {
     pub text: String,
 }
 
+impl StubTypeAlias {
+    /// The name being defined, e.g. `Vector`.
+    pub fn name(&self) -> &str {
+        let text = self.text.strip_prefix("type ").unwrap_or(&self.text);
+        text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default()
+    }
+}
+
 /// Configuration for stub extraction.
 pub struct ExtractConfig {
     pub include_private: bool,
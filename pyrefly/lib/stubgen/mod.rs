@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+pub mod doc;
 pub mod emit;
 pub mod extract;
 
@@ -13,6 +14,7 @@ mod tests {
     use std::path::PathBuf;
 
     use dupe::Dupe;
+    use pyrefly_build::handle::Handle;
     use pyrefly_util::forgetter::Forgetter;
     use pyrefly_util::fs_anyhow;
     use pyrefly_util::globs::FilteredGlobs;
@@ -21,11 +23,16 @@ mod tests {
     use pyrefly_util::includes::Includes;
     use pyrefly_util::thread_pool::TEST_THREAD_COUNT;
 
+    use super::doc::ModuleDoc;
+    use super::doc::emit_markdown;
+    use super::doc::extract_module_doc;
     use super::emit::emit_stub;
     use super::extract::ExtractConfig;
     use super::extract::extract_module_stub;
+    use crate::report::type_database::type_database;
     use crate::state::require::Require;
     use crate::state::state::State;
+    use crate::state::state::Transaction;
     use crate::test::util::TestEnv;
 
     fn run_stubgen(input: &str) -> String {
@@ -96,6 +103,18 @@ class BaseModel:
     /// Shared stubgen runner: sets up `FilteredGlobs`, `State`, and
     /// `Transaction`, then extracts and emits stubs for the matched files.
     fn run_stubgen_inner(t: &mut TestEnv, includes: Globs, config: &ExtractConfig) -> String {
+        run_on_files(t, includes, |transaction, handle| {
+            extract_module_stub(transaction, handle, config).map(|stub| emit_stub(&stub))
+        })
+    }
+
+    /// Check the files matched by `includes` and return the output of `render`
+    /// for the last one.
+    fn run_on_files(
+        t: &mut TestEnv,
+        includes: Globs,
+        render: impl Fn(&Transaction, &Handle) -> Option<String>,
+    ) -> String {
         let f_globs = Box::new(FilteredGlobs::new(
             includes,
             Globs::empty(),
@@ -120,8 +139,8 @@ class BaseModel:
         let mut result = String::new();
         for handle in &handles {
             transaction.run(&[handle.dupe()], Require::Everything, None);
-            if let Some(stub) = extract_module_stub(transaction, handle, config) {
-                result = emit_stub(&stub);
+            if let Some(output) = render(transaction, handle) {
+                result = output;
             }
         }
         result
@@ -196,6 +215,150 @@ class BaseModel:
         );
     }
 
+    /// Check `input` as `input.py` and return the output of `render` for it.
+    fn run_on_input(
+        input: &str,
+        render: impl Fn(&Transaction, &Handle) -> Option<String>,
+    ) -> String {
+        let tdir = tempfile::tempdir().unwrap();
+        let path = tdir.path().join("input.py");
+        fs_anyhow::write(&path, input).unwrap();
+        let mut t = TestEnv::new();
+        t.add(&path.display().to_string(), input);
+        let includes = Globs::new(vec![path.display().to_string()]).unwrap();
+        run_on_files(&mut t, includes, render)
+    }
+
+    fn run_doc(input: &str, emit: impl Fn(&ModuleDoc) -> String) -> String {
+        run_on_input(input, |transaction, handle| {
+            extract_module_doc(transaction, handle).map(|doc| emit(&doc))
+        })
+    }
+
+    #[test]
+    fn test_doc_markdown() {
+        let input = r#"
+"""Shapes."""
+
+class Square:
+    """A square."""
+
+    def __init__(self, side: int) -> None:
+        self.side = side
+
+    def area(self):
+        """The area, in square units."""
+        return self.side * self.side
+
+    def _hidden(self) -> None: ...
+
+def unit():
+    return Square(1)
+"#;
+        let expected = r#"# `input`
+
+Shapes.
+
+## `Square`
+
+```python
+class Square
+```
+
+A square.
+
+### `Square.__init__`
+
+```python
+def __init__(self, side: int) -> None
+```
+
+### `Square.area`
+
+```python
+def area(self) -> int
+```
+
+The area, in square units.
+
+## `unit`
+
+```python
+def unit() -> Square
+```
+"#;
+        pretty_assertions::assert_str_eq!(expected, run_doc(input, emit_markdown));
+    }
+
+    #[test]
+    fn test_doc_json() {
+        let input = r#"
+"""Shapes."""
+
+class Square:
+    def area(self) -> int: ...
+
+SIDE: int = 1
+"#;
+        let actual = run_doc(input, |doc| serde_json::to_string_pretty(doc).unwrap());
+        let expected = r#"{
+  "name": "input",
+  "docstring": "Shapes.",
+  "items": [
+    {
+      "kind": "class",
+      "name": "Square",
+      "signature": "class Square",
+      "members": [
+        {
+          "kind": "function",
+          "name": "area",
+          "signature": "def area(self) -> int"
+        }
+      ]
+    },
+    {
+      "kind": "variable",
+      "name": "SIDE",
+      "signature": "SIDE: int"
+    }
+  ]
+}"#;
+        pretty_assertions::assert_str_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_type_database() {
+        let input = r#"
+type Vector = list[float]
+
+class Point:
+    x: int
+
+    def scale(self, factor: int):
+        return Point()
+
+def _origin() -> Point:
+    return Point()
+"#;
+        let actual = run_on_input(input, |transaction, handle| {
+            // Paths are JSON-escaped in the output, so escape the temporary path the same way.
+            let escaped_path = serde_json::to_string(&handle.path().to_string()).unwrap();
+            Some(
+                type_database(transaction, std::slice::from_ref(handle))
+                    .unwrap()
+                    .replace(&escaped_path, r#""input.py""#),
+            )
+        });
+        let expected = r#"{"module":"input","name":"Vector","kind":"type_alias","signature":"type Vector = list[float]","path":"input.py","range":"2:6-12"}
+{"module":"input","name":"Point","kind":"class","signature":"class Point","path":"input.py","range":"4:7-12"}
+{"module":"input","name":"Point.x","kind":"variable","signature":"x: int","path":"input.py","range":"5:5-6"}
+{"module":"input","name":"Point.scale","kind":"function","signature":"def scale(self, factor: int) -> Point","path":"input.py","range":"7:9-14"}
+{"module":"input","name":"_origin","kind":"function","signature":"def _origin() -> Point","path":"input.py","range":"10:5-12"}
+"#;
+        pretty_assertions::assert_str_eq!(expected, actual);
+    }
+
     #[test]
     fn test_stubgen_functions() {
         assert_stubgen_snapshot("functions");
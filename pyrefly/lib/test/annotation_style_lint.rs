@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use pyrefly_python::sys_info::PythonVersion;
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+fn env_with_lint() -> TestEnv {
+    TestEnv::new().enable_annotation_style_lint_error()
+}
+
+testcase!(
+    test_implicit_optional,
+    env_with_lint(),
+    r#"
+def f(
+    a: int = None,  # E: Default `None` is not assignable to parameter `a` with type `int`, add `| None` to the annotation
+    b: "str" = None,  # E: Default `None` is not assignable to parameter `b` with type `str`, add `| None` to the annotation
+    c: int | None = None,
+    d: object = None,
+    e: int = 0,
+) -> None: ...
+
+def g[T: int](x: T = None) -> None: ...  # E: Default `None` is not assignable to parameter `x`
+"#,
+);
+
+testcase!(
+    test_implicit_optional_off_by_default,
+    r#"
+def f(a: int = None) -> None: ...  # E: Default `None` is not assignable to parameter `a` with type `int`
+"#,
+);
+
+testcase!(
+    test_bare_generic,
+    env_with_lint(),
+    r#"
+def f(x: list, y: dict[str, int]) -> None: ...  # E: Cannot determine the type parameter
+"#,
+);
+
+testcase!(
+    test_deprecated_typing_alias,
+    env_with_lint(),
+    r#"
+import typing
+from typing import Dict, List as L
+def f(
+    a: L[int],  # E: `typing.List` is deprecated, use `list` instead
+    b: Dict[str, int],  # E: `typing.Dict` is deprecated, use `dict` instead
+    c: typing.Tuple[int, ...],  # E: `typing.Tuple` is deprecated, use `tuple` instead
+    d: "typing.Type[int]",  # E: `typing.Type` is deprecated, use `type` instead
+    e: list[int],
+) -> None: ...
+"#,
+);
+
+testcase!(
+    test_deprecated_typing_alias_old_version,
+    TestEnv::new_with_version(PythonVersion::new(3, 8, 0)).enable_annotation_style_lint_error(),
+    r#"
+from typing import List
+def f(x: List[int]) -> None: ...
+"#,
+);
+
+testcase!(
+    test_unnecessary_quoted_annotation,
+    env_with_lint(),
+    r#"
+from __future__ import annotations
+from typing import Annotated, Literal, cast
+
+class A: ...
+
+def f(
+    a: "A",  # E: Quotes are unnecessary because this annotation is not evaluated at runtime
+    b: list["A"],  # E: Quotes are unnecessary because this annotation is not evaluated at runtime
+    c: Literal["a"],
+    d: Annotated[A, "metadata"],
+) -> "A":  # E: Quotes are unnecessary because this annotation is not evaluated at runtime
+    return cast("A", c)
+
+x: "A" = A()  # E: Quotes are unnecessary because this annotation is not evaluated at runtime
+"#,
+);
+
+testcase!(
+    test_quoted_annotation_evaluated,
+    env_with_lint(),
+    r#"
+def f(x: "A") -> "A":
+    return x
+
+class A: ...
+"#,
+);
+
+testcase!(
+    test_unnecessary_quoted_annotation_lazy_annotations,
+    TestEnv::new_with_version(PythonVersion::new(3, 14, 0)).enable_annotation_style_lint_error(),
+    r#"
+def f(x: "A") -> None: ...  # E: Quotes are unnecessary because this annotation is not evaluated at runtime
+
+class A: ...
+"#,
+);
@@ -352,6 +352,75 @@ a, *b = ()  # E: Cannot unpack tuple[()] (of size 0) into 1+ values
     "#,
 );
 
+testcase!(
+    test_unpack_unbounded_middle,
+    r#"
+from typing import assert_type
+def f(x: tuple[int, *tuple[str, ...], bytes]):
+    # With no starred target the length is exact, so the middle covers the positions
+    # between the prefix and the suffix.
+    a, b = x
+    assert_type(a, int)
+    assert_type(b, bytes)
+    o, p, q = x
+    assert_type(p, str)
+    assert_type(q, bytes)
+    c, *d, e = x
+    assert_type(c, int)
+    assert_type(d, list[str])
+    assert_type(e, bytes)
+    *g, h, i = x
+    assert_type(g, list[int | str])
+    assert_type(h, int | str)
+    assert_type(i, bytes)
+    (j,) = x  # E: Cannot unpack tuple[int, *tuple[str, ...], bytes] (of size >= 2) into 1 value
+    k, *l, m, n = x  # OK
+    "#,
+);
+
+testcase!(
+    test_unpack_unbounded_tuple,
+    r#"
+from typing import assert_type
+def f(xs: tuple[int, ...], ys: tuple[int, str, *tuple[bytes, ...]]):
+    # An unbounded tuple may have any length, so any exact-length target fits.
+    (a,) = xs
+    b, c, d = xs
+    assert_type(b, int)
+    e, *rest = xs
+    assert_type(e, int)
+    assert_type(rest, list[int])
+    *init, last = xs
+    assert_type(init, list[int])
+    assert_type(last, int)
+    f, *g = ys
+    assert_type(f, int)
+    assert_type(g, list[str | bytes])
+    h, i, j, *k = ys
+    assert_type(j, bytes)
+    assert_type(k, list[bytes])
+    *l, m = ys
+    assert_type(l, list[int | str | bytes])
+    assert_type(m, str | bytes)
+    (n,) = ys  # E: Cannot unpack tuple[int, str, *tuple[bytes, ...]] (of size >= 2) into 1 value
+    o, p, q, r, *s = ys  # OK
+    "#,
+);
+
+testcase!(
+    test_unpack_nested,
+    r#"
+from typing import assert_type
+def f(x: tuple[int, tuple[str, tuple[bytes, bool]]]):
+    a, (b, (c, d)) = x
+    assert_type(a, int)
+    assert_type(b, str)
+    assert_type(c, bytes)
+    assert_type(d, bool)
+    a, (b,) = x  # E: Cannot unpack tuple[str, tuple[bytes, bool]] (of size 2) into 1 value
+    "#,
+);
+
 testcase!(
     test_multiple_annotations,
     r#"
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::test::util::TestEnv;
 use crate::testcase;
 
 testcase!(
@@ -662,3 +663,19 @@ def f(v: A | B):
         assert_type(v.x, int | Any)
     "#,
 );
+
+testcase!(
+    test_hasattr_narrowing_disabled,
+    TestEnv::new().disable_hasattr_narrowing(),
+    r#"
+from typing import assert_type
+class C:
+    x: int
+
+def test(c: C):
+    if hasattr(c, "x"):
+        assert_type(c.x, int)
+    if hasattr(c, "y"):
+        c.y  # E: Object of class `C` has no attribute `y`
+    "#,
+);
@@ -397,6 +397,30 @@ class A[T]:
     "#,
 );
 
+testcase!(
+    test_generic_classvar_legacy,
+    r#"
+from typing import ClassVar, Generic, TypeVar
+T = TypeVar("T")
+class A(Generic[T]):
+    x: ClassVar[T]  # E: `ClassVar` arguments may not contain any type variables
+    y: ClassVar[dict[str, T]]  # E: `ClassVar` arguments may not contain any type variables
+    "#,
+);
+
+testcase!(
+    test_classvar_write_through_instance,
+    r#"
+from typing import ClassVar
+class A:
+    x: ClassVar[int] = 0
+    y: ClassVar[str] = 0  # E: `Literal[0]` is not assignable to `str`
+a = A()
+a.x = 1  # E: A ClassVar may not be mutated from an instance of the class
+A.x = 1
+    "#,
+);
+
 testcase!(
     test_self_attribute_annotated_twice,
     r#"
@@ -1404,6 +1428,38 @@ def test[T: A](a: T) -> T:
     "#,
 );
 
+testcase!(
+    test_class_attribute_on_type_of_typevar_bound,
+    r#"
+from typing import Self, TypeVar, assert_type
+
+class A:
+    x: int = 0
+    def __init__(self, y: int) -> None: ...
+    @classmethod
+    def make(cls) -> Self: ...
+    @classmethod
+    def name(cls) -> str: ...
+    @staticmethod
+    def helper() -> bytes: ...
+
+L = TypeVar("L", bound=A)
+
+def test[T: A](cls: type[T]) -> T:
+    assert_type(cls.x, int)
+    assert_type(cls.make(), T)
+    assert_type(cls.name(), str)
+    assert_type(cls.helper(), bytes)
+    assert_type(cls(1), T)
+    cls.missing  # E: Class `A` has no class attribute `missing`
+    return cls.make()
+
+def test_legacy(cls: type[L]) -> L:
+    assert_type(cls.make(), L)
+    return cls(1)
+    "#,
+);
+
 testcase!(
     test_attribute_access_on_quantified_bound_by_union,
     r#"
@@ -2387,6 +2443,21 @@ def f(x: Color) -> Color:
 "#,
 );
 
+fn env_module_for_suggestions() -> TestEnv {
+    TestEnv::one("foo", "value_one: int = 1\n_private_value: int = 2")
+}
+
+testcase!(
+    test_missing_module_attribute_suggestion,
+    env_module_for_suggestions(),
+    r#"
+import foo
+from foo import value_onw  # E: Could not import `value_onw` from `foo`\n  Did you mean `value_one`?
+foo.value_on  # E: No attribute `value_on` in module `foo`\n  Did you mean `value_one`?
+foo._private_valu  # E: No attribute `_private_valu` in module `foo` # !E: Did you mean
+"#,
+);
+
 testcase!(
     test_union_attribute_missing_no_suggestion,
     r#"
@@ -2406,6 +2477,27 @@ def f(x: str | int | None):
 "#,
 );
 
+testcase!(
+    test_optional_attribute_none_check_hint,
+    r#"
+class A:
+    x: int
+def f(a: A | None, b: list[A | None], c: str | int | None):
+    a.x  # E: Object of class `NoneType` has no attribute `x`\n  `a` may be `None`. Check `if a is not None:` before accessing `x`
+    b[0].x  # E: The value may be `None`. Check that it is not `None` before accessing `x`
+    c.upper()  # E: Object of class `NoneType` has no attribute `upper` # !E: may be `None`
+"#,
+);
+
+testcase!(
+    test_optional_attribute_none_check_hint_disabled,
+    TestEnv::new().disable_optional_attribute_hints(),
+    r#"
+def f(x: str | None):
+    x.upper()  # E: Object of class `NoneType` has no attribute `upper` # !E: may be `None`
+"#,
+);
+
 testcase!(
     test_union_attribute_missing_no_suggestion_mostly_have_it,
     r#"
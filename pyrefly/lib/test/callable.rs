@@ -183,6 +183,43 @@ def test(x: Callable[int]):  # E: Expected 2 arguments for `Callable`, got 1
 "#,
 );
 
+testcase!(
+    test_callable_invalid_parameters,
+    r#"
+from typing import Callable
+def f(x: Callable[(int, str), int]):  # E: the first argument must be a list of parameter types
+    pass
+def g(x: Callable[int, int]):  # E: Callable types can only have `ParamSpec` in this position, got `int`
+    pass
+"#,
+);
+
+testcase!(
+    test_callable_aliased_forms,
+    TestEnv::one("foo", "from typing import ParamSpec\nP = ParamSpec('P')"),
+    r#"
+import collections.abc
+import typing
+from typing import Any, Concatenate, assert_type
+import foo
+
+Fn = collections.abc.Callable
+AnyFn = typing.Callable[..., int]
+
+def f(x: Fn[[int], str], y: AnyFn, z: Fn[Concatenate[int, ...], str]):
+    assert_type(x, typing.Callable[[int], str])
+    assert_type(y(1, "a"), int)
+    assert_type(z(1, "a"), str)
+
+def decorate(f: Fn[foo.P, int]) -> Fn[Concatenate[str, foo.P], int]: ...
+
+@decorate
+def h(x: int) -> int: ...
+
+assert_type(h("a", 1), int)
+"#,
+);
+
 testcase!(
     test_callable_constructor,
     r#"
@@ -681,6 +718,22 @@ def mixed_type_err(xs: tuple[int, int] | Iterable[str]):
 "#,
 );
 
+testcase!(
+    test_splat_unpacked_tuple_prefix,
+    r#"
+def test(x: int, y: str, *args: int): ...
+def ok(xs: tuple[int, str, *tuple[int, ...]]):
+    test(*xs) # OK
+def bad_prefix(xs: tuple[str, *tuple[int, ...]]):
+    test(*xs) # E: Argument `str` is not assignable to parameter `x` with type `int` # E: Argument `int` is not assignable to parameter `y` with type `str`
+def bad_rest(xs: tuple[int, str, *tuple[str, ...]]):
+    test(*xs) # E: Argument `str` is not assignable to parameter `*args` with type `int`
+def too_many(xs: tuple[int, int, *tuple[int, ...]]):
+    def f(x: int): ...
+    f(*xs) # E: Expected 1 positional argument, got 2
+"#,
+);
+
 // Normally, positional arguments can not come after keyword arguments. Splat args are an
 // exception. However, splat args are still evaluated first, so they consume positional params
 // before any keyword arguments.
@@ -1491,6 +1544,35 @@ class Factory(Generic[T]):
 "#,
 );
 
+testcase!(
+    test_classmethod_and_staticmethod_as_callback,
+    r#"
+from typing import Callable, assert_type
+
+class C:
+    @classmethod
+    def cm(cls, x: int) -> str: ...
+    @staticmethod
+    def sm(x: int) -> str: ...
+
+def takes(f: Callable[[int], str]) -> None: ...
+def takes_str(f: Callable[[str], str]) -> None: ...
+
+takes(C.cm)
+takes(C.sm)
+takes(C().cm)
+takes(C().sm)
+takes_str(C.cm)  # E: is not assignable to parameter `f`
+takes_str(C.sm)  # E: is not assignable to parameter `f`
+
+f = C.cm
+g = C.sm
+assert_type(f(1), str)
+assert_type(g(1), str)
+callbacks: list[Callable[[int], str]] = [C.cm, C.sm]
+"#,
+);
+
 testcase!(
     test_callable_instance_with_unknown_base,
     r#"
@@ -1602,11 +1684,25 @@ f = lambda x=1: x
 );
 
 testcase!(
-    bug = "Pyrefly does not contextually type lambda parameters in generic callback arguments (e.g. `sorted(key=...)`), so they become implicit `Any` and are flagged, even though the type is derivable (Pyright infers `int` here)",
     test_implicit_any_lambda_in_generic_call,
     TestEnv::new().enable_implicit_any_lambda_error(),
     r#"
-xs = sorted([3, 1, 2], key=lambda x: x)  # E: Type of lambda parameter `x` is unknown  # E: Return type of lambda is unknown
+xs = sorted([3, 1, 2], key=lambda x: x)
+"#,
+);
+
+testcase!(
+    test_higher_order_builtins_with_lambda,
+    r#"
+from typing import assert_type
+class A:
+    name: str
+def f(xs: list[A], ys: list[int | None]):
+    assert_type(sorted(xs, key=lambda x: x.name), list[A])
+    assert_type(min(xs, key=lambda x: x.name), A)
+    assert_type(max(xs, key=lambda x: x.name), A)
+    assert_type(list(filter(None, ys)), list[int])
+    sorted(xs, key=lambda x: x.missing)  # E: Object of class `A` has no attribute `missing`
 "#,
 );
 
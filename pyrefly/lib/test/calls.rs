@@ -662,3 +662,96 @@ def f() -> list[object]:
     return collect(["x"], 1 + "oops")  # E: `+` is not supported between `Literal[1]` and `Literal['oops']`
     "#,
 );
+
+testcase!(
+    test_getattr_literal_name,
+    r#"
+from typing import Any, assert_type
+class C:
+    x: int
+    def f(self) -> str: ...
+
+def test(c: C, name: str):
+    assert_type(getattr(c, "x"), int)
+    assert_type(getattr(c, "x", None), int | None)
+    assert_type(getattr(c, "f")(), str)
+    # Unknown names and missing attributes fall back to `getattr`'s signature.
+    assert_type(getattr(c, name), Any)
+    assert_type(getattr(c, "y"), Any)
+"#,
+);
+
+testcase!(
+    test_setattr_literal_name,
+    r#"
+from typing import Any
+class C:
+    x: int
+    @property
+    def p(self) -> int: ...
+
+def test(c: C, name: str, value: Any):
+    setattr(c, "x", 1)
+    setattr(c, "x", "oops")  # E: `Literal['oops']` is not assignable to attribute `x` with type `int`
+    setattr(c, "y", 1)  # E: Object of class `C` has no attribute `y`
+    setattr(c, "p", 1)  # E: Attribute `p` of class `C` is a read-only property and cannot be set
+    # Unknown names fall back to `setattr`'s signature.
+    setattr(c, name, "oops")
+    setattr(c, "x", value)
+"#,
+);
+
+testcase!(
+    test_getattr_setattr_literal_name_pair,
+    r#"
+from typing import assert_type
+class C:
+    x: int
+    @property
+    def p(self) -> int: ...
+
+def copy_attr(src: C, dst: C):
+    # A literal-name `getattr` yields the attribute type, which `setattr` then checks.
+    setattr(dst, "x", getattr(src, "x"))
+    setattr(dst, "x", getattr(src, "x", None))  # E: `int | None` is not assignable to attribute `x` with type `int`
+    assert_type(getattr(src, "p"), int)
+    setattr(dst, "p", getattr(src, "p"))  # E: Attribute `p` of class `C` is a read-only property and cannot be set
+"#,
+);
+
+testcase!(
+    test_subprocess_run_output,
+    r#"
+import subprocess
+from typing import assert_type
+
+def test(cmd: list[str], flag: bool):
+    # Output that isn't captured is `None`.
+    assert_type(subprocess.run(cmd), subprocess.CompletedProcess[None])
+    assert_type(subprocess.run(cmd, check=True, text=True), subprocess.CompletedProcess[None])
+    assert_type(subprocess.run(cmd, stdout=None, capture_output=False), subprocess.CompletedProcess[None])
+    subprocess.run(cmd).stdout.decode()  # E: Object of class `NoneType` has no attribute `decode`
+    # Anything that may capture output keeps typeshed's result.
+    assert_type(subprocess.run(cmd, capture_output=True), subprocess.CompletedProcess[bytes])
+    assert_type(subprocess.run(cmd, capture_output=True, text=True), subprocess.CompletedProcess[str])
+    assert_type(subprocess.run(cmd, stdout=subprocess.PIPE, text=True), subprocess.CompletedProcess[str])
+    assert_type(subprocess.run(cmd, capture_output=flag, text=True), subprocess.CompletedProcess[str])
+"#,
+);
+
+testcase!(
+    test_os_getenv_default,
+    r#"
+import os
+from typing import assert_type
+
+def test(default: str | None):
+    assert_type(os.getenv("HOME"), str | None)
+    assert_type(os.getenv("HOME", ""), str)
+    assert_type(os.getenv("HOME", default="/"), str)
+    assert_type(os.getenv("HOME", default), str | None)
+    assert_type(os.environ["HOME"], str)
+    assert_type(os.environ.get("HOME"), str | None)
+    assert_type(os.environ.get("HOME", ""), str)
+"#,
+);
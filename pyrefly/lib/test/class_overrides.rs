@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::config::base::ConstructorOverrides;
 use crate::test::util::TestEnv;
 use crate::testcase;
 
@@ -1927,3 +1928,81 @@ class B(A):
         pass
  "#,
 );
+
+testcase!(
+    test_strict_override,
+    TestEnv::new().enable_strict_override_error(),
+    r#"
+from typing import Any
+class A:
+    def f(self, x: int = 0) -> None: ...
+    def g(self, x: int, *, flag: bool = False) -> None: ...
+    def h(self, x: int = 0) -> None: ...
+class B(A):
+    def f(self, x: int = 1) -> None: ...  # E: Class member `B.f` overrides parent class `A` in an inconsistent manner
+    def g(self, x: int, **kwargs: Any) -> None: ...  # E: Class member `B.g` overrides parent class `A` in an inconsistent manner
+    def h(self, x: int = 0, y: int = 0) -> None: ...
+ "#,
+);
+
+testcase!(
+    test_constructor_overrides_check,
+    TestEnv::new().with_constructor_overrides(ConstructorOverrides::Check),
+    r#"
+class A:
+    def __init__(self, x: int) -> None: ...
+class B(A):
+    def __init__(self, x: str) -> None: ...  # E: Class member `B.__init__` overrides parent class `A` in an inconsistent manner
+class C:
+    def __init__(self, x: int) -> None: ...
+ "#,
+);
+
+testcase!(
+    test_constructor_overrides_ignore,
+    TestEnv::new().with_constructor_overrides(ConstructorOverrides::Ignore),
+    r#"
+from typing import override
+class A:
+    def __init__(self, x: int) -> None: ...
+class B(A):
+    @override
+    def __init__(self, x: str) -> None: ...
+ "#,
+);
+
+testcase!(
+    test_final_across_modules,
+    TestEnv::one(
+        "foo",
+        r#"
+from typing import Final, final
+@final
+class A: ...
+class B:
+    x: Final = 1
+    @final
+    def f(self) -> None: ...
+"#,
+    ),
+    r#"
+from foo import A, B
+class C(A): ...  # E: Cannot extend final class `A`
+class D(B):
+    x = 2  # E: `x` is declared as final in parent class `B`
+    def f(self) -> None: ...  # E: `f` is declared as final in parent class `B`
+ "#,
+);
+
+testcase!(
+    test_final_suppressed_by_kind,
+    r#"
+from typing import final
+@final
+class A:
+    @final
+    def f(self) -> None: ...
+class B(A):  # pyrefly: ignore[overrides-final]
+    def f(self) -> None: ...  # pyrefly: ignore[overrides-final]
+ "#,
+);
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::test::util::TestEnv;
 use crate::testcase;
 
 testcase!(
@@ -175,6 +176,48 @@ assert_type(x, int)
     "#,
 );
 
+testcase!(
+    test_metaclass_call_returns_something_else_everywhere,
+    r#"
+from typing import Callable, Protocol, assert_type
+class Meta(type):
+    def __call__(cls, x: int) -> str: ...
+class C(metaclass=Meta):
+    def __init__(self, y: bytes) -> None: ...
+class Factory(Protocol):
+    def __call__(self, x: int) -> str: ...
+class Constructor(Protocol):
+    def __call__(self, y: bytes) -> C: ...
+assert_type(C(1), str)
+assert_type(C.__call__(1), str)
+f1: Callable[[int], str] = C
+f2: Factory = C
+f3: Callable[[bytes], C] = C  # E: `type[C]` is not assignable to `(bytes) -> C`
+f4: Constructor = C  # E: `type[C]` is not assignable to `Constructor`
+def g(x: object) -> None:
+    if isinstance(x, C):
+        assert_type(x, C)
+    "#,
+);
+
+testcase!(
+    test_metaclass_call_returns_instance_everywhere,
+    r#"
+from typing import Callable, Protocol
+class Meta(type):
+    def __call__[T](cls: type[T], *args, **kwargs) -> T: ...
+class C(metaclass=Meta):
+    def __init__(self, y: bytes) -> None: ...
+class Constructor(Protocol):
+    def __call__(self, y: bytes) -> C: ...
+C(b"")
+C(1)  # E: Argument `Literal[1]` is not assignable to parameter `y` with type `bytes`
+f1: Callable[[bytes], C] = C
+f2: Constructor = C
+f3: Callable[[int], C] = C  # E: `type[C]` is not assignable to `(int) -> C`
+    "#,
+);
+
 testcase!(
     test_metaclass_invalid_generic,
     r#"
@@ -1202,3 +1245,79 @@ x = C([1, 2])
 assert_type(x, C[int])
     "#,
 );
+
+testcase!(
+    test_inconsistent_constructor,
+    TestEnv::new().enable_inconsistent_constructor_error(),
+    r#"
+from typing import Self, overload
+class A:
+    def __new__(cls, x: int) -> Self: ...
+    def __init__(self, x: int, y: int) -> None: ...  # E: `A.__new__` and `A.__init__` accept incompatible arguments
+class B:
+    def __new__(cls, x: int) -> Self: ...
+    def __init__(self, x: str) -> None: ...  # E: `B.__new__` and `B.__init__` accept incompatible arguments
+class C(A):
+    def __init__(self, y: str) -> None: ...  # E: `C.__new__` and `C.__init__` accept incompatible arguments
+class D:
+    @overload
+    def __new__(cls, x: int) -> Self: ...
+    @overload
+    def __new__(cls, x: str) -> Self: ...
+    def __new__(cls, x: int | str) -> Self: ...
+    def __init__(self, x: bytes) -> None: ...  # E: `D.__new__` and `D.__init__` accept incompatible arguments
+    "#,
+);
+
+testcase!(
+    test_consistent_constructor,
+    TestEnv::new().enable_inconsistent_constructor_error(),
+    r#"
+from typing import Any, Self
+class A:
+    def __new__(cls, x: int) -> Self: ...
+    def __init__(self, x: int) -> None: ...
+class B:
+    def __new__(cls, *args: Any, **kwargs: Any) -> Self: ...
+    def __init__(self, x: int, y: str = "") -> None: ...
+class C:
+    def __new__(cls, x: int, y: str = "") -> Self: ...
+    def __init__(self, x: int) -> None: ...
+class D:
+    def __init__(self, x: int) -> None: ...
+class E:
+    def __new__(cls, x: int) -> int: ...
+    def __init__(self) -> None: ...
+class F:
+    def __new__(cls, x: int = 0) -> Self: ...
+    def __init__(self, x: int) -> None: ...
+class G:
+    def __new__(cls, x: int) -> Self: ...
+    def __init__(self, *args, **kwargs) -> None: ...
+class H:
+    def __new__(cls, x: int | str, *, key: str = "") -> Self: ...
+    def __init__(self, x: int) -> None: ...
+class I:
+    def __new__(cls, x: int, /) -> Self: ...
+    def __init__(self, *args: int) -> None: ...
+    "#,
+);
+
+testcase!(
+    test_consistent_constructor_overlapping_overloads,
+    TestEnv::new().enable_inconsistent_constructor_error(),
+    r#"
+from typing import Self, overload
+class A:
+    @overload
+    def __new__(cls, x: int) -> Self: ...
+    @overload
+    def __new__(cls, x: str) -> Self: ...
+    def __new__(cls, x: int | str) -> Self: ...
+    @overload
+    def __init__(self, x: int, y: int = 0) -> None: ...
+    @overload
+    def __init__(self, x: bytes) -> None: ...
+    def __init__(self, x: int | bytes, y: int = 0) -> None: ...
+    "#,
+);
@@ -509,6 +509,22 @@ x: C[list[A]] = C([B()])
 "#,
 );
 
+testcase!(
+    test_context_nested_generic_calls,
+    r#"
+from collections import defaultdict
+
+class A: ...
+class B(A): ...
+
+def wrap[T](x: T) -> list[T]: ...
+
+x: list[list[A]] = wrap(wrap(B()))
+y: dict[str, list[A]] = defaultdict(list)
+z: list[list[A]] = wrap([B()])
+"#,
+);
+
 testcase!(
     bug = "Propagating the hint should still allow for a narrower inferred type",
     test_context_ctor_return_narrow,
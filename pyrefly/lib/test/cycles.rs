@@ -741,7 +741,9 @@ reveal_type(g(1))  # E: revealed type: Literal['done'] | Unknown
 );
 
 // Verify that mutually recursive inferred returns spanning modules form an
-// SCC and converge through the cross-module `solve_idx_erased` path.
+// SCC and converge through the cross-module `solve_idx_erased` path. The
+// back-edge on the export is deferred to the next iteration rather than seeded
+// with `Any`, so no `Unknown` leaks into the result.
 
 fn env_iterative_cross_module_cycle() -> TestEnv {
     let mut env = TestEnv::new();
@@ -775,8 +777,72 @@ testcase!(
 from typing import reveal_type
 from a import f
 from b import g
-reveal_type(f(1))  # E: revealed type: Literal[0] | Unknown
-reveal_type(g(1))  # E: revealed type: Literal[0] | Unknown
+reveal_type(f(1))  # E: revealed type: Literal[0]
+reveal_type(g(1))  # E: revealed type: Literal[0]
+"#,
+);
+
+fn env_cross_module_attribute_cycle() -> TestEnv {
+    let mut env = TestEnv::new();
+    env.add(
+        "a",
+        r#"
+import b
+
+def f(x: int):
+    if x <= 0:
+        return 0
+    return b.g(x - 1)
+"#,
+    );
+    env.add(
+        "b",
+        r#"
+import a
+
+def g(x: int):
+    return a.f(x)
+"#,
+    );
+    env
+}
+
+testcase!(
+    iterative_cross_module_attribute_cycle,
+    env_cross_module_attribute_cycle(),
+    r#"
+from typing import reveal_type
+from a import f
+from b import g
+reveal_type(f(1))  # E: revealed type: Literal[0]
+reveal_type(g(1))  # E: revealed type: Literal[0]
+"#,
+);
+
+fn env_non_convergent_import_cycle() -> TestEnv {
+    let mut env = TestEnv::new();
+    env.add(
+        "a",
+        r#"
+from b import y
+x = [y]  # E: The cycle passes through imports between modules
+"#,
+    );
+    env.add(
+        "b",
+        r#"
+from a import x
+y = [x]
+"#,
+    );
+    env
+}
+
+testcase!(
+    non_convergent_import_cycle_names_modules,
+    env_non_convergent_import_cycle(),
+    r#"
+from a import x
 "#,
 );
 
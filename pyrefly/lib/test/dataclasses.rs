@@ -2589,7 +2589,7 @@ class Immutable:
 "#,
 );
 
-// Subclass of a frozen dataclass: only `BadOverride` fires (it carries the
+// Subclass of a frozen dataclass: only `OverridesFinal` fires (it carries the
 // richer "declared as final in parent class `Base`" message). Our
 // `Cannot override __setattr__/__delattr__ in a frozen dataclass`
 // diagnostic is suppressed here because it is scoped to the class that is
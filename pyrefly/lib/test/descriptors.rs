@@ -134,6 +134,25 @@ def f(c: C):
     "#,
 );
 
+testcase!(
+    test_property_setter_converts_value,
+    r#"
+from typing import assert_type
+class C:
+    @property
+    def port(self) -> int:
+        return 0
+    @port.setter
+    def port(self, value: str | int) -> None:
+        pass
+def f(c: C):
+    c.port = "8080"
+    c.port = 8080
+    c.port = None  # E: `None` is not assignable to parameter `value`
+    assert_type(c.port, int)
+    "#,
+);
+
 testcase!(
     test_deprecated_overloaded_property_setter,
     r#"
@@ -1224,3 +1224,51 @@ for x in E:  # E: Type `type[E]` is not iterable
     reveal_type(x)  # E: revealed type: Unknown
     "#,
 );
+
+testcase!(
+    test_enum_class_iteration_and_len,
+    r#"
+from typing import assert_type
+from enum import Enum, Flag
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+
+class Perm(Flag):
+    R = 4
+    W = 2
+
+for c in Color:
+    assert_type(c, Color)
+for p in Perm:
+    assert_type(p, Perm)
+assert_type(list(Color), list[Color])
+assert_type(len(Color), int)
+assert_type(len(Perm), int)
+    "#,
+);
+
+testcase!(
+    test_enum_lookup_by_literal_value,
+    r#"
+from typing import assert_type, Literal
+from enum import Enum, StrEnum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    CRIMSON = 1  # Alias of RED
+
+class Shade(StrEnum):
+    LIGHT = "light"
+    DARK = "dark"
+
+def f(x: int) -> None:
+    assert_type(Color(2), Literal[Color.GREEN])
+    assert_type(Color(x), Color)
+    assert_type(Color(3), Color)
+    assert_type(Shade("dark"), Literal[Shade.DARK])
+    assert_type(Shade["LIGHT"], Literal[Shade.LIGHT])
+    "#,
+);
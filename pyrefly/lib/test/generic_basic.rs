@@ -203,6 +203,33 @@ x: D
     "#,
 );
 
+testcase!(
+    test_untype_with_missing_targs_function_signature,
+    TestEnv::new().enable_implicit_any_error(),
+    r#"
+from typing import Any, Generic, TypeVar, assert_type
+T = TypeVar("T")
+class A(Generic[T]): pass
+
+def f(
+    a: A,  # E: Cannot determine the type parameter `T` for generic class `A`
+) -> A:  # E: Cannot determine the type parameter `T` for generic class `A`
+    assert_type(a, A[Any])
+    return a
+    "#,
+);
+
+testcase!(
+    test_untype_with_missing_targs_ignored_by_default,
+    r#"
+from typing import Any, assert_type
+class C[T]: pass
+
+def f(a: C):
+    assert_type(a, C[Any])
+    "#,
+);
+
 testcase!(
     test_typevar_default_contains_nested_typevar,
     r#"
@@ -798,6 +825,18 @@ assert_type(g(0, [""]), int | str)
     "#,
 );
 
+testcase!(
+    test_call_error_reports_inferred_type_variables,
+    r#"
+def f[T](x: T, y: list[T]) -> T: ...
+def g[K, V](x: K, y: V, z: dict[K, V]) -> None: ...
+
+f(0, [""])  # E: Type variable `T` was inferred as `int | str`
+def test(d: dict[str, str]):
+    g(0, "", d)  # E: Type variable `K` was inferred as `int`
+    "#,
+);
+
 // Regression test for https://github.com/facebook/pyrefly/issues/2130
 testcase!(
     test_generic_return_type_with_union_of_scoped_type_params,
@@ -771,6 +771,24 @@ x: list[T]  # E: Type variable `T` is not in scope
     "#,
 );
 
+testcase!(
+    test_unbounded_typevar_nested,
+    r#"
+from typing import Callable, Generic, TypeVar
+T = TypeVar("T")
+a: T | None = None  # E: Type variable `T` is not in scope
+b: dict[str, list[T]] = {}  # E: Type variable `T` is not in scope
+c: Callable[[T], None]  # E: Type variable `T` is not in scope
+
+def f(x: T) -> T:
+    y: list[T] = [x]
+    return y[0]
+
+class C(Generic[T]):
+    attr: list[T]
+    "#,
+);
+
 testcase!(
     test_typevar_violates_annotation,
     r#"
@@ -68,6 +68,28 @@ assert_type(y, X)
 "#,
 );
 
+testcase!(
+    test_imports_star_shadows_definition,
+    env_class_x(),
+    r#"
+from typing import assert_type
+def x() -> None: ...
+class Y: ...
+from foo import *  # E: `from foo import *` overwrites the existing definition of `x`
+assert_type(x, X)
+assert_type(Y(), Y)
+"#,
+);
+
+testcase!(
+    test_imports_star_reimport_not_shadowing,
+    env_class_x(),
+    r#"
+from foo import x
+from foo import *
+"#,
+);
+
 testcase!(
     test_imports_star_dunder,
     TestEnv::one("foo", "def __derp__() -> int: ..."),
@@ -243,10 +265,65 @@ assert_type(not_a_real_value, Any)
 testcase!(
     test_bad_relative_import,
     r#"
-from ... import does_not_exist  # E: Could not resolve relative import `...`
+from ... import does_not_exist  # E: Could not resolve relative import `...` because `main` is a top-level module
+"#,
+);
+
+fn env_relative_imports() -> TestEnv {
+    let mut t = TestEnv::new();
+    t.add_with_path(
+        "pkg",
+        "pkg/__init__.py",
+        "from .sub import x\nfrom . import sub as sub",
+    );
+    t.add_with_path("pkg.sub", "pkg/sub.py", "x: int = 1");
+    t.add_with_path(
+        "pkg.mod",
+        "pkg/mod.py",
+        r#"
+from .sub import x
+from ... import y  # E: Could not resolve relative import `...` because it goes beyond the top-level package `pkg`
+"#,
+    );
+    // A namespace package, with no `__init__`.
+    t.add_with_path("ns.a", "ns/a.py", "from .b import y");
+    t.add_with_path("ns.b", "ns/b.py", "y: str = ''");
+    t
+}
+
+testcase!(
+    test_relative_imports,
+    env_relative_imports(),
+    r#"
+from typing import assert_type
+import pkg
+import pkg.mod
+from ns.a import y
+assert_type(pkg.x, int)
+assert_type(pkg.sub.x, int)
+assert_type(y, str)
 "#,
 );
 
+#[test]
+fn test_relative_import_from_unknown_module() -> anyhow::Result<()> {
+    let mut env = TestEnv::new();
+    env.add("foo", "x: int = 1");
+    env.add_with_path(
+        "__unknown__",
+        "my-script.py",
+        r#"
+from . import foo  # E: Could not resolve relative import `.` because this file isn't under any search root
+from foo import x
+"#,
+    );
+    let (state, handle) = env.to_state();
+    state
+        .transaction()
+        .get_errors([&handle("__unknown__")])
+        .check_against_expectations()
+}
+
 fn env_all_x() -> TestEnv {
     TestEnv::one(
         "foo",
@@ -1773,6 +1850,26 @@ from *a # E: Parse error: Expected an indented block after `class` definition #
 "#,
 );
 
+// A syntax error in a dependency is reported there, without turning the rest of it into `Any`.
+testcase!(
+    test_import_from_module_with_parse_error,
+    TestEnv::one(
+        "foo",
+        r#"
+def f() -> int: ...
+y =  # E: Parse error: Expected an expression
+class C:
+    x: str
+"#,
+    ),
+    r#"
+from typing import assert_type
+from foo import f, C
+assert_type(f(), int)
+assert_type(C().x, str)
+"#,
+);
+
 const PKGUTIL_INIT: &str =
     "from pkgutil import extend_path\n__path__ = extend_path(__path__, __name__)\n";
 
@@ -2246,3 +2343,64 @@ from pkg import my_typing as mt
 x: mt.Annotated[int, "metadata"] = 5
 "#,
 );
+
+testcase!(
+    test_unused_import,
+    TestEnv::new().enable_unused_import_error(),
+    r#"
+import os  # E: Import `os` is unused
+import sys
+from typing import Dict, List  # E: Import `Dict` is unused
+from collections import OrderedDict as OrderedDict
+import json  # noqa: F401
+import re  # noqa
+import enum  # noqa: E501  # E: Import `enum` is unused
+import abc
+__all__ = ["abc"]
+x: List[int] = []
+print(sys.argv)
+"#,
+);
+
+fn env_unused_import_in_init() -> TestEnv {
+    let mut t = TestEnv::new().enable_unused_import_error();
+    t.add_with_path("pkg", "pkg/__init__.py", "import os");
+    t
+}
+
+testcase!(
+    test_unused_import_in_init,
+    env_unused_import_in_init(),
+    r#"
+import pkg
+print(pkg)
+"#,
+);
+
+fn env_dynamic_modules() -> TestEnv {
+    TestEnv::new().with_dynamic_modules(&[
+        (
+            "myapp.settings",
+            &[("DEBUG", "bool"), ("TIMEOUT", "datetime.timedelta | None")],
+        ),
+        ("plugins", &[("REGISTRY", "dict[str, Callable[[], None]]")]),
+    ])
+}
+
+testcase!(
+    test_dynamic_modules,
+    env_dynamic_modules(),
+    r#"
+import datetime
+from typing import Callable, assert_type
+import myapp.settings
+from myapp import settings
+from myapp.settings import DEBUG, MISSING  # E: Could not import `MISSING` from `myapp.settings`
+from plugins import REGISTRY
+from plugins import Callable as C  # E: Could not import `Callable` from `plugins`
+assert_type(myapp.settings.DEBUG, bool)
+assert_type(settings.TIMEOUT, datetime.timedelta | None)
+assert_type(DEBUG, bool)
+assert_type(REGISTRY, dict[str, Callable[[], None]])
+"#,
+);
@@ -237,6 +237,18 @@ fn test_incremental_minimal_recompute() {
     i.check(&["main"], &["main"]); // `foo` is required by this point
 }
 
+#[test]
+fn test_identical_content_no_recompute() {
+    let mut i = Incremental::new();
+    i.set("main", "import foo; x = foo.x");
+    i.set("foo", "x = 7");
+    i.check(&["main"], &["main", "foo"]);
+    // Writing the same contents back (e.g. a branch switch that restores a file) is not a change.
+    i.set("foo", "x = 7");
+    i.set("main", "import foo; x = foo.x");
+    i.check(&["main"], &[]);
+}
+
 #[test]
 fn test_incremental_cyclic() {
     let mut i = Incremental::new();
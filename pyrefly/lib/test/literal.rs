@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use crate::test::util::TestEnv;
 use crate::testcase;
 
 testcase!(
@@ -644,3 +645,174 @@ def foo():
     assert_type(x, Color)
     "#,
 );
+
+testcase!(
+    test_str_for_literal_string_param,
+    r#"
+from typing import LiteralString
+
+def execute(sql: LiteralString) -> None: ...
+
+def f(table: LiteralString, user: str):
+    execute(f"SELECT * FROM {table}")
+    execute(f"SELECT * FROM {user}")  # E: Argument `str` is not assignable to parameter `sql` with type `LiteralString`
+    execute(user)  # pyrefly: ignore[bad-argument-type-literal-string]
+    execute(user)  # pyrefly: ignore[bad-argument-type]
+    "#,
+);
+
+testcase!(
+    test_literal_string_preserved_by_narrowing,
+    r#"
+from typing import LiteralString, assert_type
+
+def execute(sql: LiteralString) -> None: ...
+
+def f(query: LiteralString, flag: bool):
+    if query:
+        assert_type(query, LiteralString)
+    if isinstance(query, str):
+        assert_type(query, LiteralString)
+    if flag:
+        query = query + " LIMIT 1"
+    execute(query)
+    execute(query if flag else "SELECT 1")
+    "#,
+);
+
+testcase!(
+    test_literal_union_limit,
+    TestEnv::new().with_literal_union_limit(2),
+    r#"
+from typing import assert_type, Literal
+def f(x: int):
+    if x == 0:
+        y = "a"
+    else:
+        y = "b"
+    assert_type(y, Literal["a", "b"])
+
+def g(x: int):
+    if x == 0:
+        y = "a"
+    elif x == 1:
+        y = "b"
+    else:
+        y = "c"
+    assert_type(y, str)
+"#,
+);
+
+testcase!(
+    test_enum_union_limit,
+    TestEnv::new().with_enum_union_limit(2),
+    r#"
+from enum import Enum
+from typing import assert_type, Literal
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+    ALPHA = 4
+def f(x: int):
+    if x == 0:
+        y = Color.RED
+    else:
+        y = Color.GREEN
+    assert_type(y, Literal[Color.RED, Color.GREEN])
+
+def g(x: int):
+    if x == 0:
+        y = Color.RED
+    elif x == 1:
+        y = Color.GREEN
+    else:
+        y = Color.BLUE
+    assert_type(y, Color)
+
+def h(x: int):
+    if x == 0:
+        y = 1
+    else:
+        y = Color.RED
+    assert_type(y, Literal[1, Color.RED])
+"#,
+);
+
+testcase!(
+    test_union_limit,
+    TestEnv::new().with_union_limit(2),
+    r#"
+from typing import assert_type
+class Base: ...
+class A(Base): ...
+class B(Base): ...
+class C(Base): ...
+class D: ...
+def f(x: int):
+    if x == 0:
+        y = A()
+    else:
+        y = B()
+    assert_type(y, A | B)
+
+def g(x: int):
+    if x == 0:
+        y = A()
+    elif x == 1:
+        y = B()
+    else:
+        y = C()
+    assert_type(y, Base)
+
+def h(x: int):
+    if x == 0:
+        y = A()
+    elif x == 1:
+        y = B()
+    else:
+        y = D()
+    assert_type(y, object)
+"#,
+);
+
+testcase!(
+    test_literal_string_sinks,
+    TestEnv::new().with_literal_string_sinks(&["main.Cursor.execute", "main.run(shell=True)"]),
+    r#"
+from typing import LiteralString
+class Cursor:
+    def execute(self, sql: str, params: tuple[object, ...] = ()) -> None: ...
+def run(args: str | list[str], shell: bool = False) -> None: ...
+
+def f(cursor: Cursor, user: str, table: LiteralString):
+    cursor.execute("SELECT * FROM t WHERE name = ?", (user,))
+    cursor.execute(f"SELECT * FROM {table}")
+    cursor.execute(f"SELECT * FROM t WHERE name = '{user}'")  # E: `main.Cursor.execute` expects a `LiteralString`, but got `str`
+    run(user)
+    run(["ls", user], shell=True)
+    run(user, shell=True)  # E: `main.run` expects a `LiteralString`, but got `str`
+    cursor.execute(sql=user)  # E: `main.Cursor.execute` expects a `LiteralString`, but got `str`
+    run(shell=True, args=user)  # E: `main.run` expects a `LiteralString`, but got `str`
+    run(user, shell=False)
+"#,
+);
+
+testcase!(
+    test_literal_string_sinks_overload,
+    TestEnv::new().with_literal_string_sinks(&["main.query(mode='raw')"]),
+    r#"
+from typing import Literal, overload
+@overload
+def query(sql: str, mode: Literal["raw"]) -> bytes: ...
+@overload
+def query(sql: str, mode: Literal["text"] = ...) -> str: ...
+def query(sql: str, mode: str = "text") -> bytes | str: ...
+
+def f(user: str):
+    query(user)
+    query(user, mode="text")
+    query(user, mode="raw")  # E: `main.query` expects a `LiteralString`, but got `str`
+    query(mode="raw", sql="SELECT 1")
+"#,
+);
@@ -5166,3 +5166,60 @@ class Derived(Base):
 ";
     assert_eq!(expected, after);
 }
+
+/// Apply the quick fix titled `title` at the first occurrence of `cursor` in `code`,
+/// with the annotation style lints enabled.
+fn annotation_style_quickfix_after(code: &str, cursor: &str, title: &str) -> Option<String> {
+    let mut env = TestEnv::new();
+    env.add("main", code);
+    let (state, handle_for_module) = env.enable_annotation_style_lint_error().to_state();
+    let handle = handle_for_module("main");
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(&handle).unwrap();
+    let position = TextSize::try_from(code.find(cursor).unwrap()).unwrap();
+    let (_, edits) = transaction
+        .local_quickfix_code_actions_sorted(
+            &handle,
+            TextRange::new(position, position),
+            ImportFormat::Absolute,
+            None,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(action_title, _)| action_title == title)?;
+    Some(apply_refactor_edits_for_module(&module_info, &edits))
+}
+
+#[test]
+fn quickfix_implicit_optional() {
+    let code = "def f(x: int = None) -> None: ...\n";
+    let after = annotation_style_quickfix_after(code, "int", "Add `| None` to annotation").unwrap();
+    assert_eq!("def f(x: int | None = None) -> None: ...\n", after);
+}
+
+#[test]
+fn quickfix_implicit_optional_quoted() {
+    let code = "def f(x: \"int\" = None) -> None: ...\n";
+    let after =
+        annotation_style_quickfix_after(code, "\"int\"", "Add `| None` to annotation").unwrap();
+    assert_eq!("def f(x: \"int | None\" = None) -> None: ...\n", after);
+}
+
+#[test]
+fn quickfix_deprecated_typing_alias() {
+    let code = "import typing\ndef f(x: typing.List[int]) -> None: ...\n";
+    let after =
+        annotation_style_quickfix_after(code, "List", "Replace `typing.List` with `list`").unwrap();
+    assert_eq!("import typing\ndef f(x: list[int]) -> None: ...\n", after);
+}
+
+#[test]
+fn quickfix_unnecessary_quoted_annotation() {
+    let code = "from __future__ import annotations\ndef f(x: \"A\") -> None: ...\nclass A: ...\n";
+    let after =
+        annotation_style_quickfix_after(code, "\"A\"", "Remove quotes from annotation").unwrap();
+    assert_eq!(
+        "from __future__ import annotations\ndef f(x: A) -> None: ...\nclass A: ...\n",
+        after
+    );
+}
@@ -12,6 +12,7 @@ use pyrefly_python::module::TextRangeWithModule;
 use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
+use crate::state::lsp::ImportBehavior;
 use crate::state::state::State;
 use crate::test::util::TestEnv;
 use crate::test::util::code_frame_of_source_at_range;
@@ -956,6 +957,63 @@ Definition Result:
     );
 }
 
+fn get_test_report_stop_at_reexports(state: &State, handle: &Handle, position: TextSize) -> String {
+    let defs = state
+        .transaction()
+        .goto_definition_with_import_behavior(handle, position, ImportBehavior::StopAtReexports)
+        .unwrap_or_default();
+    defs.into_iter()
+        .map(|TextRangeWithModule { module, range }| {
+            format!(
+                "Definition Result:\n{}",
+                code_frame_of_source_at_range(module.contents(), range)
+            )
+        })
+        .next()
+        .unwrap_or_else(|| "Definition Result: None".to_owned())
+}
+
+#[test]
+fn stop_at_reexports_test() {
+    let code_impl: &str = r#"
+def f():
+        pass"#;
+    let code_reexporter: &str = r#"
+from .impl import f as f
+"#;
+    let code_test: &str = r#"
+from .reexporter import f
+
+bar = f()
+#     ^
+    "#;
+    let report = get_batched_lsp_operations_report(
+        &[
+            ("main", code_test),
+            ("reexporter", code_reexporter),
+            ("impl", code_impl),
+        ],
+        get_test_report_stop_at_reexports,
+    );
+    assert_eq!(
+        r#"
+# main.py
+4 | bar = f()
+          ^
+Definition Result:
+2 | from .impl import f as f
+                           ^
+
+
+# reexporter.py
+
+# impl.py
+"#
+        .trim(),
+        report.trim()
+    );
+}
+
 #[test]
 fn import_of_import_test() {
     let code_import_provider2: &str = r#"
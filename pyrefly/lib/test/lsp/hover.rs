@@ -12,6 +12,7 @@ use pyrefly_build::handle::Handle;
 use ruff_text_size::TextSize;
 
 use crate::lsp::wasm::hover::get_hover;
+use crate::lsp::wasm::hover::get_hover_with_signature_line_width;
 use crate::state::require::Require;
 use crate::state::state::State;
 use crate::test::util::TestEnv;
@@ -2072,3 +2073,146 @@ method
     assert_sphinx_resolved_as_code(&report, "py-meth", "test");
     assert_sphinx_resolved_as_code(&report, "c-func", "other");
 }
+
+#[test]
+fn hover_signature_line_width() {
+    let code = r#"
+def f(x: int, y: str) -> None: ...
+f
+^
+"#;
+    let hover_with_width = |width| {
+        move |state: &State, handle: &Handle, position: TextSize| {
+            match get_hover_with_signature_line_width(
+                &state.transaction(),
+                handle,
+                position,
+                false,
+                Some(width),
+            ) {
+                Some(Hover {
+                    contents: HoverContents::Markup(markup),
+                    ..
+                }) => markup.value,
+                _ => "None".to_owned(),
+            }
+        }
+    };
+    let report = get_batched_lsp_operations_report(&[("main", code)], hover_with_width(80));
+    assert!(
+        report.contains("def f(x: int, y: str) -> None: ..."),
+        "Expected a single-line signature, got: {report}"
+    );
+    let report = get_batched_lsp_operations_report(&[("main", code)], hover_with_width(10));
+    assert!(
+        report.contains("def f(\n    x: int,\n    y: str\n) -> None: ..."),
+        "Expected a multi-line signature, got: {report}"
+    );
+}
+
+#[test]
+fn hover_property_and_descriptor_setter_type() {
+    let code = r#"
+from typing import Any
+class Converter:
+    def __get__(self, obj: Any, objtype: Any = None) -> int: ...
+    def __set__(self, obj: Any, value: str | bytes) -> None: ...
+class C:
+    d = Converter()
+    @property
+    def port(self) -> int:
+        return 0
+    @port.setter
+    def port(self, value: str | int) -> None:
+        pass
+    @property
+    def name(self) -> str:
+        return ""
+    @name.setter
+    def name(self, value: str) -> None:
+        pass
+c = C()
+c.port
+# ^
+c.d
+# ^
+c.name
+# ^
+"#;
+    let report = get_batched_lsp_operations_report(&[("main", code)], |state, handle, position| {
+        match get_hover(&state.transaction(), handle, position, false) {
+            Some(Hover {
+                contents: HoverContents::Markup(markup),
+                ..
+            }) => markup.value,
+            _ => "None".to_owned(),
+        }
+    });
+    assert_eq!(
+        r#"
+# main.py
+21 | c.port
+       ^
+```python
+(attribute) port: int
+(setter) port: str | int
+```
+
+23 | c.d
+       ^
+```python
+(attribute) d: int
+(setter) d: str | bytes
+```
+
+25 | c.name
+       ^
+```python
+(attribute) name: str
+```
+"#
+        .trim(),
+        report.trim(),
+    );
+}
+
+#[test]
+fn hover_shows_large_aliased_union_by_name() {
+    let code = r#"
+from typing import TypeAlias
+Scalar: TypeAlias = int | str | bytes | bytearray | memoryview | None
+Small: TypeAlias = int | str
+def f(x: Scalar, y: Small) -> None:
+    x
+#   ^
+    y
+#   ^
+"#;
+    let report = get_batched_lsp_operations_report(&[("main", code)], |state, handle, position| {
+        match get_hover(&state.transaction(), handle, position, false) {
+            Some(Hover {
+                contents: HoverContents::Markup(markup),
+                ..
+            }) => markup.value,
+            _ => "None".to_owned(),
+        }
+    });
+    assert_eq!(
+        r#"
+# main.py
+6 |     x
+        ^
+```python
+(parameter) x: Scalar
+```
+
+8 |     y
+        ^
+```python
+(parameter) y: int | str
+```
+"#
+        .trim(),
+        report.trim(),
+    );
+}
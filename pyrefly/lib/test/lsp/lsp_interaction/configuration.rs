@@ -638,10 +638,15 @@ fn test_did_change_workspace_folder() {
     interaction.shutdown().expect("Failed to shutdown");
 }
 
-fn get_diagnostics_result() -> serde_json::Value {
+fn get_diagnostics_result(path: &Path) -> serde_json::Value {
+    let uri = Url::from_file_path(path).unwrap().to_string();
     json!({"items": [
             {"code":"unsupported-operation","codeDescription":{"href":"https://pyrefly.org/en/docs/error-kinds/#unsupported-operation"},"message":"`+` is not supported between `Literal[1]` and `Literal['']`\n  Argument `Literal['']` is not assignable to parameter `value` with type `int` in function `int.__add__`",
-            "range":{"end":{"character":6,"line":5},"start":{"character":0,"line":5}},"severity":1,"source":"Pyrefly"}],"kind":"full"
+            "range":{"end":{"character":6,"line":5},"start":{"character":0,"line":5}},"severity":1,"source":"Pyrefly",
+            "relatedInformation":[
+                {"location":{"uri":uri,"range":{"end":{"character":1,"line":5},"start":{"character":0,"line":5}}},"message":"has type `Literal[1]`"},
+                {"location":{"uri":uri,"range":{"end":{"character":6,"line":5},"start":{"character":4,"line":5}}},"message":"has type `Literal['']`"}
+            ]}],"kind":"full"
     })
 }
 
@@ -955,7 +960,7 @@ fn test_diagnostics_default_workspace_with_config() {
     interaction
         .client
         .diagnostic("type_errors.py")
-        .expect_response(get_diagnostics_result())
+        .expect_response(get_diagnostics_result(&root.join("type_errors.py")))
         .expect("Failed to receive expected response");
 
     interaction.client.did_change_configuration();
@@ -1043,7 +1048,11 @@ fn test_diagnostics_file_not_in_includes() {
     interaction
         .client
         .diagnostic("diagnostics_file_not_in_includes/type_errors_include.py")
-        .expect_response(get_diagnostics_result())
+        .expect_response(get_diagnostics_result(
+            &root
+                .path()
+                .join("diagnostics_file_not_in_includes/type_errors_include.py"),
+        ))
         .expect("Failed to receive expected response");
 
     // prove that it ignores a file not in project includes
@@ -1081,7 +1090,11 @@ fn test_diagnostics_file_in_excludes() {
     interaction
         .client
         .diagnostic("diagnostics_file_in_excludes/type_errors_include.py")
-        .expect_response(get_diagnostics_result())
+        .expect_response(get_diagnostics_result(
+            &root
+                .path()
+                .join("diagnostics_file_in_excludes/type_errors_include.py"),
+        ))
         .expect("Failed to receive expected response");
 
     // prove that it ignores a file not in project includes
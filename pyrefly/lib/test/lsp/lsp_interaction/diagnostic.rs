@@ -692,7 +692,19 @@ fn test_error_documentation_links() {
                         "start": {"character": 9, "line": 9}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join("error_docs_test.py"))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 9},
+                                "start": {"character": 3, "line": 9}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 },
                 {
                     "code": "bad-context-manager",
@@ -1124,7 +1136,19 @@ fn test_shows_stdlib_type_errors_with_force_on() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join(stdlib_filepath))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -1180,7 +1204,19 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join("filtering_stdlib_errors/usr/local/lib/python3.12/stdlib_file.py"))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -1213,7 +1249,19 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join("filtering_stdlib_errors/usr/local/lib/python3.8/stdlib_file.py"))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -1240,7 +1288,19 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join("filtering_stdlib_errors/usr/lib/python3.12/stdlib_file.py"))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -1273,7 +1333,19 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join("filtering_stdlib_errors/usr/lib64/python3.12/stdlib_file.py"))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -1366,7 +1438,19 @@ fn test_shows_stdlib_errors_when_explicitly_included_in_project_includes() {
                         "start": {"character": 9, "line": 5}
                     },
                     "severity": 1,
-                    "source": "Pyrefly"
+                    "source": "Pyrefly",
+                    "relatedInformation": [{
+                        "location": {
+                            "uri": Url::from_file_path(test_files_root.path().join(stdlib_filepath))
+                                .unwrap()
+                                .to_string(),
+                            "range": {
+                                "end": {"character": 6, "line": 5},
+                                "start": {"character": 3, "line": 5}
+                            }
+                        },
+                        "message": "declared type"
+                    }]
                 }
             ],
             "kind": "full"
@@ -180,3 +180,56 @@ fn test_consecutive_file_watcher_events() {
 
     interaction.shutdown().unwrap();
 }
+
+/// Test that deleting and recreating a dependency of an open file on disk (e.g. a
+/// git checkout followed by codegen) is reflected in the open file's diagnostics.
+#[test]
+fn test_dependency_deleted_and_recreated_on_disk() {
+    let root = get_test_files_root();
+    let root_path = root.path().join("streaming");
+    let mut interaction = LspInteraction::new();
+    interaction.set_root(root_path.clone());
+    interaction
+        .initialize(InitializeSettings {
+            configuration: Some(Some(
+                json!([{"pyrefly": {"displayTypeErrors": "force-on"}}]),
+            )),
+            workspace_folders: Some(vec![(
+                "streaming".to_owned(),
+                Url::from_file_path(root_path.clone()).unwrap(),
+            )]),
+            file_watch: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    let b_path = root_path.join("b.py");
+    let c_path = root_path.join("c.py");
+    let b_contents = std::fs::read_to_string(&b_path).unwrap();
+
+    interaction.client.did_open("c.py");
+    interaction
+        .client
+        .expect_file_watcher_register()
+        .expect("Register file watcher for c");
+    interaction
+        .client
+        .expect_publish_diagnostics_eventual_error_count(c_path.clone(), 0)
+        .expect("c.py should start without errors");
+
+    std::fs::remove_file(&b_path).unwrap();
+    interaction.client.file_deleted("b.py");
+    interaction
+        .client
+        .expect_publish_diagnostics_eventual_error_count(c_path.clone(), 1)
+        .expect("Deleting b.py should make the import in c.py fail");
+
+    std::fs::write(&b_path, b_contents).unwrap();
+    interaction.client.file_created("b.py");
+    interaction
+        .client
+        .expect_publish_diagnostics_eventual_error_count(c_path, 0)
+        .expect("Recreating b.py should resolve the import in c.py again");
+
+    interaction.shutdown().unwrap();
+}
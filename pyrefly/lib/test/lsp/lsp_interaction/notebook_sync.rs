@@ -127,7 +127,17 @@ fn test_notebook_did_open() {
                     "end": {"line": 1, "character": 5}
                 },
                 "severity": 1,
-                "source": "Pyrefly"
+                "source": "Pyrefly",
+                "relatedInformation": [{
+                    "location": {
+                        "uri": interaction.cell_uri("notebook.ipynb", "cell3"),
+                        "range": {
+                            "start": {"line": 0, "character": 3},
+                            "end": {"line": 0, "character": 6}
+                        }
+                    },
+                    "message": "declared type"
+                }]
             }],
             "kind": "full"
         }))
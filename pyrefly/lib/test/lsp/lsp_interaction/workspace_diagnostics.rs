@@ -69,6 +69,41 @@ fn test_workspace_diagnostics_for_non_open_file() {
     interaction.shutdown().unwrap();
 }
 
+/// A client without workspace folder support reports its root through `rootUri`,
+/// which is treated as a workspace folder, so workspace diagnostics apply to it.
+#[test]
+fn test_workspace_diagnostics_for_root_uri() {
+    let root = get_test_files_root();
+    let root_path = root.path().join("workspace_diagnostics");
+    let mut interaction = LspInteraction::new_with_args(LspInteractionArgs {
+        args: LspArgs {
+            indexing_mode: IndexingMode::LazyBlocking,
+            ..LspInteractionArgs::default().args
+        },
+        ..Default::default()
+    });
+    interaction.set_root(root_path.clone());
+    interaction
+        .initialize(InitializeSettings {
+            root_uri: Some(Url::from_file_path(root_path.clone()).unwrap()),
+            configuration: Some(Some(
+                json!([{"pyrefly": {"diagnosticMode": "workspace", "displayTypeErrors": "force-on"}}]),
+            )),
+            ..Default::default()
+        })
+        .expect("Failed to initialize");
+
+    interaction.client.did_open("clean.py");
+
+    let errors_path = root_path.join("errors.py");
+    interaction
+        .client
+        .expect_publish_diagnostics_eventual_error_count(errors_path, 1)
+        .expect("Expected 1 diagnostic for non-open errors.py in the rootUri workspace");
+
+    interaction.shutdown().unwrap();
+}
+
 /// Clean non-open workspace files should not be published.
 ///
 /// In workspace diagnostic mode, a non-open file with no diagnostics should
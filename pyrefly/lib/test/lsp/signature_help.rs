@@ -188,6 +188,34 @@ Signature Help Result: active=0
     );
 }
 
+#[test]
+fn unpacked_kwargs_test() {
+    let code = r#"
+from typing import TypedDict, Unpack
+
+class Payload(TypedDict):
+    foo: int
+    bar: str
+
+def f(**kwargs: Unpack[Payload]) -> None: ...
+
+f(bar=)
+#    ^
+"#;
+    let report = get_batched_lsp_operations_report_allow_error(&[("main", code)], get_test_report);
+    assert_eq!(
+        r#"
+# main.py
+10 | f(bar=)
+          ^
+Signature Help Result: active=0
+- def f(*, foo: int, bar: str, **kwargs: Unpack[Payload]) -> None: ..., parameters=[foo: int, bar: str, **kwargs: Unpack[Payload]], active parameter = 1
+"#
+        .trim(),
+        report.trim(),
+    );
+}
+
 #[test]
 fn parameter_documentation_test() {
     let code = r#"
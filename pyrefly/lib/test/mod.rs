@@ -9,6 +9,7 @@
 
 mod abstract_methods;
 mod annotation;
+mod annotation_style_lint;
 mod assign;
 mod attribute_narrow;
 mod attributes;
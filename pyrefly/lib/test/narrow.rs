@@ -403,6 +403,16 @@ def f(x: bool | None):
     "#,
 );
 
+testcase!(
+    test_not_and_absorbs_literal,
+    r#"
+from typing import reveal_type
+def f(x: bool | None):
+    if not (x is True and x is None):  # E: Identity comparison `True is None` is always False
+        reveal_type(x)  # E: revealed type: bool | None
+    "#,
+);
+
 testcase!(
     test_assert,
     r#"
@@ -486,6 +496,25 @@ assert_type(x, Literal[42] | str)
     "#,
 );
 
+testcase!(
+    test_while_overwrite_nested,
+    r#"
+from typing import assert_type, Literal
+def f() -> str | None: ...
+def g() -> bool: ...
+x = f()
+while x is None:
+    for _ in range(3):
+        if g():
+            x = 42
+            break
+    if g():
+        x = "done"
+        break
+assert_type(x, Literal[42] | str)
+    "#,
+);
+
 testcase!(
     test_while_narrow,
     r#"
@@ -829,6 +858,38 @@ def f(x: Falsey | Truthy):
     "#,
 );
 
+testcase!(
+    test_dunder_len_truthy_narrow,
+    r#"
+from typing import assert_type, Literal
+class Empty:
+    def __len__(self) -> Literal[0]:
+        return 0
+class Pair:
+    def __len__(self) -> Literal[2]:
+        return 2
+class Sized:
+    def __len__(self) -> int:
+        return 0
+class BoolWins:
+    def __bool__(self) -> Literal[True]:
+        return True
+    def __len__(self) -> Literal[0]:
+        return 0
+def f(x: Empty | Pair, y: Sized | None, z: BoolWins | None):
+    if x:
+        assert_type(x, Pair)
+    else:
+        assert_type(x, Empty)
+    if y:
+        assert_type(y, Sized)
+    else:
+        assert_type(y, Sized | None)
+    if not z:
+        assert_type(z, None)
+    "#,
+);
+
 testcase!(
     test_type_eq,
     r#"
@@ -1740,6 +1801,28 @@ def f(c: C, x: int | str):
     "#,
 );
 
+testcase!(
+    test_typeis_methods,
+    r#"
+from typing import TypeIs, assert_type
+class C:
+    def is_int(self, x: object) -> TypeIs[int]: ...
+    @classmethod
+    def is_str(cls, x: object) -> TypeIs[str]: ...
+    @staticmethod
+    def is_bytes(x: object) -> TypeIs[bytes]: ...
+def f(c: C, x: int | str | bytes):
+    if c.is_int(x):
+        assert_type(x, int)
+    else:
+        assert_type(x, str | bytes)
+    if C.is_str(x):
+        assert_type(x, str)
+    if c.is_bytes(x):
+        assert_type(x, bytes)
+    "#,
+);
+
 testcase!(
     test_typeguard_generic_function,
     r#"
@@ -3204,6 +3287,20 @@ def f(x: int | Callable[[], int]) -> int:
     "#,
 );
 
+testcase!(
+    test_callable_narrow_both_branches,
+    r#"
+from typing import Callable, assert_type
+class B:
+    def __call__(self) -> int: ...
+def f(x: str | B | Callable[[], int] | None):
+    if callable(x):
+        assert_type(x, B | Callable[[], int])
+    else:
+        assert_type(x, str | None)
+    "#,
+);
+
 testcase!(
     test_isinstance_local_var,
     r#"
@@ -4086,3 +4183,26 @@ def g(x: A | B | C) -> None:
         assert_type(x, A)
 "#,
 );
+
+testcase!(
+    test_eq_final_constant,
+    r#"
+from typing import Final, Literal, assert_type
+
+STATUS_OK: Final = 200
+STATUS_NOT_FOUND: Final = 404
+
+class Status:
+    ERROR: Final = 500
+
+def f(status: Literal[200, 404, 500]):
+    if status == STATUS_OK:
+        assert_type(status, Literal[200])
+    elif status == Status.ERROR:
+        assert_type(status, Literal[500])
+    else:
+        assert_type(status, Literal[404])
+    if status != STATUS_NOT_FOUND:
+        assert_type(status, Literal[200, 500])
+"#,
+);
@@ -1597,3 +1597,118 @@ def f(c: C) -> None:
             assert_type(c.x, int)
     "#,
 );
+
+testcase!(
+    test_class_pattern_dataclass_match_args,
+    r#"
+from dataclasses import dataclass
+from typing import assert_type
+
+@dataclass
+class Point:
+    x: int
+    y: str
+
+def f(p: Point | int):
+    match p:
+        case Point(a, b):
+            assert_type(p, Point)
+            assert_type(a, int)
+            assert_type(b, str)
+    match p:
+        case Point(y=b):
+            assert_type(b, str)
+    match p:
+        case Point(1, "a", 3):  # E: Cannot match positional sub-patterns in `Point`
+            pass
+    match p:
+        case len():  # E: Expected class object, got
+            pass
+    "#,
+);
+
+testcase!(
+    test_mapping_pattern_typed_dict_keys,
+    r#"
+from typing import NotRequired, TypedDict, assert_type
+
+class Movie(TypedDict, closed=True):
+    title: str
+    year: int
+
+class Book(TypedDict, closed=True):
+    title: str
+    pages: int
+
+class Options(TypedDict):
+    verbose: NotRequired[bool]
+
+def f(x: Movie | Book, o: Options):
+    match x:
+        case {"year": y}:
+            assert_type(x, Movie)
+            assert_type(y, int)
+        case {"title": t, "pages": p}:
+            assert_type(x, Book)
+            assert_type(t, str)
+            assert_type(p, int)
+    match o:
+        case {"verbose": v}:
+            assert_type(v, bool)
+    "#,
+);
+
+testcase!(
+    test_mapping_pattern_typed_dict_keys_fall_through,
+    r#"
+from typing import TypedDict, assert_type
+
+class Movie(TypedDict, closed=True):
+    title: str
+    year: int
+
+class Book(TypedDict, closed=True):
+    title: str
+    pages: int
+
+def f(x: Movie | Book):
+    match x:
+        case {"year": 2000}:
+            assert_type(x, Movie)
+        case _:
+            assert_type(x, Movie | Book)
+
+def g(x: Movie | Book):
+    match x:
+        case {"year": _}:
+            assert_type(x, Movie)
+        case _:
+            assert_type(x, Book)
+    "#,
+);
+
+testcase!(
+    test_mapping_pattern_variable_key_fall_through,
+    r#"
+from typing import TypedDict, assert_type
+
+class Movie(TypedDict, closed=True):
+    title: str
+    year: int
+
+class Book(TypedDict, closed=True):
+    title: str
+    pages: int
+
+class Keys:
+    extra: str = "extra"
+
+def f(x: Movie | Book):
+    match x:
+        case {"year": _, Keys.extra: _}:
+            assert_type(x, Movie)
+        case _:
+            # The subject may have fallen through for lacking `Keys.extra`, not `"year"`.
+            assert_type(x, Movie | Book)
+    "#,
+);
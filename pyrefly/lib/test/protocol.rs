@@ -1383,6 +1383,29 @@ def test2(x: C):
 "#,
 );
 
+testcase!(
+    test_runtime_checkable_isinstance_narrows_to_intersection,
+    r#"
+from typing import Protocol, reveal_type, runtime_checkable
+
+@runtime_checkable
+class SupportsClose(Protocol):
+    def close(self) -> None: ...
+
+class A:
+    x: int
+
+class B:
+    def close(self) -> None: ...
+
+def f(a: A, ab: A | B):
+    if isinstance(a, SupportsClose):
+        reveal_type(a)  # E: revealed type: A & SupportsClose
+    if isinstance(ab, SupportsClose):
+        reveal_type(ab)  # E: revealed type: B
+"#,
+);
+
 testcase!(
     test_runtime_checkable_generics_no_error,
     r#"
@@ -1767,6 +1790,39 @@ class Impl(A[S], B[S], Protocol):
 "#,
 );
 
+testcase!(
+    test_protocol_expanding_recursion_hits_limit,
+    r#"
+from typing import Protocol
+
+class P[T](Protocol):
+    def f(self) -> P[list[T]]: ...
+
+class C[T]:
+    def f(self) -> C[list[T]]: ...
+
+def g(x: C[int]) -> P[int]:
+    return x  # E: Recursion limit exceeded while checking whether `C[int]` is assignable to `P[int]` (in the definition of `main.P`); falling back to `Any`
+"#,
+);
+
+testcase!(
+    test_subset_recursion_limit_is_configurable,
+    TestEnv::new().with_subset_recursion_limit(1),
+    r#"
+from typing import Protocol
+
+class P(Protocol):
+    def f(self) -> int: ...
+
+class C:
+    def f(self) -> int: ...
+
+def g(x: C) -> P:
+    return x  # E: Recursion limit exceeded while checking whether `C` is assignable to `P` (in the definition of `main.P`); falling back to `Any`
+"#,
+);
+
 testcase!(
     test_protocol_overloaded_generic_self_non_conforming_still_rejected,
     r#"
@@ -1406,3 +1406,90 @@ def skipped_parent(value: int) -> int:
         "nested declaration and parent body should follow skipped parent body, got lines {lines:?}",
     );
 }
+
+#[test]
+fn test_lazy_query_analyzes_file_on_demand() {
+    let tdir = TempDir::new().unwrap();
+    fs_anyhow::write(&tdir.path().join("lib.py"), "def f() -> int: ...").unwrap();
+    let file_path = tdir.path().join("main.py");
+    fs_anyhow::write(&file_path, "from lib import f\nx = f()").unwrap();
+
+    init_test();
+    let mut config = ConfigFile::default();
+    config.python_environment.set_empty_to_default();
+    config.search_path_from_args = vec![tdir.path().to_path_buf()];
+    config.configure();
+    let query = Query::new_lazy(
+        ConfigFinder::new_constant(ArcId::new(config)),
+        TEST_THREAD_COUNT,
+    );
+
+    // The file was never added, but is analyzed when queried.
+    let types = query
+        .get_types_in_file(
+            ModuleName::from_str("main"),
+            ModulePath::filesystem(file_path),
+        )
+        .unwrap();
+    assert!(
+        types.iter().any(|(_, ty)| ty == "builtins.int"),
+        "Expected `x` to be `int`: {types:?}"
+    );
+}
+
+#[test]
+fn test_lazy_query_analyzes_file_for_every_query() {
+    let tdir = TempDir::new().unwrap();
+    fs_anyhow::write(&tdir.path().join("lib.py"), "def f() -> int: ...").unwrap();
+    let file_path = tdir.path().join("main.py");
+    fs_anyhow::write(
+        &file_path,
+        "from lib import f\nclass C:\n    x: int = f()\ndef g() -> None:\n    f()\n",
+    )
+    .unwrap();
+
+    init_test();
+    let mut config = ConfigFile::default();
+    config.python_environment.set_empty_to_default();
+    config.search_path_from_args = vec![tdir.path().to_path_buf()];
+    config.configure();
+    let module_name = ModuleName::from_str("main");
+    let path = ModulePath::filesystem(file_path.clone());
+
+    // Each query runs against a fresh lazy `Query`, so it must analyze the file itself.
+    let lazy_query = || {
+        Query::new_lazy(
+            ConfigFinder::new_constant(ArcId::new(config.clone())),
+            TEST_THREAD_COUNT,
+        )
+    };
+
+    let attributes = lazy_query()
+        .get_attributes(module_name, path.clone(), "C")
+        .unwrap();
+    assert_eq!(
+        attributes
+            .iter()
+            .map(|a| (a.name.as_str(), a.annotation.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("x", "builtins.int")]
+    );
+
+    let callees = lazy_query()
+        .get_callees_with_location(module_name, path, None)
+        .unwrap();
+    assert!(
+        callees.iter().any(|(_, callee)| callee.target == "lib.f"),
+        "Expected a call to `lib.f`, got: {:?}",
+        callees.iter().map(|(_, c)| &c.target).collect::<Vec<_>>()
+    );
+
+    // The second call finds both types cached and checks them against the file directly.
+    let query = lazy_query();
+    for _ in 0..2 {
+        assert_eq!(
+            query.is_subtype(module_name, file_path.clone(), "bool", "int"),
+            Ok(true)
+        );
+    }
+}
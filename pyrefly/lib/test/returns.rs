@@ -153,6 +153,41 @@ def f(b: bool) -> int:  # E: Function declared to return `int`, but one or more
 "#,
 );
 
+testcase!(
+    test_return_fall_through_location,
+    r#"
+from enum import Enum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+def f(b: bool) -> int:  # E: The function can fall through after the statement on line 169
+    if b:
+        return 1
+    print("no return")
+
+def g(c: Color) -> int:  # E: The function can fall through after the statement on line 172
+    match c:  # E: Match on `Color` is not exhaustive
+        case Color.RED:
+            return 1
+        case Color.GREEN:
+            return 2
+
+def h(c: Color) -> int:
+    match c:
+        case Color.RED:
+            return 1
+        case Color.GREEN | Color.BLUE:
+            return 2
+
+def k(xs: list[int]) -> int:  # E: The function can fall through after the statement on line 186
+    for x in xs:
+        return x
+"#,
+);
+
 testcase!(
     test_return_catch,
     r#"
@@ -379,6 +379,26 @@ typing.assert_type(typing.__name__, str)
 "#,
 );
 
+testcase!(
+    test_namespace_dicts,
+    r#"
+import typing
+from types import MappingProxyType
+from typing import assert_type, Any
+class C:
+    x: int = 1
+def f() -> None:
+    assert_type(locals(), dict[str, Any])
+assert_type(globals(), dict[str, Any])
+assert_type(__dict__, dict[str, Any])
+assert_type(typing.__dict__, dict[str, Any])
+assert_type(C.__dict__, MappingProxyType[str, Any])
+assert_type(C().__dict__, dict[str, Any])
+globals()["y"] = 1
+typing.__dict__["cast"]
+"#,
+);
+
 testcase!(
     test_argument_shadows_type,
     r#"
@@ -856,6 +876,44 @@ def f(x: A):
     "#,
 );
 
+testcase!(
+    test_iterable_next_only_iterator,
+    r#"
+from typing import assert_type
+class It[T]:
+    def __next__(self) -> T: ...
+class A:
+    def __iter__(self) -> It[str]: ...
+class AsyncIt:
+    async def __anext__(self) -> int: ...
+class B:
+    def __aiter__(self) -> AsyncIt: ...
+async def f(x: A, y: B):
+    for s in x:
+        assert_type(s, str)
+    async for i in y:
+        assert_type(i, int)
+    for _ in y:  # E: Type `B` is not iterable
+        pass
+    "#,
+);
+
+testcase!(
+    test_iterate_builtins_precise,
+    r#"
+from typing import assert_type
+def f(xs: list[int], ys: tuple[str, ...]):
+    for i, x in enumerate(xs):
+        assert_type(i, int)
+        assert_type(x, int)
+    for x, y in zip(xs, ys):
+        assert_type(x, int)
+        assert_type(y, str)
+    for y in reversed(ys):
+        assert_type(y, str)
+    "#,
+);
+
 testcase!(
     test_getitem_iteration,
     r#"
@@ -1703,6 +1761,8 @@ assert_type(Literal[4] | int, type[int])
 assert_type(LiteralString | Literal["test"], type[LiteralString])
 assert_type(LiteralString | str, type[str])
 assert_type(Literal[True] | bool, type[bool])
+assert_type(Literal[False] | bool | None, type[bool | None])
+assert_type(Literal[1, "a"] | int | str | int, type[int | str])
 
 def f(cond: bool, x: LiteralString, y: str):
     assert_type(x if cond else y, str)
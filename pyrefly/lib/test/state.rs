@@ -47,6 +47,7 @@ use crate::state::load::FileContents;
 use crate::state::require::Require;
 use crate::state::require::RequireLevels;
 use crate::state::state::State;
+use crate::state::state::Transaction;
 use crate::test::util::TestEnv;
 
 #[derive(Debug)]
@@ -311,6 +312,17 @@ fn test_cross_module_literal_promotion() {
         .unwrap();
 }
 
+#[test]
+fn test_try_new_transaction_during_recheck() {
+    let test_env = TestEnv::one("main", "x: int = 1");
+    let state = State::new(test_env.config_finder(), TEST_THREAD_COUNT);
+    // A recheck only holds the state for reading until it commits, so readers are not blocked.
+    let committing = state.new_committable_transaction(Require::Errors, None);
+    assert!(state.try_new_transaction(Require::Exports, None).is_some());
+    state.commit_transaction(committing, None);
+    assert!(state.try_new_transaction(Require::Exports, None).is_some());
+}
+
 #[test]
 fn test_multiple_path() {
     const LIB_PYI: &str = "x: int";
@@ -1005,6 +1017,50 @@ fn test_search_exports_cancellation() {
     );
 }
 
+#[test]
+fn test_run_cancellable() {
+    let mut t = TestEnv::new();
+    t.add("foo", "x: int = 1");
+    let (state, handle) = t.to_state();
+    let foo = handle("foo");
+    let edit = |t: &mut Transaction| {
+        t.set_memory(vec![(
+            PathBuf::from("foo.py"),
+            Some(Arc::new(FileContents::from_source(
+                "x: int = ''".to_owned(),
+            ))),
+        )])
+    };
+
+    // A cancelled transaction stops before rechecking the changed module.
+    let mut t = state.new_committable_transaction(Require::Everything, None);
+    edit(t.as_mut());
+    t.as_mut().get_cancellation_handle().cancel();
+    assert!(
+        t.as_mut()
+            .run_cancellable(&[foo.dupe()], Require::Everything, None)
+            .is_err()
+    );
+    drop(t);
+
+    // Starting afresh runs to completion.
+    let mut t = state.new_committable_transaction(Require::Everything, None);
+    edit(t.as_mut());
+    assert!(
+        t.as_mut()
+            .run_cancellable(&[foo.dupe()], Require::Everything, None)
+            .is_ok()
+    );
+    assert_eq!(
+        t.as_ref()
+            .get_errors([&foo])
+            .collect_errors()
+            .ordinary
+            .len(),
+        1
+    );
+}
+
 #[test]
 fn test_compute_stdlib_uses_custom_typeshed_when_configured() {
     use std::fs;
@@ -373,3 +373,82 @@ Module docstring.
 3 + "3"
 "#,
 );
+
+testcase!(
+    test_pyrefly_ignore_block,
+    r#"
+x: int = "x"  # E:
+# pyrefly: ignore-start[bad-assignment, bad-return]
+y: int = "y"
+def foo() -> str:
+    return 1
+3 + "3"  # E:
+# pyrefly: ignore-end
+z: int = "z"  # E:
+"#,
+);
+
+testcase!(
+    test_pyrefly_ignore_block_nested,
+    r#"
+# pyrefly: ignore-start[bad-assignment]
+# pyrefly: ignore-start[unsupported-operation]
+x: int = "x"
+3 + "3"
+# pyrefly: ignore-end
+y: int = "y"
+3 + "3"  # E:
+# pyrefly: ignore-end
+z: int = "z"  # E:
+"#,
+);
+
+testcase!(
+    test_pyrefly_ignore_block_unclosed,
+    r#"
+x: int = "x"  # E:
+# pyrefly: ignore-start[bad-assignment]
+y: int = "y"
+z: int = "z"
+"#,
+);
+
+testcase!(
+    // The error codes are required, so a bare `ignore-start` suppresses nothing.
+    test_pyrefly_ignore_block_requires_codes,
+    r#"
+# pyrefly: ignore-start
+x: int = "x"  # E:
+# pyrefly: ignore-end
+"#,
+);
+
+testcase!(
+    test_missing_ignore_code,
+    TestEnv::new().enable_missing_ignore_code_error(),
+    r#"
+x: int = "x"  # type: ignore  # E: does not name the error codes
+# pyrefly: ignore  # E: does not name the error codes
+y: int = "y"
+z: int = "z"  # pyrefly: ignore[bad-assignment]
+"#,
+);
+
+testcase!(
+    test_require_ignore_codes,
+    TestEnv::new().with_require_ignore_codes(),
+    r#"
+x: int = "x"  # type: ignore  # E: not assignable
+y: int = "y"  # pyrefly: ignore  # E: not assignable
+z: int = "z"  # pyrefly: ignore[bad-assignment]
+"#,
+);
+
+testcase!(
+    test_require_ignore_codes_file_level,
+    TestEnv::new().with_require_ignore_codes(),
+    r#"
+# pyrefly: ignore-errors
+x: int = "x"  # E: not assignable
+"#,
+);
@@ -171,6 +171,42 @@ assert_type(X1(), str)
 "#,
 );
 
+testcase!(
+    test_imported_final_bool_flag,
+    TestEnv::one(
+        "flags",
+        r#"
+from typing import Final
+ENABLED: Final = False
+DEBUG: Final[bool] = True
+MUTABLE = False
+"#
+    ),
+    r#"
+from typing import assert_type
+import flags
+import flags as f
+from flags import ENABLED, DEBUG as D, MUTABLE
+
+if ENABLED:
+    a: int = "dead"
+if not D:
+    b: int = "dead"
+if flags.ENABLED:
+    c: int = "dead"
+if f.ENABLED:
+    d: int = "dead"
+if MUTABLE:
+    e: int = "live"  # E: `Literal['live']` is not assignable to `int`
+
+if D:
+    X = str
+else:
+    X = int
+assert_type(X(), str)
+"#,
+);
+
 testcase!(
     test_platform,
     r#"
@@ -558,3 +594,112 @@ testcase!(
 import foo
 "#,
 );
+
+testcase!(
+    test_type_checking_only_import_used_at_runtime,
+    r#"
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    from decimal import Decimal
+    import fractions
+    D = Decimal  # Fine, this is also only evaluated during type checking.
+
+def f(x: "Decimal", y: "fractions.Fraction") -> list["Decimal"]:
+    return []
+
+isinstance(1, Decimal)  # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+fractions.Fraction(1)  # E: `fractions` is imported only under `if TYPE_CHECKING:`
+"#,
+);
+
+testcase!(
+    test_type_checking_only_import_used_in_function_and_class,
+    r#"
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    from decimal import Decimal
+    import fractions
+if TYPE_CHECKING:
+    from decimal import Decimal
+
+def f() -> None:
+    Decimal(1)  # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+    fractions.Fraction(1)  # E: `fractions` is imported only under `if TYPE_CHECKING:`
+
+class C:
+    d = Decimal(1)  # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+    def m(self) -> None:
+        isinstance(self, Decimal)  # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+"#,
+);
+
+// Annotations are evaluated at runtime before Python 3.14.
+testcase!(
+    test_type_checking_only_import_in_runtime_annotation,
+    TestEnv::new_with_version(PythonVersion::new(3, 13, 0)),
+    r#"
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    from decimal import Decimal
+
+def f() -> list[Decimal]:  # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+    return []
+
+def g() -> list["Decimal"]:
+    return []
+
+def h(x: Decimal) -> Decimal:  # E: `Decimal` is imported only under `if TYPE_CHECKING:` # E: `Decimal` is imported only under `if TYPE_CHECKING:`
+    return x
+
+def i(x: "Decimal") -> "Decimal":
+    y: Decimal = x  # Fine, local variable annotations are never evaluated.
+    return y
+"#,
+);
+
+testcase!(
+    test_type_checking_only_import_in_deferred_annotation,
+    TestEnv::new_with_version(PythonVersion::new(3, 14, 0)),
+    r#"
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    from decimal import Decimal
+
+def f(x: Decimal) -> list[Decimal]:
+    return []
+"#,
+);
+
+testcase!(
+    test_type_checking_only_import_with_future_annotations,
+    TestEnv::new_with_version(PythonVersion::new(3, 13, 0)),
+    r#"
+from __future__ import annotations
+from typing import TYPE_CHECKING
+if TYPE_CHECKING:
+    from decimal import Decimal
+
+def f(x: Decimal) -> list[Decimal]:
+    return []
+"#,
+);
+
+testcase!(
+    test_type_checking_import_with_fallback,
+    r#"
+import typing
+if typing.TYPE_CHECKING:
+    from decimal import Decimal
+else:
+    Decimal = float
+isinstance(1, Decimal)
+
+def f() -> bool:
+    return isinstance(1, Decimal)
+
+if typing.TYPE_CHECKING:
+    from fractions import Fraction
+from fractions import Fraction
+Fraction(1)
+"#,
+);
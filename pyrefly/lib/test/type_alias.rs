@@ -1355,3 +1355,47 @@ X: TypeAlias = int | str
 X()  # E: Expected a callable
     "#,
 );
+
+testcase!(
+    test_type_alias_type_var_defaults,
+    r#"
+from typing import Callable, TypeAlias, TypeVar, Unpack, assert_type
+
+T = TypeVar("T", default=int)
+Legacy: TypeAlias = dict[str, T]
+
+type Scoped[K, V = str] = dict[K, V]
+type Args[**P = [int, str]] = Callable[P, None]
+type Items[*Ts = Unpack[tuple[int, str]]] = tuple[*Ts]
+
+def f(a: Legacy, b: Scoped[int], c: Args, d: Items) -> None:
+    assert_type(a, dict[str, int])
+    assert_type(b, dict[int, str])
+    assert_type(c, Callable[[int, str], None])
+    assert_type(d, tuple[int, str])
+    "#,
+);
+
+testcase!(
+    test_large_aliased_union_displayed_by_name,
+    r#"
+from typing import TypeAlias
+Scalar: TypeAlias = int | str | bytes | bytearray | memoryview | None
+Small: TypeAlias = int | str
+def f(x: Scalar) -> None: ...
+def g(x: Small) -> None: ...
+f([1])  # E: Argument `list[int]` is not assignable to parameter `x` with type `Scalar`
+g([1])  # E: Argument `list[int]` is not assignable to parameter `x` with type `int | str`
+    "#,
+);
+
+testcase!(
+    test_large_aliased_union_expanded_without_limit,
+    TestEnv::new().with_union_display_limit(0),
+    r#"
+from typing import TypeAlias
+Scalar: TypeAlias = int | str | bytes | bytearray | memoryview | None
+def f(x: Scalar) -> None: ...
+f([1])  # E: with type `int | str | bytes | bytearray | memoryview | None`
+    "#,
+);
@@ -190,6 +190,30 @@ foo({"x": 1, "y": 2})
     "#,
 );
 
+testcase!(
+    test_typed_dict_literal_unpack_typed_dict,
+    r#"
+from typing import TypedDict, NotRequired
+class Point(TypedDict):
+    x: int
+    y: int
+class MaybePoint(TypedDict):
+    x: int
+    y: NotRequired[int]
+class Coord(TypedDict):
+    x: int
+    y: int
+    z: int
+
+def f(p: Point, mp: MaybePoint, d: dict[str, int]):
+    c1: Coord = {**p, "z": 1}
+    c2: Coord = {**p}  # E: Missing required key `z` for TypedDict `Coord`
+    c3: Coord = {**mp, "z": 1}  # E: Missing required key `y` for TypedDict `Coord`
+    c4: Coord = {**mp, "y": 2, "z": 1}
+    c5: Coord = {**p, "z": "1"}  # E: `Literal['1']` is not assignable to TypedDict key `z` with type `int`
+    "#,
+);
+
 testcase!(
     test_typed_dict_callable,
     r#"
@@ -2873,3 +2897,46 @@ def test(x: T) -> object:
     return x.get("name")
     "#,
 );
+
+testcase!(
+    test_typed_dict_str_enum_key,
+    r#"
+from typing import Literal, NotRequired, TypedDict, assert_type
+from enum import Enum, StrEnum
+class Key(StrEnum):
+    NAME = "name"
+    YEAR = "year"
+    RATING = "rating"
+class Other(Enum):
+    NAME = "name"
+class Movie(TypedDict):
+    name: str
+    year: int
+    rating: NotRequired[float]
+def f(m: Movie, k: Literal[Key.NAME, Key.YEAR]) -> None:
+    assert_type(m[Key.NAME], str)
+    assert_type(m[k], str | int)
+    m[Key.YEAR] = 1982
+    m[Key.YEAR] = "1982"  # E: `Literal['1982']` is not assignable to TypedDict key `year` with type `int`
+    del m[Key.RATING]
+    m[Other.NAME]  # E: Invalid key for TypedDict `Movie`
+    "#,
+);
+
+testcase!(
+    test_dict_literal_key_union,
+    r#"
+from typing import Callable, Literal, assert_type
+from enum import Enum
+class Command(Enum):
+    START = 1
+    STOP = 2
+    RESTART = 3
+handlers: dict[Literal[Command.START, Command.STOP], Callable[[], None]] = {}
+modes: dict[Literal["fast", "slow"], int] = {"fast": 1, "slow": 2}
+assert_type(handlers[Command.START], Callable[[], None])
+assert_type(modes["fast"], int)
+handlers[Command.RESTART]  # E: `Literal[Command.RESTART]` is not assignable to parameter `key`
+modes["medium"]  # E: `Literal['medium']` is not assignable to parameter `key`
+    "#,
+);
@@ -40,10 +40,14 @@ use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
 use crate::binding::binding::KeyExport;
+use crate::config::base::ConstructorOverrides;
 use crate::config::base::InferReturnTypes;
 use crate::config::base::UntypedDefBehavior;
 use crate::config::config::ConfigFile;
+use crate::config::dynamic_modules::DynamicModules;
 use crate::config::finder::ConfigFinder;
+use crate::config::literal_string_sink::LiteralStringSink;
+use crate::config::literal_string_sink::LiteralStringSinks;
 use crate::error::error::print_errors;
 use crate::module::finder::DirEntryCache;
 use crate::module::finder::find_import;
@@ -118,9 +122,14 @@ pub struct TestEnv {
     open_unpacking_error: bool,
     missing_override_decorator_error: bool,
     missing_super_call_error: bool,
+    missing_ignore_code_error: bool,
+    unused_import_error: bool,
+    require_ignore_codes: bool,
     not_required_key_access_error: bool,
     pytorch_efficiency_lint_error: bool,
+    annotation_style_lint_error: bool,
     incompatible_comparison_error: bool,
+    strict_override_error: bool,
     untyped_class_decorator_error: bool,
     untyped_function_decorator_error: bool,
     unused_call_result_error: bool,
@@ -128,14 +137,25 @@ pub struct TestEnv {
     strict_callable_subtyping: bool,
     strict_partial_subtyping: bool,
     spec_compliant_overloads: bool,
+    hasattr_narrowing: bool,
+    union_display_limit: usize,
+    literal_union_limit: usize,
+    enum_union_limit: usize,
+    union_limit: usize,
+    subset_recursion_limit: usize,
+    literal_string_sinks: LiteralStringSinks,
+    constructor_overrides: ConstructorOverrides,
+    optional_attribute_hints: bool,
     no_any_return_error: bool,
     no_any_return_explicit_error: bool,
     no_any_return_implicit_error: bool,
     implicit_any_lambda_error: bool,
     invalid_abstract_method_error: bool,
+    inconsistent_constructor_error: bool,
     unknown_variable_type_error: bool,
     default_require_level: Require,
     extra_file_extensions: Vec<String>,
+    dynamic_modules: DynamicModules,
     /// The `Require` level passed to `run()` in `to_state()`. Controls whether
     /// IDE features (indexing, hover) are enabled. Defaults to `Require::Everything`.
     run_require: Require,
@@ -165,9 +185,14 @@ impl TestEnv {
             open_unpacking_error: false,
             missing_override_decorator_error: false,
             missing_super_call_error: false,
+            missing_ignore_code_error: false,
+            unused_import_error: false,
+            require_ignore_codes: false,
             not_required_key_access_error: false,
             pytorch_efficiency_lint_error: false,
+            annotation_style_lint_error: false,
             incompatible_comparison_error: false,
+            strict_override_error: false,
             untyped_class_decorator_error: false,
             untyped_function_decorator_error: false,
             unused_call_result_error: false,
@@ -175,14 +200,25 @@ impl TestEnv {
             strict_callable_subtyping: false,
             strict_partial_subtyping: false,
             spec_compliant_overloads: false,
+            hasattr_narrowing: true,
+            union_display_limit: ConfigFile::DEFAULT_UNION_DISPLAY_LIMIT,
+            literal_union_limit: ConfigFile::DEFAULT_LITERAL_UNION_LIMIT,
+            enum_union_limit: ConfigFile::DEFAULT_ENUM_UNION_LIMIT,
+            union_limit: ConfigFile::DEFAULT_UNION_LIMIT,
+            subset_recursion_limit: ConfigFile::DEFAULT_SUBSET_RECURSION_LIMIT,
+            literal_string_sinks: LiteralStringSinks::default(),
+            constructor_overrides: ConstructorOverrides::default(),
+            optional_attribute_hints: true,
             no_any_return_error: false,
             no_any_return_explicit_error: false,
             no_any_return_implicit_error: false,
             implicit_any_lambda_error: false,
             invalid_abstract_method_error: false,
+            inconsistent_constructor_error: false,
             unknown_variable_type_error: false,
             default_require_level: Require::Exports,
             extra_file_extensions: Vec::new(),
+            dynamic_modules: DynamicModules::default(),
             run_require: Require::Everything,
         }
     }
@@ -333,6 +369,21 @@ impl TestEnv {
         self
     }
 
+    pub fn enable_missing_ignore_code_error(mut self) -> Self {
+        self.missing_ignore_code_error = true;
+        self
+    }
+
+    pub fn enable_unused_import_error(mut self) -> Self {
+        self.unused_import_error = true;
+        self
+    }
+
+    pub fn with_require_ignore_codes(mut self) -> Self {
+        self.require_ignore_codes = true;
+        self
+    }
+
     pub fn enable_not_required_key_access_error(mut self) -> Self {
         self.not_required_key_access_error = true;
         self
@@ -343,11 +394,21 @@ impl TestEnv {
         self
     }
 
+    pub fn enable_annotation_style_lint_error(mut self) -> Self {
+        self.annotation_style_lint_error = true;
+        self
+    }
+
     pub fn enable_incompatible_comparison_error(mut self) -> Self {
         self.incompatible_comparison_error = true;
         self
     }
 
+    pub fn enable_strict_override_error(mut self) -> Self {
+        self.strict_override_error = true;
+        self
+    }
+
     pub fn enable_untyped_class_decorator_error(mut self) -> Self {
         self.untyped_class_decorator_error = true;
         self
@@ -383,6 +444,55 @@ impl TestEnv {
         self
     }
 
+    pub fn disable_hasattr_narrowing(mut self) -> Self {
+        self.hasattr_narrowing = false;
+        self
+    }
+
+    pub fn with_union_display_limit(mut self, limit: usize) -> Self {
+        self.union_display_limit = limit;
+        self
+    }
+
+    pub fn with_literal_union_limit(mut self, limit: usize) -> Self {
+        self.literal_union_limit = limit;
+        self
+    }
+
+    pub fn with_enum_union_limit(mut self, limit: usize) -> Self {
+        self.enum_union_limit = limit;
+        self
+    }
+
+    pub fn with_union_limit(mut self, limit: usize) -> Self {
+        self.union_limit = limit;
+        self
+    }
+
+    pub fn with_subset_recursion_limit(mut self, limit: usize) -> Self {
+        self.subset_recursion_limit = limit;
+        self
+    }
+
+    pub fn with_constructor_overrides(
+        mut self,
+        constructor_overrides: ConstructorOverrides,
+    ) -> Self {
+        self.constructor_overrides = constructor_overrides;
+        self
+    }
+
+    pub fn disable_optional_attribute_hints(mut self) -> Self {
+        self.optional_attribute_hints = false;
+        self
+    }
+
+    pub fn with_literal_string_sinks(mut self, sinks: &[&str]) -> Self {
+        self.literal_string_sinks =
+            LiteralStringSinks::new(sinks.map(|s| LiteralStringSink::new(s).unwrap()));
+        self
+    }
+
     pub fn enable_no_any_return_error(mut self) -> Self {
         self.no_any_return_error = true;
         self
@@ -408,6 +518,11 @@ impl TestEnv {
         self
     }
 
+    pub fn enable_inconsistent_constructor_error(mut self) -> Self {
+        self.inconsistent_constructor_error = true;
+        self
+    }
+
     pub fn enable_unknown_variable_type_error(mut self) -> Self {
         self.unknown_variable_type_error = true;
         self
@@ -428,6 +543,25 @@ impl TestEnv {
         self
     }
 
+    /// Declare modules in the `dynamic-modules` config, as (module, [(name, type)]) pairs.
+    pub fn with_dynamic_modules(mut self, modules: &[(&str, &[(&str, &str)])]) -> Self {
+        self.dynamic_modules = DynamicModules::new(
+            modules
+                .iter()
+                .map(|(module, names)| {
+                    (
+                        (*module).to_owned(),
+                        names
+                            .iter()
+                            .map(|(name, ty)| ((*name).to_owned(), (*ty).to_owned()))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+        self
+    }
+
     pub fn with_version(mut self, version: PythonVersion) -> Self {
         self.version = version;
         self
@@ -506,6 +640,18 @@ impl TestEnv {
         config.root.strict_callable_subtyping = Some(self.strict_callable_subtyping);
         config.root.strict_partial_subtyping = Some(self.strict_partial_subtyping);
         config.root.spec_compliant_overloads = Some(self.spec_compliant_overloads);
+        config.root.hasattr_narrowing = Some(self.hasattr_narrowing);
+        config.root.require_ignore_codes = Some(self.require_ignore_codes);
+        config.root.union_display_limit = Some(self.union_display_limit);
+        config.root.literal_union_limit = Some(self.literal_union_limit);
+        config.root.enum_union_limit = Some(self.enum_union_limit);
+        config.root.union_limit = Some(self.union_limit);
+        config.root.subset_recursion_limit = Some(self.subset_recursion_limit);
+        config.root.constructor_overrides = Some(self.constructor_overrides);
+        config.root.optional_attribute_hints = Some(self.optional_attribute_hints);
+        if !self.literal_string_sinks.is_empty() {
+            config.root.literal_string_sinks = Some(self.literal_string_sinks.dupe());
+        }
         if config.root.errors.is_none() {
             config.root.errors = Some(ErrorDisplayConfig::new(HashMap::new()));
         };
@@ -543,6 +689,12 @@ impl TestEnv {
         if self.missing_super_call_error {
             errors.set_error_severity(ErrorKind::MissingSuperCall, Severity::Error);
         }
+        if self.missing_ignore_code_error {
+            errors.set_error_severity(ErrorKind::MissingIgnoreCode, Severity::Error);
+        }
+        if self.unused_import_error {
+            errors.set_error_severity(ErrorKind::UnusedImport, Severity::Error);
+        }
         if self.not_required_key_access_error {
             errors.set_error_severity(ErrorKind::NotRequiredKeyAccess, Severity::Error);
         }
@@ -558,9 +710,15 @@ impl TestEnv {
         if self.pytorch_efficiency_lint_error {
             config.root.pytorch_efficiency_lints = Some(true);
         }
+        if self.annotation_style_lint_error {
+            config.root.annotation_style_lints = Some(true);
+        }
         if self.incompatible_comparison_error {
             errors.set_error_severity(ErrorKind::IncompatibleComparison, Severity::Error);
         }
+        if self.strict_override_error {
+            errors.set_error_severity(ErrorKind::StrictOverride, Severity::Error);
+        }
         if self.untyped_class_decorator_error {
             errors.set_error_severity(ErrorKind::UntypedClassDecorator, Severity::Error);
         }
@@ -579,10 +737,14 @@ impl TestEnv {
         if self.invalid_abstract_method_error {
             errors.set_error_severity(ErrorKind::InvalidAbstractMethod, Severity::Error);
         }
+        if self.inconsistent_constructor_error {
+            errors.set_error_severity(ErrorKind::InconsistentConstructor, Severity::Error);
+        }
         if self.unknown_variable_type_error {
             errors.set_error_severity(ErrorKind::UnknownVariableType, Severity::Error);
         }
         config.extra_file_extensions = self.extra_file_extensions.clone();
+        config.dynamic_modules = self.dynamic_modules.clone();
         let mut sourcedb = MapDatabase::new(config.get_sys_info());
         for (name, path, _) in self.modules.iter() {
             sourcedb.insert(*name, path.dupe());
@@ -139,6 +139,16 @@ let docsSidebar = [
                 id: 'stubgen',
                 label: 'Generating Stub Files',
             },
+            {
+                type: 'doc' as const,
+                id: 'test-annotations',
+                label: 'Testing Type Behavior',
+            },
+            {
+                type: 'doc' as const,
+                id: 'verify-runtime',
+                label: 'Verifying Stubs at Runtime',
+            },
         ],
     },
     {